@@ -0,0 +1,51 @@
+use codspeed_criterion_compat::{black_box, criterion_group, criterion_main, Criterion};
+use jumbf::parser::SuperBox;
+
+const C2PA_MANIFEST_STORE: &[u8; 46948] = include_bytes!("../src/tests/fixtures/C.c2pa");
+
+/// Number of copies of the sample manifest store to wrap in one superbox.
+const COPIES: usize = 64;
+
+// Build a superbox which contains `COPIES` copies of the sample C2PA manifest
+// store as sibling child superboxes.
+fn scaled_manifest_store() -> Vec<u8> {
+    let desc = [
+        0x00, 0x00, 0x00, 0x19, // box size
+        0x6a, 0x75, 0x6d, 0x64, // box type = 'jumd'
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // UUID
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        0x00, // toggles
+    ];
+
+    let len = 8 + desc.len() + COPIES * C2PA_MANIFEST_STORE.len();
+
+    let mut jumbf = Vec::with_capacity(len);
+    jumbf.extend_from_slice(&(len as u32).to_be_bytes());
+    jumbf.extend_from_slice(b"jumb");
+    jumbf.extend_from_slice(&desc);
+
+    for _ in 0..COPIES {
+        jumbf.extend_from_slice(C2PA_MANIFEST_STORE);
+    }
+
+    jumbf
+}
+
+pub fn parse_parallel(c: &mut Criterion) {
+    let jumbf = scaled_manifest_store();
+
+    let mut group = c.benchmark_group("parse scaled C2PA Manifest Store");
+
+    group.bench_function("serial", |b| {
+        b.iter(|| SuperBox::from_slice(black_box(&jumbf)).unwrap());
+    });
+
+    group.bench_function("parallel", |b| {
+        b.iter(|| SuperBox::from_slice_parallel(black_box(&jumbf)).unwrap());
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, parse_parallel);
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+use std::io::Cursor;
+
+use codspeed_criterion_compat::{
+    black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput,
+};
+use jumbf::{
+    bench_support::{deep, huge_payload, to_jumbf, wide},
+    builder::SuperBoxBuilder,
+    parser::SuperBox,
+};
+
+const DEPTHS: [usize; 3] = [10, 100, 1000];
+const WIDTHS: [usize; 3] = [10, 1000, 10000];
+const PAYLOAD_LENS: [usize; 2] = [1 << 20, 16 << 20];
+
+fn bench_tree(
+    c: &mut Criterion,
+    name: &str,
+    sizes: &[usize],
+    generate: fn(usize) -> SuperBoxBuilder<'static>,
+) {
+    let mut group = c.benchmark_group(name);
+
+    for &size in sizes {
+        let sbox = generate(size);
+        let jumbf = to_jumbf(&sbox);
+        group.throughput(Throughput::Bytes(jumbf.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("parse", size), &jumbf, |b, jumbf| {
+            b.iter(|| SuperBox::from_slice(black_box(jumbf)).unwrap());
+        });
+
+        group.bench_with_input(BenchmarkId::new("write", size), &sbox, |b, sbox| {
+            b.iter(|| {
+                let mut out = Cursor::new(Vec::<u8>::with_capacity(jumbf.len()));
+                black_box(sbox).write_jumbf(&mut out).unwrap();
+                out
+            });
+        });
+    }
+
+    group.finish();
+}
+
+pub fn deep_trees(c: &mut Criterion) {
+    bench_tree(c, "deeply nested superboxes", &DEPTHS, deep);
+}
+
+pub fn wide_trees(c: &mut Criterion) {
+    bench_tree(c, "superbox with many children", &WIDTHS, wide);
+}
+
+pub fn huge_payloads(c: &mut Criterion) {
+    bench_tree(c, "superbox with huge payload", &PAYLOAD_LENS, huge_payload);
+}
+
+criterion_group!(benches, deep_trees, wide_trees, huge_payloads);
+criterion_main!(benches);
@@ -0,0 +1,186 @@
+#ifndef JUMBF_H
+#define JUMBF_H
+
+/* Generated by cbindgen from jumbf-capi. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Builds a JUMBF superbox.
+typedef struct JumbfBuilder JumbfBuilder;
+
+// A parsed JUMBF superbox.
+//
+// The superbox owns a copy of its JUMBF data, so the buffer that was passed
+// to [`jumbf_parse()`] may be released as soon as that function returns.
+typedef struct JumbfSuperBox JumbfSuperBox;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Parse `len` bytes starting at `data` as a JUMBF superbox.
+//
+// Returns `NULL` if the data is not a valid superbox. Any data following the
+// superbox is ignored. The result must be released via [`jumbf_free()`].
+//
+// # Safety
+//
+// `data` must point to at least `len` readable bytes.
+struct JumbfSuperBox *jumbf_parse(const uint8_t *data, size_t len);
+
+// Release a superbox returned by [`jumbf_parse()`] or
+// [`jumbf_find_by_label()`]. Does nothing if `sbox` is `NULL`.
+//
+// # Safety
+//
+// `sbox` must be `NULL` or a pointer returned by this API which has not
+// already been released.
+void jumbf_free(struct JumbfSuperBox *sbox);
+
+// Find a descendant superbox by label. Hierarchical labels separated by `/`
+// may be used, as in `SuperBox::find_by_label()`.
+//
+// Returns `NULL` if no unique matching superbox is found. The result must be
+// released via [`jumbf_free()`].
+//
+// # Safety
+//
+// `sbox` must be a valid superbox pointer and `label` must be a
+// NUL-terminated string.
+struct JumbfSuperBox *jumbf_find_by_label(const struct JumbfSuperBox *sbox, const char *label);
+
+// Return the label of a superbox as a newly-allocated, NUL-terminated
+// string.
+//
+// Returns `NULL` if the superbox has no label. The result must be released
+// via [`jumbf_string_free()`].
+//
+// # Safety
+//
+// `sbox` must be a valid superbox pointer.
+char *jumbf_get_label(const struct JumbfSuperBox *sbox);
+
+// Copy the 16-byte content type UUID of a superbox into `uuid`.
+//
+// Returns `false` if the superbox could not be read.
+//
+// # Safety
+//
+// `sbox` must be a valid superbox pointer and `uuid` must point to at least
+// 16 writable bytes.
+bool jumbf_get_uuid(const struct JumbfSuperBox *sbox, uint8_t *uuid);
+
+// Find the payload of the superbox's first child box, if that box is not
+// itself a superbox.
+//
+// On success, stores a pointer to the payload in `*data` and its length in
+// `*len` and returns `true`. The payload pointer remains valid until `sbox`
+// is released.
+//
+// # Safety
+//
+// `sbox` must be a valid superbox pointer; `data` and `len` must be valid
+// for writes.
+bool jumbf_get_payload(const struct JumbfSuperBox *sbox, const uint8_t **data, size_t *len);
+
+// Find the serialized form of a superbox.
+//
+// Stores a pointer to the JUMBF data in `*data` and its length in `*len`
+// and returns `true`. The pointer remains valid until `sbox` is released.
+//
+// # Safety
+//
+// `sbox` must be a valid superbox pointer; `data` and `len` must be valid
+// for writes.
+bool jumbf_get_bytes(const struct JumbfSuperBox *sbox, const uint8_t **data, size_t *len);
+
+// Release a string returned by this API. Does nothing if `s` is `NULL`.
+//
+// # Safety
+//
+// `s` must be `NULL` or a string returned by this API which has not already
+// been released.
+void jumbf_string_free(char *s);
+
+// Create a new, empty superbox with the 16-byte content type UUID found at
+// `uuid`.
+//
+// The result must be released via [`jumbf_builder_free()`], unless it is
+// passed to [`jumbf_builder_add_super_box()`].
+//
+// # Safety
+//
+// `uuid` must point to at least 16 readable bytes.
+struct JumbfBuilder *jumbf_builder_new(const uint8_t *uuid);
+
+// Set a requestable label for the superbox.
+//
+// Returns `false` if `label` is not valid UTF-8.
+//
+// # Safety
+//
+// `builder` must be a valid builder pointer and `label` must be a
+// NUL-terminated string.
+bool jumbf_builder_set_label(struct JumbfBuilder *builder, const char *label);
+
+// Add a child box with the four-byte box type found at `tbox` and a copy of
+// the `len`-byte payload found at `data`.
+//
+// # Safety
+//
+// `builder` must be a valid builder pointer, `tbox` must point to at least
+// 4 readable bytes, and `data` must point to at least `len` readable bytes.
+bool jumbf_builder_add_data_box(struct JumbfBuilder *builder,
+                                const uint8_t *tbox,
+                                const uint8_t *data,
+                                size_t len);
+
+// Add a child superbox.
+//
+// This takes ownership of `child`, which must not be used or released
+// afterwards, even if this function returns `false`.
+//
+// # Safety
+//
+// `builder` and `child` must be distinct, valid builder pointers.
+bool jumbf_builder_add_super_box(struct JumbfBuilder *builder, struct JumbfBuilder *child);
+
+// Serialize the superbox as JUMBF.
+//
+// On success, stores a pointer to a newly-allocated buffer in `*data` and
+// its length in `*len` and returns `true`. The buffer must be released via
+// [`jumbf_buffer_free()`].
+//
+// # Safety
+//
+// `builder` must be a valid builder pointer; `data` and `len` must be valid
+// for writes.
+bool jumbf_builder_build(const struct JumbfBuilder *builder, uint8_t **data, size_t *len);
+
+// Release a builder returned by [`jumbf_builder_new()`]. Does nothing if
+// `builder` is `NULL`.
+//
+// # Safety
+//
+// `builder` must be `NULL` or a pointer returned by this API which has not
+// already been released or added to another builder.
+void jumbf_builder_free(struct JumbfBuilder *builder);
+
+// Release a buffer returned by [`jumbf_builder_build()`]. Does nothing if
+// `data` is `NULL`.
+//
+// # Safety
+//
+// `data` and `len` must be exactly as returned by [`jumbf_builder_build()`]
+// and the buffer must not already have been released.
+void jumbf_buffer_free(uint8_t *data, size_t len);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* JUMBF_H */
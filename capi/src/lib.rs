@@ -0,0 +1,429 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+#![deny(clippy::expect_used)]
+#![deny(clippy::panic)]
+#![deny(clippy::unwrap_used)]
+#![deny(warnings)]
+
+//! A C API for the [`jumbf`] crate.
+//!
+//! The C declarations for these functions are in `include/jumbf.h`, which is
+//! generated by [cbindgen].
+//!
+//! All functions report failure by returning `NULL` or `false`. Objects
+//! returned by this API must be released with the corresponding `_free`
+//! function.
+//!
+//! [cbindgen]: https://github.com/mozilla/cbindgen
+
+#[cfg(test)]
+mod tests;
+
+use std::{
+    ffi::{c_char, CStr, CString},
+    io::Cursor,
+    ptr, slice,
+};
+
+use jumbf::{
+    builder::{DataBoxBuilder, SuperBoxBuilder},
+    parser::{ChildBox, SuperBox},
+    BoxType, Uuid,
+};
+
+/// A parsed JUMBF superbox.
+///
+/// The superbox owns a copy of its JUMBF data, so the buffer that was passed
+/// to [`jumbf_parse()`] may be released as soon as that function returns.
+pub struct JumbfSuperBox {
+    jumbf: Vec<u8>,
+}
+
+impl JumbfSuperBox {
+    fn super_box(&self) -> Option<SuperBox<'_>> {
+        SuperBox::from_slice(&self.jumbf).ok().map(|(_, sbox)| sbox)
+    }
+
+    fn into_raw(jumbf: &[u8]) -> *mut JumbfSuperBox {
+        Box::into_raw(Box::new(Self {
+            jumbf: jumbf.to_vec(),
+        }))
+    }
+}
+
+/// Parse `len` bytes starting at `data` as a JUMBF superbox.
+///
+/// Returns `NULL` if the data is not a valid superbox. Any data following the
+/// superbox is ignored. The result must be released via [`jumbf_free()`].
+///
+/// # Safety
+///
+/// `data` must point to at least `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn jumbf_parse(data: *const u8, len: usize) -> *mut JumbfSuperBox {
+    let Some(data) = byte_slice(data, len) else {
+        return ptr::null_mut();
+    };
+
+    match SuperBox::from_slice(data) {
+        Ok((_, sbox)) => JumbfSuperBox::into_raw(sbox.original),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Release a superbox returned by [`jumbf_parse()`] or
+/// [`jumbf_find_by_label()`]. Does nothing if `sbox` is `NULL`.
+///
+/// # Safety
+///
+/// `sbox` must be `NULL` or a pointer returned by this API which has not
+/// already been released.
+#[no_mangle]
+pub unsafe extern "C" fn jumbf_free(sbox: *mut JumbfSuperBox) {
+    if !sbox.is_null() {
+        drop(Box::from_raw(sbox));
+    }
+}
+
+/// Find a descendant superbox by label. Hierarchical labels separated by `/`
+/// may be used, as in `SuperBox::find_by_label()`.
+///
+/// Returns `NULL` if no unique matching superbox is found. The result must be
+/// released via [`jumbf_free()`].
+///
+/// # Safety
+///
+/// `sbox` must be a valid superbox pointer and `label` must be a
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn jumbf_find_by_label(
+    sbox: *const JumbfSuperBox,
+    label: *const c_char,
+) -> *mut JumbfSuperBox {
+    let (Some(sbox), Some(label)) = (sbox.as_ref(), c_str(label)) else {
+        return ptr::null_mut();
+    };
+
+    sbox.super_box()
+        .and_then(|sbox| {
+            sbox.find_by_label(label)
+                .map(|child| JumbfSuperBox::into_raw(child.original))
+        })
+        .unwrap_or(ptr::null_mut())
+}
+
+/// Return the label of a superbox as a newly-allocated, NUL-terminated
+/// string.
+///
+/// Returns `NULL` if the superbox has no label. The result must be released
+/// via [`jumbf_string_free()`].
+///
+/// # Safety
+///
+/// `sbox` must be a valid superbox pointer.
+#[no_mangle]
+pub unsafe extern "C" fn jumbf_get_label(sbox: *const JumbfSuperBox) -> *mut c_char {
+    sbox.as_ref()
+        .and_then(JumbfSuperBox::super_box)
+        .and_then(|sbox| sbox.desc.label.and_then(|label| CString::new(label).ok()))
+        .map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Copy the 16-byte content type UUID of a superbox into `uuid`.
+///
+/// Returns `false` if the superbox could not be read.
+///
+/// # Safety
+///
+/// `sbox` must be a valid superbox pointer and `uuid` must point to at least
+/// 16 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn jumbf_get_uuid(sbox: *const JumbfSuperBox, uuid: *mut u8) -> bool {
+    let Some(sbox) = sbox.as_ref().and_then(JumbfSuperBox::super_box) else {
+        return false;
+    };
+
+    if uuid.is_null() {
+        return false;
+    }
+
+    ptr::copy_nonoverlapping(sbox.desc.uuid.0.as_ptr(), uuid, 16);
+    true
+}
+
+/// Find the payload of the superbox's first child box, if that box is not
+/// itself a superbox.
+///
+/// On success, stores a pointer to the payload in `*data` and its length in
+/// `*len` and returns `true`. The payload pointer remains valid until `sbox`
+/// is released.
+///
+/// # Safety
+///
+/// `sbox` must be a valid superbox pointer; `data` and `len` must be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn jumbf_get_payload(
+    sbox: *const JumbfSuperBox,
+    data: *mut *const u8,
+    len: *mut usize,
+) -> bool {
+    if data.is_null() || len.is_null() {
+        return false;
+    }
+
+    let Some(sbox) = sbox.as_ref().and_then(JumbfSuperBox::super_box) else {
+        return false;
+    };
+
+    match sbox.child_boxes.first() {
+        Some(ChildBox::DataBox(dbox)) => {
+            *data = dbox.data.as_ptr();
+            *len = dbox.data.len();
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Find the serialized form of a superbox.
+///
+/// Stores a pointer to the JUMBF data in `*data` and its length in `*len`
+/// and returns `true`. The pointer remains valid until `sbox` is released.
+///
+/// # Safety
+///
+/// `sbox` must be a valid superbox pointer; `data` and `len` must be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn jumbf_get_bytes(
+    sbox: *const JumbfSuperBox,
+    data: *mut *const u8,
+    len: *mut usize,
+) -> bool {
+    let Some(sbox) = sbox.as_ref() else {
+        return false;
+    };
+
+    if data.is_null() || len.is_null() {
+        return false;
+    }
+
+    *data = sbox.jumbf.as_ptr();
+    *len = sbox.jumbf.len();
+    true
+}
+
+/// Release a string returned by this API. Does nothing if `s` is `NULL`.
+///
+/// # Safety
+///
+/// `s` must be `NULL` or a string returned by this API which has not already
+/// been released.
+#[no_mangle]
+pub unsafe extern "C" fn jumbf_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Builds a JUMBF superbox.
+pub struct JumbfBuilder {
+    sbox: SuperBoxBuilder<'static>,
+}
+
+impl JumbfBuilder {
+    fn update<F: FnOnce(SuperBoxBuilder<'static>) -> SuperBoxBuilder<'static>>(&mut self, f: F) {
+        let sbox = std::mem::replace(&mut self.sbox, SuperBoxBuilder::new([0; 16]));
+        self.sbox = f(sbox);
+    }
+}
+
+/// Create a new, empty superbox with the 16-byte content type UUID found at
+/// `uuid`.
+///
+/// The result must be released via [`jumbf_builder_free()`], unless it is
+/// passed to [`jumbf_builder_add_super_box()`].
+///
+/// # Safety
+///
+/// `uuid` must point to at least 16 readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn jumbf_builder_new(uuid: *const u8) -> *mut JumbfBuilder {
+    let Some(uuid) = byte_slice(uuid, 16).and_then(|uuid| <[u8; 16]>::try_from(uuid).ok()) else {
+        return ptr::null_mut();
+    };
+
+    Box::into_raw(Box::new(JumbfBuilder {
+        sbox: SuperBoxBuilder::new(Uuid(uuid)),
+    }))
+}
+
+/// Set a requestable label for the superbox.
+///
+/// Returns `false` if `label` is not valid UTF-8.
+///
+/// # Safety
+///
+/// `builder` must be a valid builder pointer and `label` must be a
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn jumbf_builder_set_label(
+    builder: *mut JumbfBuilder,
+    label: *const c_char,
+) -> bool {
+    let (Some(builder), Some(label)) = (builder.as_mut(), c_str(label)) else {
+        return false;
+    };
+
+    builder.update(|sbox| sbox.set_label(label));
+    true
+}
+
+/// Add a child box with the four-byte box type found at `tbox` and a copy of
+/// the `len`-byte payload found at `data`.
+///
+/// # Safety
+///
+/// `builder` must be a valid builder pointer, `tbox` must point to at least
+/// 4 readable bytes, and `data` must point to at least `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn jumbf_builder_add_data_box(
+    builder: *mut JumbfBuilder,
+    tbox: *const u8,
+    data: *const u8,
+    len: usize,
+) -> bool {
+    let Some(builder) = builder.as_mut() else {
+        return false;
+    };
+
+    let Some(tbox) = byte_slice(tbox, 4).and_then(|tbox| <[u8; 4]>::try_from(tbox).ok()) else {
+        return false;
+    };
+
+    let Some(data) = byte_slice(data, len) else {
+        return false;
+    };
+
+    let dbox = DataBoxBuilder::from_owned(BoxType(tbox), data.to_vec());
+    builder.update(|sbox| sbox.add_child_box(dbox));
+    true
+}
+
+/// Add a child superbox.
+///
+/// This takes ownership of `child`, which must not be used or released
+/// afterwards, even if this function returns `false`.
+///
+/// # Safety
+///
+/// `builder` and `child` must be distinct, valid builder pointers.
+#[no_mangle]
+pub unsafe extern "C" fn jumbf_builder_add_super_box(
+    builder: *mut JumbfBuilder,
+    child: *mut JumbfBuilder,
+) -> bool {
+    if child.is_null() {
+        return false;
+    }
+
+    let child = Box::from_raw(child);
+
+    let Some(builder) = builder.as_mut() else {
+        return false;
+    };
+
+    builder.update(|sbox| sbox.add_child_box(child.sbox));
+    true
+}
+
+/// Serialize the superbox as JUMBF.
+///
+/// On success, stores a pointer to a newly-allocated buffer in `*data` and
+/// its length in `*len` and returns `true`. The buffer must be released via
+/// [`jumbf_buffer_free()`].
+///
+/// # Safety
+///
+/// `builder` must be a valid builder pointer; `data` and `len` must be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn jumbf_builder_build(
+    builder: *const JumbfBuilder,
+    data: *mut *mut u8,
+    len: *mut usize,
+) -> bool {
+    let Some(builder) = builder.as_ref() else {
+        return false;
+    };
+
+    if data.is_null() || len.is_null() {
+        return false;
+    }
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    if builder.sbox.write_jumbf(&mut jumbf).is_err() {
+        return false;
+    }
+
+    let jumbf = jumbf.into_inner().into_boxed_slice();
+    *len = jumbf.len();
+    *data = Box::into_raw(jumbf).cast::<u8>();
+    true
+}
+
+/// Release a builder returned by [`jumbf_builder_new()`]. Does nothing if
+/// `builder` is `NULL`.
+///
+/// # Safety
+///
+/// `builder` must be `NULL` or a pointer returned by this API which has not
+/// already been released or added to another builder.
+#[no_mangle]
+pub unsafe extern "C" fn jumbf_builder_free(builder: *mut JumbfBuilder) {
+    if !builder.is_null() {
+        drop(Box::from_raw(builder));
+    }
+}
+
+/// Release a buffer returned by [`jumbf_builder_build()`]. Does nothing if
+/// `data` is `NULL`.
+///
+/// # Safety
+///
+/// `data` and `len` must be exactly as returned by [`jumbf_builder_build()`]
+/// and the buffer must not already have been released.
+#[no_mangle]
+pub unsafe extern "C" fn jumbf_buffer_free(data: *mut u8, len: usize) {
+    if !data.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(data, len)));
+    }
+}
+
+unsafe fn byte_slice<'a>(data: *const u8, len: usize) -> Option<&'a [u8]> {
+    if data.is_null() {
+        None
+    } else {
+        Some(slice::from_raw_parts(data, len))
+    }
+}
+
+unsafe fn c_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        None
+    } else {
+        CStr::from_ptr(s).to_str().ok()
+    }
+}
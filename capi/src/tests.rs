@@ -0,0 +1,113 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+#![allow(clippy::unwrap_used)]
+
+use std::{ffi::CStr, ptr, slice};
+
+use crate::*;
+
+const JSON_UUID: [u8; 16] = *b"json\x00\x11\x00\x10\x80\x00\x00\xaa\x00\x38\x9b\x71";
+
+unsafe fn build_manifest() -> Vec<u8> {
+    let child = jumbf_builder_new(JSON_UUID.as_ptr());
+    assert!(jumbf_builder_set_label(
+        child,
+        b"c2pa.assertion\0".as_ptr().cast()
+    ));
+    assert!(jumbf_builder_add_data_box(
+        child,
+        b"json".as_ptr(),
+        b"{}".as_ptr(),
+        2
+    ));
+
+    let builder = jumbf_builder_new([0u8; 16].as_ptr());
+    assert!(jumbf_builder_set_label(builder, b"c2pa\0".as_ptr().cast()));
+    assert!(jumbf_builder_add_super_box(builder, child));
+
+    let mut data: *mut u8 = ptr::null_mut();
+    let mut len: usize = 0;
+    assert!(jumbf_builder_build(builder, &mut data, &mut len));
+    jumbf_builder_free(builder);
+
+    let jumbf = slice::from_raw_parts(data, len).to_vec();
+    jumbf_buffer_free(data, len);
+    jumbf
+}
+
+#[test]
+fn round_trip() {
+    unsafe {
+        let jumbf = build_manifest();
+
+        let sbox = jumbf_parse(jumbf.as_ptr(), jumbf.len());
+        assert!(!sbox.is_null());
+
+        let label = jumbf_get_label(sbox);
+        assert_eq!(CStr::from_ptr(label).to_str().unwrap(), "c2pa");
+        jumbf_string_free(label);
+
+        let mut data: *const u8 = ptr::null();
+        let mut len: usize = 0;
+        assert!(jumbf_get_bytes(sbox, &mut data, &mut len));
+        assert_eq!(slice::from_raw_parts(data, len), jumbf.as_slice());
+        assert!(!jumbf_get_payload(sbox, &mut data, &mut len));
+
+        let child = jumbf_find_by_label(sbox, b"c2pa.assertion\0".as_ptr().cast());
+        assert!(!child.is_null());
+
+        let mut uuid = [0u8; 16];
+        assert!(jumbf_get_uuid(child, uuid.as_mut_ptr()));
+        assert_eq!(uuid, JSON_UUID);
+
+        assert!(jumbf_get_payload(child, &mut data, &mut len));
+        assert_eq!(slice::from_raw_parts(data, len), b"{}");
+
+        assert!(jumbf_find_by_label(sbox, b"c2pa.missing\0".as_ptr().cast()).is_null());
+
+        jumbf_free(child);
+        jumbf_free(sbox);
+    }
+}
+
+#[test]
+fn invalid_input() {
+    unsafe {
+        assert!(jumbf_parse(ptr::null(), 0).is_null());
+        assert!(jumbf_parse(b"not jumbf".as_ptr(), 9).is_null());
+        assert!(jumbf_get_label(ptr::null()).is_null());
+        assert!(jumbf_builder_new(ptr::null()).is_null());
+
+        let builder = jumbf_builder_new([0u8; 16].as_ptr());
+        assert!(!jumbf_builder_add_data_box(
+            builder,
+            ptr::null(),
+            ptr::null(),
+            0
+        ));
+        assert!(jumbf_builder_set_label(
+            builder,
+            b"bad/label\0".as_ptr().cast()
+        ));
+
+        let mut data: *mut u8 = ptr::null_mut();
+        let mut len: usize = 0;
+        assert!(!jumbf_builder_build(builder, &mut data, &mut len));
+        jumbf_builder_free(builder);
+
+        jumbf_free(ptr::null_mut());
+        jumbf_string_free(ptr::null_mut());
+        jumbf_buffer_free(ptr::null_mut(), 0);
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Planning the storage of JUMBF in JPEG APP11 marker segments.
+//!
+//! A JPEG marker segment can hold at most 65,535 bytes, so a larger JUMBF
+//! superbox must be split across several APP11 segments as described in
+//! ISO/IEC 18477-3 (JPEG XT box file format) and ISO/IEC 19566-5. Each
+//! segment contains:
+//!
+//! * the APP11 marker (`0xFFEB`),
+//! * the segment length (`Le`), which counts every following byte in the
+//!   segment plus the two bytes of the length itself,
+//! * the common identifier `JP`,
+//! * a two-byte box instance number (`En`), which is the same for every segment
+//!   holding a given box,
+//! * a four-byte packet sequence number (`Z`), starting at 1,
+//! * a copy of the box's header (`LBox`, `TBox`, and `XLBox` if present), and
+//! * the next portion of the box's payload.
+//!
+//! [`plan_app11_segments()`] computes these segments without copying the
+//! payload.
+
+use alloc::{vec, vec::Vec};
+use core::ops::Range;
+
+use crate::parser::{DataBox, Error};
+
+/// The largest possible value of a JPEG marker segment's length field.
+pub const MAX_SEGMENT_LEN: u16 = u16::MAX;
+
+// Bytes in the segment length, common identifier, box instance number, and
+// packet sequence number fields.
+const FIXED_OVERHEAD: usize = 2 + 2 + 2 + 4;
+
+/// A single APP11 marker segment holding a portion of a JUMBF box.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct App11Segment<'a> {
+    /// Everything in the segment which precedes the payload, starting with
+    /// the APP11 marker and ending with the copy of the box header.
+    pub header: Vec<u8>,
+
+    /// The portion of the box's payload carried by this segment.
+    pub payload: &'a [u8],
+
+    /// The range of `payload` within the JUMBF that was planned.
+    pub range: Range<usize>,
+}
+
+impl<'a> App11Segment<'a> {
+    /// Returns the total size of this segment (including the APP11 marker).
+    pub fn len(&self) -> usize {
+        self.header.len() + self.payload.len()
+    }
+
+    /// Returns `true` if this segment carries no payload.
+    pub fn is_empty(&self) -> bool {
+        self.payload.is_empty()
+    }
+}
+
+/// Plan the APP11 marker segments needed to store the JUMBF box at the start
+/// of `jumbf`, using segments of the largest possible size. Any data
+/// following that box is ignored.
+///
+/// `box_instance` is written as the `En` field of each segment and must
+/// differ from that of any other box stored in the same JPEG file.
+///
+/// The segments should be written to the JPEG file in the order returned.
+///
+/// ## Example
+///
+/// ```
+/// use hex_literal::hex;
+/// use jumbf::app11::plan_app11_segments;
+///
+/// let jumbf = hex!(
+///     "0000002f" // box size
+///     "6a756d62" // box type = 'jumb'
+///         "00000027" // box size
+///         "6a756d64" // box type = 'jumd'
+///         "00000000000000000000000000000000" // UUID
+///         "03" // toggles
+///         "746573742e7375706572626f7800" // label
+/// );
+///
+/// let segments = plan_app11_segments(&jumbf, 1).unwrap();
+/// assert_eq!(segments.len(), 1);
+///
+/// assert_eq!(
+///     segments[0].header,
+///     hex!(
+///         "ffeb" // APP11 marker
+///         "0039" // segment length
+///         "4a50" // common identifier = 'JP'
+///         "0001" // box instance number
+///         "00000001" // packet sequence number
+///         "0000002f" // box size
+///         "6a756d62" // box type = 'jumb'
+///     )
+/// );
+///
+/// assert_eq!(segments[0].payload, &jumbf[8..]);
+/// ```
+pub fn plan_app11_segments(
+    jumbf: &[u8],
+    box_instance: u16,
+) -> Result<Vec<App11Segment<'_>>, Error> {
+    plan_app11_segments_with_limit(jumbf, box_instance, MAX_SEGMENT_LEN)
+}
+
+/// Plan the APP11 marker segments needed to store the JUMBF box at the start
+/// of `jumbf`, as described for [`plan_app11_segments()`], but with no
+/// segment's length field exceeding `max_segment_len`.
+///
+/// This is useful when a container imposes a tighter limit than the JPEG
+/// format itself. `max_segment_len` is raised if necessary so that each
+/// segment can carry at least one byte of payload.
+pub fn plan_app11_segments_with_limit(
+    jumbf: &[u8],
+    box_instance: u16,
+    max_segment_len: u16,
+) -> Result<Vec<App11Segment<'_>>, Error> {
+    let (_, data_box) = DataBox::from_slice(jumbf)?;
+
+    let box_header = &data_box.original[..data_box.original.len() - data_box.data.len()];
+    let overhead = FIXED_OVERHEAD + box_header.len();
+    let max_payload = usize::from(max_segment_len).saturating_sub(overhead).max(1);
+
+    let mut segments = vec![];
+    let mut start = box_header.len();
+
+    // A box with an empty payload still needs one segment to hold its header.
+    let chunks: Vec<&[u8]> = if data_box.data.is_empty() {
+        vec![data_box.data]
+    } else {
+        data_box.data.chunks(max_payload).collect()
+    };
+
+    for (index, payload) in chunks.into_iter().enumerate() {
+        let len = u16::try_from(overhead + payload.len()).unwrap_or(u16::MAX);
+        let sequence = u32::try_from(index + 1).unwrap_or(u32::MAX);
+
+        let mut header = Vec::with_capacity(2 + overhead);
+        header.extend_from_slice(&[0xff, 0xeb]);
+        header.extend_from_slice(&len.to_be_bytes());
+        header.extend_from_slice(b"JP");
+        header.extend_from_slice(&box_instance.to_be_bytes());
+        header.extend_from_slice(&sequence.to_be_bytes());
+        header.extend_from_slice(box_header);
+
+        segments.push(App11Segment {
+            header,
+            payload,
+            range: start..start + payload.len(),
+        });
+
+        start += payload.len();
+    }
+
+    Ok(segments)
+}
@@ -0,0 +1,91 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Generators for synthetic JUMBF data structures of arbitrary scale, for use
+//! in benchmarks.
+//!
+//! Each generator returns a [`SuperBoxBuilder`], so that benchmarks can
+//! measure writing as well as parsing. [`to_jumbf()`] serializes a generated
+//! superbox:
+//!
+//! ```
+//! use jumbf::{bench_support, parser::SuperBox};
+//!
+//! let jumbf = bench_support::to_jumbf(&bench_support::wide(1000));
+//! let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+//! assert_eq!(sbox.child_boxes.len(), 1000);
+//! ```
+
+use std::io::Cursor;
+
+use crate::{
+    builder::{DataBoxBuilder, SuperBoxBuilder},
+    BoxType, Uuid,
+};
+
+/// Box type used for data boxes generated by this module.
+pub const DATA_BOX_TYPE: BoxType = BoxType(*b"abcd");
+
+/// Content type UUID used for superboxes generated by this module.
+const UUID: Uuid = Uuid([0; 16]);
+
+/// Length of the payload of each data box generated by [`deep()`] and
+/// [`wide()`].
+const SMALL_PAYLOAD_LEN: usize = 16;
+
+/// A chain of `depth` nested superboxes. The innermost superbox contains a
+/// single small data box.
+///
+/// If `depth` is 0, it is treated as 1.
+pub fn deep(depth: usize) -> SuperBoxBuilder<'static> {
+    let mut sbox = SuperBoxBuilder::new(UUID).add_child_box(small_data_box());
+
+    for _ in 1..depth {
+        sbox = SuperBoxBuilder::new(UUID).add_child_box(sbox);
+    }
+
+    sbox
+}
+
+/// A single superbox which contains `children` small data boxes.
+pub fn wide(children: usize) -> SuperBoxBuilder<'static> {
+    (0..children).fold(SuperBoxBuilder::new(UUID), |sbox, _| {
+        sbox.add_child_box(small_data_box())
+    })
+}
+
+/// A single superbox which contains one data box with a payload of `len`
+/// bytes.
+pub fn huge_payload(len: usize) -> SuperBoxBuilder<'static> {
+    SuperBoxBuilder::new(UUID)
+        .add_child_box(DataBoxBuilder::from_owned(DATA_BOX_TYPE, vec![0x5a; len]))
+}
+
+/// Serialize a superbox produced by one of the generators in this module.
+///
+/// ## Panics
+///
+/// Panics if the superbox can not be written, which does not happen for any
+/// superbox generated by this module.
+#[allow(clippy::panic)]
+pub fn to_jumbf(sbox: &SuperBoxBuilder) -> Vec<u8> {
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    if let Err(e) = sbox.write_jumbf(&mut jumbf) {
+        panic!("Unable to write generated JUMBF: {e}");
+    }
+    jumbf.into_inner()
+}
+
+fn small_data_box() -> DataBoxBuilder<'static> {
+    DataBoxBuilder::from_borrowed(DATA_BOX_TYPE, &[0x5a; SMALL_PAYLOAD_LEN])
+}
@@ -0,0 +1,192 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+#![deny(clippy::expect_used)]
+#![deny(clippy::panic)]
+#![deny(clippy::unwrap_used)]
+#![deny(warnings)]
+
+//! Command-line tool for inspecting and building JUMBF data structures.
+
+#[cfg(test)]
+mod tests;
+
+use std::{
+    error::Error,
+    fs,
+    io::Cursor,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Parser, Subcommand};
+use jumbf::{
+    builder::load_manifest,
+    parser::{ChildBox, SuperBox},
+};
+
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+/// Inspect and build JUMBF (ISO/IEC 19566-5) data structures.
+#[derive(Debug, Parser)]
+#[command(name = "jumbf-cli", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print the box structure of a JUMBF file as an indented tree.
+    Dump {
+        /// JUMBF file to read.
+        file: PathBuf,
+    },
+
+    /// Write the payload of a labeled superbox's first data box to a file.
+    Extract {
+        /// JUMBF file to read.
+        file: PathBuf,
+
+        /// Label of the superbox to extract. Use `/` to separate the labels
+        /// of nested superboxes.
+        label: String,
+
+        /// File to write the payload to.
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Verify the SHA-256 hash of every superbox whose description box
+    /// contains one.
+    ///
+    /// The hash covers the superbox's child boxes (everything after the
+    /// description box) as serialized.
+    VerifyHashes {
+        /// JUMBF file to read.
+        file: PathBuf,
+    },
+
+    /// Build a JUMBF file from a manifest description file.
+    ///
+    /// Each line of the description describes one box, nested by indenting
+    /// two spaces per level. A superbox is written as `jumb <uuid> [label]`.
+    /// Any other box is written as `<box type> <file>`; its payload is read
+    /// from the named file, relative to the description file. Blank lines and
+    /// lines beginning with `#` are ignored.
+    Build {
+        /// Manifest description file to read.
+        description: PathBuf,
+
+        /// JUMBF file to write.
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Run a command. Returns `false` if the command completed but found a
+/// problem (i.e. a hash mismatch).
+fn run(cli: Cli) -> Result<bool> {
+    match cli.command {
+        Command::Dump { file } => {
+            let jumbf = fs::read(file)?;
+            print!("{}", parse(&jumbf)?.display_tree());
+            Ok(true)
+        }
+
+        Command::Extract {
+            file,
+            label,
+            output,
+        } => {
+            let jumbf = fs::read(file)?;
+            fs::write(output, extract(&parse(&jumbf)?, &label)?)?;
+            Ok(true)
+        }
+
+        Command::VerifyHashes { file } => {
+            let jumbf = fs::read(file)?;
+            let results = verify_hashes(&parse(&jumbf)?);
+
+            for (path, ok) in &results {
+                println!("{} {path}", if *ok { "OK  " } else { "FAIL" });
+            }
+
+            if results.is_empty() {
+                println!("No hashes found");
+            }
+
+            Ok(results.iter().all(|(_, ok)| *ok))
+        }
+
+        Command::Build {
+            description,
+            output,
+        } => {
+            fs::write(output, build(&description)?)?;
+            Ok(true)
+        }
+    }
+}
+
+fn parse(jumbf: &[u8]) -> Result<SuperBox<'_>> {
+    let (_, sbox) = SuperBox::from_slice(jumbf)?;
+    Ok(sbox)
+}
+
+fn extract<'a>(sbox: &SuperBox<'a>, label: &str) -> Result<&'a [u8]> {
+    let child = sbox
+        .find_by_label(label)
+        .ok_or_else(|| format!("No unique superbox labeled {label:?}"))?;
+
+    child
+        .child_boxes
+        .iter()
+        .find_map(|child| match child {
+            ChildBox::DataBox(dbox) => Some(dbox.data),
+            ChildBox::SuperBox(_) => None,
+        })
+        .ok_or_else(|| format!("Superbox {label:?} contains no data box").into())
+}
+
+/// Check the hash of `sbox` and each of its descendants. Returns the path to
+/// each superbox which has a hash, and whether that hash matched.
+fn verify_hashes(sbox: &SuperBox) -> Vec<(String, bool)> {
+    sbox.integrity_report()
+        .hashes
+        .into_iter()
+        .map(|check| {
+            let ok = check.is_valid();
+            (check.path, ok)
+        })
+        .collect()
+}
+
+fn build(description: &Path) -> Result<Vec<u8>> {
+    let sbox = load_manifest(description)?;
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf)?;
+    Ok(jumbf.into_inner())
+}
@@ -0,0 +1,93 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+#![allow(clippy::expect_used)]
+#![allow(clippy::panic)]
+#![allow(clippy::unwrap_used)]
+
+use std::io::{self, Cursor};
+
+use jumbf::{
+    builder::{parse_manifest, DataBoxBuilder, SuperBoxBuilder},
+    BoxType,
+};
+use sha2::{Digest, Sha256};
+
+use crate::{extract, parse, verify_hashes};
+
+const MANIFEST: &str = "
+# A simple manifest
+jumb 00000000-0000-0000-0000-000000000000 c2pa
+  jumb 6a736f6e-0011-0010-8000-00aa00389b71 c2pa.assertion
+    json assertion.json
+
+  jumb 00000000-0000-0000-0000-000000000000 c2pa.signature
+    uuid signature.bin
+";
+
+fn read_file(path: &str) -> io::Result<Vec<u8>> {
+    match path {
+        "assertion.json" => Ok(b"{}".to_vec()),
+        "signature.bin" => Ok(b"signature".to_vec()),
+        _ => Err(io::Error::new(io::ErrorKind::NotFound, "no such file")),
+    }
+}
+
+fn to_jumbf(sbox: &SuperBoxBuilder) -> Vec<u8> {
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+    jumbf.into_inner()
+}
+
+#[test]
+fn build_and_extract() {
+    let jumbf = to_jumbf(&parse_manifest(MANIFEST, read_file).unwrap());
+    let sbox = parse(&jumbf).unwrap();
+
+    assert_eq!(sbox.desc.label, Some("c2pa"));
+    assert_eq!(sbox.child_boxes.len(), 2);
+
+    assert_eq!(extract(&sbox, "c2pa.assertion").unwrap(), b"{}");
+    assert_eq!(extract(&sbox, "c2pa.signature").unwrap(), b"signature");
+    assert!(extract(&sbox, "c2pa.missing").is_err());
+    assert!(extract(&sbox, "").is_err());
+}
+
+#[test]
+fn hashes() {
+    let payload = b"some data";
+
+    let mut dbox = hex_literal::hex!("00000011 61626364").to_vec();
+    dbox.extend_from_slice(payload);
+    let hash: [u8; 32] = Sha256::digest(&dbox).into();
+
+    let sbox = SuperBoxBuilder::new([0; 16]).add_child_box(
+        SuperBoxBuilder::new([0; 16])
+            .set_label("hashed")
+            .set_sha256_hash(&hash)
+            .add_child_box(DataBoxBuilder::from_borrowed(BoxType(*b"abcd"), payload)),
+    );
+
+    let mut jumbf = to_jumbf(&sbox);
+    assert_eq!(
+        verify_hashes(&parse(&jumbf).unwrap()),
+        vec![("(root)/hashed".to_owned(), true)]
+    );
+
+    let last = jumbf.len() - 1;
+    jumbf[last] ^= 0xff;
+    assert_eq!(
+        verify_hashes(&parse(&jumbf).unwrap()),
+        vec![("(root)/hashed".to_owned(), false)]
+    );
+}
@@ -11,7 +11,10 @@
 // specific language governing permissions and limitations under
 // each license.
 
-use std::fmt::{Debug, Error, Formatter};
+use core::{
+    fmt::{Debug, Display, Error, Formatter},
+    str::FromStr,
+};
 
 /// A JUMBF "box type" is encoded as a 4-byte big-endian
 /// unsigned integer. However, boxes are generally referred to by an
@@ -21,9 +24,101 @@ use std::fmt::{Debug, Error, Formatter};
 ///
 /// The box type can typically be matched with a byte string constant (i.e.
 /// `b"jumd"`).
+///
+/// A `BoxType` can be defined as a constant via [`BoxType::new()`] or the
+/// [`box_type!`] macro, which checks at compile time that the string is
+/// exactly four bytes long:
+///
+/// ```
+/// use jumbf::{box_type, BoxType};
+///
+/// const JSON: BoxType = box_type!("json");
+/// assert_eq!(JSON, BoxType::new(b"json"));
+/// assert_eq!(JSON.to_string(), "json");
+/// assert_eq!("json".parse::<BoxType>().unwrap(), JSON);
+/// ```
+///
+/// [`box_type!`]: crate::box_type!
 #[derive(Clone, Copy, Eq, PartialEq)]
 pub struct BoxType(pub [u8; 4]);
 
+impl BoxType {
+    /// Binary data box, which holds the content of an embedded file
+    /// (`b"bidb"`).
+    pub const BINARY_DATA: BoxType = BoxType(*b"bidb");
+    /// CBOR content box (`b"cbor"`).
+    pub const CBOR: BoxType = BoxType(*b"cbor");
+    /// Contiguous codestream box (`b"jp2c"`).
+    pub const CODESTREAM: BoxType = BoxType(*b"jp2c");
+    /// JUMBF description box (`b"jumd"`).
+    pub const DESCRIPTION: BoxType = BoxType(*b"jumd");
+    /// Embedded file description box (`b"bfdb"`).
+    pub const EMBEDDED_FILE_DESCRIPTION: BoxType = BoxType(*b"bfdb");
+    /// ISO base media file format free space box (`b"free"`).
+    pub const FREE: BoxType = BoxType(*b"free");
+    /// JSON content box (`b"json"`).
+    pub const JSON: BoxType = BoxType(*b"json");
+    /// JUMBF superbox (`b"jumb"`).
+    pub const SUPER_BOX: BoxType = BoxType(*b"jumb");
+    /// UUID box (`b"uuid"`).
+    pub const UUID: BoxType = BoxType(*b"uuid");
+    /// XML content box (`b"xml "`).
+    pub const XML: BoxType = BoxType(*b"xml ");
+
+    /// Create a box type from its four-byte representation.
+    pub const fn new(t: &[u8; 4]) -> Self {
+        Self(*t)
+    }
+
+    /// Returns `true` if this is one of the box types defined by ISO/IEC
+    /// 19566-5 (or the ISO base media file format `free` box), all of which
+    /// are available as associated constants (i.e. [`BoxType::JSON`]).
+    pub fn is_standard(&self) -> bool {
+        matches!(
+            *self,
+            Self::BINARY_DATA
+                | Self::CBOR
+                | Self::CODESTREAM
+                | Self::DESCRIPTION
+                | Self::EMBEDDED_FILE_DESCRIPTION
+                | Self::FREE
+                | Self::JSON
+                | Self::SUPER_BOX
+                | Self::UUID
+                | Self::XML
+        )
+    }
+
+    // Implementation detail of the `box_type!` macro. Panics (and thus fails
+    // to compile when evaluated in a const context) if `s` is not exactly
+    // four bytes long.
+    #[doc(hidden)]
+    #[allow(clippy::panic)]
+    pub const fn from_literal(s: &str) -> Self {
+        let s = s.as_bytes();
+        if s.len() != 4 {
+            panic!("Box type must be exactly four bytes");
+        }
+        Self([s[0], s[1], s[2], s[3]])
+    }
+}
+
+/// Create a [`BoxType`] constant from a string literal.
+///
+/// The literal must be exactly four bytes long. This is checked at compile
+/// time:
+///
+/// ```compile_fail
+/// let tbox = jumbf::box_type!("jpeg2000");
+/// ```
+#[macro_export]
+macro_rules! box_type {
+    ($s:literal) => {{
+        const TBOX: $crate::BoxType = $crate::BoxType::from_literal($s);
+        TBOX
+    }};
+}
+
 impl Debug for BoxType {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         if self.0.iter().all(|c| *c >= 0x20 && *c <= 0x7e) {
@@ -42,6 +137,34 @@ impl Debug for BoxType {
     }
 }
 
+/// Displays the box type as four characters.
+///
+/// Any byte which is not printable ASCII is written as a `\xNN` escape.
+impl Display for BoxType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        for c in self.0 {
+            if (0x20..=0x7e).contains(&c) {
+                write!(f, "{}", c as char)?;
+            } else {
+                write!(f, "\\x{c:02x}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for BoxType {
+    type Err = ParseBoxTypeError;
+
+    /// Parse a box type from a string which must be exactly four bytes long
+    /// (i.e. `"json"`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        <[u8; 4]>::try_from(s.as_bytes())
+            .map(Self)
+            .map_err(|_| ParseBoxTypeError(()))
+    }
+}
+
 impl From<&[u8]> for BoxType {
     fn from(t: &[u8]) -> Self {
         let mut tbox = *b"    ";
@@ -58,8 +181,35 @@ impl From<&[u8; 4]> for BoxType {
     }
 }
 
+/// An error which can be returned when parsing a [`BoxType`] from a string.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseBoxTypeError(());
+
+impl Display for ParseBoxTypeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.write_str("Box type must be exactly four bytes")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseBoxTypeError {}
+
 /// Box type for JUMBF description box (`b"jumd"`).
-pub const DESCRIPTION_BOX_TYPE: BoxType = BoxType(*b"jumd");
+pub const DESCRIPTION_BOX_TYPE: BoxType = BoxType::DESCRIPTION;
 
 /// Box type for JUMBF super box (`b"jumb"`).
-pub const SUPER_BOX_TYPE: BoxType = BoxType(*b"jumb");
+pub const SUPER_BOX_TYPE: BoxType = BoxType::SUPER_BOX;
+
+/// Box type for JUMBF embedded file description box (`b"bfdb"`).
+pub const EMBEDDED_FILE_DESCRIPTION_BOX_TYPE: BoxType = BoxType::EMBEDDED_FILE_DESCRIPTION;
+
+/// Box type for JUMBF binary data box (`b"bidb"`).
+#[cfg(feature = "parser")]
+pub const BINARY_DATA_BOX_TYPE: BoxType = BoxType::BINARY_DATA;
+
+/// Box type for JUMBF XML content box (`b"xml "`).
+#[cfg(feature = "parser")]
+pub const XML_BOX_TYPE: BoxType = BoxType::XML;
+
+/// Box type for ISO base media file format free space box (`b"free"`).
+pub const FREE_BOX_TYPE: BoxType = BoxType::FREE;
@@ -0,0 +1,99 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::Result;
+
+use crate::{
+    builder::{ToBox, WriteAndSeek},
+    BoxType,
+};
+
+/// A `CallbackDataBox` allows you to build a JUMBF data box whose payload is
+/// produced by a closure at the time the box is written.
+///
+/// This is useful for large payloads (such as thumbnails or codestreams)
+/// which can be streamed directly into the output without first being
+/// buffered in memory.
+///
+/// The size of the payload must be declared up front. The closure must write
+/// exactly that many bytes; otherwise [`write_jumbf()`] will return an error.
+/// The closure may be invoked more than once if the enclosing JUMBF data
+/// structure is written more than once.
+///
+/// Like every [`ToBox`] implementation, the closure must be [`Send`] and
+/// [`Sync`] to be added to a [`SuperBoxBuilder`].
+///
+/// ## Example
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// use jumbf::{
+///     builder::{CallbackDataBox, SuperBoxBuilder},
+///     BoxType,
+/// };
+///
+/// let thumbnail = CallbackDataBox::new(BoxType(*b"bidb"), 4, |to_stream| {
+///     to_stream.write_all(b"\xff\xd8\xff\xd9")
+/// });
+///
+/// let sbox = SuperBoxBuilder::new([0; 16]).add_child_box(thumbnail);
+///
+/// let mut jumbf = Cursor::new(Vec::<u8>::new());
+/// sbox.write_jumbf(&mut jumbf).unwrap();
+///
+/// assert_eq!(&jumbf.get_ref()[41..], b"\xff\xd8\xff\xd9");
+/// ```
+///
+/// [`SuperBoxBuilder`]: crate::builder::SuperBoxBuilder
+/// [`write_jumbf()`]: crate::builder::SuperBoxBuilder::write_jumbf()
+pub struct CallbackDataBox<F>
+where
+    F: Fn(&mut dyn WriteAndSeek) -> Result<()>,
+{
+    tbox: BoxType,
+    size: usize,
+    callback: F,
+}
+
+impl<F> CallbackDataBox<F>
+where
+    F: Fn(&mut dyn WriteAndSeek) -> Result<()>,
+{
+    /// Create a `CallbackDataBox` from a JUMBF box type, the exact size of
+    /// the payload, and a closure which will write the payload.
+    pub fn new(tbox: BoxType, size: usize, callback: F) -> Self {
+        Self {
+            tbox,
+            size,
+            callback,
+        }
+    }
+}
+
+impl<F> ToBox for CallbackDataBox<F>
+where
+    F: Fn(&mut dyn WriteAndSeek) -> Result<()> + Send + Sync,
+{
+    fn box_type(&self) -> BoxType {
+        self.tbox
+    }
+
+    fn payload_size(&self) -> Result<usize> {
+        Ok(self.size)
+    }
+
+    fn write_payload(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
+        (self.callback)(to_stream)
+    }
+}
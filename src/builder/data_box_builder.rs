@@ -13,6 +13,8 @@
 
 use std::{borrow::Cow, io::Result};
 
+#[cfg(feature = "compress")]
+use crate::compression::Compression;
 use crate::{
     builder::{ToBox, WriteAndSeek},
     BoxType,
@@ -32,6 +34,7 @@ use crate::{
 /// a larger JUMBF data structure.
 ///
 /// [`SuperBoxBuilder`]: crate::builder::SuperBoxBuilder
+#[derive(Clone)]
 pub struct DataBoxBuilder<'a> {
     tbox: BoxType,
     data: Cow<'a, [u8]>,
@@ -58,6 +61,47 @@ impl<'a> DataBoxBuilder<'a> {
             data: Cow::from(data),
         }
     }
+
+    /// Create a `DataBoxBuilder` from a JUMBF box type and a byte slice,
+    /// which will be compressed using `compression`.
+    ///
+    /// The box type should be one which indicates to readers that the
+    /// payload is compressed. See [`DataBox::decompress()`] to read the
+    /// payload back.
+    ///
+    /// This function is available when the `compress` feature is enabled.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    ///
+    /// use jumbf::{
+    ///     box_type,
+    ///     builder::{DataBoxBuilder, SuperBoxBuilder},
+    ///     compression::Compression,
+    ///     parser::SuperBox,
+    /// };
+    ///
+    /// let payload = b"{\"a\": 1, \"b\": 1, \"c\": 1, \"d\": 1}";
+    ///
+    /// let dbox = DataBoxBuilder::compressed(box_type!("c2cb"), Compression::Brotli, payload).unwrap();
+    /// let sbox = SuperBoxBuilder::new([0; 16]).add_child_box(dbox);
+    ///
+    /// let mut jumbf = Cursor::new(Vec::<u8>::new());
+    /// sbox.write_jumbf(&mut jumbf).unwrap();
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(jumbf.get_ref()).unwrap();
+    /// let dbox = sbox.data_box().unwrap();
+    ///
+    /// assert_eq!(dbox.decompress(Compression::Brotli, 1024).unwrap(), payload);
+    /// ```
+    ///
+    /// [`DataBox::decompress()`]: crate::parser::DataBox::decompress
+    #[cfg(feature = "compress")]
+    pub fn compressed(tbox: BoxType, compression: Compression, data: &[u8]) -> Result<Self> {
+        Ok(Self::from_owned(tbox, compression.compress(data)?))
+    }
 }
 
 impl<'a> ToBox for DataBoxBuilder<'a> {
@@ -72,4 +116,11 @@ impl<'a> ToBox for DataBoxBuilder<'a> {
     fn write_payload(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
         to_stream.write_all(&self.data)
     }
+
+    fn try_clone_box(&self) -> Result<Box<dyn ToBox>>
+    where
+        Self: 'static,
+    {
+        Ok(Box::new(self.clone()))
+    }
 }
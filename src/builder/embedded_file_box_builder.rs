@@ -0,0 +1,134 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::Result;
+
+use crate::{
+    box_type::EMBEDDED_FILE_DESCRIPTION_BOX_TYPE,
+    builder::{ToBox, WriteAndSeek},
+    BoxType,
+};
+
+/// An `EmbeddedFileBoxBuilder` allows you to build a JUMBF embedded file
+/// description box (`bfdb`).
+///
+/// An embedded file superbox has the content type [`Uuid::EMBEDDED_FILE`]
+/// and contains an embedded file description box followed by a binary data
+/// box (`bidb`) which contains either the file content or, if the file is
+/// referenced externally, a URL for the file.
+///
+/// ## Example
+///
+/// ```
+/// # fn example() -> std::io::Result<()> {
+/// use std::io::Cursor;
+///
+/// use jumbf::{
+///     builder::{DataBoxBuilder, EmbeddedFileBoxBuilder, SuperBoxBuilder},
+///     BoxType, Uuid,
+/// };
+///
+/// let sbox = SuperBoxBuilder::new(Uuid::EMBEDDED_FILE)
+///     .set_label("thumbnail.jpeg")
+///     .add_child_box(EmbeddedFileBoxBuilder::new("image/jpeg").set_file_name("thumbnail.jpg"))
+///     .add_child_box(DataBoxBuilder::from_owned(
+///         BoxType(*b"bidb"),
+///         vec![0xff, 0xd8, 0xff, 0xe0],
+///     ));
+///
+/// let mut jumbf = Cursor::new(Vec::<u8>::new());
+/// sbox.write_jumbf(&mut jumbf)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Uuid::EMBEDDED_FILE`]: crate::Uuid::EMBEDDED_FILE
+#[derive(Clone)]
+pub struct EmbeddedFileBoxBuilder {
+    media_type: String,
+    file_name: Option<String>,
+    external: bool,
+}
+
+impl EmbeddedFileBoxBuilder {
+    /// Create a new embedded file description box for a file with the given
+    /// media (MIME) type.
+    pub fn new<S: AsRef<str>>(media_type: S) -> Self {
+        Self {
+            media_type: media_type.as_ref().to_owned(),
+            file_name: None,
+            external: false,
+        }
+    }
+
+    /// Set an optional file name for the embedded file.
+    pub fn set_file_name<S: AsRef<str>>(mut self, file_name: S) -> Self {
+        self.file_name = Some(file_name.as_ref().to_owned());
+        self
+    }
+
+    /// Specify whether the file is referenced externally.
+    ///
+    /// If `true`, the accompanying binary data box should contain a URL for
+    /// the file instead of the file content itself.
+    pub fn set_external(mut self, external: bool) -> Self {
+        self.external = external;
+        self
+    }
+}
+
+impl ToBox for EmbeddedFileBoxBuilder {
+    fn box_type(&self) -> BoxType {
+        EMBEDDED_FILE_DESCRIPTION_BOX_TYPE
+    }
+
+    fn payload_size(&self) -> Result<usize> {
+        Ok(1 + self.media_type.len()
+            + 1
+            + self
+                .file_name
+                .as_ref()
+                .map_or(0, |file_name| file_name.len() + 1))
+    }
+
+    fn write_payload(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
+        // Toggle bit 0 (0x01) indicates that a file name is present.
+        // Toggle bit 1 (0x02) indicates that the file is referenced externally.
+        let mut toggles = 0u8;
+        if self.file_name.is_some() {
+            toggles |= 0x01;
+        }
+        if self.external {
+            toggles |= 0x02;
+        }
+
+        to_stream.write_all(&[toggles])?;
+
+        to_stream.write_all(self.media_type.as_bytes())?;
+        to_stream.write_all(&[0u8])?;
+
+        if let Some(file_name) = self.file_name.as_ref() {
+            to_stream.write_all(file_name.as_bytes())?;
+            to_stream.write_all(&[0u8])?;
+        }
+
+        Ok(())
+    }
+
+    fn try_clone_box(&self) -> Result<Box<dyn ToBox>>
+    where
+        Self: 'static,
+    {
+        Ok(Box::new(self.clone()))
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fmt::{Display, Formatter};
+
+use crate::BoxType;
+
+/// The error type for JUMBF building operations which fail because the
+/// requested JUMBF data structure would not be valid.
+///
+/// Builder methods that write JUMBF return [`std::io::Error`]. When one of
+/// these validation errors occurs, it is returned as an [`std::io::Error`] of
+/// kind [`InvalidInput`] which wraps a `BuilderError`. The original
+/// `BuilderError` can be recovered via [`std::io::Error::get_ref()`] and
+/// [`downcast_ref()`].
+///
+/// [`InvalidInput`]: std::io::ErrorKind::InvalidInput
+/// [`downcast_ref()`]: std::error::Error#method.downcast_ref
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum BuilderError {
+    /// A superbox label contains a character which is not allowed.
+    ///
+    /// JUMBF labels may not contain the NUL character (which terminates the
+    /// label), the `/` character (which separates labels in a URI path), or
+    /// the `?` and `#` characters (which introduce the query and fragment
+    /// portions of a URI request).
+    InvalidLabelCharacter {
+        /// The label that was rejected.
+        label: String,
+
+        /// The first character in the label that is not allowed.
+        c: char,
+    },
+
+    /// A child box of a superbox built in [canonical mode] is of a type that
+    /// is not permitted in canonical output.
+    ///
+    /// Canonical output may not contain free space (`free`) boxes, since
+    /// they are optional padding.
+    ///
+    /// [canonical mode]: crate::builder::SuperBoxBuilder::set_canonical()
+    NonCanonicalChildBox {
+        /// The box type that was rejected.
+        tbox: BoxType,
+    },
+
+    /// Two requestable child superboxes of a superbox built in [strict mode]
+    /// share a label.
+    ///
+    /// A parser would be unable to find either superbox by that label.
+    ///
+    /// [strict mode]: crate::builder::SuperBoxBuilder::set_strict()
+    DuplicateLabel {
+        /// The label that appears more than once.
+        label: String,
+    },
+}
+
+impl Display for BuilderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Self::InvalidLabelCharacter { label, c } => {
+                write!(f, "Label {label:?} contains invalid character {c:?}")
+            }
+            Self::NonCanonicalChildBox { tbox } => {
+                write!(f, "Box type {tbox:?} is not permitted in canonical output")
+            }
+            Self::DuplicateLabel { label } => {
+                write!(
+                    f,
+                    "More than one requestable child superbox is labeled {label:?}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuilderError {}
+
+impl From<BuilderError> for std::io::Error {
+    fn from(e: BuilderError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, e)
+    }
+}
+
+/// Verify that `label` may be used as a JUMBF superbox label.
+pub(crate) fn validate_label(label: &str) -> Result<(), BuilderError> {
+    if let Some(c) = crate::label::invalid_character(label) {
+        Err(BuilderError::InvalidLabelCharacter {
+            label: label.to_owned(),
+            c,
+        })
+    } else {
+        Ok(())
+    }
+}
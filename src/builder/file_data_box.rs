@@ -0,0 +1,116 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{
+    fs::File,
+    io::{copy, Error, Read, Result, Seek, SeekFrom},
+    path::Path,
+};
+
+use crate::{
+    builder::{ToBox, WriteAndSeek},
+    BoxType,
+};
+
+/// A `FileDataBox` allows you to build a JUMBF data box whose payload is the
+/// content of a file.
+///
+/// The file's content is streamed into the JUMBF data structure when it is
+/// written, so even very large files can be embedded without loading them
+/// into memory. The payload size is taken from the file's metadata.
+///
+/// The file must not change size between the time the enclosing superbox
+/// computes its size and the time the file's content is written; otherwise
+/// [`write_jumbf()`] will return an error.
+///
+/// ## Example
+///
+/// ```
+/// # fn example() -> std::io::Result<()> {
+/// use std::io::Cursor;
+///
+/// use jumbf::{
+///     builder::{EmbeddedFileBoxBuilder, FileDataBox, SuperBoxBuilder},
+///     BoxType, Uuid,
+/// };
+///
+/// let sbox = SuperBoxBuilder::new(Uuid::EMBEDDED_FILE)
+///     .set_label("asset.mp4")
+///     .add_child_box(EmbeddedFileBoxBuilder::new("video/mp4"))
+///     .add_child_box(FileDataBox::open(BoxType(*b"bidb"), "asset.mp4")?);
+///
+/// let mut jumbf = Cursor::new(Vec::<u8>::new());
+/// sbox.write_jumbf(&mut jumbf)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`write_jumbf()`]: crate::builder::SuperBoxBuilder::write_jumbf()
+pub struct FileDataBox {
+    tbox: BoxType,
+    file: File,
+}
+
+impl FileDataBox {
+    /// Create a `FileDataBox` from a JUMBF box type and the path to a file.
+    ///
+    /// The file is opened immediately, but not read until the box is written.
+    pub fn open<P: AsRef<Path>>(tbox: BoxType, path: P) -> Result<Self> {
+        Ok(Self::from_file(tbox, File::open(path)?))
+    }
+
+    /// Create a `FileDataBox` from a JUMBF box type and an open file.
+    ///
+    /// The entire file will be used as the payload, regardless of the file's
+    /// current position.
+    pub fn from_file(tbox: BoxType, file: File) -> Self {
+        Self { tbox, file }
+    }
+
+    /// Create a new `FileDataBox` which refers to the same file.
+    ///
+    /// See [`File::try_clone()`].
+    pub fn try_clone(&self) -> Result<Self> {
+        Ok(Self::from_file(self.tbox, self.file.try_clone()?))
+    }
+}
+
+impl ToBox for FileDataBox {
+    fn box_type(&self) -> BoxType {
+        self.tbox
+    }
+
+    fn payload_size(&self) -> Result<usize> {
+        let len = self.file.metadata()?.len();
+        usize::try_from(len).map_err(Error::other)
+    }
+
+    fn write_payload(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
+        let len = self.file.metadata()?.len();
+
+        // `Read` and `Seek` are implemented for `&File`, which lets us read
+        // the file without requiring `&mut self`.
+        let mut file = &self.file;
+        file.seek(SeekFrom::Start(0))?;
+        copy(&mut file.take(len), to_stream)?;
+
+        Ok(())
+    }
+
+    fn try_clone_box(&self) -> Result<Box<dyn ToBox>>
+    where
+        Self: 'static,
+    {
+        Ok(Box::new(self.try_clone()?))
+    }
+}
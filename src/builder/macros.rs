@@ -0,0 +1,139 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+/// Declare a JUMBF superbox and its descendants in a compact literal form.
+///
+/// The macro evaluates to a [`SuperBoxBuilder`]. A superbox is written as
+/// `jumb(uuid, key = value, ...) { children }`, where `uuid` is any
+/// expression accepted by [`SuperBoxBuilder::new()`] and each `key = value`
+/// pair calls the corresponding `SuperBoxBuilder` method:
+///
+/// | Key | Method |
+/// |-----|--------|
+/// | `label` | [`set_label()`] |
+/// | `non_requestable_label` | [`set_non_requestable_label()`] |
+/// | `id` | [`set_id()`] |
+/// | `hash` | [`set_sha256_hash()`] |
+/// | `private` | [`set_private_box()`] |
+/// | `salt` | [`set_salt()`] |
+/// | `canonical` | [`set_canonical()`] |
+///
+/// Children are separated by commas. Each child is either a nested superbox
+/// in the same form or a data box written as `"type" => payload`, where the
+/// box type is a four-byte string literal (checked at compile time, as for
+/// [`box_type!`]) and the payload is any expression which implements
+/// `AsRef<[u8]>`. The payload is copied into the builder.
+///
+/// ## Example
+///
+/// ```
+/// # fn example() -> std::io::Result<()> {
+/// use std::io::Cursor;
+///
+/// use hex_literal::hex;
+/// use jumbf::{jumbf, Uuid};
+///
+/// let sbox = jumbf! {
+///     jumb(Uuid([0; 16])) {
+///         jumb(Uuid([0; 16]), label = "a") {
+///             "abcd" => b"hi",
+///         },
+///     }
+/// };
+///
+/// let mut jumbf = Cursor::new(Vec::<u8>::new());
+/// sbox.write_jumbf(&mut jumbf)?;
+///
+/// let expected_jumbf = hex!(
+///     "0000004e" // box size
+///     "6a756d62" // box type = 'jumb'
+///         "00000019" // box size
+///         "6a756d64" // box type = 'jumd'
+///         "00000000000000000000000000000000" // UUID
+///         "00" // toggles
+///         // ---
+///         "0000002d" // box size
+///         "6a756d62" // box type = 'jumb'
+///             "0000001b" // box size
+///             "6a756d64" // box type = 'jumd'
+///             "00000000000000000000000000000000" // UUID
+///             "03" // toggles
+///             "6100" // label ("a")
+///             // ---
+///             "0000000a" // box size
+///             "61626364" // box type = 'abcd'
+///             "6869" // payload ("hi")
+/// );
+///
+/// assert_eq!(*jumbf.into_inner(), expected_jumbf);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// This macro is available when the `std` feature is enabled.
+///
+/// [`SuperBoxBuilder`]: crate::builder::SuperBoxBuilder
+/// [`SuperBoxBuilder::new()`]: crate::builder::SuperBoxBuilder::new()
+/// [`set_label()`]: crate::builder::SuperBoxBuilder::set_label()
+/// [`set_non_requestable_label()`]: crate::builder::SuperBoxBuilder::set_non_requestable_label()
+/// [`set_id()`]: crate::builder::SuperBoxBuilder::set_id()
+/// [`set_sha256_hash()`]: crate::builder::SuperBoxBuilder::set_sha256_hash()
+/// [`set_private_box()`]: crate::builder::SuperBoxBuilder::set_private_box()
+/// [`set_salt()`]: crate::builder::SuperBoxBuilder::set_salt()
+/// [`set_canonical()`]: crate::builder::SuperBoxBuilder::set_canonical()
+/// [`box_type!`]: crate::box_type!
+#[macro_export]
+macro_rules! jumbf {
+    (jumb ($uuid:expr $(, $key:ident = $value:expr)* $(,)?) { $($children:tt)* }) => {{
+        #[allow(unused_mut)]
+        let mut sbox = $crate::builder::SuperBoxBuilder::new($uuid);
+        $(sbox = $crate::jumbf!(@set sbox, $key, $value);)*
+        $crate::jumbf!(@children sbox; $($children)*);
+        sbox
+    }};
+
+    (@set $sbox:ident, label, $value:expr) => {
+        $sbox.set_label($value)
+    };
+    (@set $sbox:ident, non_requestable_label, $value:expr) => {
+        $sbox.set_non_requestable_label($value)
+    };
+    (@set $sbox:ident, id, $value:expr) => {
+        $sbox.set_id($value)
+    };
+    (@set $sbox:ident, hash, $value:expr) => {
+        $sbox.set_sha256_hash($value)
+    };
+    (@set $sbox:ident, private, $value:expr) => {
+        $sbox.set_private_box($value)
+    };
+    (@set $sbox:ident, salt, $value:expr) => {
+        $sbox.set_salt($value)
+    };
+    (@set $sbox:ident, canonical, $value:expr) => {
+        $sbox.set_canonical($value)
+    };
+
+    (@children $sbox:ident;) => {};
+    (@children $sbox:ident; jumb ($($args:tt)*) { $($children:tt)* } $(, $($rest:tt)*)?) => {
+        $sbox = $sbox.add_child_box($crate::jumbf!(jumb ($($args)*) { $($children)* }));
+        $($crate::jumbf!(@children $sbox; $($rest)*);)?
+    };
+    (@children $sbox:ident; $tbox:literal => $data:expr $(, $($rest:tt)*)?) => {
+        $sbox = $sbox.add_child_box($crate::builder::DataBoxBuilder::from_owned(
+            $crate::box_type!($tbox),
+            ::core::convert::AsRef::<[u8]>::as_ref(&$data).to_vec(),
+        ));
+        $($crate::jumbf!(@children $sbox; $($rest)*);)?
+    };
+}
@@ -0,0 +1,328 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{
+    fmt::{Display, Formatter},
+    fs, io,
+    path::Path,
+    str::FromStr,
+};
+
+use crate::{
+    builder::{DataBoxBuilder, SuperBoxBuilder},
+    BoxType, ParseUuidError, Uuid,
+};
+
+/// Parse a manifest description into a superbox builder.
+///
+/// A manifest description is a small text format which describes a JUMBF
+/// tree without writing Rust. Each line describes one box, nested by
+/// indenting two spaces per level. A superbox is written as
+/// `jumb <uuid> [label]`, where the UUID is in hyphenated form. Any other box
+/// is written as `<box type> <file>`. Blank lines and lines beginning with `#`
+/// are ignored. The first box must be an unindented superbox, and it must be
+/// the only top-level box.
+///
+/// `read_file` is called with each `<file>` name to read the payload for the
+/// corresponding data box.
+///
+/// ## Example
+///
+/// ```
+/// use jumbf::builder::parse_manifest;
+///
+/// let manifest = "
+/// jumb 00000000-0000-0000-0000-000000000000 c2pa
+///   jumb 6a736f6e-0011-0010-8000-00aa00389b71 c2pa.assertion
+///     json assertion.json
+/// ";
+///
+/// let sbox = parse_manifest(manifest, |path| {
+///     assert_eq!(path, "assertion.json");
+///     Ok(b"{}".to_vec())
+/// })
+/// .unwrap();
+/// ```
+pub fn parse_manifest<F>(
+    text: &str,
+    read_file: F,
+) -> Result<SuperBoxBuilder<'static>, ManifestError>
+where
+    F: Fn(&str) -> io::Result<Vec<u8>>,
+{
+    let mut lines = vec![];
+
+    for (index, line) in text.lines().enumerate() {
+        let number = index + 1;
+        let trimmed = line.trim_start_matches(' ');
+
+        if trimmed.trim().is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let indent = line.len() - trimmed.len();
+        if indent % 2 != 0 {
+            return Err(ManifestError::InvalidIndentation { line: number });
+        }
+
+        lines.push(Line {
+            number,
+            depth: indent / 2,
+            words: trimmed.split_whitespace().collect(),
+        });
+    }
+
+    let Some(first) = lines.first() else {
+        return Err(ManifestError::Empty);
+    };
+
+    if first.depth != 0 || first.words.first() != Some(&"jumb") {
+        return Err(ManifestError::FirstBoxNotSuperBox { line: first.number });
+    }
+
+    let mut next = 0;
+    let sbox = parse_super_box(&lines, &mut next, &read_file)?;
+
+    if let Some(line) = lines.get(next) {
+        return Err(ManifestError::MultipleTopLevelBoxes { line: line.number });
+    }
+
+    Ok(sbox)
+}
+
+/// Read a manifest description from the file at `path` and parse it as
+/// described in [`parse_manifest()`].
+///
+/// Payload file names are resolved relative to the directory containing the
+/// manifest description.
+pub fn load_manifest<P: AsRef<Path>>(path: P) -> Result<SuperBoxBuilder<'static>, ManifestError> {
+    let path = path.as_ref();
+    let text = fs::read_to_string(path).map_err(ManifestError::ReadManifest)?;
+    let base = path.parent().unwrap_or(Path::new("."));
+
+    parse_manifest(&text, |file| fs::read(base.join(file)))
+}
+
+/// Describes why a manifest description could not be parsed.
+///
+/// Returned by [`parse_manifest()`] and [`load_manifest()`]. Line numbers are
+/// one-based.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ManifestError {
+    /// The manifest description contains no boxes.
+    Empty,
+
+    /// The manifest description file could not be read.
+    ReadManifest(io::Error),
+
+    /// A line is not indented by a multiple of two spaces.
+    InvalidIndentation {
+        /// Line number.
+        line: usize,
+    },
+
+    /// A line is indented more than one level deeper than its parent.
+    UnexpectedIndentation {
+        /// Line number.
+        line: usize,
+    },
+
+    /// The first box is not an unindented superbox.
+    FirstBoxNotSuperBox {
+        /// Line number.
+        line: usize,
+    },
+
+    /// More than one unindented box was found.
+    MultipleTopLevelBoxes {
+        /// Line number of the second top-level box.
+        line: usize,
+    },
+
+    /// A superbox line is not of the form `jumb <uuid> [label]`.
+    InvalidSuperBox {
+        /// Line number.
+        line: usize,
+    },
+
+    /// A superbox's UUID could not be parsed.
+    InvalidUuid {
+        /// Line number.
+        line: usize,
+
+        /// The reason the UUID was rejected.
+        error: ParseUuidError,
+    },
+
+    /// A data box line is not of the form `<box type> <file>`.
+    InvalidDataBox {
+        /// Line number.
+        line: usize,
+    },
+
+    /// A box type is not exactly four bytes long.
+    InvalidBoxType {
+        /// Line number.
+        line: usize,
+
+        /// The box type that was rejected.
+        tbox: String,
+    },
+
+    /// A data box's payload file could not be read.
+    ReadFile {
+        /// Line number.
+        line: usize,
+
+        /// The file name, as given in the manifest description.
+        path: String,
+
+        /// The error returned while reading the file.
+        error: io::Error,
+    },
+}
+
+impl Display for ManifestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Self::Empty => f.write_str("manifest description is empty"),
+            Self::ReadManifest(e) => write!(f, "unable to read manifest description: {e}"),
+            Self::InvalidIndentation { line } => {
+                write!(
+                    f,
+                    "line {line}: indentation must be a multiple of two spaces"
+                )
+            }
+            Self::UnexpectedIndentation { line } => {
+                write!(f, "line {line}: unexpected indentation")
+            }
+            Self::FirstBoxNotSuperBox { line } => {
+                write!(f, "line {line}: first box must be an unindented superbox")
+            }
+            Self::MultipleTopLevelBoxes { line } => {
+                write!(f, "line {line}: only one top-level superbox is allowed")
+            }
+            Self::InvalidSuperBox { line } => {
+                write!(f, "line {line}: expected `jumb <uuid> [label]`")
+            }
+            Self::InvalidUuid { line, error } => write!(f, "line {line}: {error}"),
+            Self::InvalidDataBox { line } => write!(f, "line {line}: expected `<box type> <file>`"),
+            Self::InvalidBoxType { line, tbox } => {
+                write!(f, "line {line}: box type {tbox:?} must be four bytes")
+            }
+            Self::ReadFile { line, path, error } => {
+                write!(f, "line {line}: unable to read {path:?}: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ReadManifest(e) | Self::ReadFile { error: e, .. } => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// A single non-blank line of a manifest description.
+struct Line<'t> {
+    /// One-based line number, for error messages.
+    number: usize,
+
+    /// Nesting depth (indentation divided by two).
+    depth: usize,
+
+    /// Whitespace-separated words on the line.
+    words: Vec<&'t str>,
+}
+
+/// Parse the superbox described by `lines[*next]` and its children. On
+/// return, `*next` is the index of the first line after the superbox.
+fn parse_super_box<F>(
+    lines: &[Line],
+    next: &mut usize,
+    read_file: &F,
+) -> Result<SuperBoxBuilder<'static>, ManifestError>
+where
+    F: Fn(&str) -> io::Result<Vec<u8>>,
+{
+    let line = &lines[*next];
+    *next += 1;
+
+    let (uuid, label) = match line.words.as_slice() {
+        [_, uuid] => (*uuid, None),
+        [_, uuid, label] => (*uuid, Some(*label)),
+        _ => {
+            return Err(ManifestError::InvalidSuperBox { line: line.number });
+        }
+    };
+
+    let uuid = Uuid::from_str(uuid).map_err(|error| ManifestError::InvalidUuid {
+        line: line.number,
+        error,
+    })?;
+
+    let mut sbox = SuperBoxBuilder::new(uuid);
+    if let Some(label) = label {
+        sbox = sbox.set_label(label);
+    }
+
+    while let Some(child) = lines.get(*next) {
+        if child.depth <= line.depth {
+            break;
+        }
+
+        if child.depth > line.depth + 1 {
+            return Err(ManifestError::UnexpectedIndentation { line: child.number });
+        }
+
+        sbox = match child.words.as_slice() {
+            ["jumb", ..] => sbox.add_child_box(parse_super_box(lines, next, read_file)?),
+            [tbox, path] => {
+                *next += 1;
+                sbox.add_child_box(parse_data_box(child.number, tbox, path, read_file)?)
+            }
+            _ => {
+                return Err(ManifestError::InvalidDataBox { line: child.number });
+            }
+        };
+    }
+
+    Ok(sbox)
+}
+
+fn parse_data_box<F>(
+    line: usize,
+    tbox: &str,
+    path: &str,
+    read_file: &F,
+) -> Result<DataBoxBuilder<'static>, ManifestError>
+where
+    F: Fn(&str) -> io::Result<Vec<u8>>,
+{
+    let tbox = BoxType::from_str(tbox).map_err(|_| ManifestError::InvalidBoxType {
+        line,
+        tbox: tbox.to_owned(),
+    })?;
+
+    let data = read_file(path).map_err(|error| ManifestError::ReadFile {
+        line,
+        path: path.to_owned(),
+        error,
+    })?;
+
+    Ok(DataBoxBuilder::from_owned(tbox, data))
+}
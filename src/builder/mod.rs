@@ -16,12 +16,51 @@
 //!
 //! [JUMBF (ISO/IEC 19566-5:2019)]: (https://www.iso.org/standard/73604.html)
 
+mod callback_data_box;
 mod data_box_builder;
+mod embedded_file_box_builder;
+mod error;
+mod file_data_box;
+mod macros;
+mod manifest;
+mod padding_box_builder;
 mod placeholder_data_box;
+mod placeholder_registry;
+mod placeholder_super_box;
+mod prepared_super_box;
+mod streaming_super_box_writer;
 mod super_box_builder;
 pub(crate) mod to_box;
+mod write_only;
 
+pub use callback_data_box::CallbackDataBox;
 pub use data_box_builder::DataBoxBuilder;
+pub use embedded_file_box_builder::EmbeddedFileBoxBuilder;
+pub use error::BuilderError;
+pub use file_data_box::FileDataBox;
+pub use manifest::{load_manifest, parse_manifest, ManifestError};
+pub use padding_box_builder::PaddingBoxBuilder;
 pub use placeholder_data_box::PlaceholderDataBox;
+pub use placeholder_registry::PlaceholderRegistry;
+pub use placeholder_super_box::PlaceholderSuperBox;
+pub use prepared_super_box::PreparedSuperBox;
+pub use streaming_super_box_writer::StreamingSuperBoxWriter;
 pub use super_box_builder::SuperBoxBuilder;
 pub use to_box::{ToBox, WriteAndSeek};
+pub use write_only::WriteOnly;
+
+// Builder trees may be shared across threads (for example, by a pipeline which
+// signs several assets in parallel). Fail to compile if that ever regresses.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<DataBoxBuilder<'static>>();
+    assert_send_sync::<EmbeddedFileBoxBuilder>();
+    assert_send_sync::<FileDataBox>();
+    assert_send_sync::<PaddingBoxBuilder>();
+    assert_send_sync::<PlaceholderDataBox>();
+    assert_send_sync::<PlaceholderRegistry>();
+    assert_send_sync::<PlaceholderSuperBox>();
+    assert_send_sync::<PreparedSuperBox<'static>>();
+    assert_send_sync::<SuperBoxBuilder<'static>>();
+};
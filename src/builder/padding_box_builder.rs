@@ -0,0 +1,85 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::{Error, Result};
+
+use crate::{
+    box_type::FREE_BOX_TYPE,
+    builder::{ToBox, WriteAndSeek},
+    BoxType,
+};
+
+/// A `PaddingBoxBuilder` allows you to build a zero-filled `free` box of a
+/// given size.
+///
+/// Readers are expected to ignore `free` boxes, so padding can be added to a
+/// JUMBF data structure to leave room for a later in-place update (for
+/// example, an edited manifest) without changing the offsets of anything
+/// that follows it in the enclosing file.
+///
+/// ## Example
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// use jumbf::builder::{PaddingBoxBuilder, SuperBoxBuilder};
+///
+/// let sbox = SuperBoxBuilder::new([0; 16]).add_child_box(PaddingBoxBuilder::new(1024));
+///
+/// let mut jumbf = Cursor::new(Vec::<u8>::new());
+/// sbox.write_jumbf(&mut jumbf).unwrap();
+///
+/// assert_eq!(jumbf.get_ref().len(), 33 + 1024);
+/// assert_eq!(&jumbf.get_ref()[37..41], b"free");
+/// ```
+#[derive(Clone)]
+pub struct PaddingBoxBuilder {
+    size: usize,
+}
+
+impl PaddingBoxBuilder {
+    /// Create a `PaddingBoxBuilder` that will occupy exactly `size` bytes,
+    /// including the box header.
+    ///
+    /// `size` must be at least 8 bytes.
+    pub fn new(size: usize) -> Self {
+        Self { size }
+    }
+}
+
+impl ToBox for PaddingBoxBuilder {
+    fn box_type(&self) -> BoxType {
+        FREE_BOX_TYPE
+    }
+
+    fn payload_size(&self) -> Result<usize> {
+        self.size.checked_sub(8).ok_or_else(|| {
+            Error::other(format!(
+                "PaddingBoxBuilder: size ({size} bytes) must be at least 8 bytes",
+                size = self.size
+            ))
+        })
+    }
+
+    fn write_payload(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
+        let zeros: Vec<u8> = vec![0; self.payload_size()?];
+        to_stream.write_all(&zeros)
+    }
+
+    fn try_clone_box(&self) -> Result<Box<dyn ToBox>>
+    where
+        Self: 'static,
+    {
+        Ok(Box::new(self.clone()))
+    }
+}
@@ -12,11 +12,12 @@
 // each license.
 
 use std::{
-    cell::RefCell,
-    io::{Error, ErrorKind, Result},
+    io::{Error, Result, SeekFrom},
+    sync::atomic::{AtomicU64, Ordering},
 };
 
 use crate::{
+    box_type::FREE_BOX_TYPE,
     builder::{ToBox, WriteAndSeek},
     BoxType,
 };
@@ -28,13 +29,22 @@ use crate::{
 /// You can specify a data size to reserve. When the initial JUMBF data
 /// structure is created, the box will be zero-filled to the specified size.
 /// Later, you can call [`replace_payload()`] to replace that reserved space
-/// with new content.
+/// with new content, or [`finalize_payload()`] to replace it and shrink the
+/// box to fit the new content.
+///
+/// If the JUMBF is written to a stream that can not seek (see [`WriteOnly`]),
+/// [`replace_payload()`] will return an error. [`offset()`] will still report
+/// where the placeholder was written so that it can be filled in by other
+/// means.
 ///
 /// [`replace_payload()`]: Self::replace_payload()
+/// [`finalize_payload()`]: Self::finalize_payload()
+/// [`offset()`]: Self::offset()
+/// [`WriteOnly`]: crate::builder::WriteOnly
 pub struct PlaceholderDataBox {
     tbox: BoxType,
     size: usize,
-    offset: RefCell<Option<u64>>,
+    offset: RecordedOffset,
 }
 
 impl PlaceholderDataBox {
@@ -45,7 +55,7 @@ impl PlaceholderDataBox {
         Self {
             tbox,
             size,
-            offset: RefCell::new(None),
+            offset: RecordedOffset::default(),
         }
     }
 
@@ -56,7 +66,37 @@ impl PlaceholderDataBox {
     ///
     /// [`write_jumbf()`]: crate::builder::SuperBoxBuilder::write_jumbf()
     pub fn offset(&self) -> Option<u64> {
-        self.offset.clone().into_inner()
+        self.offset.get()
+    }
+
+    /// Return the offset where the payload can be written, relative to the
+    /// start of a superbox that was written at `super_box_offset` in the
+    /// stream.
+    ///
+    /// This position remains valid if the JUMBF is later copied elsewhere.
+    /// Will return `None` before the superbox's [`write_jumbf()`] method is
+    /// called or if the payload does not follow `super_box_offset`.
+    ///
+    /// [`write_jumbf()`]: crate::builder::SuperBoxBuilder::write_jumbf()
+    pub fn offset_within(&self, super_box_offset: u64) -> Option<u64> {
+        self.offset()?.checked_sub(super_box_offset)
+    }
+
+    /// Adjust the recorded offset by `delta` bytes.
+    ///
+    /// Call this after moving the JUMBF data structure that contains this
+    /// placeholder to a different position (e.g. when copying it into a
+    /// container file) so that [`replace_payload()`] and
+    /// [`finalize_payload()`] write to the new location.
+    ///
+    /// An error will be returned if no offset has been recorded yet or if the
+    /// new offset would be out of range. In either case, the recorded offset
+    /// is not changed.
+    ///
+    /// [`replace_payload()`]: Self::replace_payload()
+    /// [`finalize_payload()`]: Self::finalize_payload()
+    pub fn relocate(&self, delta: i64) -> Result<()> {
+        self.offset.relocate("PlaceholderDataBox", delta)
     }
 
     /// Replace the zero-filled placeholder content with actual content.
@@ -70,27 +110,71 @@ impl PlaceholderDataBox {
     ///
     /// [`offset()`]: Self::offset()
     pub fn replace_payload(&self, to_stream: &mut dyn WriteAndSeek, payload: &[u8]) -> Result<()> {
-        if payload.len() > self.size {
-            return Err(Error::new(
-                ErrorKind::Other,
-                format!("replace_payload: payload ({len} bytes) is larger than reserved capacity ({reserve} bytes)", len = payload.len(), reserve = self.size)
-            ));
+        let offset = self.checked_offset("replace_payload", payload)?;
+
+        to_stream.seek(SeekFrom::Start(offset))?;
+        to_stream.write_all(payload)
+    }
+
+    /// Replace the zero-filled placeholder content with actual content and
+    /// shrink this box to fit that content.
+    ///
+    /// Unlike [`replace_payload()`], which leaves zero padding at the end of
+    /// the box's payload, this method rewrites the box's length header so
+    /// that the box contains exactly `payload`. The remainder of the reserved
+    /// space is then filled with a `free` box so that the overall size of the
+    /// JUMBF data structure (and thus the lengths of any enclosing superboxes)
+    /// does not change.
+    ///
+    /// A `free` box requires at least 8 bytes. If the unused space is smaller
+    /// than that, the payload is zero-padded as in [`replace_payload()`]
+    /// instead.
+    ///
+    /// An error will be returned if `payload` is larger than the placeholder
+    /// size specified when this `PlaceholderDataBox` was created.
+    ///
+    /// [`replace_payload()`]: Self::replace_payload()
+    pub fn finalize_payload(&self, to_stream: &mut dyn WriteAndSeek, payload: &[u8]) -> Result<()> {
+        let offset = self.checked_offset("finalize_payload", payload)?;
+        let unused = self.size - payload.len();
+
+        if unused < 8 {
+            to_stream.seek(SeekFrom::Start(offset))?;
+            to_stream.write_all(payload)?;
+            return to_stream.write_all(&vec![0; unused]);
         }
 
-        let offset = self.offset.borrow();
+        let box_size = u32::try_from(payload.len() + 8).map_err(Error::other)?;
+        let free_size = u32::try_from(unused).map_err(Error::other)?;
 
-        if let Some(offset) = *offset {
-            to_stream.seek(std::io::SeekFrom::Start(offset))?;
-            to_stream.write_all(payload)
-        } else {
-            // HINT: If you receive this error, be sure to call write_jumbf() on a superbox
-            // containing this box first.
+        // The payload always follows an 8-byte box header.
+        to_stream.seek(SeekFrom::Start(offset - 8))?;
+        to_stream.write_all(&box_size.to_be_bytes())?;
 
-            Err(Error::new(
-                ErrorKind::Other,
-                "replace_payload: no offset recorded; call write_jumbf() first".to_string(),
-            ))
+        to_stream.seek(SeekFrom::Start(offset))?;
+        to_stream.write_all(payload)?;
+
+        to_stream.write_all(&free_size.to_be_bytes())?;
+        to_stream.write_all(&FREE_BOX_TYPE.0)?;
+        to_stream.write_all(&vec![0; unused - 8])
+    }
+
+    // Verify that `payload` fits within the reserved space and that the
+    // placeholder has been written. Returns the payload offset.
+    fn checked_offset(&self, method: &str, payload: &[u8]) -> Result<u64> {
+        if payload.len() > self.size {
+            return Err(Error::other(
+                format!("{method}: payload ({len} bytes) is larger than reserved capacity ({reserve} bytes)", len = payload.len(), reserve = self.size)
+            ));
         }
+
+        // HINT: If you receive this error, be sure to call write_jumbf() on a superbox
+        // containing this box first.
+        self.offset.get().ok_or_else(|| {
+            Error::other(format!(
+                "{method}: no offset recorded; call write_jumbf() first"
+            ))
+        })
     }
 }
 
@@ -108,13 +192,12 @@ impl ToBox for PlaceholderDataBox {
 
         match offset {
             0 => {
-                return Err(Error::new(
-                    ErrorKind::Other,
+                return Err(Error::other(
                     "placeholder stream should have some data already",
                 ));
             }
             _ => {
-                self.offset.replace(Some(offset));
+                self.offset.set(offset);
             }
         };
 
@@ -122,4 +205,61 @@ impl ToBox for PlaceholderDataBox {
         to_stream.write_all(&zeros)?;
         Ok(())
     }
+
+    fn try_clone_box(&self) -> Result<Box<dyn ToBox>>
+    where
+        Self: 'static,
+    {
+        Ok(Box::new(Self::new(self.tbox, self.size)))
+    }
+}
+
+/// The stream offset recorded by a placeholder when it is written.
+///
+/// This is stored atomically (rather than in a `RefCell`) so that builder
+/// trees containing placeholders can be shared across threads.
+pub(crate) struct RecordedOffset(AtomicU64);
+
+impl RecordedOffset {
+    // Since a box header precedes any offset we record, no real offset can
+    // ever reach `u64::MAX`.
+    const NONE: u64 = u64::MAX;
+
+    pub(crate) fn get(&self) -> Option<u64> {
+        match self.0.load(Ordering::Acquire) {
+            Self::NONE => None,
+            offset => Some(offset),
+        }
+    }
+
+    pub(crate) fn set(&self, offset: u64) {
+        self.0.store(offset, Ordering::Release);
+    }
+
+    /// Adjust the recorded offset by `delta` bytes.
+    pub(crate) fn relocate(&self, name: &str, delta: i64) -> Result<()> {
+        let old = self.get().ok_or_else(|| {
+            Error::other(format!(
+                "{name}::relocate: no offset recorded; call write_jumbf() first"
+            ))
+        })?;
+
+        let new = old
+            .checked_add_signed(delta)
+            .filter(|new| *new != Self::NONE)
+            .ok_or_else(|| {
+                Error::other(format!(
+                    "{name}::relocate: moving offset {old} by {delta} bytes is out of range"
+                ))
+            })?;
+
+        self.set(new);
+        Ok(())
+    }
+}
+
+impl Default for RecordedOffset {
+    fn default() -> Self {
+        Self(AtomicU64::new(Self::NONE))
+    }
 }
@@ -0,0 +1,162 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{
+    collections::BTreeMap,
+    io::{Error, Result},
+};
+
+use crate::{
+    builder::{PlaceholderDataBox, WriteAndSeek},
+    BoxType,
+};
+
+/// A `PlaceholderRegistry` owns a set of [`PlaceholderDataBox`]es, each
+/// identified by a label.
+///
+/// This avoids the need to keep a separate reference to each placeholder
+/// when a JUMBF data structure contains several of them. Create all of the
+/// placeholders with [`add()`], add each one to its superbox via
+/// [`get()`], and then call [`write_jumbf()`] on the outermost superbox as
+/// usual. Each placeholder records its offset as it is written; those offsets
+/// can then be retrieved via [`offset()`] and the content replaced via
+/// [`replace()`].
+///
+/// ## Example
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// use jumbf::{
+///     builder::{PlaceholderRegistry, SuperBoxBuilder},
+///     BoxType,
+/// };
+///
+/// let mut registry = PlaceholderRegistry::new();
+/// registry.add("sig", BoxType(*b"uuid"), 16).unwrap();
+/// registry.add("hash", BoxType(*b"hash"), 8).unwrap();
+///
+/// let sbox = SuperBoxBuilder::new([0; 16])
+///     .add_borrowed_child_box(registry.get("sig").unwrap())
+///     .add_borrowed_child_box(registry.get("hash").unwrap());
+///
+/// let mut jumbf = Cursor::new(Vec::<u8>::new());
+/// sbox.write_jumbf(&mut jumbf).unwrap();
+///
+/// assert_eq!(registry.offset("sig"), Some(41));
+/// assert_eq!(registry.offset("hash"), Some(65));
+///
+/// registry.replace(&mut jumbf, "hash", b"12345678").unwrap();
+/// assert_eq!(&jumbf.get_ref()[65..73], b"12345678");
+/// ```
+///
+/// [`add()`]: Self::add()
+/// [`get()`]: Self::get()
+/// [`offset()`]: Self::offset()
+/// [`replace()`]: Self::replace()
+/// [`write_jumbf()`]: crate::builder::SuperBoxBuilder::write_jumbf()
+#[derive(Default)]
+pub struct PlaceholderRegistry {
+    placeholders: BTreeMap<String, PlaceholderDataBox>,
+}
+
+impl PlaceholderRegistry {
+    /// Create a new, empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new placeholder data box that will reserve `size` bytes and
+    /// register it under `label`.
+    ///
+    /// The box will be given the JUMBF box type specified by `tbox`.
+    ///
+    /// An error will be returned if a placeholder with the same label has
+    /// already been added.
+    pub fn add(&mut self, label: &str, tbox: BoxType, size: usize) -> Result<()> {
+        if self.placeholders.contains_key(label) {
+            return Err(Error::other(format!(
+                "PlaceholderRegistry: duplicate label {label:?}"
+            )));
+        }
+
+        self.placeholders
+            .insert(label.to_owned(), PlaceholderDataBox::new(tbox, size));
+
+        Ok(())
+    }
+
+    /// Return the placeholder registered under `label`, if any.
+    ///
+    /// Pass this to [`SuperBoxBuilder::add_borrowed_child_box()`] to include
+    /// the placeholder in a JUMBF data structure.
+    ///
+    /// [`SuperBoxBuilder::add_borrowed_child_box()`]: crate::builder::SuperBoxBuilder::add_borrowed_child_box()
+    pub fn get(&self, label: &str) -> Option<&PlaceholderDataBox> {
+        self.placeholders.get(label)
+    }
+
+    /// Return the offset in the stream where the payload for the placeholder
+    /// registered under `label` can be written.
+    ///
+    /// Will return `None` if there is no such placeholder or if it has not
+    /// yet been written.
+    pub fn offset(&self, label: &str) -> Option<u64> {
+        self.get(label).and_then(PlaceholderDataBox::offset)
+    }
+
+    /// Return the labels and offsets of all placeholders which have been
+    /// written, in label order.
+    pub fn offsets(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.placeholders
+            .iter()
+            .filter_map(|(label, pbox)| pbox.offset().map(|offset| (label.as_str(), offset)))
+    }
+
+    /// Adjust the recorded offset of every placeholder by `delta` bytes after
+    /// the JUMBF data structure has been moved.
+    ///
+    /// See [`PlaceholderDataBox::relocate()`]. Placeholders which have not
+    /// been written are ignored. If any offset would be out of range, an
+    /// error is returned and no offsets are changed.
+    pub fn relocate(&self, delta: i64) -> Result<()> {
+        if let Some((label, offset)) = self.offsets().find(
+            |(_, offset)| !matches!(offset.checked_add_signed(delta), Some(new) if new != u64::MAX),
+        ) {
+            return Err(Error::other(format!(
+                "PlaceholderRegistry: moving offset {offset} of {label:?} by {delta} bytes is out of range"
+            )));
+        }
+
+        self.placeholders
+            .values()
+            .filter(|pbox| pbox.offset().is_some())
+            .try_for_each(|pbox| pbox.relocate(delta))
+    }
+
+    /// Replace the zero-filled content of the placeholder registered under
+    /// `label` with actual content.
+    ///
+    /// See [`PlaceholderDataBox::replace_payload()`] for details. An error
+    /// will also be returned if there is no placeholder with this label.
+    pub fn replace(
+        &self,
+        to_stream: &mut dyn WriteAndSeek,
+        label: &str,
+        payload: &[u8],
+    ) -> Result<()> {
+        self.get(label)
+            .ok_or_else(|| Error::other(format!("PlaceholderRegistry: unknown label {label:?}")))?
+            .replace_payload(to_stream, payload)
+    }
+}
@@ -0,0 +1,202 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::{Error, Result, SeekFrom};
+
+use crate::{
+    box_type::FREE_BOX_TYPE,
+    builder::{placeholder_data_box::RecordedOffset, SuperBoxBuilder, ToBox, WriteAndSeek},
+    BoxType,
+};
+
+/// A `PlaceholderSuperBox` allows you to reserve space in a JUMBF data
+/// structure for an entire superbox (description box and children) that will
+/// be created after the overall JUMBF data structure is written.
+///
+/// This is useful for two-pass signing workflows (such as C2PA) where the
+/// signature superbox can only be produced after the rest of the JUMBF data
+/// structure has been written and hashed.
+///
+/// You can specify the total number of bytes to reserve, including the
+/// superbox's header. When the initial JUMBF data structure is created, the
+/// reserved space is filled with a zero-filled `free` box. Later, you can
+/// call [`replace_super_box()`] to write the actual superbox in that space.
+///
+/// ## Example
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// use jumbf::{
+///     builder::{DataBoxBuilder, PlaceholderSuperBox, SuperBoxBuilder},
+///     BoxType,
+/// };
+///
+/// let placeholder = PlaceholderSuperBox::new(100);
+///
+/// let outer = SuperBoxBuilder::new([0; 16]).add_borrowed_child_box(&placeholder);
+///
+/// let mut jumbf = Cursor::new(Vec::<u8>::new());
+/// outer.write_jumbf(&mut jumbf).unwrap();
+/// assert_eq!(placeholder.offset(), Some(33));
+///
+/// // ... later ...
+///
+/// let sig = SuperBoxBuilder::new([0; 16])
+///     .set_label("c2pa.signature")
+///     .add_child_box(DataBoxBuilder::from_borrowed(
+///         BoxType(*b"uuid"),
+///         b"signature",
+///     ));
+///
+/// placeholder.replace_super_box(&mut jumbf, &sig).unwrap();
+/// assert_eq!(&jumbf.get_ref()[37..41], b"jumb");
+/// ```
+///
+/// [`replace_super_box()`]: Self::replace_super_box()
+pub struct PlaceholderSuperBox {
+    size: usize,
+    offset: RecordedOffset,
+}
+
+impl PlaceholderSuperBox {
+    /// Create a new placeholder that will reserve `size` bytes for a
+    /// superbox, including the superbox's header.
+    ///
+    /// `size` must be at least 8 bytes.
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            offset: RecordedOffset::default(),
+        }
+    }
+
+    /// Return the offset in the stream where the superbox will be written.
+    ///
+    /// Will return `None` before the enclosing superbox's [`write_jumbf()`]
+    /// method is called.
+    ///
+    /// [`write_jumbf()`]: crate::builder::SuperBoxBuilder::write_jumbf()
+    pub fn offset(&self) -> Option<u64> {
+        self.offset.get()
+    }
+
+    /// Return the offset of the reserved space relative to the start of an
+    /// enclosing superbox that was written at `super_box_offset` in the
+    /// stream.
+    ///
+    /// See [`PlaceholderDataBox::offset_within()`].
+    ///
+    /// [`PlaceholderDataBox::offset_within()`]: crate::builder::PlaceholderDataBox::offset_within()
+    pub fn offset_within(&self, super_box_offset: u64) -> Option<u64> {
+        self.offset()?.checked_sub(super_box_offset)
+    }
+
+    /// Adjust the recorded offset by `delta` bytes after the JUMBF data
+    /// structure has been moved.
+    ///
+    /// See [`PlaceholderDataBox::relocate()`].
+    ///
+    /// [`PlaceholderDataBox::relocate()`]: crate::builder::PlaceholderDataBox::relocate()
+    pub fn relocate(&self, delta: i64) -> Result<()> {
+        self.offset.relocate("PlaceholderSuperBox", delta)
+    }
+
+    /// Write `sbox` into the space reserved by this placeholder.
+    ///
+    /// If `sbox` is smaller than the reserved space, the remainder is filled
+    /// with a zero-filled `free` box so that the overall size of the JUMBF
+    /// data structure does not change.
+    ///
+    /// An error will be returned if `sbox` is larger than the reserved space,
+    /// or if the unused space would be too small to hold a `free` box (i.e.
+    /// between 1 and 7 bytes). In either case, the stream is not modified.
+    pub fn replace_super_box(
+        &self,
+        to_stream: &mut dyn WriteAndSeek,
+        sbox: &SuperBoxBuilder,
+    ) -> Result<()> {
+        let sbox = sbox.prepare()?;
+        let len = sbox.jumbf_size();
+
+        if len > self.size {
+            return Err(Error::other(format!(
+                "replace_super_box: superbox ({len} bytes) is larger than reserved capacity ({reserve} bytes)",
+                reserve = self.size
+            )));
+        }
+
+        let unused = self.size - len;
+        if unused > 0 && unused < 8 {
+            return Err(Error::other(format!(
+                "replace_super_box: unused space ({unused} bytes) is too small for a free box"
+            )));
+        }
+
+        let offset = self.offset.get().ok_or_else(|| {
+            // HINT: If you receive this error, be sure to call write_jumbf() on a superbox
+            // containing this box first.
+            Error::other("replace_super_box: no offset recorded; call write_jumbf() first")
+        })?;
+
+        to_stream.seek(SeekFrom::Start(offset))?;
+        sbox.write_jumbf(to_stream)?;
+
+        if unused > 0 {
+            let free_size = u32::try_from(unused).map_err(Error::other)?;
+            to_stream.write_all(&free_size.to_be_bytes())?;
+            to_stream.write_all(&FREE_BOX_TYPE.0)?;
+            to_stream.write_all(&vec![0; unused - 8])?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToBox for PlaceholderSuperBox {
+    fn box_type(&self) -> BoxType {
+        FREE_BOX_TYPE
+    }
+
+    fn payload_size(&self) -> Result<usize> {
+        self.size.checked_sub(8).ok_or_else(|| {
+            Error::other(format!(
+                "PlaceholderSuperBox: reserved size ({size} bytes) must be at least 8 bytes",
+                size = self.size
+            ))
+        })
+    }
+
+    fn write_payload(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
+        let payload_size = self.payload_size()?;
+
+        // The payload always follows an 8-byte box header.
+        let offset = to_stream.stream_position()?;
+        let offset = offset
+            .checked_sub(8)
+            .ok_or_else(|| Error::other("placeholder stream should have some data already"))?;
+
+        self.offset.set(offset);
+
+        let zeros: Vec<u8> = vec![0; payload_size];
+        to_stream.write_all(&zeros)?;
+        Ok(())
+    }
+
+    fn try_clone_box(&self) -> Result<Box<dyn ToBox>>
+    where
+        Self: 'static,
+    {
+        Ok(Box::new(Self::new(self.size)))
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::Result;
+
+use crate::builder::{to_box::jumbf_size_from_payload_size, SuperBoxBuilder, WriteAndSeek};
+
+/// A [`SuperBoxBuilder`] whose box sizes have all been computed, ready to be
+/// written in a single pass.
+///
+/// Created by [`SuperBoxBuilder::prepare()`].
+pub struct PreparedSuperBox<'a> {
+    sbox: &'a SuperBoxBuilder<'a>,
+
+    // Payload size of each box, in the order described by
+    // `SuperBoxBuilder::box_sizes()`. Never empty.
+    sizes: Vec<usize>,
+}
+
+impl<'a> PreparedSuperBox<'a> {
+    pub(crate) fn new(sbox: &'a SuperBoxBuilder<'a>, sizes: Vec<usize>) -> Self {
+        Self { sbox, sizes }
+    }
+
+    /// Returns the size of the superbox's payload (i.e. its description box
+    /// and child boxes).
+    pub fn payload_size(&self) -> usize {
+        self.sizes.first().copied().unwrap_or_default()
+    }
+
+    /// Returns the size of the superbox, including its header. This is the
+    /// number of bytes that [`write_jumbf()`] will write.
+    ///
+    /// [`write_jumbf()`]: Self::write_jumbf()
+    pub fn jumbf_size(&self) -> usize {
+        jumbf_size_from_payload_size(self.payload_size())
+    }
+
+    /// Write the superbox and all of its child boxes to a JUMBF stream.
+    ///
+    /// The sizes computed by [`SuperBoxBuilder::prepare()`] are used as is.
+    /// If any box then writes a payload of a different size, an error is
+    /// returned.
+    pub fn write_jumbf(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
+        self.sbox.write_with_sizes(&self.sizes, true, to_stream)
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::Result;
+
+use crate::{
+    box_type::SUPER_BOX_TYPE,
+    builder::{
+        to_box::{begin_streaming_box, finish_streaming_box, write_jumbf_streaming},
+        SuperBoxBuilder, ToBox, WriteAndSeek,
+    },
+};
+
+/// A `StreamingSuperBoxWriter` writes a JUMBF superbox directly to a stream
+/// without knowing the sizes of its child boxes ahead of time.
+///
+/// [`SuperBoxBuilder::write_jumbf()`] must know the size of every box before
+/// writing its header, which (unless [`ToBox::payload_size()`] is
+/// re-implemented) means that each child's [`write_payload()`] method is
+/// invoked twice. That can be expensive when child payloads are produced by
+/// generators.
+///
+/// Instead, this writer emits each box header with a placeholder length,
+/// writes the payload once, and then seeks back to fill in the actual
+/// length. Every child's [`write_payload()`] method is called exactly once
+/// and [`ToBox::payload_size()`] is never called. This includes the
+/// descendants of any [`SuperBoxBuilder`] written as a child box, which are
+/// streamed in the same way.
+///
+/// The description box for the superbox is taken from a [`SuperBoxBuilder`],
+/// so the usual methods for setting the UUID, label, ID, and so on apply.
+///
+/// You must call [`finish()`] when all child boxes have been written. If the
+/// writer is dropped without calling [`finish()`], the superbox's length
+/// will not be filled in.
+///
+/// ## Example
+///
+/// ```
+/// # fn example() -> std::io::Result<()> {
+/// use std::io::Cursor;
+///
+/// use jumbf::{
+///     builder::{DataBoxBuilder, StreamingSuperBoxWriter, SuperBoxBuilder},
+///     BoxType,
+/// };
+///
+/// let mut jumbf = Cursor::new(Vec::<u8>::new());
+///
+/// let desc = SuperBoxBuilder::new([0u8; 16]).set_label("example");
+/// let mut sbox = StreamingSuperBoxWriter::new(&mut jumbf, &desc)?;
+///
+/// sbox.write_child_box(&DataBoxBuilder::from_borrowed(
+///     BoxType(*b"abcd"),
+///     b"some data",
+/// ))?;
+///
+/// let mut nested = sbox.begin_child_super_box(&SuperBoxBuilder::new([1u8; 16]))?;
+/// nested.write_child_box(&DataBoxBuilder::from_borrowed(
+///     BoxType(*b"abcd"),
+///     b"more data",
+/// ))?;
+/// nested.finish()?;
+///
+/// sbox.finish()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`SuperBoxBuilder::write_jumbf()`]: crate::builder::SuperBoxBuilder::write_jumbf()
+/// [`write_payload()`]: crate::builder::ToBox::write_payload()
+/// [`finish()`]: Self::finish()
+pub struct StreamingSuperBoxWriter<'a> {
+    to_stream: &'a mut dyn WriteAndSeek,
+    start: u64,
+}
+
+impl<'a> StreamingSuperBoxWriter<'a> {
+    /// Begin writing a superbox to `to_stream`.
+    ///
+    /// The description box described by `sbox` is written immediately. Any
+    /// child boxes that were already added to `sbox` are also written at
+    /// this time, in order.
+    pub fn new(to_stream: &'a mut dyn WriteAndSeek, sbox: &SuperBoxBuilder) -> Result<Self> {
+        let start = begin_streaming_box(SUPER_BOX_TYPE, to_stream)?;
+        sbox.write_payload_streaming(to_stream)?;
+        Ok(Self { to_stream, start })
+    }
+
+    /// Write a child box to this superbox.
+    ///
+    /// The box's [`write_payload()`] method is called exactly once and its
+    /// length is back-patched afterward.
+    ///
+    /// [`write_payload()`]: crate::builder::ToBox::write_payload()
+    pub fn write_child_box(&mut self, boxx: &dyn ToBox) -> Result<()> {
+        write_jumbf_streaming(boxx, self.to_stream)
+    }
+
+    /// Begin writing a nested superbox within this superbox.
+    ///
+    /// The returned writer borrows this writer's stream. Call [`finish()`] on
+    /// the nested writer before writing any further children to this
+    /// superbox.
+    ///
+    /// [`finish()`]: Self::finish()
+    pub fn begin_child_super_box(
+        &mut self,
+        sbox: &SuperBoxBuilder,
+    ) -> Result<StreamingSuperBoxWriter<'_>> {
+        StreamingSuperBoxWriter::new(self.to_stream, sbox)
+    }
+
+    /// Finish writing this superbox by filling in its length.
+    ///
+    /// The stream is left positioned at the end of the superbox.
+    pub fn finish(self) -> Result<()> {
+        finish_streaming_box(self.start, self.to_stream)
+    }
+}
@@ -11,17 +11,35 @@
 // specific language governing permissions and limitations under
 // each license.
 
-use std::io::Result;
+use std::{
+    cmp::Ordering,
+    collections::BTreeSet,
+    io::{Result, SeekFrom},
+    slice,
+};
 
 use crate::{
-    box_type::{DESCRIPTION_BOX_TYPE, SUPER_BOX_TYPE},
+    box_type::{DESCRIPTION_BOX_TYPE, FREE_BOX_TYPE, SUPER_BOX_TYPE},
     builder::{
-        to_box::{jumbf_size, write_jumbf},
-        ToBox, WriteAndSeek,
+        error::validate_label,
+        to_box::{
+            begin_streaming_box, check_payload_written, finish_streaming_box,
+            jumbf_size_from_payload_size, write_box_header, write_jumbf, write_jumbf_streaming,
+            write_jumbf_with_size,
+        },
+        BuilderError, DataBoxBuilder, PreparedSuperBox, ToBox, WriteAndSeek,
     },
-    BoxType,
+    BoxType, Toggles, Uuid,
 };
 
+/// Box type for the salt box used by C2PA (`b"c2sh"`).
+const SALT_BOX_TYPE: BoxType = BoxType(*b"c2sh");
+
+/// Length of salt generated by [`SuperBoxBuilder::set_random_salt()`], in
+/// bytes. C2PA requires salt of at least 16 bytes.
+#[cfg(feature = "salt")]
+const RANDOM_SALT_LEN: usize = 16;
+
 /// A `SuperBoxBuilder` helps you create a JUMBF superbox which contains zero or
 /// more child boxes, each of which may or may not be a superbox.
 ///
@@ -52,7 +70,7 @@ use crate::{
 /// let dbox = DataBoxBuilder::from_borrowed(BoxType(*b"abcd"), b"some data");
 /// let uuid = [0u8; 16]; // replace with your app-specific UUID
 ///
-/// let sbox = SuperBoxBuilder::new(&uuid).add_child_box(dbox);
+/// let sbox = SuperBoxBuilder::new(uuid).add_child_box(dbox);
 ///
 /// let mut jumbf = Cursor::new(Vec::<u8>::new());
 /// sbox.write_jumbf(&mut jumbf)?;
@@ -77,6 +95,8 @@ use crate::{
 pub struct SuperBoxBuilder<'a> {
     desc: DescriptionBoxBuilder,
     child_boxes: Vec<OwnedOrBorrowedBox<'a>>,
+    canonical: bool,
+    strict: bool,
 }
 
 impl<'a> SuperBoxBuilder<'a> {
@@ -84,16 +104,68 @@ impl<'a> SuperBoxBuilder<'a> {
     ///
     /// A superbox is identified by an application-specific UUID.
     /// This crate does not interpret the UUID. Any 16-byte
-    /// value is allowed.
-    pub fn new(uuid: &[u8; 16]) -> Self {
+    /// value is allowed. Standard content types are available as
+    /// constants on [`Uuid`].
+    pub fn new<U: Into<Uuid>>(uuid: U) -> Self {
         Self {
-            desc: DescriptionBoxBuilder::new(uuid),
+            desc: DescriptionBoxBuilder::new(uuid.into()),
             child_boxes: vec![],
+            canonical: false,
+            strict: false,
         }
     }
 
+    /// Create a superbox with the content type [`Uuid::CODESTREAM`] which
+    /// contains `codestream` in a contiguous codestream box (`jp2c`).
+    ///
+    /// `codestream` is written as is; it is typically a JPEG 2000 or JPEG
+    /// codestream. See [`CodestreamFormat::detect()`] to check which.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # fn example() -> std::io::Result<()> {
+    /// use std::io::Cursor;
+    ///
+    /// use hex_literal::hex;
+    /// use jumbf::builder::SuperBoxBuilder;
+    ///
+    /// let sbox = SuperBoxBuilder::codestream(vec![0xff, 0x4f, 0xff, 0x51]).set_label("image");
+    ///
+    /// let mut jumbf = Cursor::new(Vec::<u8>::new());
+    /// sbox.write_jumbf(&mut jumbf)?;
+    ///
+    /// let expected_jumbf = hex!(
+    ///     "00000033" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "0000001f" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "6579d6fbdba2446bb2ac1b82feeb89d1" // UUID (codestream)
+    ///         "03" // toggles
+    ///         "696d61676500" // label ("image")
+    ///         // ---
+    ///         "0000000c" // box size
+    ///         "6a703263" // box type = 'jp2c'
+    ///         "ff4fff51" // payload
+    ///     );
+    ///
+    /// assert_eq!(*jumbf.into_inner(), expected_jumbf);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`CodestreamFormat::detect()`]: crate::codestream::CodestreamFormat::detect()
+    pub fn codestream(codestream: Vec<u8>) -> Self {
+        Self::new(Uuid::CODESTREAM)
+            .add_child_box(DataBoxBuilder::from_owned(BoxType::CODESTREAM, codestream))
+    }
+
     /// Set an application-specific label for the superbox.
     ///
+    /// The label may not contain the characters NUL, `/`, `?`, or `#`. If it
+    /// does, [`write_jumbf()`] will return an error wrapping
+    /// [`BuilderError::InvalidLabelCharacter`].
+    ///
     /// This label will flagged as "requestable," meaning a search via
     /// [`SuperBox::find_by_label()`] or an equivalent function in another
     /// JUMBF parser with this label should return it.
@@ -101,6 +173,8 @@ impl<'a> SuperBoxBuilder<'a> {
     /// If, for some reason, that is not desired, you can use
     /// [`set_non_requestable_label()`] instead.
     ///
+    /// [`write_jumbf()`]: Self::write_jumbf()
+    /// [`BuilderError::InvalidLabelCharacter`]: crate::builder::BuilderError::InvalidLabelCharacter
     /// [`SuperBox::find_by_label()`]: crate::parser::SuperBox::find_by_label()
     /// [`set_non_requestable_label()`]: Self::set_non_requestable_label()
     pub fn set_label<S: AsRef<str>>(mut self, label: S) -> Self {
@@ -109,13 +183,57 @@ impl<'a> SuperBoxBuilder<'a> {
         self
     }
 
+    /// Set a requestable label which is made unique by a UUID URN, in the form
+    /// `<prefix>:urn:uuid:<uuid>` (i.e.
+    /// `contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9`). If
+    /// `prefix` is empty, the label is just the URN.
+    ///
+    /// C2PA uses labels of this form for manifests. Such a label can be split
+    /// back into its components via [`UuidLabel::parse()`].
+    ///
+    /// [`UuidLabel::parse()`]: crate::parser::UuidLabel::parse()
+    pub fn set_label_with_urn<S: AsRef<str>, U: Into<Uuid>>(self, prefix: S, uuid: U) -> Self {
+        let prefix = prefix.as_ref();
+        let uuid = uuid.into();
+
+        if prefix.is_empty() {
+            self.set_label(format!("urn:uuid:{uuid}"))
+        } else {
+            self.set_label(format!("{prefix}:urn:uuid:{uuid}"))
+        }
+    }
+
+    /// Set a requestable label which is made unique by a random (version 4)
+    /// UUID, as described in [`set_label_with_urn()`].
+    ///
+    /// The UUID is read from the operating system's random number generator.
+    /// This method is available when the `salt` feature is enabled.
+    ///
+    /// [`set_label_with_urn()`]: Self::set_label_with_urn()
+    #[cfg(feature = "salt")]
+    pub fn set_label_with_uuid<S: AsRef<str>>(self, prefix: S) -> Self {
+        use rand::{rngs::OsRng, RngCore};
+
+        let mut uuid = [0u8; 16];
+        OsRng.fill_bytes(&mut uuid);
+
+        // Mark as a version 4, RFC 4122 variant UUID.
+        uuid[6] = (uuid[6] & 0x0f) | 0x40;
+        uuid[8] = (uuid[8] & 0x3f) | 0x80;
+
+        self.set_label_with_urn(prefix, uuid)
+    }
+
     /// Set an application-specific label for the superbox.
     ///
     /// This label is flagged as non-requestable, meaning a search via
     /// [`SuperBox::find_by_label()`] or an equivalent function in another
     /// JUMBF parser should not return it.
     ///
+    /// The same restrictions on label content apply as for [`set_label()`].
+    ///
     /// [`SuperBox::find_by_label()`]: crate::parser::SuperBox::find_by_label()
+    /// [`set_label()`]: Self::set_label()
     pub fn set_non_requestable_label<S: AsRef<str>>(mut self, label: S) -> Self {
         self.desc.label = Some(label.as_ref().to_owned());
         self.desc.requestable = false;
@@ -139,11 +257,80 @@ impl<'a> SuperBoxBuilder<'a> {
 
     /// Provide an application-specific "private" box within
     /// the description box. Takes ownership of the box.
+    ///
+    /// Any box may be used, including a `SuperBoxBuilder` with its own
+    /// description and child boxes. A private superbox can be read back via
+    /// [`DescriptionBox::private_super_box()`].
+    ///
+    /// [`DescriptionBox::private_super_box()`]: crate::parser::DescriptionBox::private_super_box()
     pub fn set_private_box(mut self, private: impl ToBox + 'static) -> Self {
         self.desc.private = Some(Box::new(private));
         self
     }
 
+    /// Attach a salt to the description box.
+    ///
+    /// The salt is written as the description box's private box, with box type
+    /// `c2sh`. C2PA uses such a box to make the hashes of otherwise identical
+    /// assertions unique. This replaces any box provided via
+    /// [`set_private_box()`].
+    ///
+    /// [`set_private_box()`]: Self::set_private_box()
+    pub fn set_salt(self, salt: &[u8]) -> Self {
+        self.set_private_box(DataBoxBuilder::from_owned(SALT_BOX_TYPE, salt.to_vec()))
+    }
+
+    /// Attach a salt of 16 cryptographically random bytes to the description
+    /// box, as described in [`set_salt()`].
+    ///
+    /// The salt is read from the operating system's random number generator.
+    /// This method is available when the `salt` feature is enabled.
+    ///
+    /// [`set_salt()`]: Self::set_salt()
+    #[cfg(feature = "salt")]
+    pub fn set_random_salt(self) -> Self {
+        use rand::{rngs::OsRng, RngCore};
+
+        let mut salt = [0u8; RANDOM_SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        self.set_salt(&salt)
+    }
+
+    /// Set additional bits in the description box's toggles byte.
+    ///
+    /// This is intended for use with future versions of the JUMBF
+    /// specification which may define new toggle bits. The bits given here
+    /// are written in addition to those implied by the other settings on this
+    /// builder (i.e. [`set_label()`] sets [`Toggles::REQUESTABLE`] and
+    /// [`Toggles::HAS_LABEL`]).
+    ///
+    /// The bits which indicate that an optional field is present
+    /// ([`Toggles::HAS_LABEL`], [`Toggles::HAS_ID`], [`Toggles::HAS_HASH`], and
+    /// [`Toggles::HAS_PRIVATE_BOX`]) are ignored, since writing one of them
+    /// without the corresponding field would produce an invalid description
+    /// box.
+    ///
+    /// [`set_label()`]: Self::set_label()
+    pub fn set_toggles(mut self, toggles: Toggles) -> Self {
+        self.desc.toggles = toggles;
+        self
+    }
+
+    /// Provide data to be written at the end of the description box, after
+    /// all of the fields defined by ISO/IEC 19566-5.
+    ///
+    /// As with [`set_toggles()`], this is intended to preserve content defined
+    /// by future versions of the JUMBF specification when rewriting a
+    /// superbox. Such content can be read via
+    /// [`DescriptionBox::trailing()`].
+    ///
+    /// [`set_toggles()`]: Self::set_toggles()
+    /// [`DescriptionBox::trailing()`]: crate::parser::DescriptionBox::trailing()
+    pub fn set_trailing_description_data(mut self, data: &[u8]) -> Self {
+        self.desc.trailing = data.to_vec();
+        self
+    }
+
     /// Add a child box. Takes ownership of the box.
     pub fn add_child_box(mut self, boxx: impl ToBox + 'static) -> Self {
         self.child_boxes
@@ -159,9 +346,595 @@ impl<'a> SuperBoxBuilder<'a> {
         self
     }
 
+    /// Insert a child box at position `index`, shifting all child boxes after
+    /// it. Takes ownership of the box.
+    ///
+    /// Positions count child boxes only; the description box is always
+    /// written first.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `index` is greater than [`child_box_count()`].
+    ///
+    /// [`child_box_count()`]: Self::child_box_count()
+    pub fn insert_child_box(mut self, index: usize, boxx: impl ToBox + 'static) -> Self {
+        self.child_boxes
+            .insert(index, OwnedOrBorrowedBox::OwnedBox(Box::new(boxx)));
+        self
+    }
+
+    /// Insert a child box at position `index` without taking ownership.
+    ///
+    /// See [`insert_child_box()`].
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `index` is greater than [`child_box_count()`].
+    ///
+    /// [`insert_child_box()`]: Self::insert_child_box()
+    /// [`child_box_count()`]: Self::child_box_count()
+    pub fn insert_borrowed_child_box<B: ToBox>(mut self, index: usize, boxx: &'a B) -> Self {
+        self.child_boxes
+            .insert(index, OwnedOrBorrowedBox::BorrowedBox(boxx));
+        self
+    }
+
+    /// Remove the child box at position `index`, shifting all child boxes
+    /// after it.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `index` is not less than [`child_box_count()`].
+    ///
+    /// [`child_box_count()`]: Self::child_box_count()
+    pub fn remove_child(mut self, index: usize) -> Self {
+        self.child_boxes.remove(index);
+        self
+    }
+
+    /// Replace the child box at position `index`. Takes ownership of the new
+    /// box.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `index` is not less than [`child_box_count()`].
+    ///
+    /// [`child_box_count()`]: Self::child_box_count()
+    pub fn replace_child(mut self, index: usize, boxx: impl ToBox + 'static) -> Self {
+        self.child_boxes[index] = OwnedOrBorrowedBox::OwnedBox(Box::new(boxx));
+        self
+    }
+
+    /// Replace the child box at position `index` without taking ownership of
+    /// the new box.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `index` is not less than [`child_box_count()`].
+    ///
+    /// [`child_box_count()`]: Self::child_box_count()
+    pub fn replace_borrowed_child<B: ToBox>(mut self, index: usize, boxx: &'a B) -> Self {
+        self.child_boxes[index] = OwnedOrBorrowedBox::BorrowedBox(boxx);
+        self
+    }
+
+    /// Sort the child boxes with a comparison function.
+    ///
+    /// The sort is stable, so child boxes which compare as equal keep their
+    /// relative order. Child boxes added after this call are not sorted.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use jumbf::{
+    ///     builder::{DataBoxBuilder, SuperBoxBuilder},
+    ///     BoxType,
+    /// };
+    ///
+    /// let sbox = SuperBoxBuilder::new([0; 16])
+    ///     .add_child_box(DataBoxBuilder::from_borrowed(BoxType(*b"bbbb"), b"2"))
+    ///     .add_child_box(DataBoxBuilder::from_borrowed(BoxType(*b"aaaa"), b"1"))
+    ///     .sort_children_by(|a, b| a.box_type().0.cmp(&b.box_type().0));
+    ///
+    /// let types: Vec<BoxType> = sbox.child_boxes().map(|child| child.box_type()).collect();
+    /// assert_eq!(types, [BoxType(*b"aaaa"), BoxType(*b"bbbb")]);
+    /// ```
+    pub fn sort_children_by<F>(mut self, mut compare: F) -> Self
+    where
+        F: FnMut(&dyn ToBox, &dyn ToBox) -> Ordering,
+    {
+        self.child_boxes
+            .sort_by(|a, b| compare(a.as_ref(), b.as_ref()));
+        self
+    }
+
+    /// Sort the child boxes by label so that the output does not depend on
+    /// the order in which child superboxes were added.
+    ///
+    /// Labeled superboxes are placed first, in byte order of their labels.
+    /// All other child boxes (including unlabeled superboxes) follow in
+    /// their original order.
+    ///
+    /// Note that some specifications (C2PA, for example) require a specific
+    /// order of child boxes which this method may not preserve.
+    pub fn sort_children_by_label(self) -> Self {
+        self.sort_children_by(|a, b| match (child_label(a), child_label(b)) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        })
+    }
+
+    /// Require that this superbox be written in canonical form.
+    ///
+    /// Canonical output is byte-for-byte reproducible for a given set of
+    /// inputs, which allows signed data structures to be regenerated exactly.
+    /// `SuperBoxBuilder` always writes child boxes in the order in which
+    /// they were added and always uses the shortest possible encoding for
+    /// each box's size. In addition, when canonical mode is enabled,
+    /// [`write_jumbf()`] will return an error wrapping
+    /// [`BuilderError::NonCanonicalChildBox`] if any child box is a free space
+    /// (`free`) box, such as a [`PaddingBoxBuilder`] or
+    /// [`PlaceholderSuperBox`].
+    ///
+    /// This setting applies only to this superbox. Nested superboxes must
+    /// enable canonical mode separately.
+    ///
+    /// Output written in canonical mode can be checked via
+    /// [`SuperBox::is_canonical()`].
+    ///
+    /// [`write_jumbf()`]: Self::write_jumbf()
+    /// [`PaddingBoxBuilder`]: crate::builder::PaddingBoxBuilder
+    /// [`PlaceholderSuperBox`]: crate::builder::PlaceholderSuperBox
+    /// [`SuperBox::is_canonical()`]: crate::parser::SuperBox::is_canonical()
+    pub fn set_canonical(mut self, canonical: bool) -> Self {
+        self.canonical = canonical;
+        self
+    }
+
+    /// Require that no two requestable child superboxes of this superbox
+    /// share a label.
+    ///
+    /// A parser can not find a child superbox by label if another child has
+    /// the same label (see [`SuperBox::find_by_label()`]). When strict mode
+    /// is enabled, [`write_jumbf()`] will instead return an error wrapping
+    /// [`BuilderError::DuplicateLabel`].
+    ///
+    /// This setting applies only to this superbox. Nested superboxes must
+    /// enable strict mode separately.
+    ///
+    /// [`SuperBox::find_by_label()`]: crate::parser::SuperBox::find_by_label()
+    /// [`write_jumbf()`]: Self::write_jumbf()
+    /// [`BuilderError::DuplicateLabel`]: crate::builder::BuilderError::DuplicateLabel
+    pub fn set_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Returns the application-specific UUID for the superbox.
+    pub fn uuid(&self) -> Uuid {
+        self.desc.uuid
+    }
+
+    /// Returns the superbox's label, if one has been set.
+    pub fn label(&self) -> Option<&str> {
+        self.desc.label.as_deref()
+    }
+
+    /// Returns `true` if the superbox's label is flagged as requestable.
+    pub fn is_requestable(&self) -> bool {
+        self.desc.requestable
+    }
+
+    /// Returns the superbox's application-specific 32-bit ID, if one has
+    /// been set.
+    pub fn id(&self) -> Option<u32> {
+        self.desc.id
+    }
+
+    /// Returns `true` if canonical mode is enabled for this superbox.
+    ///
+    /// See [`set_canonical()`].
+    ///
+    /// [`set_canonical()`]: Self::set_canonical()
+    pub fn is_canonical(&self) -> bool {
+        self.canonical
+    }
+
+    /// Returns `true` if strict mode is enabled for this superbox.
+    ///
+    /// See [`set_strict()`].
+    ///
+    /// [`set_strict()`]: Self::set_strict()
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Returns the number of child boxes, not counting the description box.
+    pub fn child_box_count(&self) -> usize {
+        self.child_boxes.len()
+    }
+
+    /// Returns the child boxes in the order in which they will be written,
+    /// not including the description box.
+    ///
+    /// Use [`ToBox::box_type()`] to tell the child boxes apart, and
+    /// [`ToBox::as_super_box_builder()`] to inspect a nested superbox.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use jumbf::{
+    ///     builder::{DataBoxBuilder, SuperBoxBuilder, ToBox},
+    ///     BoxType,
+    /// };
+    ///
+    /// let sbox = SuperBoxBuilder::new([0; 16])
+    ///     .add_child_box(DataBoxBuilder::from_borrowed(BoxType(*b"json"), b"{}"))
+    ///     .add_child_box(SuperBoxBuilder::new([1; 16]).set_label("nested"));
+    ///
+    /// let types: Vec<BoxType> = sbox.child_boxes().map(|child| child.box_type()).collect();
+    /// assert_eq!(types, [BoxType(*b"json"), BoxType(*b"jumb")]);
+    ///
+    /// let nested = sbox
+    ///     .child_boxes()
+    ///     .find_map(|child| child.as_super_box_builder());
+    /// assert_eq!(nested.unwrap().label(), Some("nested"));
+    /// ```
+    ///
+    /// [`ToBox::box_type()`]: crate::builder::ToBox::box_type()
+    /// [`ToBox::as_super_box_builder()`]: crate::builder::ToBox::as_super_box_builder()
+    pub fn child_boxes(&self) -> impl ExactSizeIterator<Item = &dyn ToBox> + '_ {
+        self.child_boxes.iter().map(OwnedOrBorrowedBox::as_ref)
+    }
+
+    /// Create an independent copy of this superbox, so that a template can be
+    /// customized (for example, per asset in a batch).
+    ///
+    /// Owned child boxes (those added via [`add_child_box()`] or
+    /// [`set_private_box()`]) are copied via [`ToBox::try_clone_box()`],
+    /// including any nested superboxes. Borrowed child boxes (those added via
+    /// [`add_borrowed_child_box()`]) are shared with the copy rather than
+    /// copied.
+    ///
+    /// An owned [`PlaceholderDataBox`] or [`PlaceholderSuperBox`] is copied
+    /// without its recorded offset.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an error if any owned box can not be cloned. The built-in box
+    /// types can all be cloned except for [`CallbackDataBox`], and
+    /// [`FileDataBox`] if its file handle can not be duplicated.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    ///
+    /// use jumbf::{
+    ///     builder::{DataBoxBuilder, SuperBoxBuilder},
+    ///     BoxType,
+    /// };
+    ///
+    /// let template = SuperBoxBuilder::new([0; 16])
+    ///     .add_child_box(DataBoxBuilder::from_borrowed(BoxType(*b"abcd"), b"common"));
+    ///
+    /// let asset = template
+    ///     .try_clone()
+    ///     .unwrap()
+    ///     .set_label("asset1")
+    ///     .add_child_box(DataBoxBuilder::from_borrowed(BoxType(*b"abcd"), b"asset1"));
+    ///
+    /// let mut jumbf = Cursor::new(Vec::<u8>::new());
+    /// asset.write_jumbf(&mut jumbf).unwrap();
+    /// assert_eq!(jumbf.into_inner().len(), 68);
+    /// ```
+    ///
+    /// [`add_child_box()`]: Self::add_child_box()
+    /// [`set_private_box()`]: Self::set_private_box()
+    /// [`add_borrowed_child_box()`]: Self::add_borrowed_child_box()
+    /// [`PlaceholderDataBox`]: crate::builder::PlaceholderDataBox
+    /// [`PlaceholderSuperBox`]: crate::builder::PlaceholderSuperBox
+    /// [`CallbackDataBox`]: crate::builder::CallbackDataBox
+    /// [`FileDataBox`]: crate::builder::FileDataBox
+    pub fn try_clone(&self) -> Result<Self> {
+        // Nested superboxes are copied without recursion so that a deeply
+        // nested tree can not overflow the stack.
+        let mut clone: Self = self.clone_empty()?;
+        let mut children = self.child_boxes.iter();
+        let mut stack: Vec<CloneFrame<'_>> = vec![];
+
+        loop {
+            let Some(frame) = stack.last_mut() else {
+                match children.next() {
+                    Some(child) => match child.try_clone_shallow()? {
+                        ClonedChild::Box(boxx) => clone.child_boxes.push(boxx),
+                        ClonedChild::SuperBox(sbox) => stack.push(CloneFrame::new(sbox)?),
+                    },
+                    None => return Ok(clone),
+                }
+                continue;
+            };
+
+            match frame.children.next() {
+                Some(child) => match child.try_clone_shallow()? {
+                    ClonedChild::Box(boxx) => frame.clone.child_boxes.push(boxx),
+                    ClonedChild::SuperBox(sbox) => stack.push(CloneFrame::new(sbox)?),
+                },
+                None => {
+                    let Some(frame) = stack.pop() else {
+                        unreachable!("stack is not empty");
+                    };
+
+                    let sbox = OwnedOrBorrowedBox::OwnedBox(Box::new(frame.clone));
+                    match stack.last_mut() {
+                        Some(parent) => parent.clone.child_boxes.push(sbox),
+                        None => clone.child_boxes.push(sbox),
+                    }
+                }
+            }
+        }
+    }
+
+    // Copy this superbox's description and settings, but not its children.
+    fn clone_empty(&self) -> Result<SuperBoxBuilder<'static>> {
+        Ok(SuperBoxBuilder {
+            desc: self.desc.try_clone()?,
+            child_boxes: vec![],
+            canonical: self.canonical,
+            strict: self.strict,
+        })
+    }
+
     /// Write this superbox and all of its child boxes to a JUMBF stream.
+    ///
+    /// This is equivalent to calling [`prepare()`] and then
+    /// [`PreparedSuperBox::write_jumbf()`].
+    ///
+    /// [`prepare()`]: Self::prepare()
+    /// [`PreparedSuperBox::write_jumbf()`]: crate::builder::PreparedSuperBox::write_jumbf()
     pub fn write_jumbf(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
-        write_jumbf(self, to_stream)
+        self.prepare()?.write_jumbf(to_stream)
+    }
+
+    /// Write this superbox and all of its child boxes starting at `offset`
+    /// within `to_stream`.
+    ///
+    /// This allows JUMBF to be written directly into a region that has been
+    /// reserved within a larger container file. Data in `to_stream` outside
+    /// of the superbox is not modified. When this returns, `to_stream` is
+    /// positioned at the end of the superbox.
+    ///
+    /// Since the superbox is written in place, the offsets recorded by any
+    /// [`PlaceholderDataBox`] or [`PlaceholderSuperBox`] are positions within
+    /// `to_stream`, and the placeholders can be filled in on `to_stream`
+    /// directly. To record offsets relative to some other base while writing
+    /// to a stream which can not seek, see [`WriteOnly::with_offset()`].
+    ///
+    /// Box sizes are computed before `to_stream` is touched, so if this fails
+    /// due to an invalid box, nothing will have been written.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    ///
+    /// use jumbf::{
+    ///     builder::{PlaceholderDataBox, SuperBoxBuilder},
+    ///     BoxType,
+    /// };
+    ///
+    /// let placeholder = PlaceholderDataBox::new(BoxType(*b"abcd"), 4);
+    /// let sbox = SuperBoxBuilder::new([0; 16]).add_borrowed_child_box(&placeholder);
+    ///
+    /// // A container with 100 bytes reserved for JUMBF at offset 20.
+    /// let mut container = Cursor::new(vec![0xff; 140]);
+    /// sbox.write_jumbf_at(&mut container, 20).unwrap();
+    ///
+    /// assert_eq!(placeholder.offset(), Some(61));
+    /// placeholder
+    ///     .replace_payload(&mut container, b"ABCD")
+    ///     .unwrap();
+    ///
+    /// let container = container.into_inner();
+    /// assert_eq!(&container[61..65], b"ABCD");
+    /// assert_eq!(container[65], 0xff);
+    /// ```
+    ///
+    /// [`PlaceholderDataBox`]: crate::builder::PlaceholderDataBox
+    /// [`PlaceholderSuperBox`]: crate::builder::PlaceholderSuperBox
+    /// [`WriteOnly::with_offset()`]: crate::builder::WriteOnly::with_offset()
+    pub fn write_jumbf_at(&self, to_stream: &mut dyn WriteAndSeek, offset: u64) -> Result<()> {
+        let prepared = self.prepare()?;
+        to_stream.seek(SeekFrom::Start(offset))?;
+        prepared.write_jumbf(to_stream)
+    }
+
+    /// Compute the size of this superbox and every box within it, so that it
+    /// can then be written in a single pass.
+    ///
+    /// Each child box's [`payload_size()`] is called exactly once, here. This
+    /// matters for boxes which must serialize their payload to determine its
+    /// size. The returned [`PreparedSuperBox`] also reports the total size
+    /// before anything is written, which is useful when space must be
+    /// reserved for the JUMBF in a container.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    ///
+    /// use jumbf::{
+    ///     builder::{DataBoxBuilder, SuperBoxBuilder},
+    ///     BoxType,
+    /// };
+    ///
+    /// let sbox = SuperBoxBuilder::new([0; 16]).add_child_box(DataBoxBuilder::from_borrowed(
+    ///     BoxType(*b"abcd"),
+    ///     b"some data",
+    /// ));
+    ///
+    /// let prepared = sbox.prepare().unwrap();
+    /// assert_eq!(prepared.jumbf_size(), 50);
+    ///
+    /// let mut jumbf = Cursor::new(Vec::<u8>::new());
+    /// prepared.write_jumbf(&mut jumbf).unwrap();
+    /// assert_eq!(jumbf.into_inner().len(), 50);
+    /// ```
+    ///
+    /// [`payload_size()`]: ToBox::payload_size()
+    pub fn prepare(&self) -> Result<PreparedSuperBox<'_>> {
+        Ok(PreparedSuperBox::new(self, self.box_sizes()?))
+    }
+
+    // Returns the payload size of this superbox and of every box within it,
+    // in the order in which they will be written: each superbox is followed
+    // by its description box and then by its child boxes.
+    //
+    // Nested superboxes are visited with an explicit stack rather than by
+    // recursion so that deeply nested trees can not overflow the thread's
+    // stack. Each box's size is computed once and added to its parent's,
+    // so the whole tree is sized in a single pass.
+    fn box_sizes(&self) -> Result<Vec<usize>> {
+        let mut sizes = vec![];
+        let mut stack = vec![SizeFrame::new(self, &mut sizes)?];
+
+        while let Some(frame) = stack.last_mut() {
+            match frame.children.next().map(OwnedOrBorrowedBox::as_ref) {
+                Some(child) => match child.as_super_box_builder() {
+                    Some(sbox) => stack.push(SizeFrame::new(sbox, &mut sizes)?),
+                    None => {
+                        let size = child.payload_size()?;
+                        sizes.push(size);
+                        frame.size += jumbf_size_from_payload_size(size);
+                    }
+                },
+                None => {
+                    let (index, size) = (frame.index, frame.size);
+                    stack.pop();
+                    sizes[index] = size;
+
+                    if let Some(parent) = stack.last_mut() {
+                        parent.size += jumbf_size_from_payload_size(size);
+                    }
+                }
+            }
+        }
+
+        Ok(sizes)
+    }
+
+    // Write this superbox given the sizes computed by `box_sizes()`. Its own
+    // box header is written only if `header` is true. As in `box_sizes()`,
+    // nested superboxes are written without recursion.
+    pub(crate) fn write_with_sizes(
+        &self,
+        sizes: &[usize],
+        header: bool,
+        to_stream: &mut dyn WriteAndSeek,
+    ) -> Result<()> {
+        let mut sizes = sizes.iter().copied();
+        let mut stack = vec![WriteFrame::begin(self, &mut sizes, header, to_stream)?];
+
+        while let Some(frame) = stack.last_mut() {
+            match frame.children.next().map(OwnedOrBorrowedBox::as_ref) {
+                Some(child) => match child.as_super_box_builder() {
+                    Some(sbox) => {
+                        stack.push(WriteFrame::begin(sbox, &mut sizes, true, to_stream)?);
+                    }
+                    None => write_jumbf_with_size(child, next_size(&mut sizes), to_stream)?,
+                },
+                None => {
+                    let (payload_start, payload_size) = (frame.payload_start, frame.payload_size);
+                    stack.pop();
+                    check_payload_written(SUPER_BOX_TYPE, payload_start, payload_size, to_stream)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the description box and child boxes without asking any of them
+    /// for their payload sizes first. Nested superboxes are streamed in the
+    /// same way.
+    ///
+    /// Used by [`StreamingSuperBoxWriter`].
+    ///
+    /// [`StreamingSuperBoxWriter`]: crate::builder::StreamingSuperBoxWriter
+    pub(crate) fn write_payload_streaming(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
+        let mut stack = vec![StreamFrame::begin(self, None, to_stream)?];
+
+        while let Some(frame) = stack.last_mut() {
+            match frame.children.next().map(OwnedOrBorrowedBox::as_ref) {
+                Some(child) => match child.as_super_box_builder() {
+                    Some(sbox) => {
+                        let start = begin_streaming_box(SUPER_BOX_TYPE, to_stream)?;
+                        stack.push(StreamFrame::begin(sbox, Some(start), to_stream)?);
+                    }
+                    None => write_jumbf_streaming(child, to_stream)?,
+                },
+                None => {
+                    let start = frame.start;
+                    stack.pop();
+                    if let Some(start) = start {
+                        finish_streaming_box(start, to_stream)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Check the requirements of canonical and strict mode.
+    fn validate(&self) -> Result<()> {
+        self.check_canonical()?;
+        self.check_strict()
+    }
+
+    fn check_strict(&self) -> Result<()> {
+        if !self.strict {
+            return Ok(());
+        }
+
+        let mut labels = BTreeSet::new();
+
+        for sbox in self
+            .child_boxes
+            .iter()
+            .filter_map(|child| child.as_ref().as_super_box_builder())
+            .filter(|sbox| sbox.is_requestable())
+        {
+            if let Some(label) = sbox.label() {
+                if !labels.insert(label) {
+                    return Err(BuilderError::DuplicateLabel {
+                        label: label.to_owned(),
+                    }
+                    .into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_canonical(&self) -> Result<()> {
+        if self.canonical {
+            if let Some(child) = self
+                .child_boxes
+                .iter()
+                .find(|child| child.as_ref().box_type() == FREE_BOX_TYPE)
+            {
+                return Err(BuilderError::NonCanonicalChildBox {
+                    tbox: child.as_ref().box_type(),
+                }
+                .into());
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -171,26 +944,179 @@ impl<'a> ToBox for SuperBoxBuilder<'a> {
     }
 
     fn payload_size(&self) -> Result<usize> {
-        let mut size: usize = jumbf_size(&self.desc)?;
+        Ok(self.box_sizes()?[0])
+    }
 
-        for child in &self.child_boxes {
-            size += jumbf_size(child.as_ref())?;
+    fn write_payload(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
+        self.write_with_sizes(&self.box_sizes()?, false, to_stream)
+    }
+
+    fn try_clone_box(&self) -> Result<Box<dyn ToBox>>
+    where
+        Self: 'static,
+    {
+        Ok(Box::new(self.try_clone()?))
+    }
+
+    fn as_super_box_builder(&self) -> Option<&SuperBoxBuilder<'_>> {
+        Some(self)
+    }
+
+    fn as_owned_super_box_builder(&self) -> Option<&SuperBoxBuilder<'static>>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn take_nested_super_boxes(&mut self) -> Vec<Box<dyn ToBox>> {
+        let mut nested = vec![];
+
+        for child in self.child_boxes.drain(..) {
+            if let OwnedOrBorrowedBox::OwnedBox(boxx) = child {
+                if boxx.as_super_box_builder().is_some() {
+                    nested.push(boxx);
+                }
+            }
         }
 
-        Ok(size)
+        nested
     }
+}
 
-    fn write_payload(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
-        write_jumbf(&self.desc, to_stream)?;
+// The default drop glue would recurse once per level of nesting, which could
+// overflow the stack for a deeply nested tree. Instead, detach owned
+// descendant superboxes and drop them one at a time.
+impl<'a> Drop for SuperBoxBuilder<'a> {
+    fn drop(&mut self) {
+        let mut stack = self.take_nested_super_boxes();
 
-        for child in &self.child_boxes {
-            write_jumbf(child.as_ref(), to_stream)?;
+        while let Some(mut sbox) = stack.pop() {
+            stack.append(&mut sbox.take_nested_super_boxes());
         }
+    }
+}
 
-        Ok(())
+// A superbox whose payload size is being computed by
+// `SuperBoxBuilder::box_sizes()`.
+struct SizeFrame<'s> {
+    children: slice::Iter<'s, OwnedOrBorrowedBox<'s>>,
+
+    // Position of this superbox's entry in the list of sizes.
+    index: usize,
+
+    // Size of the description box and of the child boxes visited so far.
+    size: usize,
+}
+
+impl<'s> SizeFrame<'s> {
+    // Reserve an entry in `sizes` for `sbox` and record the size of its
+    // description box.
+    fn new(sbox: &'s SuperBoxBuilder<'s>, sizes: &mut Vec<usize>) -> Result<Self> {
+        sbox.validate()?;
+
+        let index = sizes.len();
+        let desc_size = sbox.desc.payload_size()?;
+        sizes.extend([0, desc_size]);
+
+        Ok(Self {
+            children: sbox.child_boxes.iter(),
+            index,
+            size: jumbf_size_from_payload_size(desc_size),
+        })
     }
 }
 
+// A superbox whose payload is being written by
+// `SuperBoxBuilder::write_with_sizes()`.
+struct WriteFrame<'s> {
+    children: slice::Iter<'s, OwnedOrBorrowedBox<'s>>,
+    payload_start: u64,
+    payload_size: usize,
+}
+
+impl<'s> WriteFrame<'s> {
+    // Begin writing `sbox` by writing its header (if requested) and its
+    // description box.
+    fn begin(
+        sbox: &'s SuperBoxBuilder<'s>,
+        sizes: &mut impl Iterator<Item = usize>,
+        header: bool,
+        to_stream: &mut dyn WriteAndSeek,
+    ) -> Result<Self> {
+        let payload_size = next_size(sizes);
+        if header {
+            write_box_header(SUPER_BOX_TYPE, payload_size, to_stream)?;
+        }
+
+        let payload_start = to_stream.stream_position()?;
+        write_jumbf_with_size(&sbox.desc, next_size(sizes), to_stream)?;
+
+        Ok(Self {
+            children: sbox.child_boxes.iter(),
+            payload_start,
+            payload_size,
+        })
+    }
+}
+
+// A superbox which is being written by
+// `SuperBoxBuilder::write_payload_streaming()`.
+struct StreamFrame<'s> {
+    children: slice::Iter<'s, OwnedOrBorrowedBox<'s>>,
+
+    // Position of this superbox's header, whose length must be back-patched
+    // once its children have been written. `None` for the outermost
+    // superbox, whose header is written by the caller.
+    start: Option<u64>,
+}
+
+impl<'s> StreamFrame<'s> {
+    // Begin writing the payload of `sbox` by writing its description box.
+    fn begin(
+        sbox: &'s SuperBoxBuilder<'s>,
+        start: Option<u64>,
+        to_stream: &mut dyn WriteAndSeek,
+    ) -> Result<Self> {
+        sbox.validate()?;
+        write_jumbf(&sbox.desc, to_stream)?;
+
+        Ok(Self {
+            children: sbox.child_boxes.iter(),
+            start,
+        })
+    }
+}
+
+// A superbox which is being copied by `SuperBoxBuilder::try_clone()`.
+struct CloneFrame<'s> {
+    children: slice::Iter<'s, OwnedOrBorrowedBox<'static>>,
+    clone: SuperBoxBuilder<'static>,
+}
+
+impl<'s> CloneFrame<'s> {
+    fn new(sbox: &'s SuperBoxBuilder<'static>) -> Result<Self> {
+        Ok(Self {
+            children: sbox.child_boxes.iter(),
+            clone: sbox.clone_empty()?,
+        })
+    }
+}
+
+// Returns the label of `boxx` if it is a labeled superbox.
+fn child_label(boxx: &dyn ToBox) -> Option<&str> {
+    boxx.as_super_box_builder()?.label()
+}
+
+// Sizes are always written in the same order in which they were computed,
+// so there is always another one.
+fn next_size(sizes: &mut impl Iterator<Item = usize>) -> usize {
+    let Some(size) = sizes.next() else {
+        unreachable!("box_sizes() visits every box");
+    };
+    size
+}
+
 /// This struct is used by `SuperBoxBuilder` to construct the description
 /// box that is a required part of the superbox JUMBF data structure.
 ///
@@ -201,7 +1127,7 @@ impl<'a> ToBox for SuperBoxBuilder<'a> {
 /// called.
 struct DescriptionBoxBuilder {
     /// Application-specific UUID for the superbox's data type.
-    uuid: [u8; 16],
+    uuid: Uuid,
 
     /// Application-specific label for the superbox.
     label: Option<String>,
@@ -218,19 +1144,44 @@ struct DescriptionBoxBuilder {
 
     /// Application-specific "private" box within description box.
     private: Option<Box<dyn ToBox>>,
+
+    /// Additional toggle bits to write.
+    toggles: Toggles,
+
+    /// Data to write after all other fields.
+    trailing: Vec<u8>,
 }
 
 impl DescriptionBoxBuilder {
-    fn new(uuid: &[u8; 16]) -> Self {
+    fn new(uuid: Uuid) -> Self {
         Self {
-            uuid: *uuid,
+            uuid,
             label: None,
             requestable: false,
             id: None,
             hash: None,
             private: None,
+            toggles: Toggles::default(),
+            trailing: vec![],
         }
     }
+
+    fn try_clone(&self) -> Result<Self> {
+        Ok(Self {
+            uuid: self.uuid,
+            label: self.label.clone(),
+            requestable: self.requestable,
+            id: self.id,
+            hash: self.hash,
+            private: self
+                .private
+                .as_ref()
+                .map(|private| private.as_ref().try_clone_box())
+                .transpose()?,
+            toggles: self.toggles,
+            trailing: self.trailing.clone(),
+        })
+    }
 }
 
 impl ToBox for DescriptionBoxBuilder {
@@ -239,44 +1190,37 @@ impl ToBox for DescriptionBoxBuilder {
     }
 
     fn write_payload(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
-        use crate::toggles;
+        if let Some(label) = self.label.as_ref() {
+            validate_label(label)?;
+        }
 
-        to_stream.write_all(&self.uuid)?;
+        to_stream.write_all(&self.uuid.0)?;
 
-        // Calculate toggles byte.
-        let mut toggles = 0u8;
+        // Calculate toggles byte. Bits which describe the presence of
+        // optional fields always reflect the fields that were set.
+        let mut toggles = self.toggles & !FIELD_TOGGLES;
 
-        // Toggle bit 0 (0x01) indicates that this superbox can be requested
-        // via URI requests.
         if self.requestable {
-            toggles |= toggles::REQUESTABLE;
+            toggles |= Toggles::REQUESTABLE;
         }
 
-        // Toggle bit 1 (0x02) indicates that the label has an optional textual label.
         if self.label.is_some() {
-            toggles |= toggles::HAS_LABEL;
+            toggles |= Toggles::HAS_LABEL;
         }
 
-        // Toggle bit 2 (0x04) indicates that the label has an optional
-        // application-specific 32-bit identifier.
         if self.id.is_some() {
-            toggles |= toggles::HAS_ID;
+            toggles |= Toggles::HAS_ID;
         }
 
-        // Toggle bit 3 (0x08) indicates that a SHA-256 hash of the superbox's
-        // data box is present.
         if self.hash.is_some() {
-            toggles |= toggles::HAS_HASH;
+            toggles |= Toggles::HAS_HASH;
         }
 
-        // Toggle bit 4 (0x10) indicates that an application-specific "private"
-        // box is contained within the description box.
         if self.private.is_some() {
-            toggles |= toggles::HAS_PRIVATE_BOX;
+            toggles |= Toggles::HAS_PRIVATE_BOX;
         }
 
-        let toggles_slice = [toggles];
-        to_stream.write_all(&toggles_slice)?;
+        to_stream.write_all(&[toggles.bits()])?;
 
         if let Some(label) = self.label.as_ref() {
             to_stream.write_all(label.as_bytes())?;
@@ -295,6 +1239,8 @@ impl ToBox for DescriptionBoxBuilder {
             write_jumbf(private.as_ref(), to_stream)?;
         }
 
+        to_stream.write_all(&self.trailing)?;
+
         Ok(())
     }
 }
@@ -318,8 +1264,35 @@ impl<'a> OwnedOrBorrowedBox<'a> {
             OwnedOrBorrowedBox::BorrowedBox(boxx) => *boxx,
         }
     }
+
+    // Copy this box, unless it is an owned superbox, which the caller must
+    // copy along with its children.
+    fn try_clone_shallow(&self) -> Result<ClonedChild<'_, 'a>> {
+        match self {
+            OwnedOrBorrowedBox::OwnedBox(boxx) => match boxx.as_owned_super_box_builder() {
+                Some(sbox) => Ok(ClonedChild::SuperBox(sbox)),
+                None => Ok(ClonedChild::Box(OwnedOrBorrowedBox::OwnedBox(
+                    boxx.try_clone_box()?,
+                ))),
+            },
+            OwnedOrBorrowedBox::BorrowedBox(boxx) => {
+                Ok(ClonedChild::Box(OwnedOrBorrowedBox::BorrowedBox(*boxx)))
+            }
+        }
+    }
+}
+
+enum ClonedChild<'s, 'a> {
+    Box(OwnedOrBorrowedBox<'a>),
+    SuperBox(&'s SuperBoxBuilder<'static>),
 }
 
+// Toggle bits which indicate that an optional field is present in the
+// description box.
+const FIELD_TOGGLES: Toggles = Toggles(
+    Toggles::HAS_LABEL.0 | Toggles::HAS_ID.0 | Toggles::HAS_HASH.0 | Toggles::HAS_PRIVATE_BOX.0,
+);
+
 fn write_be_u32(to_stream: &mut dyn WriteAndSeek, v: u32) -> Result<()> {
     // Q&D implementation of big-endian formatting.
     let v_slice: [u8; 4] = [(v >> 24) as u8, (v >> 16) as u8, (v >> 8) as u8, v as u8];
@@ -13,7 +13,7 @@
 
 use std::io::{Error, ErrorKind, Result, Seek, SeekFrom, Write};
 
-use crate::BoxType;
+use crate::{builder::SuperBoxBuilder, BoxType};
 
 const MAX_32BIT_PAYLOAD_SIZE: usize = 0xfffffff7;
 
@@ -26,10 +26,14 @@ const MAX_32BIT_PAYLOAD_SIZE: usize = 0xfffffff7;
 /// not possible to implement the [`PlaceholderDataBox`] type without requiring
 /// [`Seek`].
 ///
+/// Implementations must be [`Send`] and [`Sync`] so that a builder tree can be
+/// shared across threads (for example, to compute several signatures over the
+/// same JUMBF data structure in parallel).
+///
 /// [`box_type()`]: Self::box_type()
 /// [`write_payload()`]: Self::write_payload()
 /// [`PlaceholderDataBox`]: crate::builder::PlaceholderDataBox
-pub trait ToBox {
+pub trait ToBox: Send + Sync {
     /// Specifies the type of information which will be provided by the
     /// [`write_payload()`] method.
     ///
@@ -59,13 +63,21 @@ pub trait ToBox {
     /// the content into a throwaway sink stream. Depending on the complexity
     /// of [`write_payload()`], this could be expensive.
     ///
+    /// The throwaway stream supports seeking, so [`write_payload()`] may seek
+    /// back to patch data it has already written. Positions in that stream are
+    /// relative to the start of the payload; [`write_payload()`] should
+    /// therefore compute any seek target from a position it has obtained via
+    /// [`Seek::stream_position()`]. The payload size is the furthest position
+    /// written to. In any case, [`write_payload()`] must leave the stream
+    /// positioned at the end of the payload.
+    ///
     /// [`DataBoxBuilder`]: crate::builder::DataBoxBuilder
     /// [`write_payload()`]: Self::write_payload()
     fn payload_size(&self) -> Result<usize> {
         let mut counting_sink = CountingSink::default();
         self.write_payload(&mut counting_sink)?;
         counting_sink.flush()?;
-        Ok(counting_sink.count)
+        counting_sink.count()
     }
 
     /// Write the payload for this box to the JUMBF stream.
@@ -75,14 +87,85 @@ pub trait ToBox {
     ///
     /// [`payload_size()`]: Self::payload_size()
     fn write_payload(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()>;
+
+    /// Returns an independent copy of this box.
+    ///
+    /// This is used by [`SuperBoxBuilder::try_clone()`] to copy the boxes
+    /// which a builder tree owns. A box which can be copied should override
+    /// this method; typically, the override is simply
+    /// `Ok(Box::new(self.clone()))`.
+    ///
+    /// The default implementation returns an error of kind
+    /// [`ErrorKind::Unsupported`].
+    fn try_clone_box(&self) -> Result<Box<dyn ToBox>>
+    where
+        Self: 'static,
+    {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            format!("box of type {:?} can not be cloned", self.box_type()),
+        ))
+    }
+
+    /// Returns this box as a [`SuperBoxBuilder`] if it is one.
+    ///
+    /// This allows a nested superbox found via
+    /// [`SuperBoxBuilder::child_boxes()`] to be inspected. Only
+    /// [`SuperBoxBuilder`] should override this method.
+    fn as_super_box_builder(&self) -> Option<&SuperBoxBuilder<'_>> {
+        None
+    }
+
+    // Implementation detail of `SuperBoxBuilder`, which clones nested
+    // superboxes without recursion. Only `SuperBoxBuilder` overrides this.
+    #[doc(hidden)]
+    fn as_owned_super_box_builder(&self) -> Option<&SuperBoxBuilder<'static>>
+    where
+        Self: 'static,
+    {
+        None
+    }
+
+    // Implementation detail of `SuperBoxBuilder`, which drops nested
+    // superboxes without recursion. Only `SuperBoxBuilder` overrides this.
+    #[doc(hidden)]
+    fn take_nested_super_boxes(&mut self) -> Vec<Box<dyn ToBox>> {
+        vec![]
+    }
 }
 
+#[cfg(test)]
 pub(crate) fn jumbf_size(boxx: &dyn ToBox) -> Result<usize> {
     Ok(jumbf_size_from_payload_size(boxx.payload_size()?))
 }
 
 pub(crate) fn write_jumbf(boxx: &dyn ToBox, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
-    let payload_size = boxx.payload_size()?;
+    write_jumbf_with_size(boxx, boxx.payload_size()?, to_stream)
+}
+
+/// Write a box whose payload size was previously obtained from
+/// [`ToBox::payload_size()`].
+pub(crate) fn write_jumbf_with_size(
+    boxx: &dyn ToBox,
+    payload_size: usize,
+    to_stream: &mut dyn WriteAndSeek,
+) -> Result<()> {
+    let box_type = boxx.box_type();
+    write_box_header(box_type, payload_size, to_stream)?;
+
+    // Verify that exactly the specified number of bytes was written.
+    // Otherwise, the box header we just wrote would be incorrect.
+    let payload_start = to_stream.stream_position()?;
+    boxx.write_payload(to_stream)?;
+    check_payload_written(box_type, payload_start, payload_size, to_stream)
+}
+
+/// Write a box header for a payload of `payload_size` bytes.
+pub(crate) fn write_box_header(
+    box_type: BoxType,
+    payload_size: usize,
+    to_stream: &mut dyn WriteAndSeek,
+) -> Result<()> {
     let jumbf_size = jumbf_size_from_payload_size(payload_size);
 
     if payload_size <= MAX_32BIT_PAYLOAD_SIZE {
@@ -98,18 +181,91 @@ pub(crate) fn write_jumbf(boxx: &dyn ToBox, to_stream: &mut dyn WriteAndSeek) ->
         unimplemented!();
     }
 
-    // TO DO: Check stream position and verify that exactly the
-    // specified number of bytes was written.
+    to_stream.write_all(&box_type.0)
+}
 
-    let box_type = boxx.box_type();
+/// Verify that the payload which began at `payload_start` is exactly
+/// `payload_size` bytes long.
+pub(crate) fn check_payload_written(
+    box_type: BoxType,
+    payload_start: u64,
+    payload_size: usize,
+    to_stream: &mut dyn WriteAndSeek,
+) -> Result<()> {
+    let payload_end = to_stream.stream_position()?;
+
+    let bytes_written = payload_end.saturating_sub(payload_start);
+    if bytes_written != payload_size as u64 {
+        return Err(Error::other(format!(
+            "write_jumbf: box type {box_type:?} wrote {bytes_written} bytes of payload, but payload_size() reported {payload_size} bytes"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Write a box without asking it for its payload size first.
+///
+/// The box header is written with a placeholder length; once the payload
+/// has been written, the stream is rewound and the actual length is
+/// back-patched into the header. A [`SuperBoxBuilder`] is streamed in the
+/// same way all the way down, so that none of its descendants are asked for
+/// their payload sizes either.
+///
+/// [`SuperBoxBuilder`]: crate::builder::SuperBoxBuilder
+pub(crate) fn write_jumbf_streaming(
+    boxx: &dyn ToBox,
+    to_stream: &mut dyn WriteAndSeek,
+) -> Result<()> {
+    let start = begin_streaming_box(boxx.box_type(), to_stream)?;
+    match boxx.as_super_box_builder() {
+        Some(sbox) => sbox.write_payload_streaming(to_stream)?,
+        None => boxx.write_payload(to_stream)?,
+    }
+    finish_streaming_box(start, to_stream)
+}
+
+/// Write a box header with a placeholder length and return the stream
+/// position at which the box starts.
+pub(crate) fn begin_streaming_box(
+    box_type: BoxType,
+    to_stream: &mut dyn WriteAndSeek,
+) -> Result<u64> {
+    let start = to_stream.stream_position()?;
+
+    // A length of zero means "box extends to end of stream," which is
+    // true for now. It will be replaced by finish_streaming_box().
+    to_stream.write_all(&[0u8; 4])?;
     to_stream.write_all(&box_type.0)?;
 
-    boxx.write_payload(to_stream)?;
+    Ok(start)
+}
+
+/// Back-patch the length of a box that was started by
+/// [`begin_streaming_box()`] and leave the stream positioned at the end of
+/// that box.
+pub(crate) fn finish_streaming_box(start: u64, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
+    let end = to_stream.stream_position()?;
+    let jumbf_size = end - start;
+
+    let jumbf_size = u32::try_from(jumbf_size)
+        .ok()
+        .filter(|size| *size as usize <= MAX_32BIT_PAYLOAD_SIZE + 8)
+        .ok_or_else(|| {
+            // TO DO: Support for >4GB payloads.
+            Error::other(format!(
+                "streaming box of {jumbf_size} bytes is too large to back-patch"
+            ))
+        })?;
+
+    to_stream.seek(SeekFrom::Start(start))?;
+    to_stream.write_all(&jumbf_size.to_be_bytes())?;
+    to_stream.seek(SeekFrom::Start(end))?;
 
     Ok(())
 }
 
-fn jumbf_size_from_payload_size(payload_size: usize) -> usize {
+pub(crate) fn jumbf_size_from_payload_size(payload_size: usize) -> usize {
     if payload_size <= MAX_32BIT_PAYLOAD_SIZE {
         payload_size + 8
     } else {
@@ -125,26 +281,48 @@ fn jumbf_size_from_payload_size(payload_size: usize) -> usize {
 pub trait WriteAndSeek: Write + Seek {}
 impl<T: Write + Seek> WriteAndSeek for T {}
 
+// Measures the size of a payload without storing it.
+//
+// Seeking is supported so that a `ToBox` implementation may back-patch its
+// own payload (e.g. to fill in a length once it is known). Positions are
+// relative to the start of the payload. The payload size is the furthest
+// position that has been written to, since bytes which are skipped over by
+// seeking past the end would still occupy space in a real stream.
 #[derive(Default)]
 struct CountingSink {
-    count: usize,
+    pos: u64,
+    len: u64,
+}
+
+impl CountingSink {
+    fn count(&self) -> Result<usize> {
+        usize::try_from(self.len).map_err(Error::other)
+    }
 }
 
 impl Seek for CountingSink {
-    fn seek(&mut self, _pos: SeekFrom) -> Result<u64> {
-        // Shouldn't need to seek while counting payload size.
-        Err(Error::new(
-            ErrorKind::Other,
-            "shouldn't need to seek while calculating payload size",
-        ))
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.pos = match pos {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::Current(n) => self.pos.checked_add_signed(n),
+            SeekFrom::End(n) => self.len.checked_add_signed(n),
+        }
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+
+        Ok(self.pos)
     }
 }
 
 impl Write for CountingSink {
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
-        let len = buf.len();
-        self.count += len;
-        Ok(len)
+        self.pos += buf.len() as u64;
+        self.len = self.len.max(self.pos);
+        Ok(buf.len())
     }
 
     fn flush(&mut self) -> Result<()> {
@@ -0,0 +1,127 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::{Error, ErrorKind, Result, Seek, SeekFrom, Write};
+
+/// A `WriteOnly` stream allows JUMBF to be written to a destination that
+/// implements [`Write`] but not [`Seek`], such as a network socket or a
+/// compression stream.
+///
+/// [`SuperBoxBuilder::write_jumbf()`] computes the size of each box before
+/// writing it, so it never needs to seek backward. This adapter keeps track
+/// of the number of bytes written so that the current stream position can
+/// still be reported. Any attempt to move to a different position results in
+/// an error of kind [`ErrorKind::Unsupported`].
+///
+/// A [`PlaceholderDataBox`] written to a `WriteOnly` stream will record its
+/// offset relative to the start of the `WriteOnly` stream (or to the base
+/// given to [`WriteOnly::with_offset()`]), but
+/// [`replace_payload()`] can not be used on this stream. Callers may use
+/// [`offset()`] to patch the placeholder in the final destination by other
+/// means.
+///
+/// [`StreamingSuperBoxWriter`] requires seeking and can not be used with this
+/// adapter.
+///
+/// ## Example
+///
+/// ```
+/// # fn example() -> std::io::Result<()> {
+/// use jumbf::{
+///     builder::{DataBoxBuilder, SuperBoxBuilder, WriteOnly},
+///     BoxType,
+/// };
+///
+/// let sbox = SuperBoxBuilder::new([0u8; 16]).add_child_box(DataBoxBuilder::from_borrowed(
+///     BoxType(*b"abcd"),
+///     b"some data",
+/// ));
+///
+/// // Vec<u8> implements Write but not Seek.
+/// let mut jumbf = WriteOnly::new(Vec::<u8>::new());
+/// sbox.write_jumbf(&mut jumbf)?;
+///
+/// assert_eq!(jumbf.into_inner().len(), 0x4a);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`SuperBoxBuilder::write_jumbf()`]: crate::builder::SuperBoxBuilder::write_jumbf()
+/// [`PlaceholderDataBox`]: crate::builder::PlaceholderDataBox
+/// [`replace_payload()`]: crate::builder::PlaceholderDataBox::replace_payload()
+/// [`offset()`]: crate::builder::PlaceholderDataBox::offset()
+/// [`StreamingSuperBoxWriter`]: crate::builder::StreamingSuperBoxWriter
+pub struct WriteOnly<W: Write> {
+    inner: W,
+    position: u64,
+}
+
+impl<W: Write> WriteOnly<W> {
+    /// Wrap a [`Write`] stream so that it may be used to write JUMBF.
+    pub fn new(inner: W) -> Self {
+        Self::with_offset(inner, 0)
+    }
+
+    /// Wrap a [`Write`] stream whose first byte will eventually be located
+    /// at `offset` within some larger destination.
+    ///
+    /// The reported stream position starts at `offset` rather than 0, so a
+    /// [`PlaceholderDataBox`] written to this stream records its offset
+    /// within that destination. This is useful when JUMBF is streamed into a
+    /// region of a container file which will be assembled elsewhere.
+    ///
+    /// [`PlaceholderDataBox`]: crate::builder::PlaceholderDataBox
+    pub fn with_offset(inner: W, offset: u64) -> Self {
+        Self {
+            inner,
+            position: offset,
+        }
+    }
+
+    /// Returns a reference to the underlying stream.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Consumes this adapter, returning the underlying stream.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for WriteOnly<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let len = self.inner.write(buf)?;
+        self.position += len as u64;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Seek for WriteOnly<W> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        // Only "seeks" which don't change the position are supported.
+        // These are used to query the current stream position.
+        match pos {
+            SeekFrom::Current(0) => Ok(self.position),
+            SeekFrom::Start(p) if p == self.position => Ok(self.position),
+            _ => Err(Error::new(
+                ErrorKind::Unsupported,
+                "WriteOnly stream does not support seeking",
+            )),
+        }
+    }
+}
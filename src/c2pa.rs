@@ -0,0 +1,265 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Conveniences for working with C2PA manifests.
+//!
+//! A C2PA manifest is a superbox which contains (among other things) a claim
+//! superbox (`c2pa.claim` or `c2pa.claim.v2`) and a claim signature superbox
+//! (`c2pa.signature`). The claim signature is a COSE_Sign1 structure whose
+//! detached payload is the CBOR-encoded claim. [`SignatureRef`] locates both
+//! so that the signature can be verified in a single step.
+//!
+//! Manifests are stored as children of a manifest store superbox (labeled
+//! `c2pa`). [`ManifestStore`] iterates over them.
+//!
+//! This module is available when the `parser` feature is enabled.
+
+use core::ops::Range;
+
+use crate::{
+    parser::{ChildBox, DataBox, Error, SuperBox, UuidLabel},
+    BoxType, Uuid,
+};
+
+/// Content type of a C2PA manifest store superbox
+/// (`63327061-0011-0010-8000-00aa00389b71`).
+pub const MANIFEST_STORE_TYPE: Uuid =
+    Uuid(*b"c2pa\x00\x11\x00\x10\x80\x00\x00\xaa\x00\x38\x9b\x71");
+
+/// Content type of a C2PA standard manifest superbox
+/// (`63326d61-0011-0010-8000-00aa00389b71`).
+pub const MANIFEST_TYPE: Uuid = Uuid(*b"c2ma\x00\x11\x00\x10\x80\x00\x00\xaa\x00\x38\x9b\x71");
+
+/// Content type of a C2PA update manifest superbox
+/// (`6332756d-0011-0010-8000-00aa00389b71`).
+pub const UPDATE_MANIFEST_TYPE: Uuid =
+    Uuid(*b"c2um\x00\x11\x00\x10\x80\x00\x00\xaa\x00\x38\x9b\x71");
+
+/// Labels of the claim superbox, in order of preference.
+const CLAIM_LABELS: [&str; 2] = ["c2pa.claim.v2", "c2pa.claim"];
+
+/// Label of the claim signature superbox.
+const SIGNATURE_LABEL: &str = "c2pa.signature";
+
+/// A C2PA manifest store: the outermost superbox of C2PA data, which
+/// contains one or more manifests.
+///
+/// ## Example
+///
+/// ```
+/// use jumbf::{c2pa::ManifestStore, parser::SuperBox};
+///
+/// let jumbf = include_bytes!("tests/fixtures/C.c2pa");
+/// let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+///
+/// let store = ManifestStore::from_super_box(&sbox).unwrap();
+///
+/// let labels: Vec<_> = store.manifests().filter_map(|m| m.label()).collect();
+/// assert_eq!(
+///     labels,
+///     ["contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9"]
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ManifestStore<'s, 'a> {
+    sbox: &'s SuperBox<'a>,
+}
+
+impl<'s, 'a> ManifestStore<'s, 'a> {
+    /// Interpret `sbox` as a C2PA manifest store.
+    ///
+    /// Will return [`Error::InvalidContentType`] if the content type of
+    /// `sbox` is not [`MANIFEST_STORE_TYPE`].
+    pub fn from_super_box(sbox: &'s SuperBox<'a>) -> Result<Self, Error> {
+        if sbox.desc.uuid != MANIFEST_STORE_TYPE {
+            return Err(Error::InvalidContentType {
+                expected: MANIFEST_STORE_TYPE,
+                actual: sbox.desc.uuid,
+            });
+        }
+
+        Ok(Self { sbox })
+    }
+
+    /// Returns the underlying superbox.
+    pub fn super_box(&self) -> &'s SuperBox<'a> {
+        self.sbox
+    }
+
+    /// Returns the manifests (both standard and update manifests) in this
+    /// store, in the order in which they appear.
+    ///
+    /// Ingredient manifests precede the active manifest, which is last (see
+    /// [`active_manifest()`]). Child superboxes of any other content type
+    /// are skipped.
+    ///
+    /// [`active_manifest()`]: Self::active_manifest
+    pub fn manifests(&self) -> impl DoubleEndedIterator<Item = Manifest<'s, 'a>> {
+        self.sbox
+            .child_boxes
+            .iter()
+            .filter_map(|child| match child {
+                ChildBox::SuperBox(sbox) => Manifest::from_super_box(sbox).ok(),
+                _ => None,
+            })
+    }
+
+    /// Returns the active manifest, which is the last manifest in the store.
+    pub fn active_manifest(&self) -> Option<Manifest<'s, 'a>> {
+        self.manifests().next_back()
+    }
+}
+
+/// A single C2PA manifest within a [`ManifestStore`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Manifest<'s, 'a> {
+    sbox: &'s SuperBox<'a>,
+}
+
+impl<'s, 'a> Manifest<'s, 'a> {
+    /// Interpret `sbox` as a C2PA manifest.
+    ///
+    /// Will return [`Error::InvalidContentType`] if the content type of
+    /// `sbox` is neither [`MANIFEST_TYPE`] nor [`UPDATE_MANIFEST_TYPE`].
+    pub fn from_super_box(sbox: &'s SuperBox<'a>) -> Result<Self, Error> {
+        if sbox.desc.uuid != MANIFEST_TYPE && sbox.desc.uuid != UPDATE_MANIFEST_TYPE {
+            return Err(Error::InvalidContentType {
+                expected: MANIFEST_TYPE,
+                actual: sbox.desc.uuid,
+            });
+        }
+
+        Ok(Self { sbox })
+    }
+
+    /// Returns the underlying superbox.
+    pub fn super_box(&self) -> &'s SuperBox<'a> {
+        self.sbox
+    }
+
+    /// Returns the label of this manifest, such as
+    /// `contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9`.
+    pub fn label(&self) -> Option<&'a str> {
+        self.sbox.desc.label
+    }
+
+    /// Returns the components of this manifest's label, including its URN,
+    /// if the label has the form `<prefix>:urn:uuid:<uuid>`.
+    pub fn uuid_label(&self) -> Option<UuidLabel<'a>> {
+        self.label().and_then(UuidLabel::parse)
+    }
+
+    /// Returns the URN which identifies this manifest (i.e.
+    /// `urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9`), if its label
+    /// contains one.
+    pub fn urn(&self) -> Option<&'a str> {
+        self.uuid_label().map(|label| label.urn)
+    }
+
+    /// Returns `true` if this is an update manifest.
+    pub fn is_update_manifest(&self) -> bool {
+        self.sbox.desc.uuid == UPDATE_MANIFEST_TYPE
+    }
+
+    /// Locate this manifest's claim signature and claim. See
+    /// [`SignatureRef::from_manifest()`].
+    pub fn signature(&self) -> Result<SignatureRef<'a>, Error> {
+        SignatureRef::from_manifest(self.sbox)
+    }
+}
+
+/// The claim signature of a C2PA manifest, together with the claim which
+/// it signs.
+///
+/// ## Example
+///
+/// ```
+/// use jumbf::{c2pa::SignatureRef, parser::SuperBox};
+///
+/// let jumbf = include_bytes!("tests/fixtures/C.c2pa");
+/// let (_, store) = SuperBox::from_slice(jumbf).unwrap();
+///
+/// let manifest = store
+///     .find_by_label("contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9")
+///     .unwrap();
+///
+/// let sig = SignatureRef::from_manifest(manifest).unwrap();
+///
+/// // COSE_Sign1 is CBOR tag 18.
+/// assert_eq!(sig.signature_bytes()[0], 0xd2);
+/// assert_eq!(&jumbf[sig.protected_range()], sig.claim_bytes());
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SignatureRef<'a> {
+    signature: &'a [u8],
+    claim: &'a [u8],
+    claim_offset: usize,
+}
+
+impl<'a> SignatureRef<'a> {
+    /// Locate the claim signature and claim within `manifest`, which should
+    /// be a C2PA manifest superbox.
+    ///
+    /// If the manifest contains both a `c2pa.claim.v2` and a `c2pa.claim`
+    /// superbox, the former is used.
+    ///
+    /// Will return [`Error::Malformed`] if either superbox is missing or
+    /// its label is not unique, or [`Error::MissingChildBox`] if either
+    /// superbox does not contain a CBOR content box.
+    pub fn from_manifest(manifest: &SuperBox<'a>) -> Result<Self, Error> {
+        let claim = CLAIM_LABELS
+            .iter()
+            .find_map(|label| manifest.find_by_label(label))
+            .ok_or(Error::Malformed("C2PA manifest with a claim"))?;
+
+        let signature = manifest
+            .find_by_label(SIGNATURE_LABEL)
+            .ok_or(Error::Malformed("C2PA manifest with a claim signature"))?;
+
+        let claim = cbor_box(claim)?;
+
+        Ok(Self {
+            signature: cbor_box(signature)?.data,
+            claim: claim.data,
+            claim_offset: claim.offset + claim.original.len() - claim.data.len(),
+        })
+    }
+
+    /// Returns the claim signature, which is a COSE_Sign1 structure.
+    pub fn signature_bytes(&self) -> &'a [u8] {
+        self.signature
+    }
+
+    /// Returns the CBOR-encoded claim, which is the (detached) payload
+    /// signed by [`signature_bytes()`].
+    ///
+    /// [`signature_bytes()`]: Self::signature_bytes
+    pub fn claim_bytes(&self) -> &'a [u8] {
+        self.claim
+    }
+
+    /// Returns the range of [`claim_bytes()`] within the source that was
+    /// originally parsed.
+    ///
+    /// This is useful when the signed bytes must be read or hashed from the
+    /// original asset rather than from memory.
+    ///
+    /// [`claim_bytes()`]: Self::claim_bytes
+    pub fn protected_range(&self) -> Range<usize> {
+        self.claim_offset..self.claim_offset + self.claim.len()
+    }
+}
+
+fn cbor_box<'s, 'a>(sbox: &'s SuperBox<'a>) -> Result<&'s DataBox<'a>, Error> {
+    sbox.cbor_box().ok_or(Error::MissingChildBox(BoxType::CBOR))
+}
@@ -0,0 +1,99 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Recognition of codestreams carried in contiguous codestream boxes.
+//!
+//! A superbox with the content type [`Uuid::CODESTREAM`] contains a single
+//! contiguous codestream box (`jp2c`) which holds an image codestream. This
+//! is typically a JPEG 2000 codestream, but JPEG and JPEG XS codestreams
+//! are also used. [`CodestreamFormat::detect()`] identifies which of these a
+//! payload contains by inspecting its first marker segment.
+//!
+//! [`SuperBoxBuilder::codestream()`] builds such a superbox, and
+//! [`SuperBox::codestream_payload()`] reads the codestream back from one.
+//!
+//! [`Uuid::CODESTREAM`]: crate::Uuid::CODESTREAM
+//! [`SuperBoxBuilder::codestream()`]: crate::builder::SuperBoxBuilder::codestream()
+//! [`SuperBox::codestream_payload()`]: crate::parser::SuperBox::codestream_payload()
+
+/// Start of codestream (SOC) marker which begins a JPEG 2000 codestream.
+pub const JPEG2000_SOC: [u8; 2] = [0xff, 0x4f];
+
+/// Image and tile size (SIZ) marker which must immediately follow
+/// [`JPEG2000_SOC`].
+pub const JPEG2000_SIZ: [u8; 2] = [0xff, 0x51];
+
+/// Start of image (SOI) marker which begins a JPEG codestream.
+pub const JPEG_SOI: [u8; 2] = [0xff, 0xd8];
+
+/// Start of codestream (SOC) marker which begins a JPEG XS codestream.
+pub const JPEG_XS_SOC: [u8; 2] = [0xff, 0x10];
+
+/// The kind of codestream found in a contiguous codestream box.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum CodestreamFormat {
+    /// JPEG 2000 codestream (ISO/IEC 15444-1).
+    Jpeg2000,
+
+    /// JPEG codestream (ISO/IEC 10918-1).
+    Jpeg,
+
+    /// JPEG XS codestream (ISO/IEC 21122-1).
+    JpegXs,
+}
+
+impl CodestreamFormat {
+    /// Identify the codestream format from the first bytes of `data`.
+    ///
+    /// Returns `None` if `data` does not begin with a recognized marker.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use jumbf::codestream::CodestreamFormat;
+    ///
+    /// assert_eq!(
+    ///     CodestreamFormat::detect(&[0xff, 0x4f, 0xff, 0x51, 0x00, 0x2f]),
+    ///     Some(CodestreamFormat::Jpeg2000)
+    /// );
+    /// assert_eq!(
+    ///     CodestreamFormat::detect(&[0xff, 0xd8, 0xff, 0xe0]),
+    ///     Some(CodestreamFormat::Jpeg)
+    /// );
+    /// assert_eq!(CodestreamFormat::detect(b"GIF89a"), None);
+    /// ```
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        match data.get(0..2)? {
+            soc if soc == JPEG2000_SOC => {
+                if data.get(2..4)? == JPEG2000_SIZ {
+                    Some(Self::Jpeg2000)
+                } else {
+                    None
+                }
+            }
+            soi if soi == JPEG_SOI => Some(Self::Jpeg),
+            soc if soc == JPEG_XS_SOC => Some(Self::JpegXs),
+            _ => None,
+        }
+    }
+
+    /// Returns the media (MIME) type for this codestream format.
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            Self::Jpeg2000 => "image/j2c",
+            Self::Jpeg => "image/jpeg",
+            Self::JpegXs => "image/jxsc",
+        }
+    }
+}
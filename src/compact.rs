@@ -0,0 +1,247 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    parser::{ChildBox, DataBox, SuperBox},
+    BoxType,
+};
+
+/// Statistics describing the result of [`compact_with_report()`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CompactReport {
+    /// Size of the original superbox, in bytes.
+    pub original_size: usize,
+
+    /// Size of the compacted superbox, in bytes.
+    pub compacted_size: usize,
+
+    /// Number of `free` boxes which were removed.
+    pub free_boxes_removed: usize,
+
+    /// Number of zero-filled placeholder payloads which were removed. This is
+    /// always zero unless [`CompactOptions::trim_zero_payloads`] is set.
+    pub placeholders_trimmed: usize,
+
+    /// Number of boxes whose headers were shortened by omitting an
+    /// unnecessary extended length (`XLBox`) field.
+    pub headers_shortened: usize,
+}
+
+/// Options which control how [`compact_with_options()`] rewrites a superbox.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CompactOptions {
+    /// If `true`, a data box whose payload consists entirely of zero bytes is
+    /// assumed to be a placeholder which was never filled in (such as one
+    /// written by [`PlaceholderDataBox`]) and is written with an empty
+    /// payload.
+    ///
+    /// This is `false` by default, because an all-zero payload may well be
+    /// real content, such as the CBOR encoding of `0` or a zeroed hash. Only
+    /// set it when every such box is known to be a placeholder.
+    ///
+    /// [`PlaceholderDataBox`]: crate::builder::PlaceholderDataBox
+    pub trim_zero_payloads: bool,
+}
+
+impl CompactReport {
+    /// Returns the number of bytes saved by compaction.
+    pub fn bytes_saved(&self) -> usize {
+        self.original_size.saturating_sub(self.compacted_size)
+    }
+}
+
+/// Rewrite `sbox` using the smallest possible encoding which preserves its
+/// semantic content.
+///
+/// Specifically:
+///
+/// * Each box header uses the 8-byte form unless the box is too large for a
+///   32-bit length, in which case the extended length (`XLBox`) form is used.
+/// * `free` boxes (such as those written by [`PaddingBoxBuilder`]) are removed.
+///
+/// All other boxes, including description boxes and their contents, are
+/// preserved as is. See [`compact_with_report()`] to learn how many bytes
+/// were saved, or [`compact_with_options()`] to also trim unfilled
+/// placeholders.
+///
+/// This function is available when the `parser` feature is enabled.
+///
+/// ## Example
+///
+/// ```
+/// use hex_literal::hex;
+/// use jumbf::{compact, parser::SuperBox};
+///
+/// let jumbf = hex!(
+///     "00000037" // box size
+///     "6a756d62" // box type = 'jumb'
+///         "00000019" // box size
+///         "6a756d64" // box type = 'jumd'
+///         "00000000000000000000000000000000" // UUID
+///         "00" // toggles
+///         // ----
+///         "0000000c" // box size
+///         "66726565" // box type = 'free'
+///         "00000000" // padding
+///         // ----
+///         "0000000a" // box size
+///         "6a736f6e" // box type = 'json'
+///         "7b7d" // payload
+/// );
+///
+/// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+///
+/// assert_eq!(
+///     compact(&sbox),
+///     hex!(
+///         "0000002b" // box size
+///         "6a756d62" // box type = 'jumb'
+///             "00000019" // box size
+///             "6a756d64" // box type = 'jumd'
+///             "00000000000000000000000000000000" // UUID
+///             "00" // toggles
+///             // ----
+///             "0000000a" // box size
+///             "6a736f6e" // box type = 'json'
+///             "7b7d" // payload
+///     )
+/// );
+/// ```
+///
+/// [`PaddingBoxBuilder`]: crate::builder::PaddingBoxBuilder
+pub fn compact(sbox: &SuperBox) -> Vec<u8> {
+    compact_with_report(sbox).0
+}
+
+/// Rewrite `sbox` as described in [`compact()`], and also report what was
+/// changed.
+///
+/// This function is available when the `parser` feature is enabled.
+pub fn compact_with_report(sbox: &SuperBox) -> (Vec<u8>, CompactReport) {
+    compact_with_options(sbox, &CompactOptions::default())
+}
+
+/// Rewrite `sbox` as described in [`compact()`], subject to `options`, and
+/// report what was changed.
+///
+/// This function is available when the `parser` feature is enabled.
+pub fn compact_with_options(sbox: &SuperBox, options: &CompactOptions) -> (Vec<u8>, CompactReport) {
+    let mut out = Vec::with_capacity(sbox.original.len());
+
+    let mut report = CompactReport {
+        original_size: sbox.original.len(),
+        ..CompactReport::default()
+    };
+
+    // Superboxes are visited without recursion so that deeply nested trees
+    // can't overflow the stack.
+    let mut stack = vec![Step::Enter(sbox)];
+
+    while let Some(step) = stack.pop() {
+        match step {
+            Step::Enter(sbox) => {
+                let start = out.len();
+                out.extend_from_slice(&[0; 8]);
+
+                match DataBox::from_slice(sbox.desc.original) {
+                    Ok((_, desc)) => write_box(&mut out, &mut report, &desc, desc.data),
+                    Err(_) => out.extend_from_slice(sbox.desc.original),
+                }
+
+                stack.push(Step::Exit(start, header_len(sbox.original)));
+
+                stack.extend(sbox.child_boxes.iter().rev().map(|child| match child {
+                    ChildBox::SuperBox(sbox) => Step::Enter(sbox),
+                    ChildBox::DataBox(dbox) => Step::Data(dbox),
+                }));
+            }
+
+            Step::Data(dbox) => {
+                if dbox.is_free() {
+                    report.free_boxes_removed += 1;
+                } else if options.trim_zero_payloads
+                    && !dbox.data.is_empty()
+                    && dbox.data.iter().all(|b| *b == 0)
+                {
+                    report.placeholders_trimmed += 1;
+                    write_box(&mut out, &mut report, dbox, &[]);
+                } else {
+                    write_box(&mut out, &mut report, dbox, dbox.data);
+                }
+            }
+
+            Step::Exit(start, original_header_len) => {
+                let len = out.len() - start;
+
+                match u32::try_from(len) {
+                    Ok(len) => {
+                        out[start..start + 4].copy_from_slice(&len.to_be_bytes());
+                        out[start + 4..start + 8].copy_from_slice(&BoxType::SUPER_BOX.0);
+                        if original_header_len > 8 {
+                            report.headers_shortened += 1;
+                        }
+                    }
+                    Err(_) => {
+                        let mut header = [0; 16];
+                        header[0..4].copy_from_slice(&1u32.to_be_bytes());
+                        header[4..8].copy_from_slice(&BoxType::SUPER_BOX.0);
+                        header[8..16].copy_from_slice(&(len as u64 + 8).to_be_bytes());
+                        out.splice(start..start + 8, header);
+                    }
+                }
+            }
+        }
+    }
+
+    report.compacted_size = out.len();
+    (out, report)
+}
+
+enum Step<'s, 'a> {
+    Enter(&'s SuperBox<'a>),
+    Data(&'s DataBox<'a>),
+    // Offset at which the superbox was started in the output, and the
+    // length of its original header.
+    Exit(usize, usize),
+}
+
+// Returns the length of the header of the box at the start of `original`.
+fn header_len(original: &[u8]) -> usize {
+    match original.get(0..4) {
+        Some([0, 0, 0, 1]) => 16,
+        _ => 8,
+    }
+}
+
+// Write a box of the same type as `dbox` containing `payload`, using the
+// shortest header that can represent its length.
+fn write_box(out: &mut Vec<u8>, report: &mut CompactReport, dbox: &DataBox, payload: &[u8]) {
+    match u32::try_from(payload.len() + 8) {
+        Ok(len) => {
+            if header_len(dbox.original) > 8 {
+                report.headers_shortened += 1;
+            }
+            out.extend_from_slice(&len.to_be_bytes());
+            out.extend_from_slice(&dbox.tbox.0);
+        }
+        Err(_) => {
+            out.extend_from_slice(&1u32.to_be_bytes());
+            out.extend_from_slice(&dbox.tbox.0);
+            out.extend_from_slice(&(payload.len() as u64 + 16).to_be_bytes());
+        }
+    }
+
+    out.extend_from_slice(payload);
+}
@@ -0,0 +1,112 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Compressed box payloads.
+//!
+//! Some applications store the payload of a box in compressed form. (For
+//! example, C2PA may store a brotli-compressed payload in a `c2cb` box.)
+//! The box type identifies the content as compressed; the application must
+//! know which [`Compression`] algorithm was used.
+//!
+//! Use [`DataBoxBuilder::compressed()`] to write such a box and
+//! [`DataBox::decompress()`] to read its payload.
+//!
+//! This module is available when the `compress` feature is enabled.
+//!
+//! [`DataBoxBuilder::compressed()`]: crate::builder::DataBoxBuilder::compressed
+//! [`DataBox::decompress()`]: crate::parser::DataBox::decompress
+
+use std::io::{Read, Result, Write};
+
+// Size of the internal buffers used by the brotli encoder and decoder.
+const BROTLI_BUFFER_SIZE: usize = 4096;
+
+// Brotli compression quality (0-11) and window size (10-24), chosen to
+// favor compressed size over speed.
+const BROTLI_QUALITY: u32 = 11;
+const BROTLI_WINDOW: u32 = 22;
+
+/// A compression algorithm which can be used for a box's payload.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Compression {
+    /// Brotli ([RFC 7932](https://www.rfc-editor.org/rfc/rfc7932)).
+    Brotli,
+
+    /// zlib ([RFC 1950](https://www.rfc-editor.org/rfc/rfc1950)).
+    Zlib,
+}
+
+impl Compression {
+    /// Compress `data` using this algorithm.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use jumbf::compression::Compression;
+    ///
+    /// let data = b"hello hello hello hello hello";
+    /// let compressed = Compression::Zlib.compress(data).unwrap();
+    ///
+    /// let decompressed = Compression::Zlib.decompress(&compressed, 1024).unwrap();
+    /// assert_eq!(decompressed.as_deref(), Some(&data[..]));
+    /// ```
+    pub fn compress(self, data: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            Self::Brotli => {
+                let mut out = Vec::new();
+                {
+                    let mut encoder = brotli::CompressorWriter::new(
+                        &mut out,
+                        BROTLI_BUFFER_SIZE,
+                        BROTLI_QUALITY,
+                        BROTLI_WINDOW,
+                    );
+                    encoder.write_all(data)?;
+                    encoder.flush()?;
+                }
+                Ok(out)
+            }
+
+            Self::Zlib => {
+                let mut encoder =
+                    flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::best());
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+        }
+    }
+
+    /// Decompress `data` using this algorithm.
+    ///
+    /// Returns `Ok(None)` if the decompressed data would be larger than
+    /// `max_size` bytes. A small compressed payload can expand to a very
+    /// large one, so `max_size` should be chosen with care when `data` comes
+    /// from an untrusted source.
+    ///
+    /// Will return an error if `data` is not validly compressed.
+    pub fn decompress(self, data: &[u8], max_size: usize) -> Result<Option<Vec<u8>>> {
+        let decoder: Box<dyn Read + '_> = match self {
+            Self::Brotli => Box::new(brotli::Decompressor::new(data, BROTLI_BUFFER_SIZE)),
+            Self::Zlib => Box::new(flate2::read::ZlibDecoder::new(data)),
+        };
+
+        // Read one byte past the limit so that overflow can be detected
+        // without decompressing everything.
+        let mut out = Vec::new();
+        decoder
+            .take((max_size as u64).saturating_add(1))
+            .read_to_end(&mut out)?;
+
+        Ok((out.len() <= max_size).then_some(out))
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::Uuid;
+
+/// Describes the type of content contained within a JUMBF superbox.
+///
+/// ISO/IEC 19566-5 defines several standard content types, each of which is
+/// identified by a specific UUID in the superbox's description box. Any other
+/// UUID is reported as [`ContentType::Unknown`]; its meaning is
+/// application-specific.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ContentType {
+    /// JSON content ([`Uuid::JSON`]).
+    Json,
+
+    /// CBOR content ([`Uuid::CBOR`]).
+    Cbor,
+
+    /// Contiguous codestream content ([`Uuid::CODESTREAM`]).
+    Codestream,
+
+    /// Embedded file content ([`Uuid::EMBEDDED_FILE`]).
+    EmbeddedFile,
+
+    /// UUID box content ([`Uuid::UUID`]).
+    Uuid,
+
+    /// XML content ([`Uuid::XML`]).
+    Xml,
+
+    /// Protocol Buffers content ([`Uuid::PROTOBUF`]).
+    Protobuf,
+
+    /// Any other (application-specific) content type.
+    Unknown(Uuid),
+}
+
+impl ContentType {
+    /// Returns the UUID which identifies this content type.
+    pub fn uuid(&self) -> Uuid {
+        match self {
+            Self::Json => Uuid::JSON,
+            Self::Cbor => Uuid::CBOR,
+            Self::Codestream => Uuid::CODESTREAM,
+            Self::EmbeddedFile => Uuid::EMBEDDED_FILE,
+            Self::Uuid => Uuid::UUID,
+            Self::Xml => Uuid::XML,
+            Self::Protobuf => Uuid::PROTOBUF,
+            Self::Unknown(uuid) => *uuid,
+        }
+    }
+}
+
+impl From<Uuid> for ContentType {
+    fn from(uuid: Uuid) -> Self {
+        match uuid {
+            Uuid::JSON => Self::Json,
+            Uuid::CBOR => Self::Cbor,
+            Uuid::CODESTREAM => Self::Codestream,
+            Uuid::EMBEDDED_FILE => Self::EmbeddedFile,
+            Uuid::UUID => Self::Uuid,
+            Uuid::XML => Self::Xml,
+            Uuid::PROTOBUF => Self::Protobuf,
+            uuid => Self::Unknown(uuid),
+        }
+    }
+}
+
+impl From<ContentType> for Uuid {
+    fn from(content_type: ContentType) -> Self {
+        content_type.uuid()
+    }
+}
@@ -0,0 +1,347 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Compare two parsed JUMBF data structures.
+//!
+//! [`diff()`] walks two [`SuperBox`] trees in parallel and reports each box
+//! which was added, removed, or modified. This is intended for regression
+//! tests of generated manifests and for analyzing how a JUMBF data structure
+//! has been altered.
+
+use alloc::{borrow::ToOwned, format, string::String, vec, vec::Vec};
+
+use crate::{
+    box_type::SUPER_BOX_TYPE,
+    parser::{ChildBox, DataBox, DescriptionBox, SuperBox},
+    BoxType,
+};
+
+/// Describes a single difference between two JUMBF data structures.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiffEntry {
+    /// Path to the box which differs.
+    ///
+    /// The path is a sequence of segments separated by `/`, relative to the
+    /// superboxes that were compared; the outermost superbox itself has an
+    /// empty path. Each labeled superbox is identified by its label, as in
+    /// [`SuperBox::find_by_label()`]. Any other box is identified by its box
+    /// type. If more than one sibling box would be identified the same way,
+    /// the second and subsequent boxes are given a zero-based suffix such as
+    /// `json[1]`.
+    ///
+    /// [`SuperBox::find_by_label()`]: crate::parser::SuperBox::find_by_label
+    pub path: String,
+
+    /// How the box differs.
+    pub kind: DiffKind,
+}
+
+/// Describes how a box differs between two JUMBF data structures.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DiffKind {
+    /// The box is present only in the new data structure.
+    Added,
+
+    /// The box is present only in the old data structure.
+    Removed,
+
+    /// The superbox's description box (other than its label) differs.
+    DescriptionModified,
+
+    /// The payload of a data box differs.
+    PayloadModified(ByteDiff),
+}
+
+/// Summarizes the differences between two byte slices.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ByteDiff {
+    /// Length of the old payload.
+    pub old_len: usize,
+
+    /// Length of the new payload.
+    pub new_len: usize,
+
+    /// Offset of the first byte which differs.
+    pub first_difference: usize,
+
+    /// Number of bytes which differ, counting each byte beyond the end of
+    /// the shorter payload as different.
+    pub bytes_changed: usize,
+}
+
+impl ByteDiff {
+    /// Compare two byte slices. Returns `None` if they are identical.
+    pub fn new(old: &[u8], new: &[u8]) -> Option<Self> {
+        let first_difference = old
+            .iter()
+            .zip(new.iter())
+            .position(|(a, b)| a != b)
+            .unwrap_or_else(|| old.len().min(new.len()));
+
+        if first_difference == old.len() && first_difference == new.len() {
+            return None;
+        }
+
+        let bytes_changed = old.iter().zip(new.iter()).filter(|(a, b)| a != b).count()
+            + old.len().abs_diff(new.len());
+
+        Some(Self {
+            old_len: old.len(),
+            new_len: new.len(),
+            first_difference,
+            bytes_changed,
+        })
+    }
+}
+
+/// Compare two JUMBF superboxes and report the boxes which were added,
+/// removed, or modified in `new` relative to `old`.
+///
+/// Child boxes are matched as described in [`DiffEntry::path`]. Differences
+/// within nested superboxes are reported individually, rather than as a
+/// single modification of the enclosing superbox. Entries are reported in
+/// the order the boxes appear in `old`, followed by boxes which appear only
+/// in `new`.
+///
+/// Returns an empty vector if the two superboxes are equivalent.
+///
+/// ## Example
+///
+/// ```
+/// use hex_literal::hex;
+/// use jumbf::{
+///     diff::{diff, ByteDiff, DiffEntry, DiffKind},
+///     parser::SuperBox,
+/// };
+///
+/// let old = hex!(
+///     "0000002d" // box size
+///     "6a756d62" // box type = 'jumb'
+///         "00000019" // box size
+///         "6a756d64" // box type = 'jumd'
+///         "00000000000000000000000000000000" // UUID
+///         "00" // toggles
+///         // ----
+///         "0000000c" // box size
+///         "61626364" // box type = 'abcd'
+///         "41424344" // payload
+/// );
+///
+/// let mut new = old;
+/// new[44] = b'E';
+///
+/// let (_, old) = SuperBox::from_slice(&old).unwrap();
+/// let (_, new) = SuperBox::from_slice(&new).unwrap();
+///
+/// assert_eq!(
+///     diff(&old, &new),
+///     vec![DiffEntry {
+///         path: "abcd".to_owned(),
+///         kind: DiffKind::PayloadModified(ByteDiff {
+///             old_len: 4,
+///             new_len: 4,
+///             first_difference: 3,
+///             bytes_changed: 1,
+///         }),
+///     }]
+/// );
+/// ```
+pub fn diff(old: &SuperBox, new: &SuperBox) -> Vec<DiffEntry> {
+    let mut entries = vec![];
+
+    // Path segments of the superboxes which have been matched, each with the
+    // index of its parent's segment. Full paths are only assembled when an
+    // entry is reported.
+    let mut segments: Vec<(Option<usize>, String)> = vec![];
+
+    // Work remaining, in reverse order. Nested superboxes are compared via
+    // this explicit stack rather than by recursion so that deeply nested
+    // input can not overflow the thread's stack.
+    let mut stack = vec![Step::Compare(old, new, None)];
+
+    while let Some(step) = stack.pop() {
+        let (old, new, segment) = match step {
+            Step::Compare(old, new, segment) => (old, new, segment),
+            Step::Report(entry) => {
+                entries.push(entry);
+                continue;
+            }
+        };
+
+        // Only assembled if a difference is found.
+        let mut path: Option<String> = None;
+
+        if !same_description(&old.desc, &new.desc) {
+            let path = path.get_or_insert_with(|| join(&segments, segment));
+            push(&mut entries, path, DiffKind::DescriptionModified);
+        }
+
+        let old_children = keyed_children(&old.child_boxes);
+        let new_children = keyed_children(&new.child_boxes);
+
+        let mut steps: Vec<Step> = vec![];
+
+        for (key, old_child) in &old_children {
+            let new_child = match new_children.iter().find(|(new_key, _)| new_key == key) {
+                Some((_, new_child)) => new_child,
+                None => {
+                    steps.push(report(
+                        &mut path,
+                        &segments,
+                        segment,
+                        key,
+                        DiffKind::Removed,
+                    ));
+                    continue;
+                }
+            };
+
+            match (old_child, new_child) {
+                (ChildBox::SuperBox(old), ChildBox::SuperBox(new)) => {
+                    segments.push((segment, key.clone()));
+                    steps.push(Step::Compare(old, new, Some(segments.len() - 1)));
+                }
+                (ChildBox::DataBox(old), ChildBox::DataBox(new)) if old.tbox == new.tbox => {
+                    if let Some(byte_diff) = ByteDiff::new(old.data, new.data) {
+                        let kind = DiffKind::PayloadModified(byte_diff);
+                        steps.push(report(&mut path, &segments, segment, key, kind));
+                    }
+                }
+                _ => {
+                    steps.push(report(
+                        &mut path,
+                        &segments,
+                        segment,
+                        key,
+                        DiffKind::Removed,
+                    ));
+                    steps.push(report(&mut path, &segments, segment, key, DiffKind::Added));
+                }
+            }
+        }
+
+        for (key, _) in &new_children {
+            if !old_children.iter().any(|(old_key, _)| old_key == key) {
+                steps.push(report(&mut path, &segments, segment, key, DiffKind::Added));
+            }
+        }
+
+        stack.extend(steps.into_iter().rev());
+    }
+
+    entries
+}
+
+// A unit of work for `diff()`.
+enum Step<'s, 'o, 'n> {
+    // Compare two superboxes, whose path segment (if any) is at the given
+    // index in `segments`.
+    Compare(&'s SuperBox<'o>, &'s SuperBox<'n>, Option<usize>),
+
+    // Report a difference. Reports are queued along with the comparisons of
+    // sibling superboxes so that entries are reported in box order.
+    Report(DiffEntry),
+}
+
+// Compare everything in the description box except the label (which is
+// used to match superboxes) and its position in the source.
+fn same_description(old: &DescriptionBox, new: &DescriptionBox) -> bool {
+    old.uuid == new.uuid
+        && old.requestable == new.requestable
+        && old.id == new.id
+        && old.hash == new.hash
+        && old.private.as_ref().map(data_box_content) == new.private.as_ref().map(data_box_content)
+}
+
+fn data_box_content<'a>(data_box: &DataBox<'a>) -> (BoxType, &'a [u8]) {
+    (data_box.tbox, data_box.data)
+}
+
+// Assign each child box a path segment that is unique among its siblings.
+fn keyed_children<'c, 'a>(child_boxes: &'c [ChildBox<'a>]) -> Vec<(String, &'c ChildBox<'a>)> {
+    let mut result: Vec<(String, &ChildBox)> = Vec::with_capacity(child_boxes.len());
+    let mut bases: Vec<String> = Vec::with_capacity(child_boxes.len());
+
+    for child in child_boxes {
+        let base = match child {
+            ChildBox::SuperBox(sbox) => sbox
+                .desc
+                .label
+                .map(str::to_owned)
+                .unwrap_or_else(|| box_type_segment(SUPER_BOX_TYPE)),
+            ChildBox::DataBox(dbox) => box_type_segment(dbox.tbox),
+        };
+
+        let n = bases.iter().filter(|b| **b == base).count();
+        let key = if n == 0 {
+            base.clone()
+        } else {
+            format!("{base}[{n}]")
+        };
+
+        bases.push(base);
+        result.push((key, child));
+    }
+
+    result
+}
+
+fn box_type_segment(tbox: BoxType) -> String {
+    String::from_utf8_lossy(&tbox.0).into_owned()
+}
+
+// Report a difference in the child box `key` of the superbox at `segment`,
+// whose path is assembled into `path` on first use.
+fn report<'s, 'o, 'n>(
+    path: &mut Option<String>,
+    segments: &[(Option<usize>, String)],
+    segment: Option<usize>,
+    key: &str,
+    kind: DiffKind,
+) -> Step<'s, 'o, 'n> {
+    let path = path.get_or_insert_with(|| join(segments, segment));
+
+    Step::Report(DiffEntry {
+        path: join_key(path, key),
+        kind,
+    })
+}
+
+// Assemble the path for `segment` by following parent indexes.
+fn join(segments: &[(Option<usize>, String)], segment: Option<usize>) -> String {
+    let mut keys: Vec<&str> = vec![];
+    let mut next = segment;
+
+    while let Some((parent, key)) = next.and_then(|index| segments.get(index)) {
+        keys.push(key);
+        next = *parent;
+    }
+
+    keys.reverse();
+    keys.join("/")
+}
+
+fn join_key(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{path}/{key}")
+    }
+}
+
+fn push(entries: &mut Vec<DiffEntry>, path: &str, kind: DiffKind) {
+    entries.push(DiffEntry {
+        path: path.to_owned(),
+        kind,
+    });
+}
@@ -0,0 +1,177 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Embedding JUMBF in JPEG XL files.
+//!
+//! A JPEG XL file is either a bare codestream (beginning with `0xFF0A`) or a
+//! container of ISO BMFF-style boxes (ISO/IEC 18181-2) which begins with a
+//! JPEG XL signature box and a file type box, and carries the codestream in
+//! a `jxlc` box or a sequence of `jxlp` boxes. C2PA stores its manifest
+//! store as a top-level JUMBF superbox (`jumb`) in the container.
+//!
+//! [`extract_jumbf()`] finds the top-level JUMBF superboxes in a container,
+//! [`insert_jumbf()`] adds one, and [`remove_jumbf()`] removes them all.
+//! Brotli-compressed (`brob`) boxes are not examined.
+//!
+//! ## Example
+//!
+//! ```
+//! use jumbf::{embed::jxl, parser::SuperBox};
+//!
+//! let codestream = [0xff, 0x0a, 0xfa, 0x1f]; // a (truncated) bare codestream
+//! let jumbf = include_bytes!("../tests/fixtures/C.c2pa");
+//!
+//! let file = jxl::insert_jumbf(&codestream, jumbf).unwrap();
+//! assert!(jxl::is_container(&file));
+//!
+//! let boxes = jxl::extract_jumbf(&file).unwrap();
+//! assert_eq!(boxes, [&jumbf[..]]);
+//!
+//! let (_, sbox) = SuperBox::from_slice(boxes[0]).unwrap();
+//! assert_eq!(sbox.desc.label, Some("c2pa"));
+//! ```
+
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    embed::check_jumbf,
+    parser::{DataBox, Error},
+    BoxType,
+};
+
+/// The JPEG XL signature box which begins every JPEG XL container.
+pub const SIGNATURE: [u8; 12] = *b"\x00\x00\x00\x0cJXL \x0d\x0a\x87\x0a";
+
+/// The signature which begins a bare JPEG XL codestream.
+pub const CODESTREAM_SIGNATURE: [u8; 2] = [0xff, 0x0a];
+
+/// Box type of the file type box (`ftyp`).
+pub const FILE_TYPE: BoxType = BoxType(*b"ftyp");
+
+/// Box type of a box holding the entire codestream (`jxlc`).
+pub const CODESTREAM: BoxType = BoxType(*b"jxlc");
+
+/// Box type of a box holding part of the codestream (`jxlp`).
+pub const PARTIAL_CODESTREAM: BoxType = BoxType(*b"jxlp");
+
+// File type box for a container with brand `jxl `, minor version 0, and
+// compatible brand `jxl `.
+const FTYP: [u8; 20] = *b"\x00\x00\x00\x14ftypjxl \x00\x00\x00\x00jxl ";
+
+/// Returns `true` if `data` begins with the JPEG XL container signature.
+pub fn is_container(data: &[u8]) -> bool {
+    data.starts_with(&SIGNATURE)
+}
+
+/// Returns `true` if `data` begins with the signature of a bare JPEG XL
+/// codestream.
+pub fn is_codestream(data: &[u8]) -> bool {
+    data.starts_with(&CODESTREAM_SIGNATURE)
+}
+
+/// Returns each top-level JUMBF superbox (`jumb`) in the JPEG XL container
+/// `jxl`, including its box header, in the order in which they appear.
+///
+/// A bare codestream cannot hold JUMBF, so an empty list is returned for
+/// one. Returns an error if `jxl` is neither a container nor a bare
+/// codestream, or if the container's boxes are malformed.
+pub fn extract_jumbf(jxl: &[u8]) -> Result<Vec<&[u8]>, Error> {
+    if is_codestream(jxl) {
+        return Ok(vec![]);
+    }
+
+    Ok(top_level_boxes(jxl)?
+        .into_iter()
+        .filter(|dbox| dbox.tbox == BoxType::SUPER_BOX)
+        .map(|dbox| dbox.original)
+        .collect())
+}
+
+/// Returns a copy of the JPEG XL file `jxl` with the JUMBF superbox `jumbf`
+/// added as a top-level box.
+///
+/// If `jxl` is a container, `jumbf` is inserted immediately before the first
+/// codestream box (`jxlc` or `jxlp`). Any JUMBF superboxes already in the
+/// container are kept; call [`remove_jumbf()`] first to replace them.
+///
+/// If `jxl` is a bare codestream, it is wrapped in a new container which
+/// holds a signature box, a file type box, `jumbf`, and a `jxlc` box with
+/// the codestream.
+///
+/// `jumbf` must consist of exactly one superbox. Its content is not
+/// otherwise checked.
+pub fn insert_jumbf(jxl: &[u8], jumbf: &[u8]) -> Result<Vec<u8>, Error> {
+    check_jumbf(jumbf)?;
+
+    if is_codestream(jxl) {
+        let jxlc_len = u32::try_from(jxl.len() + 8)
+            .map_err(|_| Error::Malformed("JPEG XL codestream smaller than 4 GB"))?;
+
+        let mut out =
+            Vec::with_capacity(SIGNATURE.len() + FTYP.len() + jumbf.len() + 8 + jxl.len());
+        out.extend_from_slice(&SIGNATURE);
+        out.extend_from_slice(&FTYP);
+        out.extend_from_slice(jumbf);
+        out.extend_from_slice(&jxlc_len.to_be_bytes());
+        out.extend_from_slice(&CODESTREAM.0);
+        out.extend_from_slice(jxl);
+        return Ok(out);
+    }
+
+    let at = top_level_boxes(jxl)?
+        .into_iter()
+        .find(|dbox| dbox.tbox == CODESTREAM || dbox.tbox == PARTIAL_CODESTREAM)
+        .map(|dbox| dbox.offset)
+        .ok_or(Error::MissingChildBox(CODESTREAM))?;
+
+    let mut out = Vec::with_capacity(jxl.len() + jumbf.len());
+    out.extend_from_slice(&jxl[..at]);
+    out.extend_from_slice(jumbf);
+    out.extend_from_slice(&jxl[at..]);
+    Ok(out)
+}
+
+/// Returns a copy of the JPEG XL container `jxl` without any of its
+/// top-level JUMBF superboxes.
+///
+/// A bare codestream is returned unchanged.
+pub fn remove_jumbf(jxl: &[u8]) -> Result<Vec<u8>, Error> {
+    if is_codestream(jxl) {
+        return Ok(jxl.to_vec());
+    }
+
+    let mut out = Vec::with_capacity(jxl.len());
+    for dbox in top_level_boxes(jxl)? {
+        if dbox.tbox != BoxType::SUPER_BOX {
+            out.extend_from_slice(dbox.original);
+        }
+    }
+    Ok(out)
+}
+
+// Parse the boxes of the container `jxl`, starting with the signature box.
+fn top_level_boxes(jxl: &[u8]) -> Result<Vec<DataBox<'_>>, Error> {
+    if !is_container(jxl) {
+        return Err(Error::Malformed("JPEG XL container or codestream"));
+    }
+
+    let mut boxes = vec![];
+    let mut i = jxl;
+    while !i.is_empty() {
+        let (rem, dbox) = DataBox::from_slice_at(i, jxl.len() - i.len())?;
+        boxes.push(dbox);
+        i = rem;
+    }
+
+    Ok(boxes)
+}
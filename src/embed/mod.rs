@@ -0,0 +1,50 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Embedding JUMBF in image and audio files.
+//!
+//! Each submodule handles one file format. They share a common shape:
+//! `extract_jumbf()` returns the JUMBF superboxes stored in a file,
+//! `insert_jumbf()` returns a copy of a file with a JUMBF superbox added,
+//! and `remove_jumbf()` returns a copy of a file with its JUMBF superboxes
+//! removed. All of them operate on complete files held in memory and never
+//! modify their input.
+//!
+//! See also [`crate::app11`], which plans the storage of JUMBF in JPEG
+//! files.
+//!
+//! This module is available when the `parser` feature is enabled.
+
+pub mod jxl;
+pub mod mp3;
+pub(crate) mod riff;
+pub mod wav;
+pub mod webp;
+
+use crate::{
+    parser::{DataBox, Error},
+    BoxType,
+};
+
+// Verify that `jumbf` consists of exactly one superbox.
+fn check_jumbf(jumbf: &[u8]) -> Result<(), Error> {
+    let (rem, sbox) = DataBox::from_slice(jumbf)?;
+
+    if sbox.tbox != BoxType::SUPER_BOX {
+        Err(Error::InvalidSuperBoxType(sbox.tbox))
+    } else if !rem.is_empty() {
+        Err(Error::Malformed("a single JUMBF superbox"))
+    } else {
+        Ok(())
+    }
+}
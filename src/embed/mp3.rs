@@ -0,0 +1,404 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Embedding JUMBF in MP3 files.
+//!
+//! An MP3 file may begin with an ID3v2 tag, which holds metadata in a
+//! sequence of frames. C2PA stores its manifest store in a general
+//! encapsulated object (`GEOB`) frame whose MIME type is
+//! [`C2PA_MIME_TYPE`]. Some applications instead use a private (`PRIV`)
+//! frame whose owner identifier is [`C2PA_PRIV_OWNER`]; both are recognized
+//! by [`extract_jumbf()`].
+//!
+//! ID3v2.3 and ID3v2.4 tags are supported. Tags which use
+//! unsynchronisation are rejected. Frames whose content is compressed,
+//! encrypted, or unsynchronised are copied as is but never examined.
+//!
+//! ## Example
+//!
+//! ```
+//! use jumbf::{embed::mp3, parser::SuperBox};
+//!
+//! let audio = [0xff, 0xfb, 0x90, 0x00]; // an MP3 frame header, without a tag
+//! let jumbf = include_bytes!("../tests/fixtures/C.c2pa");
+//!
+//! let file = mp3::insert_jumbf(&audio, jumbf).unwrap();
+//! assert!(file.starts_with(b"ID3"));
+//! assert!(file.ends_with(&audio));
+//!
+//! let boxes = mp3::extract_jumbf(&file).unwrap();
+//! assert_eq!(boxes, [&jumbf[..]]);
+//!
+//! let (_, sbox) = SuperBox::from_slice(boxes[0]).unwrap();
+//! assert_eq!(sbox.desc.label, Some("c2pa"));
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::{embed::check_jumbf, parser::Error};
+
+/// MIME type of a `GEOB` frame which holds a C2PA manifest store.
+pub const C2PA_MIME_TYPE: &str = "application/x-c2pa-manifest-store";
+
+/// Owner identifier of a `PRIV` frame which holds a C2PA manifest store.
+pub const C2PA_PRIV_OWNER: &str = "c2pa";
+
+/// The kind of ID3v2 frame in which a JUMBF superbox is stored.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Id3Frame {
+    /// A general encapsulated object (`GEOB`) frame with the MIME type
+    /// [`C2PA_MIME_TYPE`]. This is the frame specified by C2PA.
+    #[default]
+    Geob,
+
+    /// A private (`PRIV`) frame with the owner identifier
+    /// [`C2PA_PRIV_OWNER`].
+    Priv,
+}
+
+// Tag header flags.
+const UNSYNCHRONISATION: u8 = 0x80;
+const EXTENDED_HEADER: u8 = 0x40;
+const FOOTER: u8 = 0x10;
+
+// Largest value which can be stored in a 28-bit syncsafe integer.
+const MAX_SYNCSAFE: usize = (1 << 28) - 1;
+
+/// Returns the JUMBF data stored in each C2PA `GEOB` or `PRIV` frame of the
+/// ID3v2 tag at the start of the MP3 file `mp3`, in the order in which they
+/// appear.
+///
+/// Returns an empty list if `mp3` does not begin with an ID3v2 tag. Each
+/// result is expected to be a JUMBF superbox, but is not checked.
+pub fn extract_jumbf(mp3: &[u8]) -> Result<Vec<&[u8]>, Error> {
+    let Some(tag) = Tag::parse(mp3)? else {
+        return Ok(Vec::new());
+    };
+
+    Ok(tag.frames.iter().filter_map(c2pa_payload).collect())
+}
+
+/// Returns a copy of the MP3 file `mp3` with the JUMBF superbox `jumbf`
+/// added in a new C2PA `GEOB` frame.
+///
+/// This is equivalent to [`insert_jumbf_with_frame()`] with
+/// [`Id3Frame::Geob`].
+pub fn insert_jumbf(mp3: &[u8], jumbf: &[u8]) -> Result<Vec<u8>, Error> {
+    insert_jumbf_with_frame(mp3, jumbf, Id3Frame::Geob)
+}
+
+/// Returns a copy of the MP3 file `mp3` with the JUMBF superbox `jumbf`
+/// added in a new frame of the given kind.
+///
+/// If `mp3` begins with an ID3v2 tag, the frame is added after its existing
+/// frames, which are kept; call [`remove_jumbf()`] first to replace an
+/// existing C2PA frame. The tag's padding and extended header (if any) are
+/// removed. Otherwise, a new ID3v2.4 tag holding only the new frame is
+/// added to the start of the file.
+///
+/// `jumbf` must consist of exactly one superbox. Its content is not
+/// otherwise checked.
+pub fn insert_jumbf_with_frame(
+    mp3: &[u8],
+    jumbf: &[u8],
+    frame: Id3Frame,
+) -> Result<Vec<u8>, Error> {
+    check_jumbf(jumbf)?;
+
+    let (id, content) = match frame {
+        Id3Frame::Geob => {
+            // Text encoding (ISO-8859-1), MIME type, empty file name, and
+            // empty content description, followed by the object.
+            let mut content = Vec::with_capacity(C2PA_MIME_TYPE.len() + 4 + jumbf.len());
+            content.push(0);
+            content.extend_from_slice(C2PA_MIME_TYPE.as_bytes());
+            content.extend_from_slice(&[0, 0, 0]);
+            content.extend_from_slice(jumbf);
+            (*b"GEOB", content)
+        }
+        Id3Frame::Priv => {
+            let mut content = Vec::with_capacity(C2PA_PRIV_OWNER.len() + 1 + jumbf.len());
+            content.extend_from_slice(C2PA_PRIV_OWNER.as_bytes());
+            content.push(0);
+            content.extend_from_slice(jumbf);
+            (*b"PRIV", content)
+        }
+    };
+
+    let new_frame = Frame {
+        id,
+        flags: [0, 0],
+        content: &content,
+    };
+
+    let (mut tag, audio) = match Tag::parse(mp3)? {
+        Some(tag) => {
+            let audio = &mp3[tag.len..];
+            (tag, audio)
+        }
+        None => (
+            Tag {
+                version: [4, 0],
+                flags: 0,
+                frames: Vec::new(),
+                len: 0,
+            },
+            mp3,
+        ),
+    };
+
+    tag.frames.push(new_frame);
+
+    let mut out = tag.write()?;
+    out.extend_from_slice(audio);
+    Ok(out)
+}
+
+/// Returns a copy of the MP3 file `mp3` without any of the C2PA `GEOB` or
+/// `PRIV` frames in its ID3v2 tag.
+///
+/// If no other frames remain, the tag is removed. The tag's padding and
+/// extended header (if any) are removed. A file which does not begin with
+/// an ID3v2 tag is returned unchanged.
+pub fn remove_jumbf(mp3: &[u8]) -> Result<Vec<u8>, Error> {
+    let Some(mut tag) = Tag::parse(mp3)? else {
+        return Ok(mp3.to_vec());
+    };
+
+    let audio = &mp3[tag.len..];
+    tag.frames.retain(|frame| c2pa_payload(frame).is_none());
+
+    let mut out = if tag.frames.is_empty() {
+        Vec::with_capacity(audio.len())
+    } else {
+        tag.write()?
+    };
+
+    out.extend_from_slice(audio);
+    Ok(out)
+}
+
+// An ID3v2 tag.
+struct Tag<'a> {
+    // Major version and revision.
+    version: [u8; 2],
+    flags: u8,
+    frames: Vec<Frame<'a>>,
+
+    // Length of the tag in the original file, including its header and any
+    // footer.
+    len: usize,
+}
+
+struct Frame<'a> {
+    id: [u8; 4],
+    flags: [u8; 2],
+    content: &'a [u8],
+}
+
+impl<'a> Tag<'a> {
+    // Parse the ID3v2 tag at the start of `mp3`, if there is one.
+    fn parse(mp3: &'a [u8]) -> Result<Option<Self>, Error> {
+        if !mp3.starts_with(b"ID3") {
+            return Ok(None);
+        }
+
+        let header = mp3
+            .get(0..10)
+            .ok_or_else(|| Error::Incomplete(10 - mp3.len()))?;
+        let version = [header[3], header[4]];
+        let flags = header[5];
+        let size = read_syncsafe(&header[6..10])?;
+
+        if version[0] != 3 && version[0] != 4 {
+            return Err(Error::Malformed("ID3v2.3 or ID3v2.4 tag"));
+        }
+        if flags & UNSYNCHRONISATION != 0 {
+            return Err(Error::Malformed("ID3v2 tag without unsynchronisation"));
+        }
+
+        let footer_len = if version[0] == 4 && flags & FOOTER != 0 {
+            10
+        } else {
+            0
+        };
+        let len = 10 + size + footer_len;
+        if mp3.len() < len {
+            return Err(Error::Incomplete(len - mp3.len()));
+        }
+
+        let mut i = &mp3[10..10 + size];
+
+        if flags & EXTENDED_HEADER != 0 {
+            let ext_len = match version[0] {
+                // The size does not include the size field itself.
+                3 => read_u32(i)?
+                    .checked_add(4)
+                    .ok_or(Error::Malformed("ID3v2 extended header"))?,
+                _ => read_syncsafe(i.get(0..4).unwrap_or_default())?,
+            };
+            i = i
+                .get(ext_len..)
+                .ok_or(Error::Malformed("ID3v2 extended header"))?;
+        }
+
+        let mut frames = Vec::new();
+        while let Some(header) = i.get(0..10) {
+            // Padding (or the end of the frames).
+            if header[0] == 0 {
+                break;
+            }
+
+            let size = match version[0] {
+                3 => read_u32(&header[4..8])?,
+                _ => read_syncsafe(&header[4..8])?,
+            };
+
+            // The size comes from the file and may be as large as `u32::MAX`,
+            // which would overflow a 32-bit `usize`.
+            let end = size
+                .checked_add(10)
+                .ok_or(Error::Malformed("ID3v2 frame within its tag"))?;
+            let content = i
+                .get(10..end)
+                .ok_or(Error::Malformed("ID3v2 frame within its tag"))?;
+
+            frames.push(Frame {
+                id: [header[0], header[1], header[2], header[3]],
+                flags: [header[8], header[9]],
+                content,
+            });
+
+            i = &i[end..];
+        }
+
+        Ok(Some(Self {
+            version,
+            flags,
+            frames,
+            len,
+        }))
+    }
+
+    // Serialize this tag without padding or an extended header.
+    fn write(&self) -> Result<Vec<u8>, Error> {
+        let size: usize = self
+            .frames
+            .iter()
+            .map(|frame| 10 + frame.content.len())
+            .sum();
+
+        let size = u32::try_from(size)
+            .ok()
+            .filter(|size| *size as usize <= MAX_SYNCSAFE)
+            .ok_or(Error::Malformed("ID3v2 tag smaller than 256 MB"))?;
+
+        let flags = self.flags & !EXTENDED_HEADER;
+
+        let mut out = Vec::with_capacity(20 + size as usize);
+        out.extend_from_slice(b"ID3");
+        out.extend_from_slice(&self.version);
+        out.push(flags);
+        out.extend_from_slice(&syncsafe(size));
+
+        for frame in &self.frames {
+            // Frame sizes are smaller than the tag size, so they can't
+            // overflow.
+            let len = frame.content.len() as u32;
+
+            out.extend_from_slice(&frame.id);
+            if self.version[0] == 3 {
+                out.extend_from_slice(&len.to_be_bytes());
+            } else {
+                out.extend_from_slice(&syncsafe(len));
+            }
+            out.extend_from_slice(&frame.flags);
+            out.extend_from_slice(frame.content);
+        }
+
+        if self.version[0] == 4 && flags & FOOTER != 0 {
+            out.extend_from_slice(b"3DI");
+            out.extend_from_slice(&self.version);
+            out.push(flags);
+            out.extend_from_slice(&syncsafe(size));
+        }
+
+        Ok(out)
+    }
+}
+
+// If `frame` is a C2PA `GEOB` or `PRIV` frame, return its JUMBF data.
+fn c2pa_payload<'a>(frame: &Frame<'a>) -> Option<&'a [u8]> {
+    // Skip frames whose content is compressed, encrypted, or otherwise
+    // transformed.
+    if frame.flags[1] != 0 {
+        return None;
+    }
+
+    match &frame.id {
+        b"GEOB" => {
+            let (&encoding, i) = frame.content.split_first()?;
+            let (mime_type, i) = split_terminated(i, 1)?;
+            if mime_type != C2PA_MIME_TYPE.as_bytes() {
+                return None;
+            }
+
+            // UTF-16 strings are terminated by two NUL bytes.
+            let width = if encoding == 1 || encoding == 2 { 2 } else { 1 };
+            let (_file_name, i) = split_terminated(i, width)?;
+            let (_description, object) = split_terminated(i, width)?;
+            Some(object)
+        }
+        b"PRIV" => {
+            let (owner, data) = split_terminated(frame.content, 1)?;
+            (owner == C2PA_PRIV_OWNER.as_bytes()).then_some(data)
+        }
+        _ => None,
+    }
+}
+
+// Split `i` at the first NUL terminator made of `width` bytes (aligned to
+// `width`), returning the string before it and the data after it.
+fn split_terminated(i: &[u8], width: usize) -> Option<(&[u8], &[u8])> {
+    let at = i
+        .chunks(width)
+        .position(|c| c.len() == width && c.iter().all(|b| *b == 0))?
+        * width;
+
+    Some((&i[..at], &i[at + width..]))
+}
+
+fn read_u32(i: &[u8]) -> Result<usize, Error> {
+    match i.get(0..4) {
+        Some([b0, b1, b2, b3]) => Ok(u32::from_be_bytes([*b0, *b1, *b2, *b3]) as usize),
+        _ => Err(Error::Malformed("ID3v2 size field")),
+    }
+}
+
+fn read_syncsafe(i: &[u8]) -> Result<usize, Error> {
+    match i.get(0..4) {
+        Some(bytes) if bytes.iter().all(|b| b & 0x80 == 0) => {
+            Ok(bytes.iter().fold(0, |n, b| (n << 7) | usize::from(*b)))
+        }
+        _ => Err(Error::Malformed("ID3v2 size field")),
+    }
+}
+
+fn syncsafe(n: u32) -> [u8; 4] {
+    [
+        ((n >> 21) & 0x7f) as u8,
+        ((n >> 14) & 0x7f) as u8,
+        ((n >> 7) & 0x7f) as u8,
+        (n & 0x7f) as u8,
+    ]
+}
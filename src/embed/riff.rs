@@ -0,0 +1,118 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+// Reading and writing the RIFF container format used by WebP and WAV files.
+//
+// A RIFF file consists of a `RIFF` chunk whose payload is a four-byte form
+// type followed by a sequence of chunks. Each chunk has a four-byte ID, a
+// four-byte little-endian payload size, and the payload, followed by a
+// padding byte if the size is odd. The padding byte is not included in the
+// size.
+
+use alloc::vec::Vec;
+
+use crate::parser::Error;
+
+/// A chunk within a RIFF file.
+pub(crate) struct Chunk<'a> {
+    /// Chunk ID (i.e. `b"VP8X"`).
+    pub(crate) id: [u8; 4],
+
+    /// Chunk payload, not including any padding byte.
+    pub(crate) data: &'a [u8],
+}
+
+/// Parse the chunks of the RIFF file `riff`, which must have the form type
+/// `form`.
+///
+/// Any data following the `RIFF` chunk is ignored. A padding byte that is
+/// missing at the very end of the file is tolerated, since many writers
+/// omit it.
+pub(crate) fn parse<'a>(riff: &'a [u8], form: &[u8; 4]) -> Result<Vec<Chunk<'a>>, Error> {
+    if riff.get(0..4) != Some(b"RIFF") || riff.get(8..12) != Some(form) {
+        return Err(Error::Malformed("RIFF file"));
+    }
+
+    let size = read_u32(riff, 4)?;
+    let end = size.checked_add(8).ok_or(Error::Malformed("RIFF file"))?;
+    let mut i = riff
+        .get(12..end)
+        .ok_or_else(|| incomplete(end, riff.len()))?;
+
+    let mut chunks = Vec::new();
+    while !i.is_empty() {
+        let id = i
+            .get(0..4)
+            .and_then(|id| <[u8; 4]>::try_from(id).ok())
+            .ok_or_else(|| incomplete(8, i.len()))?;
+        let size = read_u32(i, 4)?;
+
+        let end = size.saturating_add(8);
+        let data = i.get(8..end).ok_or_else(|| incomplete(end, i.len()))?;
+
+        chunks.push(Chunk { id, data });
+
+        let padded = end.saturating_add(size & 1).min(i.len());
+        i = &i[padded..];
+    }
+
+    Ok(chunks)
+}
+
+/// Append a chunk with the given ID and payload to `out`, followed by a
+/// padding byte if needed.
+pub(crate) fn write_chunk(out: &mut Vec<u8>, id: &[u8; 4], data: &[u8]) -> Result<(), Error> {
+    let size =
+        u32::try_from(data.len()).map_err(|_| Error::Malformed("RIFF chunk smaller than 4 GB"))?;
+
+    out.extend_from_slice(id);
+    out.extend_from_slice(&size.to_le_bytes());
+    out.extend_from_slice(data);
+    if data.len() % 2 == 1 {
+        out.push(0);
+    }
+
+    Ok(())
+}
+
+/// Assemble a RIFF file with the form type `form` from `chunks`.
+pub(crate) fn write<'c, I>(form: &[u8; 4], chunks: I) -> Result<Vec<u8>, Error>
+where
+    I: IntoIterator<Item = (&'c [u8; 4], &'c [u8])>,
+{
+    let mut out = Vec::new();
+    out.extend_from_slice(b"RIFF\0\0\0\0");
+    out.extend_from_slice(form);
+
+    for (id, data) in chunks {
+        write_chunk(&mut out, id, data)?;
+    }
+
+    let size = u32::try_from(out.len() - 8)
+        .map_err(|_| Error::Malformed("RIFF file smaller than 4 GB"))?;
+    out[4..8].copy_from_slice(&size.to_le_bytes());
+
+    Ok(out)
+}
+
+fn read_u32(i: &[u8], at: usize) -> Result<usize, Error> {
+    i.get(at..at + 4)
+        .and_then(|n| <[u8; 4]>::try_from(n).ok())
+        .map(|n| u32::from_le_bytes(n) as usize)
+        .ok_or_else(|| incomplete(at + 4, i.len()))
+}
+
+// Report that `needed` bytes were required but only `available` were present.
+fn incomplete(needed: usize, available: usize) -> Error {
+    Error::Incomplete(needed.saturating_sub(available).max(1))
+}
@@ -0,0 +1,100 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Embedding JUMBF in WAV files.
+//!
+//! A WAV file is a RIFF file with the form type `WAVE`. C2PA stores its
+//! manifest store in a chunk with the ID `C2PA`.
+//!
+//! ## Example
+//!
+//! ```
+//! use hex_literal::hex;
+//! use jumbf::{embed::wav, parser::SuperBox};
+//!
+//! let audio = hex!(
+//!     "52494646" // 'RIFF'
+//!     "0c000000" // RIFF size
+//!     "57415645" // form type = 'WAVE'
+//!     "64617461" // chunk ID = 'data'
+//!     "00000000" // chunk size
+//! );
+//! let jumbf = include_bytes!("../tests/fixtures/C.c2pa");
+//!
+//! let file = wav::insert_jumbf(&audio, jumbf).unwrap();
+//!
+//! let boxes = wav::extract_jumbf(&file).unwrap();
+//! assert_eq!(boxes, [&jumbf[..]]);
+//!
+//! let (_, sbox) = SuperBox::from_slice(boxes[0]).unwrap();
+//! assert_eq!(sbox.desc.label, Some("c2pa"));
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::{
+    embed::{check_jumbf, riff},
+    parser::Error,
+};
+
+/// ID of the chunk which holds a C2PA manifest store (`C2PA`).
+pub const C2PA_CHUNK_ID: [u8; 4] = *b"C2PA";
+
+const FORM_TYPE: &[u8; 4] = b"WAVE";
+
+/// Returns the payload of each `C2PA` chunk in the WAV file `wav`, in the
+/// order in which they appear.
+///
+/// Each payload is expected to be a JUMBF superbox, but is not checked.
+pub fn extract_jumbf(wav: &[u8]) -> Result<Vec<&[u8]>, Error> {
+    Ok(riff::parse(wav, FORM_TYPE)?
+        .into_iter()
+        .filter(|chunk| chunk.id == C2PA_CHUNK_ID)
+        .map(|chunk| chunk.data)
+        .collect())
+}
+
+/// Returns a copy of the WAV file `wav` with the JUMBF superbox `jumbf`
+/// added in a new `C2PA` chunk at the end of the file.
+///
+/// Any `C2PA` chunks already in the file are kept; call [`remove_jumbf()`]
+/// first to replace them.
+///
+/// `jumbf` must consist of exactly one superbox. Its content is not
+/// otherwise checked.
+pub fn insert_jumbf(wav: &[u8], jumbf: &[u8]) -> Result<Vec<u8>, Error> {
+    check_jumbf(jumbf)?;
+
+    let chunks = riff::parse(wav, FORM_TYPE)?;
+
+    riff::write(
+        FORM_TYPE,
+        chunks
+            .iter()
+            .map(|chunk| (&chunk.id, chunk.data))
+            .chain([(&C2PA_CHUNK_ID, jumbf)]),
+    )
+}
+
+/// Returns a copy of the WAV file `wav` without any of its `C2PA` chunks.
+pub fn remove_jumbf(wav: &[u8]) -> Result<Vec<u8>, Error> {
+    let chunks = riff::parse(wav, FORM_TYPE)?;
+
+    riff::write(
+        FORM_TYPE,
+        chunks
+            .iter()
+            .filter(|chunk| chunk.id != C2PA_CHUNK_ID)
+            .map(|chunk| (&chunk.id, chunk.data)),
+    )
+}
@@ -0,0 +1,176 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Embedding JUMBF in WebP files.
+//!
+//! A WebP file is a RIFF file with the form type `WEBP`. C2PA stores its
+//! manifest store in a chunk with the ID `C2PA`, which follows the image
+//! data.
+//!
+//! Chunks other than the image data may only appear in a file which uses
+//! the extended format, whose first chunk is a `VP8X` chunk holding feature
+//! flags and the canvas size. [`insert_jumbf()`] converts a file in the
+//! simple (lossy `VP8 ` or lossless `VP8L`) format to the extended format,
+//! taking the canvas size and alpha flag from the image data.
+//!
+//! ## Example
+//!
+//! ```
+//! use hex_literal::hex;
+//! use jumbf::{embed::webp, parser::SuperBox};
+//!
+//! let image = hex!(
+//!     "52494646" // 'RIFF'
+//!     "12000000" // RIFF size
+//!     "57454250" // form type = 'WEBP'
+//!     "5650384c" // chunk ID = 'VP8L'
+//!     "05000000" // chunk size
+//!     "2f00000000" // lossless bitstream header (1x1)
+//!     "00" // padding
+//! );
+//! let jumbf = include_bytes!("../tests/fixtures/C.c2pa");
+//!
+//! let file = webp::insert_jumbf(&image, jumbf).unwrap();
+//!
+//! let boxes = webp::extract_jumbf(&file).unwrap();
+//! assert_eq!(boxes, [&jumbf[..]]);
+//!
+//! let (_, sbox) = SuperBox::from_slice(boxes[0]).unwrap();
+//! assert_eq!(sbox.desc.label, Some("c2pa"));
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::{
+    embed::{check_jumbf, riff},
+    parser::Error,
+};
+
+/// ID of the chunk which holds a C2PA manifest store (`C2PA`).
+pub const C2PA_CHUNK_ID: [u8; 4] = *b"C2PA";
+
+/// ID of the chunk which begins a file in the extended format (`VP8X`).
+pub const VP8X_CHUNK_ID: [u8; 4] = *b"VP8X";
+
+/// ID of the chunk which holds lossy image data (`VP8 `).
+pub const VP8_CHUNK_ID: [u8; 4] = *b"VP8 ";
+
+/// ID of the chunk which holds lossless image data (`VP8L`).
+pub const VP8L_CHUNK_ID: [u8; 4] = *b"VP8L";
+
+/// Flag in the `VP8X` chunk which indicates that the image has an alpha
+/// channel.
+pub const VP8X_ALPHA_FLAG: u8 = 0x10;
+
+const FORM_TYPE: &[u8; 4] = b"WEBP";
+
+/// Returns the payload of each `C2PA` chunk in the WebP file `webp`, in the
+/// order in which they appear.
+///
+/// Each payload is expected to be a JUMBF superbox, but is not checked.
+pub fn extract_jumbf(webp: &[u8]) -> Result<Vec<&[u8]>, Error> {
+    Ok(riff::parse(webp, FORM_TYPE)?
+        .into_iter()
+        .filter(|chunk| chunk.id == C2PA_CHUNK_ID)
+        .map(|chunk| chunk.data)
+        .collect())
+}
+
+/// Returns a copy of the WebP file `webp` with the JUMBF superbox `jumbf`
+/// added in a new `C2PA` chunk at the end of the file.
+///
+/// If `webp` uses the simple format, a `VP8X` chunk is added so that the
+/// file uses the extended format. Any `C2PA` chunks already in the file are
+/// kept; call [`remove_jumbf()`] first to replace them.
+///
+/// `jumbf` must consist of exactly one superbox. Its content is not
+/// otherwise checked.
+pub fn insert_jumbf(webp: &[u8], jumbf: &[u8]) -> Result<Vec<u8>, Error> {
+    check_jumbf(jumbf)?;
+
+    let chunks = riff::parse(webp, FORM_TYPE)?;
+    let vp8x = match chunks.first() {
+        Some(first) if first.id == VP8X_CHUNK_ID => None,
+        Some(first) if first.id == VP8_CHUNK_ID => Some(vp8x_for_lossy(first.data)?),
+        Some(first) if first.id == VP8L_CHUNK_ID => Some(vp8x_for_lossless(first.data)?),
+        _ => return Err(Error::Malformed("WebP image data")),
+    };
+
+    riff::write(
+        FORM_TYPE,
+        vp8x.iter()
+            .map(|vp8x| (&VP8X_CHUNK_ID, &vp8x[..]))
+            .chain(chunks.iter().map(|chunk| (&chunk.id, chunk.data)))
+            .chain([(&C2PA_CHUNK_ID, jumbf)]),
+    )
+}
+
+/// Returns a copy of the WebP file `webp` without any of its `C2PA` chunks.
+///
+/// If a `VP8X` chunk was added by [`insert_jumbf()`], it is kept, since a
+/// file in the extended format remains valid without additional chunks.
+pub fn remove_jumbf(webp: &[u8]) -> Result<Vec<u8>, Error> {
+    let chunks = riff::parse(webp, FORM_TYPE)?;
+
+    riff::write(
+        FORM_TYPE,
+        chunks
+            .iter()
+            .filter(|chunk| chunk.id != C2PA_CHUNK_ID)
+            .map(|chunk| (&chunk.id, chunk.data)),
+    )
+}
+
+// Build the payload of a `VP8X` chunk for an image with the given size.
+fn vp8x(flags: u8, width: u32, height: u32) -> [u8; 10] {
+    let width = width.saturating_sub(1).to_le_bytes();
+    let height = height.saturating_sub(1).to_le_bytes();
+
+    [
+        flags, 0, 0, 0, width[0], width[1], width[2], height[0], height[1], height[2],
+    ]
+}
+
+// A lossy bitstream begins with a three-byte frame tag, the start code
+// `9d 01 2a`, and two 16-bit little-endian fields whose low 14 bits hold
+// the width and height.
+fn vp8x_for_lossy(data: &[u8]) -> Result<[u8; 10], Error> {
+    match data.get(0..10) {
+        Some([_, _, _, 0x9d, 0x01, 0x2a, w0, w1, h0, h1]) => {
+            let width = u16::from_le_bytes([*w0, *w1]) & 0x3fff;
+            let height = u16::from_le_bytes([*h0, *h1]) & 0x3fff;
+            Ok(vp8x(0, width.into(), height.into()))
+        }
+        _ => Err(Error::Malformed("WebP lossy bitstream header")),
+    }
+}
+
+// A lossless bitstream begins with the signature `2f` and a 32-bit
+// little-endian field holding the width minus one (14 bits), the height
+// minus one (14 bits), and the alpha flag (1 bit).
+fn vp8x_for_lossless(data: &[u8]) -> Result<[u8; 10], Error> {
+    match data.get(0..5) {
+        Some([0x2f, b0, b1, b2, b3]) => {
+            let bits = u32::from_le_bytes([*b0, *b1, *b2, *b3]);
+            let width = (bits & 0x3fff) + 1;
+            let height = ((bits >> 14) & 0x3fff) + 1;
+            let flags = if bits & (1 << 28) != 0 {
+                VP8X_ALPHA_FLAG
+            } else {
+                0
+            };
+            Ok(vp8x(flags, width, height))
+        }
+        _ => Err(Error::Malformed("WebP lossless bitstream header")),
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::fmt::{Display, Formatter};
+
+use crate::{builder::BuilderError, parser};
+
+/// An error from either building or parsing JUMBF data.
+///
+/// The builder reports errors as [`std::io::Error`] and the parser reports
+/// errors as [`parser::Error`]. Applications which both read and write JUMBF
+/// can use this type to carry either kind of error via the `?` operator.
+///
+/// This type is available when both the `parser` and `std` features are
+/// enabled.
+///
+/// ## Example
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// use jumbf::{builder::SuperBoxBuilder, parser::SuperBox, Error};
+///
+/// fn relabel(jumbf: &[u8], label: &str) -> Result<Vec<u8>, Error> {
+///     let (_, sbox) = SuperBox::from_slice(jumbf)?;
+///
+///     let mut out = Cursor::new(Vec::new());
+///     SuperBoxBuilder::new(sbox.desc.uuid)
+///         .set_label(label)
+///         .write_jumbf(&mut out)?;
+///
+///     Ok(out.into_inner())
+/// }
+///
+/// let jumbf = include_bytes!("tests/fixtures/C.c2pa");
+/// assert!(relabel(jumbf, "c2pa").is_ok());
+///
+/// let err = relabel(jumbf, "not/valid").unwrap_err();
+/// assert!(err.builder_error().is_some());
+///
+/// let err = relabel(&jumbf[..10], "c2pa").unwrap_err();
+/// assert!(err.parse_error().is_some());
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// An error occurred while building or writing JUMBF data.
+    ///
+    /// If the requested data structure was invalid, this wraps a
+    /// [`BuilderError`]; see [`Error::builder_error()`].
+    Io(std::io::Error),
+
+    /// An error occurred while parsing JUMBF data.
+    Parse(parser::Error),
+}
+
+impl Error {
+    /// Returns the [`BuilderError`] that caused this error, if any.
+    pub fn builder_error(&self) -> Option<&BuilderError> {
+        match self {
+            Self::Io(e) => e.get_ref()?.downcast_ref(),
+            Self::Parse(_) => None,
+        }
+    }
+
+    /// Returns the parser error that caused this error, if any.
+    pub fn parse_error(&self) -> Option<&parser::Error> {
+        match self {
+            Self::Io(_) => None,
+            Self::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Self::Io(e) => write!(f, "Unable to build JUMBF: {e}"),
+            Self::Parse(e) => write!(f, "Unable to parse JUMBF: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<BuilderError> for Error {
+    fn from(e: BuilderError) -> Self {
+        Self::Io(e.into())
+    }
+}
+
+impl From<parser::Error> for Error {
+    fn from(e: parser::Error) -> Self {
+        Self::Parse(e)
+    }
+}
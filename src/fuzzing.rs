@@ -0,0 +1,347 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Owned descriptions of JUMBF data structures for structured fuzzing.
+//!
+//! [`SuperBoxSpec`] describes the same data structure as a
+//! [`SuperBoxBuilder`], but owns all of its content and implements
+//! [`Arbitrary`]. A fuzz target can therefore ask for a random but valid JUMBF
+//! tree and check the build→parse→rebuild invariants via
+//! [`SuperBoxSpec::check_round_trip()`]:
+//!
+//! ```ignore
+//! #![no_main]
+//!
+//! use jumbf::fuzzing::SuperBoxSpec;
+//! use libfuzzer_sys::fuzz_target;
+//!
+//! fuzz_target!(|spec: SuperBoxSpec| {
+//!     spec.check_round_trip().unwrap();
+//! });
+//! ```
+//!
+//! This module is available when the `arbitrary` feature is enabled.
+//!
+//! [`SuperBoxBuilder`]: crate::builder::SuperBoxBuilder
+
+use std::{
+    fmt::{Display, Formatter},
+    io::Cursor,
+};
+
+use arbitrary::{Arbitrary, Unstructured};
+
+use crate::{
+    box_type::SUPER_BOX_TYPE,
+    builder::{DataBoxBuilder, SuperBoxBuilder},
+    parser::{ChildBox, DataBox, Error, SuperBox},
+    BoxType, Toggles, Uuid,
+};
+
+/// Maximum nesting depth of generated superboxes.
+const MAX_DEPTH: usize = 4;
+
+/// Maximum number of child boxes in each generated superbox.
+const MAX_CHILDREN: usize = 8;
+
+/// An owned description of a JUMBF superbox.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SuperBoxSpec {
+    /// Application-specific UUID for the superbox's data type.
+    pub uuid: Uuid,
+
+    /// Application-specific label for the superbox.
+    ///
+    /// Generated labels never contain characters which are not permitted in
+    /// a JUMBF label.
+    pub label: Option<String>,
+
+    /// True if the superbox can be requested by label. Always `false` if
+    /// there is no label.
+    pub requestable: bool,
+
+    /// Application-specific 32-bit ID.
+    pub id: Option<u32>,
+
+    /// SHA-256 hash of the superbox's data payload.
+    pub hash: Option<[u8; 32]>,
+
+    /// Application-specific "private" box within the description box.
+    pub private: Option<DataBoxSpec>,
+
+    /// Toggle bits which are not defined by ISO/IEC 19566-5.
+    pub unknown_toggles: u8,
+
+    /// Data at the end of the description box which follows all of the fields
+    /// defined by ISO/IEC 19566-5.
+    pub trailing_description_data: Vec<u8>,
+
+    /// Child boxes.
+    pub child_boxes: Vec<ChildBoxSpec>,
+}
+
+/// An owned description of a single child box within a [`SuperBoxSpec`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ChildBoxSpec {
+    /// A superbox.
+    SuperBox(SuperBoxSpec),
+
+    /// Any other kind of box.
+    DataBox(DataBoxSpec),
+}
+
+/// An owned description of a JUMBF box other than a superbox.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DataBoxSpec {
+    /// Box type.
+    ///
+    /// Generated child boxes are never of type `jumb`, since those would be
+    /// parsed as superboxes.
+    pub tbox: BoxType,
+
+    /// Box contents.
+    pub data: Vec<u8>,
+}
+
+impl SuperBoxSpec {
+    /// Create a description of an existing parsed superbox.
+    pub fn from_super_box(sbox: &SuperBox) -> Self {
+        Self {
+            uuid: sbox.desc.uuid,
+            label: sbox.desc.label.map(str::to_owned),
+            requestable: sbox.desc.requestable,
+            id: sbox.desc.id,
+            hash: sbox.desc.hash.copied(),
+            private: sbox.desc.private.as_ref().map(DataBoxSpec::from_data_box),
+            unknown_toggles: sbox.desc.toggles().unknown().bits(),
+            trailing_description_data: sbox.desc.trailing().to_vec(),
+            child_boxes: sbox
+                .child_boxes
+                .iter()
+                .map(|child| match child {
+                    ChildBox::SuperBox(sbox) => ChildBoxSpec::SuperBox(Self::from_super_box(sbox)),
+                    ChildBox::DataBox(dbox) => {
+                        ChildBoxSpec::DataBox(DataBoxSpec::from_data_box(dbox))
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Create a [`SuperBoxBuilder`] which will write the described superbox.
+    ///
+    /// The builder holds its own copy of all box payloads.
+    pub fn to_builder(&self) -> SuperBoxBuilder<'static> {
+        let mut sbox = SuperBoxBuilder::new(self.uuid);
+
+        if let Some(label) = self.label.as_ref() {
+            sbox = if self.requestable {
+                sbox.set_label(label)
+            } else {
+                sbox.set_non_requestable_label(label)
+            };
+        }
+
+        if let Some(id) = self.id {
+            sbox = sbox.set_id(id);
+        }
+
+        if let Some(hash) = self.hash.as_ref() {
+            sbox = sbox.set_sha256_hash(hash);
+        }
+
+        if let Some(private) = self.private.as_ref() {
+            sbox = sbox.set_private_box(private.to_builder());
+        }
+
+        if self.unknown_toggles != 0 {
+            sbox = sbox.set_toggles(Toggles(self.unknown_toggles));
+        }
+
+        if !self.trailing_description_data.is_empty() {
+            sbox = sbox.set_trailing_description_data(&self.trailing_description_data);
+        }
+
+        for child in &self.child_boxes {
+            sbox = match child {
+                ChildBoxSpec::SuperBox(child) => sbox.add_child_box(child.to_builder()),
+                ChildBoxSpec::DataBox(child) => sbox.add_child_box(child.to_builder()),
+            };
+        }
+
+        sbox
+    }
+
+    /// Write the described superbox as JUMBF.
+    pub fn to_jumbf(&self) -> std::io::Result<Vec<u8>> {
+        let mut jumbf = Cursor::new(Vec::<u8>::new());
+        self.to_builder().write_jumbf(&mut jumbf)?;
+        Ok(jumbf.into_inner())
+    }
+
+    /// Verify that the described superbox survives a build→parse→rebuild
+    /// round trip.
+    ///
+    /// Specifically, the superbox is written as JUMBF, which must then parse
+    /// without error and without leftover data. The parsed superbox must be
+    /// described by a spec equal to `self`, and writing that spec must
+    /// reproduce the original JUMBF exactly.
+    pub fn check_round_trip(&self) -> Result<(), RoundTripError> {
+        let jumbf = self.to_jumbf().map_err(RoundTripError::Build)?;
+
+        let (rem, sbox) = SuperBox::from_slice(&jumbf).map_err(RoundTripError::Parse)?;
+        if !rem.is_empty() {
+            return Err(RoundTripError::TrailingData(rem.len()));
+        }
+
+        let parsed = Self::from_super_box(&sbox);
+        if parsed != *self {
+            return Err(RoundTripError::Mismatch(Box::new(parsed)));
+        }
+
+        let rebuilt = parsed.to_jumbf().map_err(RoundTripError::Build)?;
+        if rebuilt != jumbf {
+            return Err(RoundTripError::NotReproducible);
+        }
+
+        Ok(())
+    }
+
+    fn arbitrary_at_depth(u: &mut Unstructured<'_>, depth: usize) -> arbitrary::Result<Self> {
+        let uuid = Uuid(u.arbitrary()?);
+
+        let label: Option<String> = u.arbitrary()?;
+        let label = label.map(|label| {
+            label
+                .chars()
+                .filter(|c| !matches!(c, '\0' | '/' | '?' | '#'))
+                .collect::<String>()
+        });
+
+        let requestable = label.is_some() && u.arbitrary()?;
+
+        let id = u.arbitrary()?;
+        let hash = u.arbitrary()?;
+
+        let private = if u.arbitrary()? {
+            Some(DataBoxSpec {
+                tbox: BoxType(u.arbitrary()?),
+                data: u.arbitrary()?,
+            })
+        } else {
+            None
+        };
+
+        let unknown_toggles = Toggles(u.arbitrary()?).unknown().bits();
+        let trailing_description_data = u.arbitrary()?;
+
+        let child_count = u.int_in_range(0..=MAX_CHILDREN)?;
+        let mut child_boxes = Vec::with_capacity(child_count);
+
+        for _ in 0..child_count {
+            if depth < MAX_DEPTH && u.ratio(1, 4)? {
+                child_boxes.push(ChildBoxSpec::SuperBox(Self::arbitrary_at_depth(
+                    u,
+                    depth + 1,
+                )?));
+            } else {
+                child_boxes.push(ChildBoxSpec::DataBox(DataBoxSpec::arbitrary(u)?));
+            }
+        }
+
+        Ok(Self {
+            uuid,
+            label,
+            requestable,
+            id,
+            hash,
+            private,
+            unknown_toggles,
+            trailing_description_data,
+            child_boxes,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for SuperBoxSpec {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Self::arbitrary_at_depth(u, 0)
+    }
+}
+
+impl DataBoxSpec {
+    /// Create a description of an existing parsed box.
+    pub fn from_data_box(dbox: &DataBox) -> Self {
+        Self {
+            tbox: dbox.tbox,
+            data: dbox.data.to_vec(),
+        }
+    }
+
+    /// Create a [`DataBoxBuilder`] which will write the described box.
+    pub fn to_builder(&self) -> DataBoxBuilder<'static> {
+        DataBoxBuilder::from_owned(self.tbox, self.data.clone())
+    }
+}
+
+impl<'a> Arbitrary<'a> for DataBoxSpec {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut tbox = BoxType(u.arbitrary()?);
+        if tbox == SUPER_BOX_TYPE {
+            tbox = BoxType(*b"jumB");
+        }
+
+        Ok(Self {
+            tbox,
+            data: u.arbitrary()?,
+        })
+    }
+}
+
+/// Describes how a [`SuperBoxSpec`] failed a round trip.
+///
+/// Returned by [`SuperBoxSpec::check_round_trip()`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum RoundTripError {
+    /// The builder was unable to write the superbox.
+    Build(std::io::Error),
+
+    /// The parser was unable to read the JUMBF that was written.
+    Parse(Error),
+
+    /// The parser did not consume the entire JUMBF that was written. Holds
+    /// the number of bytes left over.
+    TrailingData(usize),
+
+    /// The parsed superbox differs from the original description. Holds the
+    /// description of the parsed superbox.
+    Mismatch(Box<SuperBoxSpec>),
+
+    /// Rebuilding the parsed superbox did not produce identical JUMBF.
+    NotReproducible,
+}
+
+impl Display for RoundTripError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Self::Build(e) => write!(f, "Unable to build JUMBF: {e}"),
+            Self::Parse(e) => write!(f, "Unable to parse JUMBF: {e}"),
+            Self::TrailingData(len) => write!(f, "Parser left {len} bytes unread"),
+            Self::Mismatch(parsed) => write!(f, "Parsed superbox differs: {parsed:?}"),
+            Self::NotReproducible => f.write_str("Rebuilt JUMBF differs from original"),
+        }
+    }
+}
+
+impl std::error::Error for RoundTripError {}
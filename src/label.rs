@@ -0,0 +1,179 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Utilities for working with JUMBF superbox labels.
+//!
+//! A label is a UTF-8 string which identifies a superbox among its siblings.
+//! Labels are compared exactly, byte for byte; see [`matches()`]. A label
+//! may not contain any of the [`INVALID_CHARACTERS`], since those have
+//! special meaning in a JUMBF request URI.
+//!
+//! When a label appears in a request URI (see [`JumbfUri`]), characters
+//! which are not permitted in a URI fragment are percent-encoded. Use
+//! [`percent_encode()`] and [`percent_decode()`] to convert between the two
+//! forms.
+//!
+//! [`JumbfUri`]: crate::uri::JumbfUri
+
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+use crate::uuid::hex_value;
+
+/// Characters which may not appear in a label.
+///
+/// NUL terminates the label within a description box. `/` separates the
+/// labels in a request URI path, and `?` and `#` introduce the query and
+/// fragment portions of a request URI.
+pub const INVALID_CHARACTERS: [char; 4] = ['\0', '/', '?', '#'];
+
+/// Returns the first character in `label` which is not permitted in a
+/// label, if any.
+///
+/// ## Example
+///
+/// ```
+/// use jumbf::label;
+///
+/// assert_eq!(label::invalid_character("c2pa.signature"), None);
+/// assert_eq!(label::invalid_character("c2pa/signature"), Some('/'));
+/// ```
+pub fn invalid_character(label: &str) -> Option<char> {
+    label.chars().find(|c| INVALID_CHARACTERS.contains(c))
+}
+
+/// Returns `true` if `label` contains no characters which are not permitted
+/// in a label.
+pub fn is_valid(label: &str) -> bool {
+    invalid_character(label).is_none()
+}
+
+/// Convert arbitrary text (a file name, for example) into a valid label.
+///
+/// ASCII letters are converted to lowercase and each character which is not
+/// permitted in a label is replaced with `_`. Other characters are kept as
+/// is. Returns `text` unchanged if no conversion is needed.
+///
+/// ## Example
+///
+/// ```
+/// use jumbf::label;
+///
+/// assert_eq!(
+///     label::normalize("Thumbnail/Front.JPG"),
+///     "thumbnail_front.jpg"
+/// );
+/// assert_eq!(label::normalize("c2pa.claim"), "c2pa.claim");
+/// ```
+pub fn normalize(text: &str) -> Cow<'_, str> {
+    let needs_change = |c: char| c.is_ascii_uppercase() || INVALID_CHARACTERS.contains(&c);
+
+    if !text.chars().any(needs_change) {
+        return Cow::Borrowed(text);
+    }
+
+    Cow::Owned(
+        text.chars()
+            .map(|c| {
+                if INVALID_CHARACTERS.contains(&c) {
+                    '_'
+                } else {
+                    c.to_ascii_lowercase()
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Returns `true` if the superbox label `label` satisfies a request for
+/// `requested`.
+///
+/// ISO/IEC 19566-5 does not define any normalization of labels, so they are
+/// compared exactly: case matters, and no Unicode normalization is applied.
+/// Any percent-encoding in a request URI must be removed (see
+/// [`percent_decode()`]) before comparison.
+pub fn matches(label: &str, requested: &str) -> bool {
+    label == requested
+}
+
+/// Percent-encode `label` for use in the path of a JUMBF request URI.
+///
+/// Every byte which is not permitted in a URI fragment (RFC 3986), as well as
+/// `%` itself, is encoded. Returns `label` unchanged if no encoding is
+/// needed.
+///
+/// ## Example
+///
+/// ```
+/// use jumbf::label;
+///
+/// assert_eq!(label::percent_encode("c2pa.claim"), "c2pa.claim");
+/// assert_eq!(label::percent_encode("my label"), "my%20label");
+/// assert_eq!(label::percent_encode("é"), "%C3%A9");
+/// ```
+pub fn percent_encode(label: &str) -> Cow<'_, str> {
+    if label.bytes().all(is_fragment_byte) {
+        return Cow::Borrowed(label);
+    }
+
+    let mut encoded = String::with_capacity(label.len() * 3);
+    for b in label.bytes() {
+        if is_fragment_byte(b) {
+            encoded.push(b as char);
+        } else {
+            encoded.push('%');
+            encoded.push(hex_digit(b >> 4));
+            encoded.push(hex_digit(b & 0xf));
+        }
+    }
+
+    Cow::Owned(encoded)
+}
+
+/// Decode percent-encoded bytes in one label of a JUMBF request URI path.
+///
+/// Will return `None` if `segment` contains a malformed escape sequence or
+/// if the decoded label is not valid UTF-8. Returns `segment` unchanged if it
+/// contains no escape sequences.
+///
+/// The decoded label is not checked for [`INVALID_CHARACTERS`]; a decoded
+/// `%2F`, for instance, yields a label which can not match any superbox.
+pub fn percent_decode(segment: &str) -> Option<Cow<'_, str>> {
+    if !segment.contains('%') {
+        return Some(Cow::Borrowed(segment));
+    }
+
+    let mut decoded = Vec::with_capacity(segment.len());
+    let mut bytes = segment.bytes();
+
+    while let Some(b) = bytes.next() {
+        if b == b'%' {
+            let hi = bytes.next().and_then(hex_value)?;
+            let lo = bytes.next().and_then(hex_value)?;
+            decoded.push((hi << 4) | lo);
+        } else {
+            decoded.push(b);
+        }
+    }
+
+    String::from_utf8(decoded).ok().map(Cow::Owned)
+}
+
+// Returns `true` if `b` may appear unencoded in a URI fragment, other than
+// the characters which are not permitted in a label anyway.
+fn is_fragment_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@".contains(&b)
+}
+
+fn hex_digit(v: u8) -> char {
+    char::from(b"0123456789ABCDEF"[usize::from(v)])
+}
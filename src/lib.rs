@@ -11,24 +11,91 @@
 // specific language governing permissions and limitations under
 // each license.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(clippy::expect_used)]
 #![deny(clippy::panic)]
 #![deny(clippy::unwrap_used)]
 #![deny(warnings)]
-#![cfg_attr(feature = "parser", doc = include_str!("../README.md"))]
+#![cfg_attr(
+    all(feature = "parser", feature = "std"),
+    doc = include_str!("../README.md")
+)]
+
+extern crate alloc;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "parser")]
+pub mod app11;
+
+#[cfg(feature = "std")]
+pub mod bench_support;
+
+#[cfg_attr(not(any(feature = "parser", feature = "std")), allow(dead_code))]
 mod box_type;
-pub use box_type::BoxType;
+pub use box_type::{BoxType, ParseBoxTypeError};
 
+#[cfg(feature = "std")]
 pub mod builder;
 
+#[cfg(feature = "parser")]
+pub mod c2pa;
+
+#[cfg(feature = "parser")]
+mod compact;
+#[cfg(feature = "parser")]
+pub use compact::{
+    compact, compact_with_options, compact_with_report, CompactOptions, CompactReport,
+};
+
+pub mod codestream;
+
+#[cfg(feature = "compress")]
+pub mod compression;
+
+mod content_type;
+pub use content_type::ContentType;
+
 #[cfg(feature = "parser")]
 mod debug;
 
+#[cfg(all(feature = "parser", feature = "std"))]
+mod error;
+#[cfg(all(feature = "parser", feature = "std"))]
+pub use error::Error;
+
+#[cfg(feature = "parser")]
+pub mod diff;
+
+#[cfg(feature = "parser")]
+pub mod embed;
+
+#[cfg(feature = "arbitrary")]
+pub mod fuzzing;
+
+pub mod label;
+
 #[cfg(feature = "parser")]
 pub mod parser;
 
+#[cfg(feature = "parser")]
+pub mod scan;
+
+#[cfg(feature = "parser")]
+pub mod schema;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
 mod toggles;
+pub use toggles::Toggles;
+
+#[cfg(feature = "parser")]
+pub mod uri;
+
+mod uuid;
+pub use uuid::{ParseUuidError, Uuid};
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
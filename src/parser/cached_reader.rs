@@ -0,0 +1,190 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::{self, ErrorKind, Read, Seek, SeekFrom};
+
+/// Default size of each cached block, in bytes.
+const DEFAULT_BLOCK_SIZE: usize = 4096;
+
+/// Default number of blocks to cache.
+const DEFAULT_CAPACITY: usize = 16;
+
+/// A `CachedReader` wraps a [`Read`] + [`Seek`] implementation and keeps
+/// the most recently used fixed-size blocks of it in memory.
+///
+/// Locating boxes within a large file involves many small reads of box
+/// headers, often near one another. Reading through a `CachedReader` means
+/// that each block is read from the underlying file (or network stream) only
+/// once while it remains in the cache. When the cache is full, the least
+/// recently used block is discarded.
+///
+/// `CachedReader` itself implements [`Read`] and [`Seek`], so it can be used
+/// with any function that reads JUMBF from a stream, such as
+/// [`SuperBox::from_reader()`].
+///
+/// This type assumes that the underlying data does not change while it is
+/// being read.
+///
+/// ## Example
+///
+/// ```
+/// # fn example() -> std::io::Result<()> {
+/// use std::fs::File;
+///
+/// use jumbf::parser::{CachedReader, SuperBox};
+///
+/// let mut reader = CachedReader::new(File::open("manifest.c2pa")?);
+///
+/// let mut buf = vec![];
+/// let sbox = SuperBox::from_reader(&mut reader, &mut buf)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`SuperBox::from_reader()`]: crate::parser::SuperBox::from_reader
+#[derive(Debug)]
+pub struct CachedReader<R> {
+    inner: R,
+    block_size: usize,
+    capacity: usize,
+
+    // Ordered from least to most recently used. The capacity is expected to
+    // be small, so a linear search is adequate.
+    blocks: Vec<Block>,
+
+    pos: u64,
+    len: Option<u64>,
+}
+
+#[derive(Debug)]
+struct Block {
+    index: u64,
+
+    // Shorter than the block size only for the last block of the stream.
+    data: Vec<u8>,
+}
+
+impl<R: Read + Seek> CachedReader<R> {
+    /// Wrap `inner`, caching up to 16 blocks of 4096 bytes each.
+    ///
+    /// Reading starts at the beginning of `inner`, regardless of its current
+    /// position.
+    pub fn new(inner: R) -> Self {
+        Self::with_block_size(inner, DEFAULT_BLOCK_SIZE, DEFAULT_CAPACITY)
+    }
+
+    /// Wrap `inner`, caching up to `capacity` blocks of `block_size` bytes
+    /// each.
+    ///
+    /// Both `block_size` and `capacity` are treated as at least 1.
+    pub fn with_block_size(inner: R, block_size: usize, capacity: usize) -> Self {
+        Self {
+            inner,
+            block_size: block_size.max(1),
+            capacity: capacity.max(1),
+            blocks: vec![],
+            pos: 0,
+            len: None,
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Consumes this `CachedReader`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Returns the block with the given index, reading it from the underlying
+    /// reader if it is not already cached.
+    fn block(&mut self, index: u64) -> io::Result<&[u8]> {
+        if let Some(i) = self.blocks.iter().position(|b| b.index == index) {
+            let block = self.blocks.remove(i);
+            self.blocks.push(block);
+        } else {
+            let start = index
+                .checked_mul(self.block_size as u64)
+                .ok_or_else(|| io::Error::from(ErrorKind::InvalidInput))?;
+
+            self.inner.seek(SeekFrom::Start(start))?;
+
+            let mut data = Vec::with_capacity(self.block_size);
+            (&mut self.inner)
+                .take(self.block_size as u64)
+                .read_to_end(&mut data)?;
+
+            if self.blocks.len() >= self.capacity {
+                self.blocks.remove(0);
+            }
+
+            self.blocks.push(Block { index, data });
+        }
+
+        Ok(self
+            .blocks
+            .last()
+            .map(|b| b.data.as_slice())
+            .unwrap_or_default())
+    }
+}
+
+impl<R: Read + Seek> Read for CachedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let block_size = self.block_size as u64;
+        let offset = (self.pos % block_size) as usize;
+        let block = self.block(self.pos / block_size)?;
+
+        let available = block.get(offset..).unwrap_or_default();
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+
+        self.pos += len as u64;
+        Ok(len)
+    }
+}
+
+impl<R: Read + Seek> Seek for CachedReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::Current(delta) => self.pos.checked_add_signed(delta),
+            SeekFrom::End(delta) => {
+                let len = match self.len {
+                    Some(len) => len,
+                    None => {
+                        let len = self.inner.seek(SeekFrom::End(0))?;
+                        self.len = Some(len);
+                        len
+                    }
+                };
+                len.checked_add_signed(delta)
+            }
+        };
+
+        self.pos = new_pos.ok_or_else(|| {
+            io::Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+
+        Ok(self.pos)
+    }
+}
@@ -11,18 +11,25 @@
 // specific language governing permissions and limitations under
 // each license.
 
-use std::fmt::{Debug, Formatter};
-
-use nom::{
-    number::complete::{be_u32, be_u64},
-    Needed,
+use alloc::{borrow::Cow, string::String};
+use core::{
+    fmt::{Debug, Formatter},
+    ops::Range,
+    str::from_utf8,
 };
 
+use nom::number::complete::{be_u32, be_u64};
+
+#[cfg(feature = "std")]
+use crate::parser::read::{parse_error, read_box};
 use crate::{
+    box_type::FREE_BOX_TYPE,
     debug::*,
     parser::{Error, ParseResult, SuperBox},
     BoxType,
 };
+#[cfg(feature = "compress")]
+use crate::{compression::Compression, parser::ParseLimit};
 
 /// Represents a single JUMBF box.
 ///
@@ -61,6 +68,10 @@ pub struct DataBox<'a> {
     /// It is preserved in case a future client wishes to re-serialize this
     /// box as is.
     pub original: &'a [u8],
+
+    /// Offset of the start of this box (i.e. its header) from the start of
+    /// the source that was originally parsed.
+    pub offset: usize,
 }
 
 impl<'a> DataBox<'a> {
@@ -70,27 +81,56 @@ impl<'a> DataBox<'a> {
     /// The returned object uses zero-copy, and so has the same lifetime as the
     /// input.
     pub fn from_slice(original: &'a [u8]) -> ParseResult<'a, Self> {
-        let (i, len) = be_u32(original)?;
+        Self::from_slice_at(original, 0)
+    }
+
+    /// Read a single JUMBF box from `reader` into `buf` and parse it as
+    /// a box.
+    ///
+    /// Exactly one box is read, as described by its header. Any previous
+    /// contents of `buf` are discarded. The returned object borrows from
+    /// `buf`, which allows the parser to remain zero-copy.
+    ///
+    /// Parse errors are reported as I/O errors of kind
+    /// [`ErrorKind::InvalidData`] wrapping the parser's [`Error`].
+    ///
+    /// This function is available when the `std` feature is enabled.
+    ///
+    /// [`ErrorKind::InvalidData`]: std::io::ErrorKind::InvalidData
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: std::io::Read + ?Sized>(
+        reader: &mut R,
+        buf: &'a mut Vec<u8>,
+    ) -> std::io::Result<Self> {
+        let source = read_box(reader, buf)?;
+        let (_, dbox) = Self::from_slice(source).map_err(parse_error)?;
+        Ok(dbox)
+    }
+
+    /// Parse a JUMBF box which begins `offset` bytes from the start of the
+    /// source that was originally parsed.
+    pub(crate) fn from_slice_at(original: &'a [u8], offset: usize) -> ParseResult<'a, Self> {
+        let (i, len) = be_u32::<_, Error>(original)?;
 
         let (i, tbox): (&'a [u8], BoxType) = if i.len() >= 4 {
             let (tbox, i) = i.split_at(4);
             (i, tbox.into())
         } else {
-            return Err(nom::Err::Error(Error::Incomplete(Needed::new(4))));
+            return Err(Error::Incomplete(4));
         };
 
         let (i, len, original_len) = match len {
             0 => (i, i.len(), original.len()),
             1 => {
-                let (i, len) = be_u64(i)?;
+                let (i, len) = be_u64::<_, Error>(i)?;
                 if len >= 16 {
                     (i, len as usize - 16, len as usize)
                 } else {
-                    return Err(nom::Err::Error(Error::InvalidBoxLength(len as u32)));
+                    return Err(Error::InvalidBoxLength(len as u32));
                 }
             }
             2..=7 => {
-                return Err(nom::Err::Error(Error::InvalidBoxLength(len)));
+                return Err(Error::InvalidBoxLength(len));
             }
             len => (i, len as usize - 8, len as usize),
         };
@@ -103,10 +143,232 @@ impl<'a> DataBox<'a> {
                     tbox,
                     data,
                     original: &original[0..original_len],
+                    offset,
                 },
             ))
         } else {
-            Err(nom::Err::Error(Error::Incomplete(Needed::new(len))))
+            Err(Error::Incomplete(len))
+        }
+    }
+
+    /// Returns the range of bytes occupied by this box (including its header)
+    /// within the source that was originally parsed.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::parser::SuperBox;
+    ///
+    /// let jumbf = hex!(
+    ///     "0000002c" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000019" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "6a736f6e00110010800000aa00389b71" // UUID (JSON)
+    ///         "00" // toggles
+    ///         // ----
+    ///         "0000000b" // box size
+    ///         "6a736f6e" // box type = 'json'
+    ///         "7b7d20" // payload
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    /// let json_box = sbox.data_box().unwrap();
+    /// assert_eq!(json_box.range(), 33..44);
+    /// ```
+    pub fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.original.len()
+    }
+
+    /// Interpret this box's payload as UTF-8 text.
+    ///
+    /// Many payloads (such as JSON and XML content) are text. Returns
+    /// [`Error::Utf8Error`] if the payload is not valid UTF-8. A byte order
+    /// mark, if present, is returned as part of the string.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::parser::DataBox;
+    ///
+    /// let jumbf = hex!(
+    ///     "0000000a" // box size
+    ///     "6a736f6e" // box type = 'json'
+    ///     "7b7d" // payload
+    /// );
+    ///
+    /// let (_, dbox) = DataBox::from_slice(&jumbf).unwrap();
+    /// assert_eq!(dbox.as_str().unwrap(), "{}");
+    /// ```
+    pub fn as_str(&self) -> Result<&'a str, Error> {
+        from_utf8(self.data).map_err(Error::Utf8Error)
+    }
+
+    /// Interpret this box's payload as UTF-8 text, replacing any invalid
+    /// sequences with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// Borrows the payload if it is valid UTF-8. See
+    /// [`String::from_utf8_lossy()`].
+    pub fn to_string_lossy(&self) -> Cow<'a, str> {
+        String::from_utf8_lossy(self.data)
+    }
+
+    /// Write this box exactly as it was originally parsed (i.e. the
+    /// contents of [`original`]) to `to_stream`.
+    ///
+    /// This function is available when the `std` feature is enabled.
+    ///
+    /// [`original`]: Self::original
+    #[cfg(feature = "std")]
+    pub fn write_original(&self, to_stream: &mut impl std::io::Write) -> std::io::Result<()> {
+        to_stream.write_all(self.original)
+    }
+
+    /// Write this box's payload (i.e. the contents of [`data`]) to
+    /// `to_stream`.
+    ///
+    /// This is useful for exporting content such as thumbnails or embedded
+    /// files directly to a file without an intermediate copy.
+    ///
+    /// This function is available when the `std` feature is enabled.
+    ///
+    /// [`data`]: Self::data
+    #[cfg(feature = "std")]
+    pub fn copy_payload_to(&self, to_stream: &mut impl std::io::Write) -> std::io::Result<()> {
+        to_stream.write_all(self.data)
+    }
+
+    /// Returns a reader over this box's payload (i.e. the contents of
+    /// [`data`]), which also implements [`Seek`](std::io::Seek) and
+    /// [`BufRead`](std::io::BufRead).
+    ///
+    /// This allows the payload to be handed to a decoder (for an image,
+    /// CBOR, a zip archive, etc.) that consumes a reader, without copying it.
+    ///
+    /// This function is available when the `std` feature is enabled.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::io::{Read, Seek, SeekFrom};
+    ///
+    /// use hex_literal::hex;
+    /// use jumbf::parser::DataBox;
+    ///
+    /// let jumbf = hex!(
+    ///     "0000000f" // box size
+    ///     "61626364" // box type = 'abcd'
+    ///     "7061796c6f6164" // payload ("payload")
+    /// );
+    ///
+    /// let (_, dbox) = DataBox::from_slice(&jumbf).unwrap();
+    /// let mut reader = dbox.reader();
+    ///
+    /// reader.seek(SeekFrom::Start(3)).unwrap();
+    ///
+    /// let mut rest = String::new();
+    /// reader.read_to_string(&mut rest).unwrap();
+    /// assert_eq!(rest, "load");
+    /// ```
+    ///
+    /// [`data`]: Self::data
+    #[cfg(feature = "std")]
+    pub fn reader(&self) -> std::io::Cursor<&'a [u8]> {
+        std::io::Cursor::new(self.data)
+    }
+
+    /// Decompress this box's payload, which was compressed using
+    /// `compression` (for example, by [`DataBoxBuilder::compressed()`]).
+    ///
+    /// Will return [`Error::LimitExceeded`] (citing [`ParseLimit::BoxSize`])
+    /// if the decompressed payload would be larger than `max_size` bytes, or
+    /// [`Error::Malformed`] if the payload is not validly compressed.
+    ///
+    /// This function is available when the `compress` feature is enabled.
+    ///
+    /// [`DataBoxBuilder::compressed()`]: crate::builder::DataBoxBuilder::compressed
+    #[cfg(feature = "compress")]
+    pub fn decompress(&self, compression: Compression, max_size: usize) -> Result<Vec<u8>, Error> {
+        compression
+            .decompress(self.data, max_size)
+            .map_err(|_| Error::Malformed("compressed payload"))?
+            .ok_or(Error::LimitExceeded {
+                limit: ParseLimit::BoxSize,
+                max: max_size,
+            })
+    }
+
+    /// Returns `true` if this is a free space (`free`) box.
+    ///
+    /// Free space boxes carry no information and are typically used as
+    /// padding so that a JUMBF data structure can later be updated in place.
+    /// Readers should ignore their content.
+    pub fn is_free(&self) -> bool {
+        self.tbox == FREE_BOX_TYPE
+    }
+
+    /// Returns the offset of the *data* portion of this box from the start of
+    /// the source that was originally parsed.
+    pub(crate) fn data_offset(&self) -> usize {
+        self.offset + self.original.len() - self.data.len()
+    }
+
+    /// Returns the offset of the start of this box (i.e. its header) within
+    /// `ancestor`, which may be the enclosing [`SuperBox`] or any superbox
+    /// which contains it.
+    ///
+    /// Both boxes must have been parsed from the same source. To find the
+    /// offset of this box within the source itself, use [`range()`].
+    ///
+    /// Will return `None` if this box is not contained within `ancestor`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::parser::SuperBox;
+    ///
+    /// let jumbf = hex!(
+    ///     "00000050" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000019" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "00000000000000000000000000000000" // UUID
+    ///         "00" // toggles
+    ///         // ----
+    ///         "0000002f" // box size
+    ///         "6a756d62" // box type = 'jumb'
+    ///             "0000001b" // box size
+    ///             "6a756d64" // box type = 'jumd'
+    ///             "00000000000000000000000000000000" // UUID
+    ///             "02" // toggles
+    ///             "6100" // label ("a")
+    ///             // ----
+    ///             "0000000c" // box size
+    ///             "61626364" // box type = 'abcd'
+    ///             "41424344" // payload
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    /// let child = sbox.child_boxes[0].as_super_box().unwrap();
+    /// let abcd = child.data_box().unwrap();
+    ///
+    /// assert_eq!(abcd.offset_within(child), Some(35));
+    /// assert_eq!(abcd.offset_within(&sbox), Some(68));
+    /// assert_eq!(abcd.range().start, 68);
+    /// ```
+    ///
+    /// [`range()`]: Self::range
+    pub fn offset_within(&self, ancestor: &SuperBox) -> Option<usize> {
+        let ancestor = ancestor.range();
+        let range = self.range();
+
+        if range.start >= ancestor.start && range.end <= ancestor.end {
+            Some(range.start - ancestor.start)
+        } else {
+            None
         }
     }
 
@@ -159,11 +421,12 @@ impl<'a> DataBox<'a> {
 }
 
 impl<'a> Debug for DataBox<'a> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
         f.debug_struct("DataBox")
             .field("tbox", &self.tbox)
             .field("data", &DebugByteSlice(self.data))
             .field("original", &DebugByteSlice(self.original))
+            .field("offset", &self.offset)
             .finish()
     }
 }
@@ -11,21 +11,24 @@
 // specific language governing permissions and limitations under
 // each license.
 
-use std::{
+use core::{
     fmt::{Debug, Formatter},
+    ops::Range,
     str::from_utf8,
 };
 
 use nom::{
     bytes::complete::take_until,
     number::complete::{be_u32, be_u8},
-    Needed,
 };
 
+#[cfg(feature = "std")]
+use crate::parser::read::{parse_error, read_box};
 use crate::{
-    box_type::DESCRIPTION_BOX_TYPE,
+    box_type::{DESCRIPTION_BOX_TYPE, SUPER_BOX_TYPE},
     debug::*,
-    parser::{DataBox, Error, ParseResult},
+    parser::{DataBox, Error, ParseReport, ParseResult, ParseWarning, SuperBox},
+    Toggles, Uuid,
 };
 
 /// A JUMBF description box describes the contents of its superbox.
@@ -35,7 +38,7 @@ use crate::{
 #[derive(Clone, Eq, PartialEq)]
 pub struct DescriptionBox<'a> {
     /// Application-specific UUID for the superbox's data type.
-    pub uuid: &'a [u8; 16],
+    pub uuid: Uuid,
 
     /// Application-specific label for the superbox.
     pub label: Option<&'a str>,
@@ -61,6 +64,10 @@ pub struct DescriptionBox<'a> {
     /// It is preserved in case a future client wishes to re-serialize this
     /// box as is.
     pub original: &'a [u8],
+
+    /// Offset of the start of this box (i.e. its header) from the start of
+    /// the source that was originally parsed.
+    pub offset: usize,
 }
 
 impl<'a> DescriptionBox<'a> {
@@ -70,8 +77,49 @@ impl<'a> DescriptionBox<'a> {
     /// The returned object uses zero-copy, and so has the same lifetime as the
     /// input.
     pub fn from_slice(i: &'a [u8]) -> ParseResult<'a, Self> {
-        let (i, boxx): (&'a [u8], DataBox<'a>) = DataBox::from_slice(i)?;
-        let (_, desc) = Self::from_box(boxx)?;
+        Self::from_slice_at(i, 0)
+    }
+
+    /// Read a single JUMBF box from `reader` into `buf` and parse it as
+    /// a description box.
+    ///
+    /// Exactly one box is read, as described by its header. Any previous
+    /// contents of `buf` are discarded. The returned object borrows from
+    /// `buf`, which allows the parser to remain zero-copy.
+    ///
+    /// Parse errors are reported as I/O errors of kind
+    /// [`ErrorKind::InvalidData`] wrapping the parser's [`Error`].
+    ///
+    /// This function is available when the `std` feature is enabled.
+    ///
+    /// [`ErrorKind::InvalidData`]: std::io::ErrorKind::InvalidData
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: std::io::Read + ?Sized>(
+        reader: &mut R,
+        buf: &'a mut Vec<u8>,
+    ) -> std::io::Result<Self> {
+        let source = read_box(reader, buf)?;
+        let (_, dbox) = Self::from_slice(source).map_err(parse_error)?;
+        Ok(dbox)
+    }
+
+    /// Parse a JUMBF description box which begins `offset` bytes from the
+    /// start of the source that was originally parsed.
+    pub(crate) fn from_slice_at(i: &'a [u8], offset: usize) -> ParseResult<'a, Self> {
+        Self::from_slice_with_report(i, offset, None)
+    }
+
+    /// Parse a JUMBF description box which begins `offset` bytes from the
+    /// start of the source that was originally parsed. If `report` is
+    /// provided, recoverable deviations from the specification are recorded
+    /// there rather than treated as errors.
+    pub(crate) fn from_slice_with_report(
+        i: &'a [u8],
+        offset: usize,
+        report: Option<&mut ParseReport>,
+    ) -> ParseResult<'a, Self> {
+        let (i, boxx): (&'a [u8], DataBox<'a>) = DataBox::from_slice_at(i, offset)?;
+        let (_, desc) = Self::from_box_with_report(boxx, report)?;
         Ok((i, desc))
     }
 
@@ -84,41 +132,54 @@ impl<'a> DescriptionBox<'a> {
     /// Returns a tuple of the remainder of the input from the box (which should
     /// typically be empty) and the new [`DescriptionBox`] object.
     pub fn from_box(boxx: DataBox<'a>) -> ParseResult<'a, Self> {
-        use crate::toggles;
+        Self::from_box_with_report(boxx, None)
+    }
 
+    fn from_box_with_report(
+        boxx: DataBox<'a>,
+        report: Option<&mut ParseReport>,
+    ) -> ParseResult<'a, Self> {
         if boxx.tbox != DESCRIPTION_BOX_TYPE {
-            return Err(nom::Err::Error(Error::InvalidDescriptionBoxType(boxx.tbox)));
+            return Err(Error::InvalidDescriptionBoxType(boxx.tbox));
         }
 
-        let (i, uuid): (&'a [u8], &'a [u8; 16]) = if boxx.data.len() >= 16 {
+        let (i, uuid): (&'a [u8], Uuid) = if boxx.data.len() >= 16 {
             let (uuid, i) = boxx.data.split_at(16);
-            let uuid = uuid[0..16]
-                .try_into()
-                .map_err(|_| nom::Err::Error(Error::Incomplete(Needed::new(16))))?;
-            (i, uuid)
+            let uuid: [u8; 16] = uuid[0..16].try_into().map_err(|_| Error::Incomplete(16))?;
+            (i, Uuid(uuid))
         } else {
-            return Err(nom::Err::Error(Error::Incomplete(Needed::new(16))));
+            return Err(Error::Incomplete(16));
         };
 
-        let (i, toggles) = be_u8(i)?;
+        let (i, toggles) = be_u8::<_, Error>(i)?;
+        let toggles = Toggles(toggles);
 
         // Toggle bit 0 (0x01) indicates that this superbox can be requested
         // via URI requests.
-        let requestable = toggles & toggles::REQUESTABLE != 0;
+        let requestable = toggles.contains(Toggles::REQUESTABLE);
 
         // Toggle bit 1 (0x02) indicates that the label has an optional textual label.
-        let (i, label) = if toggles & toggles::HAS_LABEL != 0 {
-            let (i, label) = take_until("\0")(i)?;
+        let (i, label) = if toggles.contains(Toggles::HAS_LABEL) {
+            let (i, label) = match (take_until::<_, _, Error>("\0")(i), report) {
+                (Ok((i, label)), _) => (&i[1..], label),
+                (Err(_), Some(report)) => {
+                    report.warn(ParseWarning::UnterminatedLabel {
+                        offset: boxx.offset,
+                    });
+                    (&i[i.len()..], i)
+                }
+                (Err(e), None) => return Err(e.into()),
+            };
             let label = from_utf8(label).map_err(Error::Utf8Error)?;
-            (&i[1..], Some(label))
+            (i, Some(label))
         } else {
             (i, None)
         };
 
         // Toggle bit 2 (0x04) indicates that the label has an optional
         // application-specific 32-bit identifier.
-        let (i, id) = if toggles & toggles::HAS_ID != 0 {
-            let (i, id) = be_u32(i)?;
+        let (i, id) = if toggles.contains(Toggles::HAS_ID) {
+            let (i, id) = be_u32::<_, Error>(i)?;
             (i, Some(id))
         } else {
             (i, None)
@@ -126,15 +187,13 @@ impl<'a> DescriptionBox<'a> {
 
         // Toggle bit 3 (0x08) indicates that a SHA-256 hash of the superbox's
         // data box is present.
-        let (i, hash) = if toggles & toggles::HAS_HASH != 0 {
+        let (i, hash) = if toggles.contains(Toggles::HAS_HASH) {
             let (x, sig): (&'a [u8], &'a [u8; 32]) = if i.len() >= 32 {
                 let (sig, x) = i.split_at(32);
-                let sig = sig[0..32]
-                    .try_into()
-                    .map_err(|_| nom::Err::Error(Error::Incomplete(Needed::new(32))))?;
+                let sig = sig[0..32].try_into().map_err(|_| Error::Incomplete(32))?;
                 (x, sig)
             } else {
-                return Err(nom::Err::Error(Error::Incomplete(Needed::new(32))));
+                return Err(Error::Incomplete(32));
             };
 
             (x, Some(sig))
@@ -144,8 +203,9 @@ impl<'a> DescriptionBox<'a> {
 
         // Toggle bit 4 (0x10) indicates that an application-specific "private"
         // box is contained within the description box.
-        let (i, private) = if toggles & toggles::HAS_PRIVATE_BOX != 0 {
-            let (i, private) = DataBox::from_slice(i)?;
+        let (i, private) = if toggles.contains(Toggles::HAS_PRIVATE_BOX) {
+            let offset = boxx.data_offset() + boxx.data.len() - i.len();
+            let (i, private) = DataBox::from_slice_at(i, offset)?;
             (i, Some(private))
         } else {
             (i, None)
@@ -161,21 +221,117 @@ impl<'a> DescriptionBox<'a> {
                 hash,
                 private,
                 original: boxx.original,
+                offset: boxx.offset,
             },
         ))
     }
+
+    /// Returns the range of bytes occupied by this box (including its header)
+    /// within the source that was originally parsed.
+    pub fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.original.len()
+    }
+
+    /// Write this box exactly as it was originally parsed (i.e. the
+    /// contents of [`original`]) to `to_stream`.
+    ///
+    /// This function is available when the `std` feature is enabled.
+    ///
+    /// [`original`]: Self::original
+    #[cfg(feature = "std")]
+    pub fn write_original(&self, to_stream: &mut impl std::io::Write) -> std::io::Result<()> {
+        to_stream.write_all(self.original)
+    }
+
+    /// Returns the raw toggles byte, as read from [`original`].
+    ///
+    /// This includes any bits which are not defined by ISO/IEC 19566-5 and
+    /// are therefore not otherwise represented in this struct.
+    ///
+    /// [`original`]: Self::original
+    pub fn toggles(&self) -> Toggles {
+        // The toggles byte follows the box header and the 16-byte UUID.
+        Toggles(
+            self.original
+                .get(self.header_len() + 16)
+                .copied()
+                .unwrap_or(0),
+        )
+    }
+
+    /// Returns any bytes in [`original`] which follow the fields described by
+    /// the toggles byte.
+    ///
+    /// ISO/IEC 19566-5 does not define any such content, but a future version
+    /// of the specification might. This allows tools which rewrite JUMBF to
+    /// preserve it (see [`SuperBoxBuilder::set_trailing_description_data()`]).
+    ///
+    /// [`original`]: Self::original
+    /// [`SuperBoxBuilder::set_trailing_description_data()`]: crate::builder::SuperBoxBuilder::set_trailing_description_data()
+    pub fn trailing(&self) -> &'a [u8] {
+        let mut len = self.header_len() + 16 + 1;
+
+        if let Some(label) = self.label {
+            len += label.len() + 1;
+        }
+
+        if self.id.is_some() {
+            len += 4;
+        }
+
+        if self.hash.is_some() {
+            len += 32;
+        }
+
+        if let Some(private) = self.private.as_ref() {
+            len += private.original.len();
+        }
+
+        self.original.get(len..).unwrap_or_default()
+    }
+
+    /// Re-parse the [`private`] box as a JUMBF superbox.
+    ///
+    /// ISO/IEC 19566-5 allows any box to be used as the private box, including
+    /// a superbox with its own description and child boxes. Returns `None` if
+    /// there is no private box or if it is not of `jumb` type. Otherwise,
+    /// returns the result of [`SuperBox::from_data_box()`]. Any error is
+    /// reported as occurring within this box's superbox.
+    ///
+    /// [`private`]: Self::private
+    pub fn private_super_box(&self) -> Option<Result<SuperBox<'a>, Error>> {
+        let private = self.private.as_ref()?;
+        if private.tbox != SUPER_BOX_TYPE {
+            return None;
+        }
+
+        Some(
+            SuperBox::from_data_box(private)
+                .map(|(_, sbox)| sbox)
+                .map_err(|e| e.in_super_box(self.label, private.offset)),
+        )
+    }
+
+    fn header_len(&self) -> usize {
+        if self.original.starts_with(&[0, 0, 0, 1]) {
+            16
+        } else {
+            8
+        }
+    }
 }
 
 impl<'a> Debug for DescriptionBox<'a> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
         f.debug_struct("DescriptionBox")
-            .field("uuid", &DebugByteSlice(self.uuid))
+            .field("uuid", &DebugByteSlice(&self.uuid.0))
             .field("label", &self.label)
             .field("requestable", &self.requestable)
             .field("id", &self.id)
             .field("hash", &DebugOption32ByteSlice(&self.hash))
             .field("private", &self.private)
             .field("original", &DebugByteSlice(self.original))
+            .field("offset", &self.offset)
             .finish()
     }
 }
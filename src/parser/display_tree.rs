@@ -0,0 +1,128 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use alloc::vec;
+use core::fmt::{Display, Error, Formatter};
+
+use crate::{
+    parser::{ChildBox, DataBox, SuperBox},
+    BoxType,
+};
+
+/// Number of payload bytes shown for each data box.
+const PREVIEW_LEN: usize = 16;
+
+/// Renders a [`SuperBox`] as an indented tree, one box per line.
+///
+/// Returned by [`SuperBox::display_tree()`]. Each superbox is shown with its
+/// label (if any), total size, and UUID. Each data box is shown with its box
+/// type, total size, and the first few bytes of its payload in hex.
+pub struct DisplayTree<'r, 'a>(pub(crate) &'r SuperBox<'a>);
+
+impl<'r, 'a> Display for DisplayTree<'r, 'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        // Boxes remaining to be shown, in reverse order, with their depths.
+        // Nested superboxes are walked via this explicit stack rather than
+        // by recursion so that deeply nested input can not overflow the
+        // thread's stack.
+        let mut stack = vec![(Line::SuperBox(self.0), 0)];
+
+        while let Some((line, depth)) = stack.pop() {
+            match line {
+                Line::SuperBox(sbox) => {
+                    fmt_super_box(f, sbox, depth)?;
+
+                    stack.extend(sbox.child_boxes.iter().rev().map(|child| {
+                        let line = match child {
+                            ChildBox::SuperBox(sbox) => Line::SuperBox(sbox),
+                            ChildBox::DataBox(dbox) => Line::DataBox(dbox),
+                        };
+                        (line, depth + 1)
+                    }));
+                }
+                Line::DataBox(dbox) => fmt_data_box(f, dbox, depth)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// A box which is shown as a single line of a `DisplayTree`.
+enum Line<'r, 'a> {
+    SuperBox(&'r SuperBox<'a>),
+    DataBox(&'r DataBox<'a>),
+}
+
+// Write the superbox itself, but not its child boxes.
+fn fmt_super_box(f: &mut Formatter<'_>, sbox: &SuperBox, depth: usize) -> Result<(), Error> {
+    fmt_indent(f, depth)?;
+    f.write_str("jumb")?;
+
+    if let Some(label) = sbox.desc.label {
+        write!(f, " {label:?}")?;
+    }
+
+    writeln!(
+        f,
+        " ({len} bytes, uuid {uuid})",
+        len = sbox.original.len(),
+        uuid = sbox.desc.uuid
+    )
+}
+
+fn fmt_data_box(f: &mut Formatter<'_>, dbox: &DataBox, depth: usize) -> Result<(), Error> {
+    fmt_indent(f, depth)?;
+    fmt_box_type(f, dbox.tbox)?;
+    write!(f, " ({len} bytes)", len = dbox.original.len())?;
+
+    if !dbox.data.is_empty() {
+        f.write_str(":")?;
+
+        for b in dbox.data.iter().take(PREVIEW_LEN) {
+            write!(f, " {b:02x}")?;
+        }
+
+        if dbox.data.len() > PREVIEW_LEN {
+            f.write_str(" ...")?;
+        }
+    }
+
+    writeln!(f)
+}
+
+// Write two spaces per level of nesting. This is written in chunks because a
+// width argument such as `{:indent$}` is limited to `u16::MAX`.
+fn fmt_indent(f: &mut Formatter<'_>, depth: usize) -> Result<(), Error> {
+    const SPACES: &str = "                                                                ";
+
+    let mut remaining = depth.saturating_mul(2);
+    while remaining > 0 {
+        let chunk = remaining.min(SPACES.len());
+        f.write_str(&SPACES[..chunk])?;
+        remaining -= chunk;
+    }
+
+    Ok(())
+}
+
+fn fmt_box_type(f: &mut Formatter<'_>, tbox: BoxType) -> Result<(), Error> {
+    if tbox.0.iter().all(|c| *c >= 0x20 && *c <= 0x7e) {
+        for c in tbox.0 {
+            write!(f, "{}", c as char)?;
+        }
+        Ok(())
+    } else {
+        write!(f, "{tbox:?}")
+    }
+}
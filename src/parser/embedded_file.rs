@@ -0,0 +1,305 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use alloc::{borrow::Cow, vec::Vec};
+use core::{
+    fmt::{Debug, Formatter},
+    str::from_utf8,
+};
+
+use nom::{bytes::complete::take_until, number::complete::be_u8};
+
+use crate::{
+    box_type::{BINARY_DATA_BOX_TYPE, EMBEDDED_FILE_DESCRIPTION_BOX_TYPE, SUPER_BOX_TYPE},
+    debug::*,
+    parser::{ChildBox, DataBox, Error, ParseResult, SuperBox},
+    Uuid,
+};
+
+/// Toggle bit 0 (0x01) indicates that a file name is present.
+const HAS_FILE_NAME: u8 = 0x01;
+
+/// Toggle bit 1 (0x02) indicates that the file is referenced externally and
+/// the binary data box contains a URL rather than the file content.
+const EXTERNAL_REFERENCE: u8 = 0x02;
+
+/// A JUMBF embedded file description box (`bfdb`) describes a file which is
+/// embedded in (or referenced by) an embedded file superbox.
+#[derive(Clone, Eq, PartialEq)]
+pub struct EmbeddedFileDescriptionBox<'a> {
+    /// Media (MIME) type of the embedded file.
+    pub media_type: &'a str,
+
+    /// Optional file name for the embedded file.
+    pub file_name: Option<&'a str>,
+
+    /// True if the file is referenced externally. In that case, the
+    /// accompanying binary data box contains a URL for the file instead of
+    /// the file content itself.
+    pub external: bool,
+
+    /// Original box data.
+    ///
+    /// This the original byte slice that was parsed to create this box.
+    /// It is preserved in case a future client wishes to re-serialize this
+    /// box as is.
+    pub original: &'a [u8],
+}
+
+impl<'a> EmbeddedFileDescriptionBox<'a> {
+    /// Parse a JUMBF embedded file description box, and return a tuple of the
+    /// remainder of the input and the parsed box.
+    ///
+    /// The returned object uses zero-copy, and so has the same lifetime as the
+    /// input.
+    pub fn from_slice(i: &'a [u8]) -> ParseResult<'a, Self> {
+        let (i, boxx): (&'a [u8], DataBox<'a>) = DataBox::from_slice(i)?;
+        let (_, desc) = Self::from_box(boxx)?;
+        Ok((i, desc))
+    }
+
+    /// Convert an existing JUMBF box to a JUMBF embedded file description box.
+    ///
+    /// This consumes the existing [`DataBox`] object and will return an
+    /// appropriate error if the box doesn't match the expected syntax for an
+    /// embedded file description box.
+    ///
+    /// Returns a tuple of the remainder of the input from the box (which should
+    /// typically be empty) and the new [`EmbeddedFileDescriptionBox`] object.
+    pub fn from_box(boxx: DataBox<'a>) -> ParseResult<'a, Self> {
+        if boxx.tbox != EMBEDDED_FILE_DESCRIPTION_BOX_TYPE {
+            return Err(Error::InvalidEmbeddedFileDescriptionBoxType(boxx.tbox));
+        }
+
+        let (i, toggles) = be_u8::<_, Error>(boxx.data)?;
+
+        let (i, media_type) = take_until::<_, _, Error>("\0")(i)?;
+        let media_type = from_utf8(media_type).map_err(Error::Utf8Error)?;
+        let i = &i[1..];
+
+        let (i, file_name) = if toggles & HAS_FILE_NAME != 0 {
+            let (i, file_name) = take_until::<_, _, Error>("\0")(i)?;
+            let file_name = from_utf8(file_name).map_err(Error::Utf8Error)?;
+            (&i[1..], Some(file_name))
+        } else {
+            (i, None)
+        };
+
+        Ok((
+            i,
+            Self {
+                media_type,
+                file_name,
+                external: toggles & EXTERNAL_REFERENCE != 0,
+                original: boxx.original,
+            },
+        ))
+    }
+}
+
+impl<'a> Debug for EmbeddedFileDescriptionBox<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        f.debug_struct("EmbeddedFileDescriptionBox")
+            .field("media_type", &self.media_type)
+            .field("file_name", &self.file_name)
+            .field("external", &self.external)
+            .field("original", &DebugByteSlice(self.original))
+            .finish()
+    }
+}
+
+/// An embedded file is a JUMBF superbox whose content type is
+/// [`Uuid::EMBEDDED_FILE`]. It contains an embedded file description box
+/// (`bfdb`) followed by a binary data box (`bidb`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmbeddedFile<'a> {
+    /// Description of the embedded file.
+    pub desc: EmbeddedFileDescriptionBox<'a>,
+
+    /// Binary data box.
+    ///
+    /// If [`EmbeddedFileDescriptionBox::external`] is `false`, this box's
+    /// `data` contains the file content. Otherwise, it contains a URL for the
+    /// file.
+    pub data: DataBox<'a>,
+}
+
+impl<'a> EmbeddedFile<'a> {
+    /// Interpret a parsed [`SuperBox`] as an embedded file.
+    ///
+    /// Will return an error if the superbox's content type is not
+    /// [`Uuid::EMBEDDED_FILE`] or if the superbox does not contain the
+    /// expected `bfdb` and `bidb` boxes.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::parser::{EmbeddedFile, SuperBox};
+    ///
+    /// let jumbf = hex!(
+    ///     "00000044" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000019" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "40cb0c32bb8a489da70b2ad6f47f4369" // UUID (embedded file)
+    ///         "00" // toggles
+    ///         // ----
+    ///         "00000014" // box size
+    ///         "62666462" // box type = 'bfdb'
+    ///         "00" // toggles
+    ///         "696d6167652f6a70656700" // media type ("image/jpeg")
+    ///         // ----
+    ///         "0000000f" // box size
+    ///         "62696462" // box type = 'bidb'
+    ///         "ffd8ffe0001a4a" // payload (file content)
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    /// let file = EmbeddedFile::from_super_box(&sbox).unwrap();
+    ///
+    /// assert_eq!(file.desc.media_type, "image/jpeg");
+    /// assert_eq!(file.desc.file_name, None);
+    /// assert!(!file.desc.external);
+    /// assert_eq!(file.data.data, &hex!("ffd8ffe0001a4a"));
+    /// ```
+    pub fn from_super_box(sbox: &SuperBox<'a>) -> Result<Self, Error> {
+        if sbox.desc.uuid != Uuid::EMBEDDED_FILE {
+            return Err(Error::InvalidContentType {
+                expected: Uuid::EMBEDDED_FILE,
+                actual: sbox.desc.uuid,
+            });
+        }
+
+        let mut children = sbox.child_boxes.iter();
+
+        let desc = match children.next() {
+            Some(ChildBox::DataBox(dbox)) => {
+                let (_, desc) = EmbeddedFileDescriptionBox::from_box(dbox.clone())?;
+                desc
+            }
+            Some(ChildBox::SuperBox(_)) => {
+                return Err(Error::InvalidEmbeddedFileDescriptionBoxType(SUPER_BOX_TYPE));
+            }
+            None => {
+                return Err(Error::MissingChildBox(EMBEDDED_FILE_DESCRIPTION_BOX_TYPE));
+            }
+        };
+
+        let data = match children.next() {
+            Some(ChildBox::DataBox(dbox)) if dbox.tbox == BINARY_DATA_BOX_TYPE => dbox.clone(),
+            Some(ChildBox::DataBox(dbox)) => {
+                return Err(Error::InvalidBinaryDataBoxType(dbox.tbox));
+            }
+            Some(ChildBox::SuperBox(_)) => {
+                return Err(Error::InvalidBinaryDataBoxType(SUPER_BOX_TYPE));
+            }
+            None => {
+                return Err(Error::MissingChildBox(BINARY_DATA_BOX_TYPE));
+            }
+        };
+
+        Ok(Self { desc, data })
+    }
+
+    /// If the file is referenced externally, return the URL for the file.
+    ///
+    /// Returns `Ok(None)` if the file content is embedded in the binary data
+    /// box. Will return an error if the URL is not valid UTF-8.
+    pub fn external_url(&self) -> Result<Option<&'a str>, Error> {
+        if !self.desc.external {
+            return Ok(None);
+        }
+
+        // The URL is typically NUL-terminated.
+        let url = self.data.data;
+        let url = url.strip_suffix(b"\0").unwrap_or(url);
+
+        Ok(Some(from_utf8(url).map_err(Error::Utf8Error)?))
+    }
+
+    /// Return the content of the embedded file.
+    ///
+    /// If the file content is embedded in the binary data box, it is returned
+    /// as a borrowed slice and `resolver` is not called. If the file is
+    /// referenced externally, `resolver` is called to retrieve the content
+    /// from the URL.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::parser::{EmbeddedFile, Error, SuperBox};
+    ///
+    /// let jumbf = hex!(
+    ///     "00000057" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000019" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "40cb0c32bb8a489da70b2ad6f47f4369" // UUID (embedded file)
+    ///         "00" // toggles
+    ///         // ----
+    ///         "00000014" // box size
+    ///         "62666462" // box type = 'bfdb'
+    ///         "02" // toggles (external)
+    ///         "696d6167652f6a70656700" // media type ("image/jpeg")
+    ///         // ----
+    ///         "00000022" // box size
+    ///         "62696462" // box type = 'bidb'
+    ///         "68747470733a2f2f6578616d706c652e636f6d2f782e6a706700" // URL
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    /// let file = EmbeddedFile::from_super_box(&sbox).unwrap();
+    ///
+    /// let fetch = |url: &str| -> Result<Vec<u8>, Error> {
+    ///     assert_eq!(url, "https://example.com/x.jpg");
+    ///     Ok(vec![0xff, 0xd8])
+    /// };
+    ///
+    /// assert_eq!(*file.payload_bytes(&fetch).unwrap(), [0xff, 0xd8]);
+    /// ```
+    pub fn payload_bytes(&self, resolver: &dyn ReferenceResolver) -> Result<Cow<'a, [u8]>, Error> {
+        match self.external_url()? {
+            Some(url) => Ok(Cow::Owned(resolver.resolve(url)?)),
+            None => Ok(Cow::Borrowed(self.data.data)),
+        }
+    }
+}
+
+/// A `ReferenceResolver` retrieves the content of an embedded file which is
+/// referenced externally (i.e. by URL) rather than stored in the JUMBF data
+/// structure.
+///
+/// This crate does not perform any network or file access itself. Applications
+/// that wish to support external references should implement this trait with
+/// appropriate fetch logic and pass it to [`EmbeddedFile::payload_bytes()`].
+///
+/// This trait is implemented for any closure of the form
+/// `Fn(&str) -> Result<Vec<u8>, Error>`.
+pub trait ReferenceResolver {
+    /// Retrieve the content referenced by `url`.
+    ///
+    /// If the content can not be retrieved, implementations should typically
+    /// return [`Error::UnresolvedReference`].
+    fn resolve(&self, url: &str) -> Result<Vec<u8>, Error>;
+}
+
+impl<F> ReferenceResolver for F
+where
+    F: Fn(&str) -> Result<Vec<u8>, Error>,
+{
+    fn resolve(&self, url: &str) -> Result<Vec<u8>, Error> {
+        self(url)
+    }
+}
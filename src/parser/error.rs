@@ -11,63 +11,241 @@
 // specific language governing permissions and limitations under
 // each license.
 
-use std::str::Utf8Error;
-
-use nom::{
-    error::{ErrorKind, FromExternalError, ParseError},
-    IResult,
+use alloc::{borrow::ToOwned, boxed::Box, string::String, vec::Vec};
+use core::{
+    fmt::{Display, Formatter},
+    str::Utf8Error,
 };
 
-use crate::BoxType;
+use nom::error::{ErrorKind, FromExternalError, ParseError};
+
+use crate::{parser::ParseLimit, BoxType, Uuid};
 
 /// The error type for JUMBF parsing operations.
-#[derive(Clone, Debug, thiserror::Error, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Error {
     /// Invalid length value.
-    #[error("Box length value {0} is reserved")]
     InvalidBoxLength(u32),
 
     /// Not a description box.
-    #[error("Superbox box type should be 'jumb', was '{0:#?}'")]
     InvalidSuperBoxType(BoxType),
 
     /// Not a description box.
-    #[error("Description box type should be 'jumd', was '{0:#?}'")]
     InvalidDescriptionBoxType(BoxType),
 
+    /// Not an embedded file description box.
+    InvalidEmbeddedFileDescriptionBoxType(BoxType),
+
+    /// Not a binary data box.
+    InvalidBinaryDataBoxType(BoxType),
+
+    /// Superbox content type was not as expected.
+    InvalidContentType {
+        /// UUID for the content type that was expected.
+        expected: Uuid,
+
+        /// UUID for the content type that was found.
+        actual: Uuid,
+    },
+
+    /// A required child box was not found.
+    MissingChildBox(BoxType),
+
+    /// A reference (such as an external URL or a JUMBF URI) could not be
+    /// resolved.
+    UnresolvedReference {
+        /// URL that could not be resolved.
+        url: String,
+
+        /// Description of the problem.
+        message: String,
+    },
+
+    /// A resource limit specified via [`ParseOptions`] was exceeded.
+    ///
+    /// [`ParseOptions`]: crate::parser::ParseOptions
+    LimitExceeded {
+        /// Which limit was exceeded.
+        limit: ParseLimit,
+
+        /// The maximum value that was configured for that limit.
+        max: usize,
+    },
+
     /// UTF-8 decoding error.
-    #[error("Unable to decode description box as UTF-8: {0:?}")]
     Utf8Error(Utf8Error),
 
-    /// JUMBF data was incomplete.
-    #[error("Incomplete data, missing: {0:?}")]
-    Incomplete(nom::Needed),
+    /// JUMBF data was incomplete. Holds the number of additional bytes
+    /// that were needed, as far as is known. (More may be needed once those
+    /// bytes are available.)
+    Incomplete(usize),
 
-    /// Error from nom parsing framework.
-    #[error("nom error: {0:?}")]
-    NomError(ErrorKind),
+    /// The input ended before a required field.
+    UnexpectedEof,
+
+    /// The input did not have the expected structure. Holds a short
+    /// description of what was expected.
+    Malformed(&'static str),
+
+    /// An error occurred within a superbox.
+    ///
+    /// Errors in the outermost box (for example, an invalid description box
+    /// or a box length which exceeds the input) are reported without this
+    /// context.
+    InSuperBox {
+        /// Offset, from the start of the source that was originally parsed,
+        /// of the box that could not be parsed.
+        offset: usize,
+
+        /// Labels of the enclosing superboxes, outermost first. An entry is
+        /// `None` if that superbox has no label.
+        path: Vec<Option<String>>,
+
+        /// The error that occurred.
+        source: Box<Error>,
+    },
 }
 
-impl<'a> ParseError<&'a [u8]> for Error {
-    fn from_error_kind(_input: &'a [u8], kind: ErrorKind) -> Self {
-        Error::NomError(kind)
+impl Error {
+    /// Returns the underlying error, without any [`Error::InSuperBox`]
+    /// context.
+    pub fn root_cause(&self) -> &Error {
+        match self {
+            Self::InSuperBox { source, .. } => source.root_cause(),
+            e => e,
+        }
     }
 
-    fn append(_input: &'a [u8], kind: ErrorKind, _other: Self) -> Self {
-        Error::NomError(kind)
+    /// Record that this error occurred within the superbox labeled `label`.
+    ///
+    /// `offset` is only used if the error does not already have context
+    /// from a more deeply nested superbox.
+    pub(crate) fn in_super_box(self, label: Option<&str>, offset: usize) -> Self {
+        self.in_super_boxes([label], offset)
+    }
+
+    /// Record that this error occurred within the nested superboxes labeled
+    /// `labels`, outermost first.
+    ///
+    /// This is equivalent to calling [`in_super_box()`] once per label,
+    /// innermost first, but extends the path in a single pass.
+    ///
+    /// [`in_super_box()`]: Self::in_super_box
+    pub(crate) fn in_super_boxes<'l>(
+        self,
+        labels: impl IntoIterator<Item = Option<&'l str>>,
+        offset: usize,
+    ) -> Self {
+        let labels = labels.into_iter().map(|label| label.map(ToOwned::to_owned));
+
+        match self {
+            Self::InSuperBox {
+                offset,
+                mut path,
+                source,
+            } => {
+                path.splice(0..0, labels);
+                Self::InSuperBox {
+                    offset,
+                    path,
+                    source,
+                }
+            }
+            e => Self::InSuperBox {
+                offset,
+                path: labels.collect(),
+                source: Box::new(e),
+            },
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::InvalidBoxLength(len) => write!(f, "Box length value {len} is reserved"),
+            Self::InvalidSuperBoxType(tbox) => {
+                write!(f, "Superbox box type should be 'jumb', was '{tbox:#?}'")
+            }
+            Self::InvalidDescriptionBoxType(tbox) => {
+                write!(f, "Description box type should be 'jumd', was '{tbox:#?}'")
+            }
+            Self::InvalidEmbeddedFileDescriptionBoxType(tbox) => write!(
+                f,
+                "Embedded file description box type should be 'bfdb', was '{tbox:#?}'"
+            ),
+            Self::InvalidBinaryDataBoxType(tbox) => {
+                write!(f, "Binary data box type should be 'bidb', was '{tbox:#?}'")
+            }
+            Self::InvalidContentType { expected, actual } => {
+                write!(
+                    f,
+                    "Superbox content type should be {expected}, was {actual}"
+                )
+            }
+            Self::MissingChildBox(tbox) => {
+                write!(f, "Superbox is missing required child box '{tbox:#?}'")
+            }
+            Self::UnresolvedReference { url, message } => {
+                write!(f, "Unable to resolve external reference '{url}': {message}")
+            }
+            Self::LimitExceeded { limit, max } => {
+                write!(f, "Parse limit {limit:?} exceeded (maximum is {max})")
+            }
+            Self::Utf8Error(e) => write!(f, "Unable to decode text as UTF-8: {e:?}"),
+            Self::Incomplete(needed) => {
+                write!(f, "Incomplete data, missing at least {needed} bytes")
+            }
+            Self::UnexpectedEof => f.write_str("Unexpected end of input"),
+            Self::Malformed(expected) => write!(f, "Malformed input: {expected}"),
+            Self::InSuperBox {
+                offset,
+                path,
+                source,
+            } => {
+                write!(f, "{source} at offset {offset} inside ")?;
+                for (index, label) in path.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str("/")?;
+                    }
+                    f.write_str(label.as_deref().unwrap_or("(unlabeled)"))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InSuperBox { source, .. } => Some(source.as_ref()),
+            Self::Utf8Error(e) => Some(e),
+            _ => None,
+        }
     }
 }
 
-impl From<Error> for nom::Err<Error> {
-    fn from(e: Error) -> Self {
-        nom::Err::Error(e)
+// The parser is implemented with nom, but nom's types are not exposed via
+// the public API. These impls allow nom combinators to produce `Error`
+// directly and allow `?` to unwrap the resulting `nom::Err`.
+
+impl<'a> ParseError<&'a [u8]> for Error {
+    fn from_error_kind(_input: &'a [u8], kind: ErrorKind) -> Self {
+        kind.into()
+    }
+
+    fn append(_input: &'a [u8], kind: ErrorKind, _other: Self) -> Self {
+        kind.into()
     }
 }
 
 impl From<nom::Err<Error>> for Error {
     fn from(e: nom::Err<Error>) -> Self {
         match e {
-            nom::Err::Incomplete(n) => Self::Incomplete(n),
+            nom::Err::Incomplete(nom::Needed::Size(n)) => Self::Incomplete(n.get()),
+            nom::Err::Incomplete(nom::Needed::Unknown) => Self::Incomplete(1),
             nom::Err::Error(e) | nom::Err::Failure(e) => e,
         }
     }
@@ -75,12 +253,20 @@ impl From<nom::Err<Error>> for Error {
 
 impl<I, E> FromExternalError<I, E> for Error {
     fn from_external_error(_input: I, kind: ErrorKind, _e: E) -> Error {
-        Error::NomError(kind)
+        kind.into()
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        match kind {
+            ErrorKind::Eof => Self::UnexpectedEof,
+            ErrorKind::TakeUntil => Self::Malformed("NUL-terminated string"),
+            _ => Self::Malformed("JUMBF box structure"),
+        }
     }
 }
 
-/// Holds the result of JUMBF parsing functions.
-///
-/// Note that this type is also a [`Result`], so the usual functions (`map`,
-/// `unwrap`, etc.) are available.
-pub type ParseResult<'a, T, E = crate::parser::Error> = IResult<&'a [u8], T, E>;
+/// Holds the result of JUMBF parsing functions: a tuple of the remainder of
+/// the input and the parsed value, or an [`Error`].
+pub type ParseResult<'a, T> = Result<(&'a [u8], T), Error>;
@@ -0,0 +1,194 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! An event-based (SAX-style) JUMBF parser.
+//!
+//! Unlike [`SuperBox::from_slice()`], the parser in this module does not
+//! construct a tree of boxes. Instead, it reports each box to an [`EventSink`]
+//! as it is encountered. This allocates only a stack of the enclosing
+//! superboxes and is useful when the caller only needs to locate a single box
+//! or compute statistics over a large JUMBF data structure.
+//!
+//! [`SuperBox::from_slice()`]: crate::parser::SuperBox::from_slice
+
+use alloc::{vec, vec::Vec};
+use core::ops::ControlFlow;
+
+use crate::{
+    box_type::SUPER_BOX_TYPE,
+    parser::{DataBox, DescriptionBox, Error, ParseResult},
+};
+
+/// An `EventSink` receives callbacks from [`parse_events()`] as boxes are
+/// encountered.
+///
+/// All methods have default implementations which do nothing, so an
+/// implementation need only override the callbacks it is interested in.
+///
+/// Each method may return [`ControlFlow::Break`] to stop parsing early.
+///
+/// The `depth` parameter gives the nesting depth of the superbox to which the
+/// callback applies. The outermost superbox has depth 0.
+pub trait EventSink<'a> {
+    /// Called when a superbox (`jumb`) is started, before its description box
+    /// has been parsed.
+    ///
+    /// `original` is the byte slice for the entire superbox, including its
+    /// header.
+    fn start_super_box(&mut self, original: &'a [u8], depth: usize) -> ControlFlow<()> {
+        let _ = (original, depth);
+        ControlFlow::Continue(())
+    }
+
+    /// Called when the description box for the current superbox has been
+    /// parsed.
+    fn description(&mut self, desc: DescriptionBox<'a>, depth: usize) -> ControlFlow<()> {
+        let _ = (desc, depth);
+        ControlFlow::Continue(())
+    }
+
+    /// Called for each child box of the current superbox which is not itself a
+    /// superbox.
+    fn data_box(&mut self, data_box: DataBox<'a>, depth: usize) -> ControlFlow<()> {
+        let _ = (data_box, depth);
+        ControlFlow::Continue(())
+    }
+
+    /// Called when all of the child boxes of the current superbox have been
+    /// reported.
+    fn end_super_box(&mut self, depth: usize) -> ControlFlow<()> {
+        let _ = depth;
+        ControlFlow::Continue(())
+    }
+}
+
+/// Parse a byte-slice as a JUMBF superbox, reporting each box to `sink` as it
+/// is encountered. Children of this superbox which are also superboxes will
+/// be parsed recursively without limit.
+///
+/// Returns a tuple of the remainder of the input and a [`ControlFlow`] value
+/// which will be [`ControlFlow::Break`] if `sink` requested that parsing stop
+/// early.
+///
+/// ## Example
+///
+/// ```
+/// use std::ops::ControlFlow;
+///
+/// use hex_literal::hex;
+/// use jumbf::parser::{
+///     events::{parse_events, EventSink},
+///     DescriptionBox,
+/// };
+///
+/// struct FindLabel<'a>(Option<&'a str>);
+///
+/// impl<'a> EventSink<'a> for FindLabel<'a> {
+///     fn description(&mut self, desc: DescriptionBox<'a>, _depth: usize) -> ControlFlow<()> {
+///         self.0 = desc.label;
+///         ControlFlow::Break(())
+///     }
+/// }
+///
+/// let jumbf = hex!(
+///     "0000002f" // box size
+///     "6a756d62" // box type = 'jumb'
+///         "00000027" // box size
+///         "6a756d64" // box type = 'jumd'
+///         "00000000000000000000000000000000" // UUID
+///         "03" // toggles
+///         "746573742e7375706572626f7800" // label
+/// );
+///
+/// let mut sink = FindLabel(None);
+/// let (rem, flow) = parse_events(&jumbf, &mut sink).unwrap();
+///
+/// assert!(rem.is_empty());
+/// assert!(flow.is_break());
+/// assert_eq!(sink.0, Some("test.superbox"));
+/// ```
+pub fn parse_events<'a, S>(source: &'a [u8], sink: &mut S) -> ParseResult<'a, ControlFlow<()>>
+where
+    S: EventSink<'a> + ?Sized,
+{
+    let (i, data_box) = DataBox::from_slice(source)?;
+
+    // For each superbox which has been started but not yet ended, the input
+    // which remains to be parsed into child boxes and its offset. Nested
+    // superboxes are walked via this explicit stack rather than by recursion
+    // so that deeply nested input can not overflow the thread's stack.
+    let mut stack: Vec<(&'a [u8], usize)> = vec![];
+    let mut next = Some(data_box);
+
+    loop {
+        if let Some(data_box) = next.take() {
+            if start_super_box(data_box, &mut stack, sink)?.is_break() {
+                return Ok((i, ControlFlow::Break(())));
+            }
+        }
+
+        let depth = stack.len().saturating_sub(1);
+        let Some((rest, offset)) = stack.last_mut() else {
+            return Ok((i, ControlFlow::Continue(())));
+        };
+
+        if rest.is_empty() {
+            stack.pop();
+            if sink.end_super_box(depth).is_break() {
+                return Ok((i, ControlFlow::Break(())));
+            }
+            continue;
+        }
+
+        let (rem, child) = DataBox::from_slice_at(rest, *offset)?;
+        *rest = rem;
+        *offset += child.original.len();
+
+        if child.tbox == SUPER_BOX_TYPE {
+            next = Some(child);
+        } else if sink.data_box(child, depth).is_break() {
+            return Ok((i, ControlFlow::Break(())));
+        }
+    }
+}
+
+// Report the start of a superbox and its description box, then push its
+// child boxes onto `stack`. Its depth is the number of enclosing superboxes
+// already on `stack`.
+fn start_super_box<'a, S>(
+    data_box: DataBox<'a>,
+    stack: &mut Vec<(&'a [u8], usize)>,
+    sink: &mut S,
+) -> Result<ControlFlow<()>, Error>
+where
+    S: EventSink<'a> + ?Sized,
+{
+    if data_box.tbox != SUPER_BOX_TYPE {
+        return Err(Error::InvalidSuperBoxType(data_box.tbox));
+    }
+
+    let depth = stack.len();
+    if sink.start_super_box(data_box.original, depth).is_break() {
+        return Ok(ControlFlow::Break(()));
+    }
+
+    let data_offset = data_box.data_offset();
+    let (i, desc) = DescriptionBox::from_slice_at(data_box.data, data_offset)?;
+    let desc_len = desc.original.len();
+    if sink.description(desc, depth).is_break() {
+        return Ok(ControlFlow::Break(()));
+    }
+
+    stack.push((i, data_offset + desc_len));
+    Ok(ControlFlow::Continue(()))
+}
@@ -0,0 +1,401 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+
+use crate::{
+    box_type::{DESCRIPTION_BOX_TYPE, SUPER_BOX_TYPE},
+    parser::{DescriptionBox, Error},
+    BoxType, Uuid,
+};
+
+/// An event reported by [`JumbfFeeder`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FeedEvent {
+    /// The header for a box has been read.
+    BoxStart {
+        /// Box type.
+        tbox: BoxType,
+
+        /// Offset of the start of the box (i.e. its header) from the start of
+        /// the fed data.
+        offset: u64,
+
+        /// Total length of the box, including its header. Will be `None` if
+        /// the box extends to the end of the data.
+        len: Option<u64>,
+
+        /// Nesting depth of the box. Top-level boxes have depth 0.
+        depth: usize,
+    },
+
+    /// The description box for the enclosing superbox has been read.
+    ///
+    /// This event is reported after the [`FeedEvent::BoxStart`] event and
+    /// before the [`FeedEvent::BoxEnd`] event for the description box.
+    Description {
+        /// Application-specific UUID for the superbox's data type.
+        uuid: Uuid,
+
+        /// Application-specific label for the superbox.
+        label: Option<String>,
+
+        /// True if the superbox can be requested via its label.
+        requestable: bool,
+
+        /// Application-specific 32-bit ID.
+        id: Option<u32>,
+
+        /// SHA-256 hash of the superbox's data payload.
+        hash: Option<[u8; 32]>,
+    },
+
+    /// The end of a box has been reached.
+    BoxEnd {
+        /// Box type.
+        tbox: BoxType,
+
+        /// Offset of the end of the box from the start of the fed data.
+        offset: u64,
+    },
+}
+
+/// A `JumbfFeeder` is an incremental, push-based JUMBF parser.
+///
+/// Data may be provided in arbitrarily-sized chunks via [`feed()`]. As soon
+/// as a box header is complete, a [`FeedEvent::BoxStart`] event is reported;
+/// a [`FeedEvent::BoxEnd`] event is reported once all of the box's content
+/// has been fed. Superboxes (`jumb`) are traversed recursively and their
+/// description boxes are reported via [`FeedEvent::Description`]. The content
+/// of all other boxes is skipped without being buffered. Description boxes
+/// are buffered until complete, and so may be no longer than
+/// [`MAX_DESCRIPTION_BOX_LEN`](Self::MAX_DESCRIPTION_BOX_LEN).
+///
+/// This is intended for scanners which receive data as a stream (for
+/// example, from object storage) and may wish to stop reading as soon as a
+/// particular box has been located. To do so, simply stop calling
+/// [`feed()`].
+///
+/// Once an error has been returned, the feeder's state is unspecified and it
+/// should not be used further.
+///
+/// ## Example
+///
+/// ```
+/// use hex_literal::hex;
+/// use jumbf::parser::{FeedEvent, JumbfFeeder};
+///
+/// let jumbf = hex!(
+///     "0000002f" // box size
+///     "6a756d62" // box type = 'jumb'
+///         "00000027" // box size
+///         "6a756d64" // box type = 'jumd'
+///         "00000000000000000000000000000000" // UUID
+///         "03" // toggles
+///         "746573742e7375706572626f7800" // label
+/// );
+///
+/// let mut feeder = JumbfFeeder::new();
+/// let mut label = None;
+///
+/// for chunk in jumbf.chunks(5) {
+///     for event in feeder.feed(chunk).unwrap() {
+///         if let FeedEvent::Description { label: l, .. } = event {
+///             label = l;
+///         }
+///     }
+/// }
+///
+/// feeder.finish().unwrap();
+/// assert_eq!(label.as_deref(), Some("test.superbox"));
+/// ```
+///
+/// [`feed()`]: Self::feed()
+#[derive(Debug, Default)]
+pub struct JumbfFeeder {
+    /// Number of bytes fed so far.
+    position: u64,
+
+    /// Partial box header, if any.
+    header: Vec<u8>,
+
+    /// Boxes which have been started but not yet ended.
+    open_boxes: Vec<OpenBox>,
+}
+
+#[derive(Debug)]
+struct OpenBox {
+    tbox: BoxType,
+
+    /// Absolute offset of the end of this box, or `None` if it extends to the
+    /// end of the data.
+    end: Option<u64>,
+
+    content: Content,
+}
+
+#[derive(Debug)]
+enum Content {
+    /// Superbox: content is a sequence of boxes.
+    Children { has_children: bool },
+
+    /// Description box: content is buffered (along with its header) so that
+    /// it can be parsed when complete.
+    Description(Vec<u8>),
+
+    /// Any other box: content is skipped.
+    Skip,
+}
+
+impl JumbfFeeder {
+    /// Maximum length, including its header, of a description box that the
+    /// feeder will buffer. A longer description box is reported as
+    /// [`Error::Malformed`].
+    pub const MAX_DESCRIPTION_BOX_LEN: usize = 64 * 1024;
+
+    /// Create a new feeder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of bytes that have been fed so far.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Feed the next chunk of data to the parser.
+    ///
+    /// Returns the events that became available as a result of this chunk.
+    pub fn feed(&mut self, mut data: &[u8]) -> Result<Vec<FeedEvent>, Error> {
+        let mut events: Vec<FeedEvent> = vec![];
+
+        loop {
+            self.close_completed_boxes(&mut events)?;
+
+            if data.is_empty() {
+                return Ok(events);
+            }
+
+            let in_leaf = self
+                .open_boxes
+                .last()
+                .is_some_and(|b| !matches!(b.content, Content::Children { .. }));
+
+            let consumed = if in_leaf {
+                self.feed_leaf_content(data)?
+            } else {
+                self.feed_header(data, &mut events)?
+            };
+
+            data = &data[consumed..];
+            self.position += consumed as u64;
+        }
+    }
+
+    /// Signal that there is no more data.
+    ///
+    /// Any boxes which extend to the end of the data are ended. Will return an
+    /// error if any box is incomplete.
+    pub fn finish(mut self) -> Result<Vec<FeedEvent>, Error> {
+        let mut events: Vec<FeedEvent> = vec![];
+        self.close_completed_boxes(&mut events)?;
+
+        if !self.header.is_empty() {
+            return Err(Error::Incomplete(
+                self.header_len().unwrap_or(8) - self.header.len(),
+            ));
+        }
+
+        while let Some(open_box) = self.open_boxes.pop() {
+            if let Some(end) = open_box.end {
+                return Err(Error::Incomplete((end - self.position) as usize));
+            }
+            self.end_box(open_box, &mut events)?;
+        }
+
+        Ok(events)
+    }
+
+    // Consume as much of `data` as belongs to the current (non-superbox) box.
+    fn feed_leaf_content(&mut self, data: &[u8]) -> Result<usize, Error> {
+        let position = self.position;
+        let Some(open_box) = self.open_boxes.last_mut() else {
+            return Ok(0);
+        };
+
+        let len = match open_box.end {
+            Some(end) => data.len().min((end - position) as usize),
+            None => data.len(),
+        };
+
+        if let Content::Description(buf) = &mut open_box.content {
+            if buf.len() + len > Self::MAX_DESCRIPTION_BOX_LEN {
+                return Err(Error::Malformed("description box no longer than 64 KiB"));
+            }
+            buf.extend_from_slice(&data[0..len]);
+        }
+
+        Ok(len)
+    }
+
+    // Consume bytes of a box header. Once the header is complete, start the
+    // box.
+    fn feed_header(&mut self, data: &[u8], events: &mut Vec<FeedEvent>) -> Result<usize, Error> {
+        let mut consumed = 0;
+
+        loop {
+            let needed = self.header_len().unwrap_or(8);
+            if self.header.len() >= needed {
+                break;
+            }
+
+            let take = (needed - self.header.len()).min(data.len() - consumed);
+            if take == 0 {
+                return Ok(consumed);
+            }
+
+            self.header
+                .extend_from_slice(&data[consumed..consumed + take]);
+            consumed += take;
+        }
+
+        let header = core::mem::take(&mut self.header);
+        let offset = self.position + consumed as u64 - header.len() as u64;
+
+        let tbox: BoxType = header[4..8].into();
+
+        let len = match u32::from_be_bytes([header[0], header[1], header[2], header[3]]) {
+            0 => None,
+            1 => {
+                let mut xl = [0u8; 8];
+                xl.copy_from_slice(&header[8..16]);
+                let len = u64::from_be_bytes(xl);
+                if len < 16 {
+                    return Err(Error::InvalidBoxLength(len as u32));
+                }
+                Some(len)
+            }
+            len @ 2..=7 => {
+                return Err(Error::InvalidBoxLength(len));
+            }
+            len => Some(len as u64),
+        };
+
+        // The length is taken from the input, so a hostile XLBox header could
+        // put the end of the box beyond the range of a `u64`.
+        let end = match len {
+            Some(len) => Some(
+                offset
+                    .checked_add(len)
+                    .ok_or(Error::Malformed("box length within 64-bit range"))?,
+            ),
+            None => None,
+        };
+
+        // A box must fit within its parent.
+        if let Some(parent) = self.open_boxes.last_mut() {
+            if let (Some(parent_end), Some(end)) = (parent.end, end) {
+                if end > parent_end {
+                    return Err(Error::Malformed("child box within its parent box"));
+                }
+            }
+
+            if let Content::Children { has_children } = &mut parent.content {
+                if !*has_children && tbox != DESCRIPTION_BOX_TYPE {
+                    return Err(Error::InvalidDescriptionBoxType(tbox));
+                }
+                *has_children = true;
+            }
+        }
+
+        let in_super_box = self
+            .open_boxes
+            .last()
+            .is_some_and(|b| b.tbox == SUPER_BOX_TYPE);
+
+        let content = if tbox == SUPER_BOX_TYPE {
+            Content::Children {
+                has_children: false,
+            }
+        } else if tbox == DESCRIPTION_BOX_TYPE && in_super_box {
+            Content::Description(header)
+        } else {
+            Content::Skip
+        };
+
+        events.push(FeedEvent::BoxStart {
+            tbox,
+            offset,
+            len,
+            depth: self.open_boxes.len(),
+        });
+
+        self.open_boxes.push(OpenBox { tbox, end, content });
+
+        Ok(consumed)
+    }
+
+    // Returns the length of the box header being accumulated, if enough of it
+    // is available to know.
+    fn header_len(&self) -> Option<usize> {
+        if self.header.len() < 4 {
+            None
+        } else if self.header[0..4] == [0, 0, 0, 1] {
+            Some(16)
+        } else {
+            Some(8)
+        }
+    }
+
+    fn close_completed_boxes(&mut self, events: &mut Vec<FeedEvent>) -> Result<(), Error> {
+        while let Some(open_box) = self.open_boxes.last() {
+            if open_box.end != Some(self.position) {
+                break;
+            }
+
+            if let Some(open_box) = self.open_boxes.pop() {
+                self.end_box(open_box, events)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn end_box(&mut self, open_box: OpenBox, events: &mut Vec<FeedEvent>) -> Result<(), Error> {
+        match open_box.content {
+            Content::Children {
+                has_children: false,
+            } => {
+                return Err(Error::Incomplete(8));
+            }
+            Content::Description(buf) => {
+                let (_, desc) = DescriptionBox::from_slice(&buf)?;
+                events.push(FeedEvent::Description {
+                    uuid: desc.uuid,
+                    label: desc.label.map(|label| label.to_owned()),
+                    requestable: desc.requestable,
+                    id: desc.id,
+                    hash: desc.hash.copied(),
+                });
+            }
+            _ => (),
+        }
+
+        events.push(FeedEvent::BoxEnd {
+            tbox: open_box.tbox,
+            offset: self.position,
+        });
+
+        Ok(())
+    }
+}
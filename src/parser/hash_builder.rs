@@ -0,0 +1,183 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use alloc::{borrow::ToOwned, vec, vec::Vec};
+use core::ops::Range;
+
+use sha2::digest::{Digest, Output};
+
+use crate::parser::{Error, SuperBox};
+
+/// A `HashBuilder` computes a digest over the serialized bytes of a chosen
+/// set of boxes within a superbox, optionally excluding some of their
+/// descendants.
+///
+/// This supports hashing rules such as those used by C2PA, which hash the
+/// serialized JUMBF of a claim's assertions while excluding certain boxes.
+///
+/// Boxes are identified by paths of labels, resolved relative to the
+/// superbox being hashed as described in [`SuperBox::find_by_label()`].
+/// Bytes are hashed in document order, and each byte is hashed at most once
+/// even if included boxes overlap.
+///
+/// Any hash function which implements the [`Digest`] trait (such as
+/// `sha2::Sha256`, `sha2::Sha384`, or `sha2::Sha512`) may be used.
+///
+/// This struct is available when the `hash` feature is enabled.
+///
+/// ## Example
+///
+/// ```
+/// use hex_literal::hex;
+/// use jumbf::parser::{HashBuilder, SuperBox};
+/// use sha2::{Digest, Sha256};
+///
+/// let jumbf = hex!(
+///     "00000078" // box size
+///     "6a756d62" // box type = 'jumb'
+///         "00000019" // box size
+///         "6a756d64" // box type = 'jumd'
+///         "00000000000000000000000000000000" // UUID
+///         "00" // toggles
+///         // ----
+///         "00000057" // box size
+///         "6a756d62" // box type = 'jumb'
+///             "00000028" // box size
+///             "6a756d64" // box type = 'jumd'
+///             "6332637300110010800000aa00389b71" // UUID
+///             "03" // toggles
+///             "633270612e7369676e617475726500" // label
+///             // ----
+///             "00000027" // box size
+///             "75756964" // box type = 'uuid'
+///             "6332637300110010800000aa00389b71" // UUID
+///             "7369676e61747572652e2e2e2e2e2e" // data
+///     );
+///
+/// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+///
+/// let hasher = HashBuilder::new().exclude("c2pa.signature");
+/// assert_eq!(hasher.ranges(&sbox).unwrap(), vec![0..33]);
+///
+/// assert_eq!(
+///     hasher.hash::<Sha256>(&sbox).unwrap(),
+///     Sha256::digest(&jumbf[0..33])
+/// );
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct HashBuilder<'p> {
+    include: Vec<&'p str>,
+    exclude: Vec<&'p str>,
+}
+
+impl<'p> HashBuilder<'p> {
+    /// Create a `HashBuilder` which, until [`include()`] is called, hashes
+    /// the entire superbox.
+    ///
+    /// [`include()`]: Self::include
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hash the superbox identified by `path` (including its header).
+    ///
+    /// Once this has been called, only the included superboxes are hashed.
+    pub fn include(mut self, path: &'p str) -> Self {
+        self.include.push(path);
+        self
+    }
+
+    /// Exclude the superbox identified by `path` (including its header) from
+    /// the hash.
+    ///
+    /// Paths which can not be resolved are ignored, so that optional boxes
+    /// may be excluded.
+    pub fn exclude(mut self, path: &'p str) -> Self {
+        self.exclude.push(path);
+        self
+    }
+
+    /// Returns the byte ranges, relative to the start of `sbox`, which will
+    /// be hashed. The ranges are sorted and do not overlap.
+    ///
+    /// Will return [`Error::UnresolvedReference`] if any path given to
+    /// [`include()`] can not be resolved.
+    ///
+    /// [`include()`]: Self::include
+    pub fn ranges(&self, sbox: &SuperBox) -> Result<Vec<Range<usize>>, Error> {
+        if let Some(path) = self
+            .include
+            .iter()
+            .find(|path| sbox.find_by_label(path).is_none())
+        {
+            return Err(Error::UnresolvedReference {
+                url: (*path).to_owned(),
+                message: "no unique superbox with this label path".to_owned(),
+            });
+        }
+
+        let included = if self.include.is_empty() {
+            let whole = 0..sbox.original.len();
+            vec![whole]
+        } else {
+            sbox.exclusion_ranges(&self.include)
+        };
+
+        let excluded = sbox.exclusion_ranges(&self.exclude);
+
+        Ok(subtract(included, &excluded))
+    }
+
+    /// Compute the digest of the bytes identified by [`ranges()`] using the
+    /// hash function `D`.
+    ///
+    /// Will return [`Error::UnresolvedReference`] if any path given to
+    /// [`include()`] can not be resolved.
+    ///
+    /// [`include()`]: Self::include
+    /// [`ranges()`]: Self::ranges
+    pub fn hash<D: Digest>(&self, sbox: &SuperBox) -> Result<Output<D>, Error> {
+        let mut hasher = D::new();
+
+        for range in self.ranges(sbox)? {
+            hasher.update(sbox.original.get(range).unwrap_or_default());
+        }
+
+        Ok(hasher.finalize())
+    }
+}
+
+// Remove `excluded` from `included`. Both must be sorted and non-overlapping.
+fn subtract(included: Vec<Range<usize>>, excluded: &[Range<usize>]) -> Vec<Range<usize>> {
+    let mut result = Vec::with_capacity(included.len());
+
+    for mut range in included {
+        for exclusion in excluded {
+            if exclusion.end <= range.start || exclusion.start >= range.end {
+                continue;
+            }
+
+            if exclusion.start > range.start {
+                result.push(range.start..exclusion.start);
+            }
+
+            range.start = exclusion.end.min(range.end);
+        }
+
+        if !range.is_empty() {
+            result.push(range);
+        }
+    }
+
+    result
+}
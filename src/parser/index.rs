@@ -0,0 +1,213 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use alloc::{vec, vec::Vec};
+use core::ops::Range;
+
+use crate::parser::{ChildBox, DataBox, SuperBox};
+
+/// An index over a parsed [`SuperBox`] and all of its descendants which
+/// supports navigating upwards from any box to its ancestors.
+///
+/// [`SuperBox`] only records its children. This index additionally records
+/// the parent of every superbox and child box in the tree. Since every box
+/// parsed from a single source starts at a different offset, boxes are
+/// identified by their offset from the start of that source (i.e. the
+/// `offset` field of [`SuperBox`] or [`DataBox`]).
+///
+/// Description boxes (and any private boxes they contain) are not indexed.
+///
+/// ## Example
+///
+/// ```
+/// use hex_literal::hex;
+/// use jumbf::parser::{SuperBox, SuperBoxIndex};
+///
+/// let jumbf = hex!(
+///     "00000050" // box size
+///     "6a756d62" // box type = 'jumb'
+///         "00000019" // box size
+///         "6a756d64" // box type = 'jumd'
+///         "00000000000000000000000000000000" // UUID
+///         "00" // toggles
+///         // ----
+///         "0000002f" // box size
+///         "6a756d62" // box type = 'jumb'
+///             "0000001b" // box size
+///             "6a756d64" // box type = 'jumd'
+///             "6a736f6e00110010800000aa00389b71" // UUID (JSON)
+///             "02" // toggles
+///             "6100" // label ("a")
+///             // ----
+///             "0000000c" // box size
+///             "6a736f6e" // box type = 'json'
+///             "7b7d200a" // payload
+///     );
+///
+/// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+/// let index = SuperBoxIndex::new(&sbox);
+///
+/// // The JSON box starts at offset 68.
+/// let parent = index.parent_of(68).unwrap();
+/// assert_eq!(parent.desc.label, Some("a"));
+///
+/// let path: Vec<usize> = index.path_to(68).unwrap().iter().map(|b| b.offset()).collect();
+/// assert_eq!(path, vec![0, 33, 68]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SuperBoxIndex<'s, 'a> {
+    // Sorted by offset, which is also depth-first order.
+    entries: Vec<Entry<'s, 'a>>,
+}
+
+#[derive(Clone, Debug)]
+struct Entry<'s, 'a> {
+    node: BoxRef<'s, 'a>,
+    parent: Option<usize>,
+}
+
+/// A reference to a box within a [`SuperBoxIndex`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BoxRef<'s, 'a> {
+    /// A superbox.
+    SuperBox(&'s SuperBox<'a>),
+
+    /// Any other kind of box.
+    DataBox(&'s DataBox<'a>),
+}
+
+impl<'s, 'a> BoxRef<'s, 'a> {
+    /// Returns the offset of the start of this box (i.e. its header) from
+    /// the start of the source that was originally parsed.
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::SuperBox(sbox) => sbox.offset,
+            Self::DataBox(dbox) => dbox.offset,
+        }
+    }
+
+    /// Returns the range of bytes occupied by this box (including its
+    /// header) within the source that was originally parsed.
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::SuperBox(sbox) => sbox.range(),
+            Self::DataBox(dbox) => dbox.range(),
+        }
+    }
+
+    /// If this refers to a superbox, return that superbox.
+    pub fn as_super_box(&self) -> Option<&'s SuperBox<'a>> {
+        match self {
+            Self::SuperBox(sbox) => Some(sbox),
+            Self::DataBox(_) => None,
+        }
+    }
+
+    /// If this refers to a data box, return that data box.
+    pub fn as_data_box(&self) -> Option<&'s DataBox<'a>> {
+        match self {
+            Self::SuperBox(_) => None,
+            Self::DataBox(dbox) => Some(dbox),
+        }
+    }
+}
+
+impl<'s, 'a> SuperBoxIndex<'s, 'a> {
+    /// Index `root` and all of its descendants.
+    pub fn new(root: &'s SuperBox<'a>) -> Self {
+        let mut entries = vec![Entry {
+            node: BoxRef::SuperBox(root),
+            parent: None,
+        }];
+
+        index_children(root, 0, &mut entries);
+
+        Self { entries }
+    }
+
+    /// Returns the number of boxes in the index.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the index contains no boxes. (This is never the
+    /// case, since the index always contains at least its root superbox.)
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the box which starts at `offset`, if any.
+    pub fn get(&self, offset: usize) -> Option<BoxRef<'s, 'a>> {
+        self.position(offset).map(|pos| self.entries[pos].node)
+    }
+
+    /// Returns the superbox which directly contains the box that starts at
+    /// `offset`.
+    ///
+    /// Returns `None` if no box starts at `offset` or if that box is the root
+    /// superbox.
+    pub fn parent_of(&self, offset: usize) -> Option<&'s SuperBox<'a>> {
+        let parent = self.entries[self.position(offset)?].parent?;
+        self.entries[parent].node.as_super_box()
+    }
+
+    /// Returns the boxes leading from the root superbox to the box that
+    /// starts at `offset`, inclusive of both.
+    ///
+    /// Returns `None` if no box starts at `offset`.
+    pub fn path_to(&self, offset: usize) -> Option<Vec<BoxRef<'s, 'a>>> {
+        let mut pos = Some(self.position(offset)?);
+        let mut path = vec![];
+
+        while let Some(p) = pos {
+            path.push(self.entries[p].node);
+            pos = self.entries[p].parent;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// Returns an iterator over all indexed boxes in depth-first order.
+    pub fn iter(&self) -> impl Iterator<Item = BoxRef<'s, 'a>> + '_ {
+        self.entries.iter().map(|entry| entry.node)
+    }
+
+    fn position(&self, offset: usize) -> Option<usize> {
+        self.entries
+            .binary_search_by_key(&offset, |entry| entry.node.offset())
+            .ok()
+    }
+}
+
+fn index_children<'s, 'a>(sbox: &'s SuperBox<'a>, parent: usize, entries: &mut Vec<Entry<'s, 'a>>) {
+    for child in &sbox.child_boxes {
+        let pos = entries.len();
+
+        match child {
+            ChildBox::SuperBox(child) => {
+                entries.push(Entry {
+                    node: BoxRef::SuperBox(child),
+                    parent: Some(parent),
+                });
+                index_children(child, pos, entries);
+            }
+            ChildBox::DataBox(child) => {
+                entries.push(Entry {
+                    node: BoxRef::DataBox(child),
+                    parent: Some(parent),
+                });
+            }
+        }
+    }
+}
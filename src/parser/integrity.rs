@@ -0,0 +1,130 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::{Display, Formatter};
+
+/// The result of [`SuperBox::integrity_report()`].
+///
+/// The report's [`Display`] implementation writes one line per check, which
+/// is suitable for logging.
+///
+/// [`SuperBox::integrity_report()`]: crate::parser::SuperBox::integrity_report
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IntegrityReport {
+    /// One entry for each superbox whose description box declares a hash,
+    /// in the order in which the superboxes appear.
+    pub hashes: Vec<HashCheck>,
+
+    /// One entry for each superbox whose contents do not exactly fill the
+    /// size declared in its header, in the order in which the superboxes
+    /// appear.
+    pub size_mismatches: Vec<SizeMismatch>,
+}
+
+impl IntegrityReport {
+    /// Returns `true` if every declared hash matched and no size mismatches
+    /// were found.
+    pub fn is_valid(&self) -> bool {
+        self.hashes.iter().all(HashCheck::is_valid) && self.size_mismatches.is_empty()
+    }
+}
+
+impl Display for IntegrityReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        for check in &self.hashes {
+            writeln!(f, "{check}")?;
+        }
+        for mismatch in &self.size_mismatches {
+            writeln!(f, "{mismatch}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The result of recomputing the hash declared by a superbox's description
+/// box.
+///
+/// The hash is the SHA-256 digest of every byte in the superbox which
+/// follows the description box.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HashCheck {
+    /// Labels of the superboxes leading to this one, separated by `/`.
+    /// An unlabeled outermost superbox is shown as `(root)`; any other
+    /// unlabeled superbox is shown as `(unlabeled)`.
+    pub path: String,
+
+    /// Offset of the superbox from the start of the source that was
+    /// originally parsed.
+    pub offset: usize,
+
+    /// The hash declared in the description box.
+    pub expected: [u8; 32],
+
+    /// The hash computed from the superbox's content.
+    pub actual: [u8; 32],
+}
+
+impl HashCheck {
+    /// Returns `true` if the computed hash matches the declared hash.
+    pub fn is_valid(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+impl Display for HashCheck {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        if self.is_valid() {
+            write!(
+                f,
+                "OK   {} (offset {}): hash matches",
+                self.path, self.offset
+            )
+        } else {
+            write!(
+                f,
+                "FAIL {} (offset {}): hash does not match",
+                self.path, self.offset
+            )
+        }
+    }
+}
+
+/// A superbox whose description box and child boxes do not exactly fill the
+/// size declared in its header.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SizeMismatch {
+    /// Labels of the superboxes leading to this one, as described in
+    /// [`HashCheck::path`].
+    pub path: String,
+
+    /// Offset of the superbox from the start of the source that was
+    /// originally parsed.
+    pub offset: usize,
+
+    /// Size of the superbox as declared in its header, in bytes.
+    pub declared: usize,
+
+    /// Size of the superbox's header plus the boxes it contains, in bytes.
+    pub actual: usize,
+}
+
+impl Display for SizeMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(
+            f,
+            "FAIL {} (offset {}): declared size is {} bytes but contents span {} bytes",
+            self.path, self.offset, self.declared, self.actual
+        )
+    }
+}
@@ -0,0 +1,239 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use alloc::vec::Vec;
+use core::{cell::OnceCell, convert::identity, ops::Range};
+
+use crate::{
+    box_type::SUPER_BOX_TYPE,
+    label,
+    parser::{super_box::boxes_from_slice, DataBox, DescriptionBox, Error, ParseResult, SuperBox},
+};
+
+/// A JUMBF superbox whose child boxes are parsed only when they are first
+/// accessed.
+///
+/// Parsing a `LazySuperBox` reads the description box and the header (type
+/// and size) of each child box, but does not look inside any child box.
+/// A child superbox is parsed (itself lazily) on first access and the result
+/// is cached for later accesses.
+///
+/// This is useful when only a small part of a large JUMBF data structure is
+/// needed. For example, reading one assertion from a C2PA manifest store
+/// need not parse every other manifest and thumbnail in the store.
+///
+/// ## Example
+///
+/// ```
+/// use hex_literal::hex;
+/// use jumbf::parser::LazySuperBox;
+///
+/// let jumbf = hex!(
+///     "00000050" // box size
+///     "6a756d62" // box type = 'jumb'
+///         "00000019" // box size
+///         "6a756d64" // box type = 'jumd'
+///         "00000000000000000000000000000000" // UUID
+///         "00" // toggles
+///         // ----
+///         "0000002f" // box size
+///         "6a756d62" // box type = 'jumb'
+///             "0000001b" // box size
+///             "6a756d64" // box type = 'jumd'
+///             "6a736f6e00110010800000aa00389b71" // UUID (JSON)
+///             "03" // toggles
+///             "6100" // label ("a")
+///             // ----
+///             "0000000c" // box size
+///             "6a736f6e" // box type = 'json'
+///             "7b7d200a" // payload
+///     );
+///
+/// let (_, sbox) = LazySuperBox::from_slice(&jumbf).unwrap();
+/// assert_eq!(sbox.child_boxes.len(), 1);
+///
+/// let a = sbox.find_by_label("a").unwrap().unwrap();
+/// assert_eq!(a.child_boxes[0].data, b"{} \n");
+/// ```
+#[derive(Clone, Debug)]
+pub struct LazySuperBox<'a> {
+    /// Description box.
+    pub desc: DescriptionBox<'a>,
+
+    /// Child boxes, as read from their headers only.
+    ///
+    /// A child of type `jumb` can be accessed as a superbox via
+    /// [`child()`](Self::child) or [`children()`](Self::children).
+    pub child_boxes: Vec<DataBox<'a>>,
+
+    /// Original box data.
+    ///
+    /// This the original byte slice that was parsed to create this box.
+    /// It is preserved in case a future client wishes to re-serialize this
+    /// box as is.
+    pub original: &'a [u8],
+
+    /// Offset of the start of this box (i.e. its header) from the start of
+    /// the source that was originally parsed.
+    pub offset: usize,
+
+    // Cached results of parsing each child superbox. Has the same length as
+    // `child_boxes`; entries for children which are not superboxes are
+    // never filled.
+    expanded: Vec<OnceCell<Result<LazySuperBox<'a>, Error>>>,
+}
+
+/// A single child box within a [`LazySuperBox`].
+#[derive(Clone, Copy, Debug)]
+pub enum LazyChildBox<'s, 'a> {
+    /// A superbox.
+    SuperBox(&'s LazySuperBox<'a>),
+
+    /// Any other kind of box.
+    DataBox(&'s DataBox<'a>),
+}
+
+impl<'a> LazySuperBox<'a> {
+    /// Parse a byte-slice as a JUMBF superbox, and return a tuple of the
+    /// remainder of the input and the lazily-parsed superbox.
+    ///
+    /// The returned object uses zero-copy, and so has the same lifetime as the
+    /// input.
+    pub fn from_slice(i: &'a [u8]) -> ParseResult<'a, Self> {
+        let (i, data_box) = DataBox::from_slice(i)?;
+        let (_, sbox) = Self::from_data_box(&data_box)?;
+        Ok((i, sbox))
+    }
+
+    /// Re-parse a [`DataBox`] as a lazily-parsed JUMBF superbox.
+    ///
+    /// Will return an error if the box isn't of `jumb` type.
+    pub fn from_data_box(data_box: &DataBox<'a>) -> ParseResult<'a, Self> {
+        if data_box.tbox != SUPER_BOX_TYPE {
+            return Err(Error::InvalidSuperBoxType(data_box.tbox));
+        }
+
+        let data_offset = data_box.data_offset();
+        let (i, desc) = DescriptionBox::from_slice_at(data_box.data, data_offset)?;
+
+        let offset = data_offset + data_box.data.len() - i.len();
+        let (i, child_boxes) = boxes_from_slice(i, offset, desc.label, identity)?;
+        let expanded = child_boxes.iter().map(|_| OnceCell::new()).collect();
+
+        Ok((
+            i,
+            Self {
+                desc,
+                child_boxes,
+                original: data_box.original,
+                offset: data_box.offset,
+                expanded,
+            },
+        ))
+    }
+
+    /// Returns the child box at `index`, parsing it as a superbox if it is of
+    /// `jumb` type and has not already been parsed.
+    ///
+    /// Returns `None` if there is no child box at `index`. Parsed superboxes
+    /// are cached by index, so `None` is also returned for a superbox which
+    /// was added to [`child_boxes`] after this superbox was parsed.
+    ///
+    /// [`child_boxes`]: Self::child_boxes
+    pub fn child(&self, index: usize) -> Option<Result<LazyChildBox<'_, 'a>, Error>> {
+        let dbox = self.child_boxes.get(index)?;
+
+        if dbox.tbox != SUPER_BOX_TYPE {
+            return Some(Ok(LazyChildBox::DataBox(dbox)));
+        }
+
+        let sbox = self.expanded.get(index)?.get_or_init(|| {
+            Self::from_data_box(dbox)
+                .map(|(_, sbox)| sbox)
+                .map_err(|e| e.in_super_box(self.desc.label, dbox.offset))
+        });
+
+        Some(match sbox {
+            Ok(sbox) => Ok(LazyChildBox::SuperBox(sbox)),
+            Err(e) => Err(e.clone()),
+        })
+    }
+
+    /// Returns an iterator over all child boxes, parsing each child superbox
+    /// as it is reached.
+    pub fn children(&self) -> impl Iterator<Item = Result<LazyChildBox<'_, 'a>, Error>> + '_ {
+        (0..self.child_boxes.len()).filter_map(|index| self.child(index))
+    }
+
+    /// Find a child superbox of this superbox by label and verify that
+    /// exactly one such child exists.
+    ///
+    /// Labels are interpreted as described in [`SuperBox::find_by_label()`].
+    /// The labels of the child superboxes of each superbox on the path to the
+    /// requested superbox are checked, which parses each of those children
+    /// lazily (that is, its description box and the headers of its child
+    /// boxes). No superbox off that path is parsed any more deeply. Will
+    /// return an error if any of those superboxes can not be parsed.
+    pub fn find_by_label(&self, label: &str) -> Result<Option<&Self>, Error> {
+        let (label, suffix) = match label.split_once('/') {
+            Some((label, suffix)) => (label, Some(suffix)),
+            None => (label, None),
+        };
+
+        let mut found: Option<&Self> = None;
+
+        for child in self.children() {
+            if let LazyChildBox::SuperBox(sbox) = child? {
+                if sbox.desc.label.is_some_and(|l| label::matches(l, label))
+                    && sbox.desc.requestable
+                {
+                    if found.is_some() {
+                        return Ok(None);
+                    }
+                    found = Some(sbox);
+                }
+            }
+        }
+
+        match (found, suffix) {
+            (Some(sbox), Some(suffix)) => sbox.find_by_label(suffix),
+            (found, _) => Ok(found),
+        }
+    }
+
+    /// Fully parse this superbox and all of its descendants.
+    pub fn to_super_box(&self) -> Result<SuperBox<'a>, Error> {
+        let (i, data_box) = DataBox::from_slice_at(self.original, self.offset)?;
+        debug_assert!(i.is_empty());
+        let (_, sbox) = SuperBox::from_data_box(&data_box)?;
+        Ok(sbox)
+    }
+
+    /// Returns the range of bytes occupied by this superbox (including its
+    /// header) within the source that was originally parsed.
+    pub fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.original.len()
+    }
+
+    /// Write this superbox, including all of its descendants, exactly as it
+    /// was originally parsed (i.e. the contents of [`original`]) to
+    /// `to_stream`.
+    ///
+    /// This function is available when the `std` feature is enabled.
+    ///
+    /// [`original`]: Self::original
+    #[cfg(feature = "std")]
+    pub fn write_original(&self, to_stream: &mut impl std::io::Write) -> std::io::Result<()> {
+        to_stream.write_all(self.original)
+    }
+}
@@ -16,12 +16,67 @@
 //!
 //! [JUMBF (ISO/IEC 19566-5:2019)]: https://www.iso.org/standard/73604.html
 
+#[cfg(feature = "std")]
+mod cached_reader;
+
 mod data_box;
 mod description_box;
+mod display_tree;
+mod embedded_file;
 mod error;
+pub mod events;
+mod feeder;
+
+#[cfg(feature = "hash")]
+mod hash_builder;
+
+mod index;
+
+#[cfg(feature = "hash")]
+mod integrity;
+
+mod lazy;
+mod observer;
+mod options;
+
+#[cfg(feature = "parallel")]
+mod parallel;
+
+#[cfg(feature = "std")]
+mod read;
+
+mod registry;
+mod report;
+mod size;
 mod super_box;
+mod super_box_ref;
+mod uuid_label;
 
+#[cfg(feature = "std")]
+pub use cached_reader::CachedReader;
 pub use data_box::DataBox;
 pub use description_box::DescriptionBox;
+pub use display_tree::DisplayTree;
+pub use embedded_file::{EmbeddedFile, EmbeddedFileDescriptionBox, ReferenceResolver};
 pub use error::{Error, ParseResult};
-pub use super_box::{ChildBox, SuperBox};
+pub use feeder::{FeedEvent, JumbfFeeder};
+#[cfg(feature = "hash")]
+pub use hash_builder::HashBuilder;
+pub use index::{BoxRef, SuperBoxIndex};
+#[cfg(feature = "hash")]
+pub use integrity::{HashCheck, IntegrityReport, SizeMismatch};
+pub use lazy::{LazyChildBox, LazySuperBox};
+pub use observer::{ParseObserver, ParseProgress};
+pub use options::{ParseLimit, ParseOptions};
+#[cfg(feature = "std")]
+pub use read::JumbfReader;
+pub use registry::{BoxRegistry, TypedPayload};
+pub use report::{ParseReport, ParseWarning};
+pub use size::{BoxSize, SizeBreakdown};
+pub use super_box::{ChildBox, StructuralEq, SuperBox};
+pub use super_box_ref::SuperBoxRef;
+pub use uuid_label::UuidLabel;
+
+pub use crate::uri::JumbfUri;
+#[cfg(feature = "hash")]
+pub use crate::uri::{hashed_label, verify_hashed_label};
@@ -0,0 +1,91 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::BoxType;
+
+/// Receives progress notifications while a superbox is parsed via
+/// [`SuperBox::from_source_with_observer()`].
+///
+/// This can be used to drive a progress indicator or to trace parsing of a
+/// large manifest store. Any `FnMut(&ParseProgress)` closure implements this
+/// trait.
+///
+/// ## Example
+///
+/// ```
+/// use hex_literal::hex;
+/// use jumbf::parser::{ParseOptions, ParseProgress, SuperBox};
+///
+/// let jumbf = hex!(
+///     "00000029" // box size
+///     "6a756d62" // box type = 'jumb'
+///         "00000019" // box size
+///         "6a756d64" // box type = 'jumd'
+///         "00000000000000000000000000000000" // UUID
+///         "00" // toggles
+///         // ----
+///         "00000008" // box size
+///         "61626364" // box type = 'abcd'
+/// );
+///
+/// let mut consumed = vec![];
+/// let mut observer = |progress: &ParseProgress| consumed.push(progress.bytes_consumed);
+///
+/// SuperBox::from_source_with_observer(&jumbf, &ParseOptions::default(), &mut observer).unwrap();
+/// assert_eq!(consumed, [8, 33, 41]);
+/// ```
+///
+/// [`SuperBox::from_source_with_observer()`]: crate::parser::SuperBox::from_source_with_observer
+pub trait ParseObserver {
+    /// Called after each box (including superboxes, description boxes, and
+    /// data boxes) has been parsed.
+    ///
+    /// A superbox is reported as soon as its header has been read, before
+    /// any of its child boxes.
+    fn box_parsed(&mut self, progress: &ParseProgress);
+}
+
+impl<F: FnMut(&ParseProgress)> ParseObserver for F {
+    fn box_parsed(&mut self, progress: &ParseProgress) {
+        self(progress)
+    }
+}
+
+/// Describes a box that has just been parsed. Passed to
+/// [`ParseObserver::box_parsed()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseProgress {
+    /// Box type.
+    pub tbox: BoxType,
+
+    /// Offset of the start of the box (i.e. its header) from the start of
+    /// the source.
+    pub offset: usize,
+
+    /// Size of the box, including its header.
+    pub size: usize,
+
+    /// Number of superboxes which enclose this box. The outermost superbox
+    /// has depth 0; its description box and child boxes have depth 1.
+    pub depth: usize,
+
+    /// Number of bytes from the start of the source that have been parsed so
+    /// far. For a superbox, this includes only its header.
+    ///
+    /// This value never decreases during a single parse, so it can be
+    /// compared with the length of the source to measure progress.
+    pub bytes_consumed: usize,
+
+    /// Number of boxes parsed so far, including this one.
+    pub boxes_parsed: usize,
+}
@@ -0,0 +1,117 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+/// Resource limits to be enforced while parsing a JUMBF superbox via
+/// [`SuperBox::from_source_with_options()`].
+///
+/// Services which parse JUMBF data from untrusted sources should set these
+/// limits so as to reject adversarial data structures designed to cause
+/// excessive allocation or processing time.
+///
+/// The default value retains all boxes and imposes no limits other than a
+/// maximum nesting depth of [`DEFAULT_MAX_DEPTH`](Self::DEFAULT_MAX_DEPTH).
+///
+/// ## Example
+///
+/// ```
+/// use jumbf::parser::ParseOptions;
+///
+/// let options = ParseOptions {
+///     max_depth: 8,
+///     max_label_len: 256,
+///     ..Default::default()
+/// };
+/// # let _ = options;
+/// ```
+///
+/// [`SuperBox::from_source_with_options()`]: crate::parser::SuperBox::from_source_with_options
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseOptions {
+    /// Maximum number of boxes (including superboxes, description boxes, and
+    /// data boxes) in the entire data structure.
+    pub max_boxes: usize,
+
+    /// Maximum number of child boxes (not including the description box)
+    /// within any single superbox.
+    pub max_children: usize,
+
+    /// Maximum length, in bytes, of any description box label.
+    pub max_label_len: usize,
+
+    /// Maximum nesting depth of superboxes. The outermost superbox has depth
+    /// 0, so a value of 0 permits no nested superboxes.
+    ///
+    /// Parsing does not recurse, but dropping the resulting [`SuperBox`]
+    /// does, so this defaults to [`DEFAULT_MAX_DEPTH`] rather than being
+    /// unlimited.
+    ///
+    /// [`SuperBox`]: crate::parser::SuperBox
+    /// [`DEFAULT_MAX_DEPTH`]: Self::DEFAULT_MAX_DEPTH
+    pub max_depth: usize,
+
+    /// Maximum declared size, in bytes, of any single box (including its
+    /// header).
+    ///
+    /// Note that a box whose declared size exceeds the available input is
+    /// always rejected with [`Error::Incomplete`].
+    ///
+    /// [`Error::Incomplete`]: crate::parser::Error::Incomplete
+    pub max_box_size: usize,
+
+    /// If `true`, free space (`free`) boxes are omitted from
+    /// [`SuperBox::child_boxes`]. They still count towards
+    /// [`max_boxes`](Self::max_boxes) and
+    /// [`max_box_size`](Self::max_box_size), but not towards
+    /// [`max_children`](Self::max_children).
+    ///
+    /// [`SuperBox::child_boxes`]: crate::parser::SuperBox::child_boxes
+    pub skip_free_boxes: bool,
+}
+
+impl ParseOptions {
+    /// Default value for [`max_depth`](Self::max_depth). This is far deeper
+    /// than any JUMBF data structure in practical use.
+    pub const DEFAULT_MAX_DEPTH: usize = 256;
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            max_boxes: usize::MAX,
+            max_children: usize::MAX,
+            max_label_len: usize::MAX,
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+            max_box_size: usize::MAX,
+            skip_free_boxes: false,
+        }
+    }
+}
+
+/// Identifies which [`ParseOptions`] limit was exceeded.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseLimit {
+    /// [`ParseOptions::max_boxes`] was exceeded.
+    Boxes,
+
+    /// [`ParseOptions::max_children`] was exceeded.
+    Children,
+
+    /// [`ParseOptions::max_label_len`] was exceeded.
+    LabelLength,
+
+    /// [`ParseOptions::max_depth`] was exceeded.
+    Depth,
+
+    /// [`ParseOptions::max_box_size`] was exceeded.
+    BoxSize,
+}
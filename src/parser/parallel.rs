@@ -0,0 +1,88 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use core::mem;
+
+use rayon::prelude::*;
+
+use crate::{
+    box_type::SUPER_BOX_TYPE,
+    parser::{ChildBox, DataBox, Error, ParseResult, SuperBox},
+};
+
+// Number of levels of nested superboxes whose children are parsed in
+// parallel.
+const PARALLEL_DEPTH: usize = 4;
+
+impl<'a> SuperBox<'a> {
+    /// Parse a byte-slice as a JUMBF superbox, and return a tuple of the
+    /// remainder of the input and the parsed super box. Children of this
+    /// superbox which are also superboxes will be parsed recursively without
+    /// limit.
+    ///
+    /// This produces the same result as [`from_slice()`], but sibling child
+    /// superboxes within the outermost few levels of nesting are parsed in
+    /// parallel on the [`rayon`] global thread pool. Any more deeply nested
+    /// superboxes are parsed serially within the task for their ancestor.
+    /// This is most useful for large JUMBF data structures with many sibling
+    /// superboxes, such as a C2PA manifest store with many ingredients.
+    ///
+    /// If more than one child box is malformed, the error for the first such
+    /// box (in the order the boxes appear) is returned, as with
+    /// [`from_slice()`].
+    ///
+    /// This function is available when the `parallel` feature is enabled.
+    ///
+    /// [`from_slice()`]: Self::from_slice
+    /// [`rayon`]: https://docs.rs/rayon
+    pub fn from_slice_parallel(i: &'a [u8]) -> ParseResult<'a, Self> {
+        let (i, data_box): (&'a [u8], DataBox<'a>) = DataBox::from_slice(i)?;
+        let sbox = Self::from_data_box_parallel(&data_box, PARALLEL_DEPTH)?;
+        Ok((i, sbox))
+    }
+
+    // Child superboxes are parsed in parallel for the outermost
+    // `parallel_depth` levels only. Each subtree below that is parsed by
+    // `from_data_box()`, which uses an explicit stack, so that the recursion
+    // here remains shallow however deeply the input is nested.
+    fn from_data_box_parallel(
+        data_box: &DataBox<'a>,
+        parallel_depth: usize,
+    ) -> Result<Self, Error> {
+        let (_, mut sbox) = Self::from_data_box_shallow(data_box)?;
+        let label = sbox.desc.label;
+
+        // Collect every result before looking for errors so that the reported
+        // error doesn't depend on which thread finished first.
+        let child_boxes: Vec<Result<ChildBox<'a>, Error>> = mem::take(&mut sbox.child_boxes)
+            .into_par_iter()
+            .map(|child| match child {
+                ChildBox::DataBox(d) if d.tbox == SUPER_BOX_TYPE => {
+                    let sbox = if parallel_depth > 1 {
+                        Self::from_data_box_parallel(&d, parallel_depth - 1)
+                    } else {
+                        Self::from_data_box(&d).map(|(_, sbox)| sbox)
+                    };
+
+                    Ok(ChildBox::SuperBox(
+                        sbox.map_err(|e| e.in_super_box(label, d.offset))?,
+                    ))
+                }
+                child => Ok(child),
+            })
+            .collect();
+
+        sbox.child_boxes = child_boxes.into_iter().collect::<Result<_, _>>()?;
+        Ok(sbox)
+    }
+}
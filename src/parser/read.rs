@@ -0,0 +1,311 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::{self, ErrorKind, Read};
+
+use crate::{
+    box_type::SUPER_BOX_TYPE,
+    parser::{
+        events::{parse_events, EventSink},
+        Error, LazySuperBox, SuperBox,
+    },
+};
+
+/// A `JumbfReader` reads a JUMBF superbox from any [`Read`] implementation and
+/// provides simple access to its contents by label.
+///
+/// This is intended for applications which only need to look up a few boxes
+/// and would rather not manage the lifetimes of the zero-copy [`SuperBox`]
+/// and [`DataBox`] types directly. The reader owns the buffer holding the
+/// superbox, but not a parsed tree; each accessor parses as much of that
+/// buffer as it needs.
+///
+/// By default, the buffer is a `Vec<u8>` filled by [`new()`]. An application
+/// which already holds the superbox in an owned buffer can instead use
+/// [`from_buffer()`] to avoid both a copy and the need to keep a separate
+/// borrow of that buffer alive. See [`from_buffer()`] for the trade-offs of
+/// each kind of buffer.
+///
+/// ## Example
+///
+/// ```
+/// # fn example() -> std::io::Result<()> {
+/// use std::io::Cursor;
+///
+/// use hex_literal::hex;
+/// use jumbf::parser::JumbfReader;
+///
+/// let jumbf = hex!(
+///     "0000004e" // box size
+///     "6a756d62" // box type = 'jumb'
+///         "00000019" // box size
+///         "6a756d64" // box type = 'jumd'
+///         "00000000000000000000000000000000" // UUID
+///         "00" // toggles
+///         // ---
+///         "0000002d" // box size
+///         "6a756d62" // box type = 'jumb'
+///             "0000001b" // box size
+///             "6a756d64" // box type = 'jumd'
+///             "00000000000000000000000000000000" // UUID
+///             "03" // toggles
+///             "6100" // label ("a")
+///             // ---
+///             "0000000a" // box size
+///             "61626364" // box type = 'abcd'
+///             "6869" // payload ("hi")
+/// );
+///
+/// let reader = JumbfReader::new(Cursor::new(jumbf))?;
+/// assert_eq!(reader.root().unwrap().child_boxes.len(), 1);
+/// assert_eq!(reader.payload("a").unwrap(), Some(&b"hi"[..]));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`DataBox`]: crate::parser::DataBox
+/// [`new()`]: Self::new
+/// [`from_buffer()`]: Self::from_buffer
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JumbfReader<B = Vec<u8>> {
+    jumbf: B,
+
+    // Length of the superbox at the start of `jumbf`.
+    len: usize,
+}
+
+impl JumbfReader {
+    /// Read a single JUMBF superbox from `reader`.
+    ///
+    /// Exactly one box is read, as described by its header. Returns an I/O
+    /// error of kind [`ErrorKind::InvalidData`] wrapping the parser's
+    /// [`Error`] if the box is not a valid superbox.
+    pub fn new<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut jumbf = vec![];
+        read_box(&mut reader, &mut jumbf)?;
+        let len = validate(&jumbf).map_err(parse_error)?;
+
+        Ok(Self { jumbf, len })
+    }
+}
+
+impl<B: AsRef<[u8]>> JumbfReader<B> {
+    /// Create a reader over the JUMBF superbox at the start of `buffer`,
+    /// taking ownership of `buffer` without copying it.
+    ///
+    /// Any data following the superbox is ignored, but is retained in the
+    /// buffer returned by [`into_inner()`].
+    ///
+    /// Will return an error if `buffer` does not begin with a valid
+    /// superbox.
+    ///
+    /// The buffer can be any type that implements `AsRef<[u8]>`. Whichever
+    /// is chosen, the [`SuperBox`] values returned by the reader borrow from
+    /// the reader, and so can not outlive it. The choice of buffer determines
+    /// who else may hold the bytes and where the reader may be used:
+    ///
+    /// * `Vec<u8>` or `Box<[u8]>`: The reader has sole ownership of the buffer,
+    ///   which is dropped with the reader or can be recovered with
+    ///   [`into_inner()`]. Cloning the reader copies the buffer.
+    ///
+    /// * `Arc<[u8]>`: The buffer is shared with its other owners, so it lives
+    ///   until the last of them (or the last reader) is dropped. Cloning the
+    ///   reader only increments the reference count, and the reader can be sent
+    ///   to and shared between threads. Converting a `Vec<u8>` with
+    ///   `Arc::from()` copies it once.
+    ///
+    /// * `Rc<[u8]>`: As with `Arc<[u8]>`, but the reference count is not
+    ///   atomic, so the reader can be neither sent to nor shared with another
+    ///   thread.
+    ///
+    /// * `&[u8]`: The reader borrows the buffer, and so can not outlive it.
+    ///   This is no different from parsing the slice directly with
+    ///   [`SuperBox::from_slice()`], apart from the accessors offered here.
+    ///
+    /// * `Cursor<Vec<u8>>` does not implement `AsRef<[u8]>`. Pass
+    ///   `cursor.into_inner()` instead, which gives up the cursor but not the
+    ///   buffer. The superbox must begin at the start of the buffer regardless
+    ///   of the cursor's position.
+    ///
+    /// The reader assumes that `buffer.as_ref()` returns the same bytes each
+    /// time it is called, as it does for all of the types above. If a custom
+    /// buffer type's contents change after the reader is created, the
+    /// reader's accessors will return an error rather than panic.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use hex_literal::hex;
+    /// use jumbf::parser::JumbfReader;
+    ///
+    /// let jumbf: Arc<[u8]> = Arc::new(hex!(
+    ///     "0000002d" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "0000001b" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "00000000000000000000000000000000" // UUID
+    ///         "03" // toggles
+    ///         "6100" // label ("a")
+    ///         // ---
+    ///         "0000000a" // box size
+    ///         "61626364" // box type = 'abcd'
+    ///         "6869" // payload ("hi")
+    /// ));
+    ///
+    /// let reader = JumbfReader::from_buffer(jumbf.clone()).unwrap();
+    /// assert_eq!(reader.root().unwrap().desc.label, Some("a"));
+    ///
+    /// // The reader shares the buffer rather than copying it.
+    /// assert_eq!(Arc::strong_count(&jumbf), 2);
+    /// ```
+    ///
+    /// [`into_inner()`]: Self::into_inner
+    pub fn from_buffer(buffer: B) -> Result<Self, Error> {
+        let len = validate(buffer.as_ref())?;
+        Ok(Self { jumbf: buffer, len })
+    }
+
+    /// Parse and return the superbox that was read.
+    ///
+    /// The reader does not retain a parsed tree, so the entire superbox is
+    /// parsed each time this is called. Prefer [`get()`] or [`payload()`] to
+    /// look up individual boxes.
+    ///
+    /// The superbox was verified when the reader was created, so this will
+    /// only return an error if the buffer's `AsRef<[u8]>` implementation no
+    /// longer returns the same bytes.
+    ///
+    /// [`get()`]: Self::get
+    /// [`payload()`]: Self::payload
+    pub fn root(&self) -> Result<SuperBox<'_>, Error> {
+        let (_, sbox) = SuperBox::from_slice(self.as_bytes())?;
+        Ok(sbox)
+    }
+
+    /// Find a descendant superbox by label, as described in
+    /// [`SuperBox::find_by_label()`], and parse it.
+    ///
+    /// Use `/` to separate the labels of nested superboxes. Returns `None` if
+    /// no unique matching superbox is found.
+    ///
+    /// The superboxes along `path` are located as described in
+    /// [`LazySuperBox::find_by_label()`], and only the matching superbox is
+    /// parsed in full. Will return an error if any of those can not be
+    /// parsed, as described in [`root()`].
+    ///
+    /// [`root()`]: Self::root
+    pub fn get(&self, path: &str) -> Result<Option<SuperBox<'_>>, Error> {
+        self.find(path, LazySuperBox::to_super_box)?.transpose()
+    }
+
+    /// Returns the payload of the first child box of the superbox found by
+    /// [`get()`], if that box is not itself a superbox. This matches
+    /// [`SuperBox::data_box()`].
+    ///
+    /// Returns `None` if no unique matching superbox is found or if its first
+    /// child box is a superbox. The matching superbox is located as described
+    /// in [`get()`], but is not itself parsed in full.
+    ///
+    /// [`get()`]: Self::get
+    pub fn payload(&self, path: &str) -> Result<Option<&[u8]>, Error> {
+        let payload = self.find(path, |sbox| {
+            sbox.child_boxes
+                .first()
+                .filter(|dbox| dbox.tbox != SUPER_BOX_TYPE)
+                .map(|dbox| dbox.data)
+        })?;
+
+        Ok(payload.flatten())
+    }
+
+    // Parse only the superboxes needed to locate `path`, then apply `f` to
+    // the matching superbox.
+    fn find<'s, T>(
+        &'s self,
+        path: &str,
+        f: impl FnOnce(&LazySuperBox<'s>) -> T,
+    ) -> Result<Option<T>, Error> {
+        let (_, root) = LazySuperBox::from_slice(self.as_bytes())?;
+        Ok(root.find_by_label(path)?.map(f))
+    }
+
+    /// Returns the superbox as it was read.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.jumbf.as_ref().get(..self.len).unwrap_or_default()
+    }
+
+    /// Consumes the reader, returning the buffer which holds the superbox.
+    pub fn into_inner(self) -> B {
+        self.jumbf
+    }
+}
+
+/// Read exactly one JUMBF box from `reader`, replacing the contents of `buf`
+/// with it.
+///
+/// Only the box header is interpreted here. If the header gives a size of
+/// zero, the box extends to the end of `reader`.
+pub(crate) fn read_box<'a, R: Read + ?Sized>(
+    reader: &mut R,
+    buf: &'a mut Vec<u8>,
+) -> io::Result<&'a [u8]> {
+    buf.clear();
+
+    let mut header = [0u8; 8];
+    reader.read_exact(&mut header)?;
+    buf.extend_from_slice(&header);
+
+    let (len, header_len) = match u32::from_be_bytes([header[0], header[1], header[2], header[3]]) {
+        0 => {
+            reader.read_to_end(buf)?;
+            return Ok(buf);
+        }
+        1 => {
+            let mut large_len = [0u8; 8];
+            reader.read_exact(&mut large_len)?;
+            buf.extend_from_slice(&large_len);
+            (u64::from_be_bytes(large_len), 16)
+        }
+        len => (len as u64, 8),
+    };
+
+    // Don't trust the header to size the buffer: a corrupt or hostile size
+    // should fail when the stream runs out, not when allocating.
+    let remaining = len.saturating_sub(header_len);
+    let read = reader.take(remaining).read_to_end(buf)?;
+
+    if (read as u64) < remaining {
+        return Err(ErrorKind::UnexpectedEof.into());
+    }
+
+    Ok(buf)
+}
+
+// Verify that `jumbf` begins with a valid superbox and return its length.
+// The boxes are walked as events rather than parsed into a tree, so that
+// validation costs no more than a stack of the enclosing superboxes however
+// the input is nested.
+fn validate(jumbf: &[u8]) -> Result<usize, Error> {
+    struct Validate;
+    impl EventSink<'_> for Validate {}
+
+    let (rem, _) = parse_events(jumbf, &mut Validate)?;
+    Ok(jumbf.len() - rem.len())
+}
+
+/// Convert a parser error into an I/O error.
+pub(crate) fn parse_error(e: Error) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, e)
+}
@@ -0,0 +1,241 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::{
+    any::Any,
+    fmt::{Debug, Formatter},
+};
+
+use crate::{
+    parser::{ChildBox, DataBox, Error, SuperBox},
+    BoxType, Uuid,
+};
+
+type DataBoxHandler =
+    Box<dyn for<'a> Fn(&DataBox<'a>) -> Result<Box<dyn Any + Send + Sync>, Error> + Send + Sync>;
+
+type SuperBoxHandler =
+    Box<dyn for<'a> Fn(&SuperBox<'a>) -> Result<Box<dyn Any + Send + Sync>, Error> + Send + Sync>;
+
+/// A set of application-provided handlers which decode box payloads into
+/// typed values.
+///
+/// Handlers are registered either by box type (for data boxes) or by content
+/// type UUID (for superboxes). [`decode()`] then walks a parsed superbox and
+/// invokes the matching handler for each box, producing a [`TypedPayload`]
+/// for each box that was handled. Boxes for which no handler is registered
+/// are skipped.
+///
+/// ## Example
+///
+/// ```
+/// use jumbf::{
+///     parser::{BoxRegistry, SuperBox},
+///     BoxType,
+/// };
+///
+/// struct CborLen(usize);
+///
+/// let mut registry = BoxRegistry::new();
+/// registry.register_box_type(BoxType::CBOR, |dbox| Ok(CborLen(dbox.data.len())));
+///
+/// let jumbf = include_bytes!("../tests/fixtures/C.c2pa");
+/// let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+///
+/// let lens: Vec<usize> = registry
+///     .decode(&sbox)
+///     .unwrap()
+///     .iter()
+///     .filter_map(|payload| payload.downcast_ref::<CborLen>())
+///     .map(|len| len.0)
+///     .collect();
+///
+/// assert!(lens.contains(&13726));
+/// ```
+///
+/// [`decode()`]: Self::decode()
+#[derive(Default)]
+pub struct BoxRegistry {
+    data_box_handlers: Vec<(BoxType, DataBoxHandler)>,
+    super_box_handlers: Vec<(Uuid, SuperBoxHandler)>,
+}
+
+impl BoxRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a handler for data boxes of type `tbox`.
+    ///
+    /// Replaces any handler previously registered for `tbox`.
+    pub fn register_box_type<T, F>(&mut self, tbox: BoxType, handler: F) -> &mut Self
+    where
+        T: Any + Send + Sync,
+        F: for<'a> Fn(&DataBox<'a>) -> Result<T, Error> + Send + Sync + 'static,
+    {
+        let handler: DataBoxHandler =
+            Box::new(move |dbox| Ok(Box::new(handler(dbox)?) as Box<dyn Any + Send + Sync>));
+
+        self.data_box_handlers.retain(|(t, _)| *t != tbox);
+        self.data_box_handlers.push((tbox, handler));
+        self
+    }
+
+    /// Register a handler for superboxes whose content type is `uuid`.
+    ///
+    /// Replaces any handler previously registered for `uuid`.
+    pub fn register_content_type<T, F>(&mut self, uuid: Uuid, handler: F) -> &mut Self
+    where
+        T: Any + Send + Sync,
+        F: for<'a> Fn(&SuperBox<'a>) -> Result<T, Error> + Send + Sync + 'static,
+    {
+        let handler: SuperBoxHandler =
+            Box::new(move |sbox| Ok(Box::new(handler(sbox)?) as Box<dyn Any + Send + Sync>));
+
+        self.super_box_handlers.retain(|(u, _)| *u != uuid);
+        self.super_box_handlers.push((uuid, handler));
+        self
+    }
+
+    /// Decode `dbox` with the handler registered for its box type.
+    ///
+    /// Returns `None` if no handler is registered for that box type.
+    pub fn decode_data_box(&self, dbox: &DataBox) -> Option<Result<TypedPayload, Error>> {
+        let (_, handler) = self
+            .data_box_handlers
+            .iter()
+            .find(|(tbox, _)| *tbox == dbox.tbox)?;
+
+        Some(handler(dbox).map(|value| TypedPayload {
+            tbox: dbox.tbox,
+            offset: dbox.offset,
+            value,
+        }))
+    }
+
+    /// Decode `sbox` with the handler registered for its content type. Child
+    /// boxes are not decoded.
+    ///
+    /// Returns `None` if no handler is registered for that content type.
+    pub fn decode_super_box(&self, sbox: &SuperBox) -> Option<Result<TypedPayload, Error>> {
+        let (_, handler) = self
+            .super_box_handlers
+            .iter()
+            .find(|(uuid, _)| *uuid == sbox.desc.uuid)?;
+
+        Some(handler(sbox).map(|value| TypedPayload {
+            tbox: BoxType::SUPER_BOX,
+            offset: sbox.offset,
+            value,
+        }))
+    }
+
+    /// Decode `sbox` and all of its descendants, in the order in which they
+    /// appear in the source.
+    ///
+    /// A superbox is decoded before its child boxes. Its child boxes are
+    /// decoded whether or not a handler was registered for the superbox
+    /// itself.
+    ///
+    /// Returns the first error reported by a handler, with the labels of
+    /// the enclosing superboxes attached.
+    pub fn decode(&self, sbox: &SuperBox) -> Result<Vec<TypedPayload>, Error> {
+        let mut payloads = vec![];
+        let mut path: Vec<Option<&str>> = vec![];
+        let mut stack = vec![Step::Enter(sbox)];
+
+        while let Some(step) = stack.pop() {
+            let (payload, offset) = match step {
+                Step::Enter(sbox) => {
+                    let payload = self.decode_super_box(sbox);
+
+                    stack.push(Step::Exit);
+                    stack.extend(sbox.child_boxes.iter().rev().map(|child| match child {
+                        ChildBox::SuperBox(sbox) => Step::Enter(sbox),
+                        ChildBox::DataBox(dbox) => Step::Data(dbox),
+                    }));
+
+                    (payload, sbox.offset)
+                }
+                Step::Data(dbox) => (self.decode_data_box(dbox), dbox.offset),
+                Step::Exit => {
+                    path.pop();
+                    continue;
+                }
+            };
+
+            if let Some(payload) = payload {
+                payloads.push(payload.map_err(|e| e.in_super_boxes(path.iter().copied(), offset))?);
+            }
+
+            if let Step::Enter(sbox) = step {
+                path.push(sbox.desc.label);
+            }
+        }
+
+        Ok(payloads)
+    }
+}
+
+enum Step<'s, 'a> {
+    Enter(&'s SuperBox<'a>),
+    Data(&'s DataBox<'a>),
+    Exit,
+}
+
+impl Debug for BoxRegistry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BoxRegistry")
+            .field(
+                "box_types",
+                &self
+                    .data_box_handlers
+                    .iter()
+                    .map(|(tbox, _)| tbox)
+                    .collect::<Vec<_>>(),
+            )
+            .field(
+                "content_types",
+                &self
+                    .super_box_handlers
+                    .iter()
+                    .map(|(uuid, _)| uuid)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// A typed value produced by a [`BoxRegistry`] handler.
+#[derive(Debug)]
+pub struct TypedPayload {
+    /// Type of the box that was decoded. This is [`BoxType::SUPER_BOX`] if
+    /// the value was produced by a content type handler.
+    pub tbox: BoxType,
+
+    /// Offset of the start of the box that was decoded from the start of the
+    /// source that was originally parsed.
+    pub offset: usize,
+
+    /// The value returned by the handler.
+    pub value: Box<dyn Any + Send + Sync>,
+}
+
+impl TypedPayload {
+    /// Returns the value returned by the handler if it is of type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.value.downcast_ref()
+    }
+}
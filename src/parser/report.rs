@@ -0,0 +1,89 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+/// Deviations from the JUMBF specification which were found by
+/// [`SuperBox::from_source_with_report()`] but did not prevent parsing.
+///
+/// [`SuperBox::from_source_with_report()`]: crate::parser::SuperBox::from_source_with_report
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ParseReport {
+    /// Warnings in the order they were found.
+    pub warnings: Vec<ParseWarning>,
+}
+
+impl ParseReport {
+    /// Returns `true` if no warnings were found.
+    pub fn is_clean(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    pub(crate) fn warn(&mut self, warning: ParseWarning) {
+        self.warnings.push(warning);
+    }
+}
+
+/// A single deviation from the JUMBF specification found while parsing.
+///
+/// All offsets are from the start of the source that was originally parsed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ParseWarning {
+    /// The box at `offset` does not declare its size using the shortest
+    /// possible encoding. (It uses an XLBox size which would fit in 32 bits,
+    /// or a size of 0 to mean "extends to end of input.")
+    NonMinimalLength {
+        /// Offset of the box.
+        offset: usize,
+    },
+
+    /// A superbox ended with `len` bytes, starting at `offset`, which were
+    /// too short to hold a box header. These bytes were ignored.
+    TrailingBytes {
+        /// Offset of the first ignored byte.
+        offset: usize,
+
+        /// Number of bytes ignored.
+        len: usize,
+    },
+
+    /// The label in the description box at `offset` is not terminated by a
+    /// NUL character. The label was taken to extend to the end of the
+    /// description box.
+    UnterminatedLabel {
+        /// Offset of the description box.
+        offset: usize,
+    },
+}
+
+impl Display for ParseWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::NonMinimalLength { offset } => {
+                write!(
+                    f,
+                    "Box at offset {offset} has a non-minimal length encoding"
+                )
+            }
+            Self::TrailingBytes { offset, len } => {
+                write!(f, "Ignored {len} trailing bytes at offset {offset}")
+            }
+            Self::UnterminatedLabel { offset } => write!(
+                f,
+                "Label in description box at offset {offset} is not NUL-terminated"
+            ),
+        }
+    }
+}
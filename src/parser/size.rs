@@ -0,0 +1,69 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use alloc::vec::Vec;
+
+use crate::BoxType;
+
+/// Describes how the bytes of a superbox are divided among its header,
+/// description box, and child boxes.
+///
+/// Returned by [`SuperBox::size_breakdown()`].
+///
+/// [`SuperBox::size_breakdown()`]: crate::parser::SuperBox::size_breakdown
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SizeBreakdown<'a> {
+    /// Size of the superbox's own box header (8 or 16 bytes).
+    pub header: usize,
+
+    /// Size of the superbox's description box, including its header.
+    pub description: usize,
+
+    /// Size of each child box, in order.
+    pub children: Vec<BoxSize<'a>>,
+}
+
+impl<'a> SizeBreakdown<'a> {
+    /// Returns the total size of the superbox, which is the sum of all of
+    /// the sizes in this breakdown.
+    pub fn total(&self) -> usize {
+        self.header + self.description + self.children.iter().map(|c| c.size).sum::<usize>()
+    }
+
+    /// Returns the number of bytes used for structure rather than content:
+    /// the superbox's header, its description box, and the header of each
+    /// child box.
+    pub fn overhead(&self) -> usize {
+        self.header + self.description + self.children.iter().map(|c| c.header).sum::<usize>()
+    }
+}
+
+/// The size of a single child box within a [`SizeBreakdown`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoxSize<'a> {
+    /// Box type.
+    pub tbox: BoxType,
+
+    /// Label of this box, if it is a labeled superbox.
+    pub label: Option<&'a str>,
+
+    /// Offset of the start of this box (i.e. its header) from the start of
+    /// the source that was originally parsed.
+    pub offset: usize,
+
+    /// Size of this box, including its header.
+    pub size: usize,
+
+    /// Size of this box's header (8 or 16 bytes).
+    pub header: usize,
+}
@@ -11,12 +11,29 @@
 // specific language governing permissions and limitations under
 // each license.
 
-use std::fmt::{Debug, Formatter};
+#[cfg(feature = "hash")]
+use alloc::{borrow::ToOwned, format};
+use alloc::{string::String, vec, vec::Vec};
+use core::{
+    fmt::{Debug, Formatter},
+    ops::Range,
+    ptr,
+    str::from_utf8,
+};
 
+#[cfg(feature = "std")]
+use crate::parser::read::{parse_error, read_box};
+#[cfg(feature = "hash")]
+use crate::parser::{HashCheck, IntegrityReport, SizeMismatch};
 use crate::{
-    box_type::SUPER_BOX_TYPE,
+    box_type::{DESCRIPTION_BOX_TYPE, SUPER_BOX_TYPE, XML_BOX_TYPE},
     debug::*,
-    parser::{DataBox, DescriptionBox, Error, ParseResult},
+    label,
+    parser::{
+        BoxSize, DataBox, DescriptionBox, DisplayTree, Error, JumbfUri, ParseLimit, ParseObserver,
+        ParseOptions, ParseProgress, ParseReport, ParseResult, ParseWarning, SizeBreakdown,
+    },
+    uri, BoxType, ContentType, Uuid,
 };
 
 /// A JUMBF superbox contains a description box and zero or more
@@ -36,6 +53,10 @@ pub struct SuperBox<'a> {
     /// It is preserved in case a future client wishes to re-serialize this
     /// box as is.
     pub original: &'a [u8],
+
+    /// Offset of the start of this box (i.e. its header) from the start of
+    /// the source that was originally parsed.
+    pub offset: usize,
 }
 
 impl<'a> SuperBox<'a> {
@@ -50,6 +71,32 @@ impl<'a> SuperBox<'a> {
         Self::from_slice_with_depth_limit(i, usize::MAX)
     }
 
+    /// Read a single JUMBF box from `reader` into `buf` and parse it as
+    /// a superbox.
+    ///
+    /// Exactly one box is read, as described by its header. Any previous
+    /// contents of `buf` are discarded. The returned object borrows from
+    /// `buf`, which allows the parser to remain zero-copy.
+    ///
+    /// Children of this superbox which are also superboxes will be parsed
+    /// recursively without limit.
+    ///
+    /// Parse errors are reported as I/O errors of kind
+    /// [`ErrorKind::InvalidData`] wrapping the parser's [`Error`].
+    ///
+    /// This function is available when the `std` feature is enabled.
+    ///
+    /// [`ErrorKind::InvalidData`]: std::io::ErrorKind::InvalidData
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: std::io::Read + ?Sized>(
+        reader: &mut R,
+        buf: &'a mut Vec<u8>,
+    ) -> std::io::Result<Self> {
+        let source = read_box(reader, buf)?;
+        let (_, sbox) = Self::from_slice(source).map_err(parse_error)?;
+        Ok(sbox)
+    }
+
     /// Parse a byte-slice as a JUMBF superbox, and return a tuple of the
     /// remainder of the input and the parsed super box. Children of this
     /// superbox which are also superboxes will be parsed recursively, to a
@@ -94,24 +141,55 @@ impl<'a> SuperBox<'a> {
         data_box: &DataBox<'a>,
         depth_limit: usize,
     ) -> ParseResult<'a, Self> {
+        // Nested superboxes are parsed with an explicit stack rather than by
+        // recursion so that adversarially deep input can not overflow the
+        // thread's stack. `current` is the superbox whose children are being
+        // examined; `stack` holds its ancestors.
+        let (i, sbox) = Self::from_data_box_shallow(data_box)?;
+        let mut current = PendingSuperBox::new(sbox, depth_limit);
+        let mut stack: Vec<PendingSuperBox<'a>> = vec![];
+
+        loop {
+            if let Some(index) = current.next_super_box() {
+                let child = match current.sbox.child_boxes.get(index) {
+                    Some(ChildBox::DataBox(d)) => match Self::from_data_box_shallow(d) {
+                        Ok((_, sbox)) => sbox,
+                        Err(e) => {
+                            let labels = stack
+                                .iter()
+                                .chain(core::iter::once(&current))
+                                .map(|pending| pending.sbox.desc.label);
+                            return Err(e.in_super_boxes(labels, d.offset));
+                        }
+                    },
+                    _ => continue,
+                };
+
+                let child = PendingSuperBox::new(child, current.depth_limit - 1);
+                stack.push(core::mem::replace(&mut current, child));
+            } else if let Some(mut parent) = stack.pop() {
+                if let Some(slot) = parent.sbox.child_boxes.get_mut(parent.next - 1) {
+                    *slot = ChildBox::SuperBox(current.sbox);
+                }
+                current = parent;
+            } else {
+                return Ok((i, current.sbox));
+            }
+        }
+    }
+
+    // Parse the description box and child boxes of `data_box` without
+    // parsing any child superboxes, which are left as `ChildBox::DataBox`.
+    pub(crate) fn from_data_box_shallow(data_box: &DataBox<'a>) -> ParseResult<'a, Self> {
         if data_box.tbox != SUPER_BOX_TYPE {
-            return Err(nom::Err::Error(Error::InvalidSuperBoxType(data_box.tbox)));
+            return Err(Error::InvalidSuperBoxType(data_box.tbox));
         }
 
-        let (i, desc) = DescriptionBox::from_slice(data_box.data)?;
+        let data_offset = data_box.data_offset();
+        let (i, desc) = DescriptionBox::from_slice_at(data_box.data, data_offset)?;
 
-        let (i, child_boxes) = boxes_from_slice(i)?;
-        let child_boxes = child_boxes
-            .into_iter()
-            .map(|d| {
-                if d.tbox == SUPER_BOX_TYPE && depth_limit > 0 {
-                    let (_, sbox) = Self::from_data_box_with_depth_limit(&d, depth_limit - 1)?;
-                    Ok(ChildBox::SuperBox(sbox))
-                } else {
-                    Ok(ChildBox::DataBox(d))
-                }
-            })
-            .collect::<Result<Vec<ChildBox<'a>>, Error>>()?;
+        let offset = data_offset + data_box.data.len() - i.len();
+        let (i, child_boxes) = boxes_from_slice(i, offset, desc.label, ChildBox::DataBox)?;
 
         Ok((
             i,
@@ -119,10 +197,189 @@ impl<'a> SuperBox<'a> {
                 desc,
                 child_boxes,
                 original: data_box.original,
+                offset: data_box.offset,
             },
         ))
     }
 
+    /// Parse a byte-slice as a JUMBF superbox, and return a tuple of the
+    /// remainder of the input and the parsed super box. Children of this
+    /// superbox which are also superboxes will be parsed recursively, subject
+    /// to the resource limits in `options`.
+    ///
+    /// Unlike [`from_slice_with_depth_limit()`], exceeding
+    /// [`ParseOptions::max_depth`] is treated as an error rather than causing
+    /// nested superboxes to be returned as [`DataBox`] structs.
+    ///
+    /// Will return [`Error::LimitExceeded`] if any limit is exceeded.
+    ///
+    /// The returned object uses zero-copy, and so has the same lifetime as the
+    /// input.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::parser::{Error, ParseLimit, ParseOptions, SuperBox};
+    ///
+    /// let jumbf = hex!(
+    ///     "0000002f" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000027" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "00000000000000000000000000000000" // UUID
+    ///         "03" // toggles
+    ///         "746573742e7375706572626f7800" // label
+    /// );
+    ///
+    /// let options = ParseOptions {
+    ///     max_label_len: 8,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     SuperBox::from_source_with_options(&jumbf, &options).unwrap_err(),
+    ///     Error::LimitExceeded {
+    ///         limit: ParseLimit::LabelLength,
+    ///         max: 8
+    ///     }
+    /// );
+    /// ```
+    ///
+    /// [`from_slice_with_depth_limit()`]: Self::from_slice_with_depth_limit
+    pub fn from_source_with_options(
+        source: &'a [u8],
+        options: &ParseOptions,
+    ) -> ParseResult<'a, Self> {
+        let (i, data_box): (&'a [u8], DataBox<'a>) = DataBox::from_slice(source)?;
+        let sbox = Self::from_data_box_with_options(&data_box, options, None, None)?;
+        Ok((i, sbox))
+    }
+
+    /// Parse a byte-slice as a JUMBF superbox, as described in
+    /// [`from_source_with_options()`], while reporting progress to
+    /// `observer`.
+    ///
+    /// `observer` is notified once for each box that is parsed. See
+    /// [`ParseObserver`] for an example.
+    ///
+    /// [`from_source_with_options()`]: Self::from_source_with_options
+    pub fn from_source_with_observer(
+        source: &'a [u8],
+        options: &ParseOptions,
+        observer: &mut impl ParseObserver,
+    ) -> ParseResult<'a, Self> {
+        let (i, data_box): (&'a [u8], DataBox<'a>) = DataBox::from_slice(source)?;
+        let sbox = Self::from_data_box_with_options(&data_box, options, None, Some(observer))?;
+        Ok((i, sbox))
+    }
+
+    /// Parse a byte-slice as a JUMBF superbox, and return a tuple of the
+    /// remainder of the input, the parsed super box, and a [`ParseReport`]
+    /// describing any deviations from the JUMBF specification which did not
+    /// prevent parsing.
+    ///
+    /// Some deviations which would cause [`from_slice()`] to fail are
+    /// tolerated here and reported instead. See [`ParseWarning`] for details.
+    ///
+    /// Superboxes may be nested at most [`ParseOptions::DEFAULT_MAX_DEPTH`]
+    /// levels deep. Will return [`Error::LimitExceeded`] if they are nested
+    /// more deeply.
+    ///
+    /// The returned object uses zero-copy, and so has the same lifetime as the
+    /// input.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::parser::{ParseWarning, SuperBox};
+    ///
+    /// let jumbf = hex!(
+    ///     "00000024" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000019" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "00000000000000000000000000000000" // UUID
+    ///         "00" // toggles
+    ///         // ----
+    ///         "000000" // trailing bytes
+    /// );
+    ///
+    /// let (_, sbox, report) = SuperBox::from_source_with_report(&jumbf).unwrap();
+    /// assert!(sbox.child_boxes.is_empty());
+    ///
+    /// assert_eq!(
+    ///     report.warnings,
+    ///     vec![ParseWarning::TrailingBytes { offset: 33, len: 3 }]
+    /// );
+    /// ```
+    ///
+    /// [`from_slice()`]: Self::from_slice
+    pub fn from_source_with_report(
+        source: &'a [u8],
+    ) -> Result<(&'a [u8], Self, ParseReport), Error> {
+        let (i, data_box): (&'a [u8], DataBox<'a>) = DataBox::from_slice(source)?;
+        let mut report = ParseReport::default();
+
+        let sbox = Self::from_data_box_with_options(
+            &data_box,
+            &ParseOptions::default(),
+            Some(&mut report),
+            None,
+        )?;
+
+        Ok((i, sbox, report))
+    }
+
+    // If `report` is provided, recoverable deviations from the specification
+    // are recorded there rather than treated as errors. If `observer` is
+    // provided, it is notified of each box as it is parsed.
+    //
+    // As in `from_data_box_with_depth_limit()`, nested superboxes are parsed
+    // with an explicit stack rather than by recursion. Child boxes are parsed
+    // one at a time so that limits are checked, and `observer` is notified,
+    // in the order in which boxes appear in the input.
+    fn from_data_box_with_options<'r>(
+        data_box: &DataBox<'a>,
+        options: &ParseOptions,
+        report: Option<&'r mut ParseReport>,
+        observer: Option<&'r mut dyn ParseObserver>,
+    ) -> Result<Self, Error> {
+        let mut parser = OptionsParser {
+            options,
+            box_count: 0,
+            report,
+            observer,
+        };
+
+        let mut current = parser.start(data_box, 0)?;
+        let mut stack: Vec<PendingSuperBox<'a>> = vec![];
+
+        loop {
+            match parser.next_super_box(&mut current, stack.len()) {
+                Ok(Some(child)) => stack.push(core::mem::replace(&mut current, child)),
+                Ok(None) => {
+                    if let Some(mut parent) = stack.pop() {
+                        parent
+                            .sbox
+                            .child_boxes
+                            .push(ChildBox::SuperBox(current.sbox));
+                        current = parent;
+                    } else {
+                        return Ok(current.sbox);
+                    }
+                }
+                Err(e) => {
+                    // `e` already carries the label and offset from
+                    // `current`, so only the ancestors need to be added.
+                    let labels = stack.iter().map(|pending| pending.sbox.desc.label);
+                    return Err(e.in_super_boxes(labels, current.sbox.offset));
+                }
+            }
+        }
+    }
+
     /// Find a child superbox of this superbox by label and verify that
     /// exactly one such child exists.
     ///
@@ -133,6 +390,22 @@ impl<'a> SuperBox<'a> {
     /// Will return `None` if no matching child superbox is found _or_ if
     /// more than one matching child superbox is found.
     pub fn find_by_label(&self, label: &str) -> Option<&Self> {
+        self.find_by_label_with(label, false)
+    }
+
+    /// Find a child superbox of this superbox by label, as described in
+    /// [`find_by_label()`], but regardless of whether the superboxes along
+    /// the path are flagged as requestable.
+    ///
+    /// A conforming reader should not locate a non-requestable superbox by
+    /// label; this method is intended for diagnostic tools.
+    ///
+    /// [`find_by_label()`]: Self::find_by_label()
+    pub fn find_any_by_label(&self, label: &str) -> Option<&Self> {
+        self.find_by_label_with(label, true)
+    }
+
+    fn find_by_label_with(&self, label: &str, include_non_requestable: bool) -> Option<&Self> {
         let (label, suffix) = match label.split_once('/') {
             Some((label, suffix)) => (label, Some(suffix)),
             None => (label, None),
@@ -144,7 +417,9 @@ impl<'a> SuperBox<'a> {
             .filter_map(|child_box| match child_box {
                 ChildBox::SuperBox(sbox) => {
                     if let Some(sbox_label) = sbox.desc.label {
-                        if sbox_label == label && sbox.desc.requestable {
+                        if label::matches(sbox_label, label)
+                            && (sbox.desc.requestable || include_non_requestable)
+                        {
                             Some(sbox)
                         } else {
                             None
@@ -160,7 +435,7 @@ impl<'a> SuperBox<'a> {
         if let Some(sbox) = matching_children.first() {
             if matching_children.len() == 1 {
                 if let Some(suffix) = suffix {
-                    return sbox.find_by_label(suffix);
+                    sbox.find_by_label_with(suffix, include_non_requestable)
                 } else {
                     Some(sbox)
                 }
@@ -172,6 +447,913 @@ impl<'a> SuperBox<'a> {
         }
     }
 
+    /// Find a superbox by the application-specific 32-bit ID in its
+    /// description box.
+    ///
+    /// This superbox and all of its descendants are searched, depth-first, and
+    /// the first matching superbox is returned. Child superboxes which were
+    /// not parsed because of a depth limit are not searched.
+    ///
+    /// Will return `None` if no superbox has this ID.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::parser::SuperBox;
+    ///
+    /// let jumbf = hex!(
+    ///     "00000046" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000019" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "00000000000000000000000000000000" // UUID
+    ///         "00" // toggles
+    ///         // ----
+    ///         "00000025" // box size
+    ///         "6a756d62" // box type = 'jumb'
+    ///             "0000001d" // box size
+    ///             "6a756d64" // box type = 'jumd'
+    ///             "00000000000000000000000000000000" // UUID
+    ///             "04" // toggles
+    ///             "0000002a" // ID
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    ///
+    /// let child = sbox.find_by_id(42).unwrap();
+    /// assert_eq!(child.offset, 33);
+    ///
+    /// assert_eq!(sbox.find_by_id(43), None);
+    /// ```
+    pub fn find_by_id(&self, id: u32) -> Option<&Self> {
+        SuperBoxes { stack: vec![self] }.find(|sbox| sbox.desc.id == Some(id))
+    }
+
+    /// Find all superboxes whose description box has the given content type
+    /// UUID.
+    ///
+    /// This superbox and all of its descendants are searched, and matching
+    /// superboxes are returned in depth-first order. Child superboxes which
+    /// were not parsed because of a depth limit are not searched.
+    ///
+    /// `uuid` may be given as a [`Uuid`], a [`ContentType`], or a `[u8; 16]`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::{parser::SuperBox, ContentType};
+    ///
+    /// let jumbf = hex!(
+    ///     "00000050" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000019" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "00000000000000000000000000000000" // UUID
+    ///         "00" // toggles
+    ///         // ----
+    ///         "0000002f" // box size
+    ///         "6a756d62" // box type = 'jumb'
+    ///             "0000001b" // box size
+    ///             "6a756d64" // box type = 'jumd'
+    ///             "6a736f6e00110010800000aa00389b71" // UUID (JSON)
+    ///             "02" // toggles
+    ///             "6100" // label ("a")
+    ///             // ----
+    ///             "0000000c" // box size
+    ///             "6a736f6e" // box type = 'json'
+    ///             "7b7d200a" // payload
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    ///
+    /// let json: Vec<_> = sbox.find_by_uuid(ContentType::Json).collect();
+    /// assert_eq!(json.len(), 1);
+    /// assert_eq!(json[0].desc.label, Some("a"));
+    /// ```
+    pub fn find_by_uuid<'s, U: Into<Uuid>>(
+        &'s self,
+        uuid: U,
+    ) -> impl Iterator<Item = &'s SuperBox<'a>> + 's {
+        let uuid = uuid.into();
+        SuperBoxes { stack: vec![self] }.filter(move |sbox| sbox.desc.uuid == uuid)
+    }
+
+    /// Find all requestable superboxes with the given label, wherever they
+    /// appear in the tree.
+    ///
+    /// Unlike [`find_by_label()`], which follows a path of labels through
+    /// direct children, this searches this superbox and all of its
+    /// descendants. Matching superboxes are returned in depth-first order.
+    /// Child superboxes which were not parsed because of a depth limit are
+    /// not searched.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use jumbf::parser::SuperBox;
+    ///
+    /// let jumbf = include_bytes!("../tests/fixtures/C.c2pa");
+    /// let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+    ///
+    /// let claims: Vec<_> = sbox.find_recursive_by_label("c2pa.claim").collect();
+    /// assert_eq!(claims.len(), 1);
+    /// ```
+    ///
+    /// [`find_by_label()`]: Self::find_by_label()
+    pub fn find_recursive_by_label<'s, 'l: 's>(
+        &'s self,
+        label: &'l str,
+    ) -> impl Iterator<Item = &'s SuperBox<'a>> + 's {
+        SuperBoxes { stack: vec![self] }.filter(move |sbox| {
+            sbox.desc.requestable
+                && sbox
+                    .desc
+                    .label
+                    .is_some_and(|sbox_label| label::matches(sbox_label, label))
+        })
+    }
+
+    /// Find all requestable superboxes whose labels end with `suffix`,
+    /// wherever they appear in the tree.
+    ///
+    /// The search proceeds as described in [`find_recursive_by_label()`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use jumbf::parser::SuperBox;
+    ///
+    /// let jumbf = include_bytes!("../tests/fixtures/C.c2pa");
+    /// let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+    ///
+    /// let hashes: Vec<_> = sbox
+    ///     .find_recursive_by_label_suffix(".hash.data")
+    ///     .filter_map(|sbox| sbox.desc.label)
+    ///     .collect();
+    ///
+    /// assert_eq!(hashes, ["c2pa.hash.data"]);
+    /// ```
+    ///
+    /// [`find_recursive_by_label()`]: Self::find_recursive_by_label()
+    pub fn find_recursive_by_label_suffix<'s, 'l: 's>(
+        &'s self,
+        suffix: &'l str,
+    ) -> impl Iterator<Item = &'s SuperBox<'a>> + 's {
+        SuperBoxes { stack: vec![self] }.filter(move |sbox| {
+            sbox.desc.requestable
+                && sbox
+                    .desc
+                    .label
+                    .is_some_and(|sbox_label| sbox_label.ends_with(suffix))
+        })
+    }
+
+    /// Find the superbox identified by a JUMBF request URI such as
+    /// `self#jumbf=c2pa/cb.adobe_1/c2pa.signature`. This superbox should be
+    /// the outermost superbox of the JUMBF data structure.
+    ///
+    /// This is a shorthand for [`JumbfUri::parse()`] followed by
+    /// [`JumbfUri::resolve()`]; see those functions for details.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::parser::SuperBox;
+    ///
+    /// let jumbf = hex!(
+    ///     "00000065" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "0000002f" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "00000000000000000000000000000000" // UUID
+    ///         "03" // toggles
+    ///         "746573742e7375706572626f785f64617461626f7800" // label
+    ///         // ------
+    ///         "0000002e" // box size
+    ///         "6a756d62" // box type = 'jumb'
+    ///             "00000026" // box size
+    ///             "6a756d64" // box type = 'jumd'
+    ///             "00000000000000000000000000000000" // UUID
+    ///             "03" // toggles
+    ///             "746573742e64617461626f7800" // label
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    /// let child = sbox.find_by_label("test.databox").unwrap();
+    ///
+    /// assert_eq!(sbox.resolve_uri("self#jumbf=test.databox").unwrap(), child);
+    ///
+    /// assert_eq!(
+    ///     sbox.resolve_uri("self#jumbf=/test.superbox_databox/test.databox")
+    ///         .unwrap(),
+    ///     child
+    /// );
+    /// ```
+    ///
+    /// [`JumbfUri::parse()`]: crate::uri::JumbfUri::parse
+    /// [`JumbfUri::resolve()`]: crate::uri::JumbfUri::resolve
+    pub fn resolve_uri(&self, uri: &str) -> Result<&Self, Error> {
+        JumbfUri::parse(uri)?.resolve(self)
+    }
+
+    /// Generate an absolute JUMBF request URI which identifies `target`
+    /// within this superbox, which should be the outermost superbox of the
+    /// JUMBF data structure.
+    ///
+    /// Returns `None` if `target` is not this superbox or one of its
+    /// descendants, if any superbox along the path to `target` (including
+    /// this one) is unlabeled or not requestable, or if the resulting URI
+    /// would not resolve back to `target` (for example, because a label is
+    /// shared by more than one sibling).
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::parser::SuperBox;
+    ///
+    /// let jumbf = hex!(
+    ///     "00000065" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "0000002f" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "00000000000000000000000000000000" // UUID
+    ///         "03" // toggles
+    ///         "746573742e7375706572626f785f64617461626f7800" // label
+    ///         // ------
+    ///         "0000002e" // box size
+    ///         "6a756d62" // box type = 'jumb'
+    ///             "00000026" // box size
+    ///             "6a756d64" // box type = 'jumd'
+    ///             "00000000000000000000000000000000" // UUID
+    ///             "03" // toggles
+    ///             "746573742e64617461626f7800" // label
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    /// let child = sbox.find_by_label("test.databox").unwrap();
+    ///
+    /// let uri = sbox.uri_of(child).unwrap();
+    /// assert_eq!(uri, "self#jumbf=/test.superbox_databox/test.databox");
+    /// assert_eq!(sbox.resolve_uri(&uri).unwrap(), child);
+    /// ```
+    pub fn uri_of(&self, target: &SuperBox) -> Option<String> {
+        let labels = self
+            .path_to(target)?
+            .into_iter()
+            .map(|sbox| sbox.desc.label.filter(|_| sbox.desc.requestable))
+            .collect::<Option<Vec<_>>>()?;
+
+        let uri = uri::self_uri(labels);
+        let resolved = JumbfUri::parse(&uri).ok()?.resolve(self).ok()?;
+
+        ptr::eq(resolved, target).then_some(uri)
+    }
+
+    // Returns the superboxes from `self` to `target` (inclusive), if
+    // `target` is `self` or one of its descendants.
+    fn path_to<'s>(&'s self, target: &SuperBox) -> Option<Vec<&'s SuperBox<'a>>> {
+        let mut path = vec![];
+        let mut stack = vec![(self, 0)];
+
+        while let Some((sbox, depth)) = stack.pop() {
+            path.truncate(depth);
+            path.push(sbox);
+
+            if ptr::eq(sbox, target) {
+                return Some(path);
+            }
+
+            stack.extend(
+                sbox.child_boxes
+                    .iter()
+                    .rev()
+                    .filter_map(|child| match child {
+                        ChildBox::SuperBox(sbox) => Some((sbox, depth + 1)),
+                        _ => None,
+                    }),
+            );
+        }
+
+        None
+    }
+
+    /// Returns the labels which are shared by more than one requestable
+    /// child superbox of this superbox, sorted and without repetition.
+    ///
+    /// [`find_by_label()`] will return `None` for any of these labels, so this
+    /// can be used to explain why a lookup failed. Only immediate children
+    /// are considered.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::parser::SuperBox;
+    ///
+    /// let jumbf = hex!(
+    ///     "00000067" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000019" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "00000000000000000000000000000000" // UUID
+    ///         "00" // toggles
+    ///         // ----
+    ///         "00000023" // box size
+    ///         "6a756d62" // box type = 'jumb'
+    ///             "0000001b" // box size
+    ///             "6a756d64" // box type = 'jumd'
+    ///             "00000000000000000000000000000000" // UUID
+    ///             "03" // toggles
+    ///             "6100" // label ("a")
+    ///         // ----
+    ///         "00000023" // box size
+    ///         "6a756d62" // box type = 'jumb'
+    ///             "0000001b" // box size
+    ///             "6a756d64" // box type = 'jumd'
+    ///             "00000000000000000000000000000000" // UUID
+    ///             "03" // toggles
+    ///             "6100" // label ("a")
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    ///
+    /// assert_eq!(sbox.find_by_label("a"), None);
+    /// assert_eq!(sbox.duplicate_labels(), vec!["a"]);
+    /// ```
+    ///
+    /// [`find_by_label()`]: Self::find_by_label
+    pub fn duplicate_labels(&self) -> Vec<&'a str> {
+        let mut labels: Vec<&'a str> = self
+            .child_boxes
+            .iter()
+            .filter_map(|child_box| match child_box {
+                ChildBox::SuperBox(sbox) if sbox.desc.requestable => sbox.desc.label,
+                _ => None,
+            })
+            .collect();
+
+        labels.sort_unstable();
+
+        let mut duplicates: Vec<&'a str> = labels
+            .windows(2)
+            .filter(|pair| pair[0] == pair[1])
+            .map(|pair| pair[0])
+            .collect();
+
+        duplicates.dedup();
+        duplicates
+    }
+
+    /// Returns the type of content in this superbox, as indicated by the UUID
+    /// in its description box.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::{parser::SuperBox, ContentType};
+    ///
+    /// let jumbf = hex!(
+    ///     "0000002c" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000019" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "6a736f6e00110010800000aa00389b71" // UUID (JSON)
+    ///         "00" // toggles
+    ///         // ----
+    ///         "0000000b" // box size
+    ///         "6a736f6e" // box type = 'json'
+    ///         "7b7d20" // payload
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    /// assert_eq!(sbox.content_type(), ContentType::Json);
+    /// ```
+    pub fn content_type(&self) -> ContentType {
+        self.desc.uuid.into()
+    }
+
+    /// Returns the range of bytes occupied by this superbox (including its
+    /// header) within the source that was originally parsed.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::parser::SuperBox;
+    ///
+    /// let jumbf = hex!(
+    ///     "00000065" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "0000002f" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "00000000000000000000000000000000" // UUID
+    ///         "03" // toggles
+    ///         "746573742e7375706572626f785f64617461626f7800" // label
+    ///         // ------
+    ///         "0000002e" // box size
+    ///         "6a756d62" // box type = 'jumb'
+    ///             "00000026" // box size
+    ///             "6a756d64" // box type = 'jumd'
+    ///             "00000000000000000000000000000000" // UUID
+    ///             "03" // toggles
+    ///             "746573742e64617461626f7800" // label
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    /// assert_eq!(sbox.range(), 0..101);
+    /// assert_eq!(sbox.desc.range(), 8..55);
+    ///
+    /// let child = sbox.find_by_label("test.databox").unwrap();
+    /// assert_eq!(child.range(), 55..101);
+    /// assert_eq!(child.desc.range(), 63..101);
+    /// ```
+    pub fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.original.len()
+    }
+
+    /// Write this superbox, including all of its descendants, exactly as it
+    /// was originally parsed (i.e. the contents of [`original`]) to
+    /// `to_stream`.
+    ///
+    /// This function is available when the `std` feature is enabled.
+    ///
+    /// [`original`]: Self::original
+    #[cfg(feature = "std")]
+    pub fn write_original(&self, to_stream: &mut impl std::io::Write) -> std::io::Result<()> {
+        to_stream.write_all(self.original)
+    }
+
+    /// Find a descendant superbox by label, as described in
+    /// [`find_by_label()`], and write the payload of its first child box
+    /// which is not itself a superbox to `to_stream`.
+    ///
+    /// Returns an error of kind [`ErrorKind::NotFound`] if no unique matching
+    /// superbox is found or if that superbox contains no such box.
+    ///
+    /// This function is available when the `std` feature is enabled.
+    ///
+    /// [`find_by_label()`]: Self::find_by_label
+    /// [`ErrorKind::NotFound`]: std::io::ErrorKind::NotFound
+    #[cfg(feature = "std")]
+    pub fn extract(&self, label: &str, to_stream: &mut impl std::io::Write) -> std::io::Result<()> {
+        use std::io::{Error, ErrorKind};
+
+        let sbox = self.find_by_label(label).ok_or_else(|| {
+            Error::new(
+                ErrorKind::NotFound,
+                format!("No unique superbox labeled {label:?}"),
+            )
+        })?;
+
+        let dbox = sbox
+            .child_boxes
+            .iter()
+            .find_map(|child| match child {
+                ChildBox::DataBox(dbox) => Some(dbox),
+                ChildBox::SuperBox(_) => None,
+            })
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::NotFound,
+                    format!("Superbox {label:?} contains no data box"),
+                )
+            })?;
+
+        dbox.copy_payload_to(to_stream)
+    }
+
+    /// Returns the size of this superbox in bytes, including its header and
+    /// all of its descendants.
+    pub fn total_size(&self) -> usize {
+        self.original.len()
+    }
+
+    /// Returns the size of this superbox's payload (its description box and
+    /// all child boxes) in bytes. This is [`total_size()`] less the size of
+    /// the superbox's own header.
+    ///
+    /// [`total_size()`]: Self::total_size
+    pub fn payload_size(&self) -> usize {
+        self.original.len() - self.header_size()
+    }
+
+    /// Returns a breakdown of this superbox's size into its header,
+    /// description box, and each child box.
+    ///
+    /// This is useful for tools which need to fit JUMBF data within a size
+    /// limit (such as the 64 KB limit on a JPEG APP11 segment) and need to
+    /// know where the bytes are going.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::{parser::SuperBox, BoxType};
+    ///
+    /// let jumbf = hex!(
+    ///     "0000003b" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000027" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "00000000000000000000000000000000" // UUID
+    ///         "03" // toggles
+    ///         "746573742e7375706572626f7800" // label
+    ///         // ----
+    ///         "0000000c" // box size
+    ///         "6a736f6e" // box type = 'json'
+    ///         "7b7d200a" // payload
+    /// );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    /// assert_eq!(sbox.total_size(), 59);
+    /// assert_eq!(sbox.payload_size(), 51);
+    ///
+    /// let sizes = sbox.size_breakdown();
+    /// assert_eq!(sizes.header, 8);
+    /// assert_eq!(sizes.description, 39);
+    /// assert_eq!(sizes.children[0].tbox, BoxType(*b"json"));
+    /// assert_eq!(sizes.children[0].size, 12);
+    /// assert_eq!(sizes.total(), 59);
+    /// assert_eq!(sizes.overhead(), 55);
+    /// ```
+    pub fn size_breakdown(&self) -> SizeBreakdown<'a> {
+        let children = self
+            .child_boxes
+            .iter()
+            .map(|child| match child {
+                ChildBox::SuperBox(sbox) => BoxSize {
+                    tbox: SUPER_BOX_TYPE,
+                    label: sbox.desc.label,
+                    offset: sbox.offset,
+                    size: sbox.original.len(),
+                    header: sbox.header_size(),
+                },
+                ChildBox::DataBox(dbox) => BoxSize {
+                    tbox: dbox.tbox,
+                    label: None,
+                    offset: dbox.offset,
+                    size: dbox.original.len(),
+                    header: dbox.original.len() - dbox.data.len(),
+                },
+            })
+            .collect();
+
+        SizeBreakdown {
+            header: self.header_size(),
+            description: self.desc.original.len(),
+            children,
+        }
+    }
+
+    // Size of this superbox's own box header.
+    fn header_size(&self) -> usize {
+        self.desc.offset.saturating_sub(self.offset)
+    }
+
+    /// Returns the offset of the start of this superbox (i.e. its header)
+    /// within `ancestor`, which may be the enclosing superbox or any superbox
+    /// which contains it.
+    ///
+    /// Both boxes must have been parsed from the same source. To find the
+    /// offset of this superbox within the source itself, use [`range()`].
+    ///
+    /// Will return `None` if this superbox is not contained within
+    /// `ancestor`.
+    ///
+    /// [`range()`]: Self::range
+    pub fn offset_within(&self, ancestor: &SuperBox) -> Option<usize> {
+        let ancestor = ancestor.range();
+        let range = self.range();
+
+        if range.start >= ancestor.start && range.end <= ancestor.end {
+            Some(range.start - ancestor.start)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the byte ranges, relative to the start of this superbox, of
+    /// the child superboxes identified by `labels`.
+    ///
+    /// Each label is resolved as described in [`find_by_label()`], so
+    /// hierarchical labels may be used. Labels which can not be resolved are
+    /// ignored.
+    ///
+    /// The returned ranges are sorted and any overlapping ranges (for example,
+    /// a superbox and one of its descendants) are merged. This makes them
+    /// suitable for excluding boxes (e.g. a C2PA signature box) from a hash
+    /// computation over the serialized JUMBF.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::parser::SuperBox;
+    ///
+    /// let jumbf = hex!(
+    ///     "00000078" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000019" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "00000000000000000000000000000000" // UUID
+    ///         "00" // toggles
+    ///         // ----
+    ///         "00000057" // box size
+    ///         "6a756d62" // box type = 'jumb'
+    ///             "00000028" // box size
+    ///             "6a756d64" // box type = 'jumd'
+    ///             "6332637300110010800000aa00389b71" // UUID
+    ///             "03" // toggles
+    ///             "633270612e7369676e617475726500" // label
+    ///             // ----
+    ///             "00000027" // box size
+    ///             "75756964" // box type = 'uuid'
+    ///             "6332637300110010800000aa00389b71" // UUID
+    ///             "7369676e61747572652e2e2e2e2e2e" // data
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    /// assert_eq!(sbox.exclusion_ranges(&["c2pa.signature"]), vec![33..120]);
+    /// ```
+    ///
+    /// [`find_by_label()`]: Self::find_by_label
+    pub fn exclusion_ranges(&self, labels: &[&str]) -> Vec<Range<usize>> {
+        let mut ranges: Vec<Range<usize>> = labels
+            .iter()
+            .filter_map(|label| self.find_by_label(label))
+            .filter_map(|sbox| {
+                sbox.offset_within(self)
+                    .map(|start| start..start + sbox.original.len())
+            })
+            .collect();
+
+        ranges.sort_by_key(|range| range.start);
+
+        let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start < last.end => {
+                    last.end = last.end.max(range.end);
+                }
+                _ => merged.push(range),
+            }
+        }
+
+        merged
+    }
+
+    /// Returns `true` if this superbox and all of its descendants are in the
+    /// canonical form produced by [`SuperBoxBuilder::set_canonical()`].
+    ///
+    /// Specifically, every box must declare its exact size using the
+    /// shortest possible encoding (i.e. no "extends to end of input" length
+    /// and no XLBox length where a 32-bit length would suffice) and no child
+    /// box may be a free space (`free`) box.
+    ///
+    /// Child superboxes which were not parsed because of a depth limit are
+    /// only checked for size encoding.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::parser::SuperBox;
+    ///
+    /// let jumbf = hex!(
+    ///     "00000031" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000019" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "00000000000000000000000000000000" // UUID
+    ///         "00" // toggles
+    ///         // ----
+    ///         "00000010" // box size
+    ///         "66726565" // box type = 'free'
+    ///         "0000000000000000" // padding
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    /// assert!(!sbox.is_canonical());
+    /// ```
+    ///
+    /// [`SuperBoxBuilder::set_canonical()`]: crate::builder::SuperBoxBuilder::set_canonical()
+    pub fn is_canonical(&self) -> bool {
+        // Child superboxes are checked as they are visited by `SuperBoxes`.
+        SuperBoxes { stack: vec![self] }.all(|sbox| {
+            has_minimal_header(sbox.original)
+                && has_minimal_header(sbox.desc.original)
+                && sbox
+                    .desc
+                    .private
+                    .iter()
+                    .all(|private| has_minimal_header(private.original))
+                && sbox.child_boxes.iter().all(|child| match child {
+                    ChildBox::SuperBox(_) => true,
+                    ChildBox::DataBox(dbox) => !dbox.is_free() && has_minimal_header(dbox.original),
+                })
+        })
+    }
+
+    /// Check the integrity of this superbox and all of its descendants.
+    ///
+    /// For each superbox whose description box declares a hash, the SHA-256
+    /// hash of the superbox's content (every byte following the description
+    /// box) is recomputed and compared with the declared hash. Each superbox
+    /// is also checked to see that its description box and child boxes
+    /// exactly fill the size declared in its header.
+    ///
+    /// Child superboxes which were not parsed because of a depth limit are
+    /// not checked.
+    ///
+    /// This function is available when the `hash` feature is enabled.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::parser::SuperBox;
+    ///
+    /// let jumbf = hex!(
+    ///     "00000041" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000039" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "00000000000000000000000000000000" // UUID
+    ///         "08" // toggles
+    ///         "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855" // hash
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    /// let report = sbox.integrity_report();
+    ///
+    /// assert!(report.is_valid());
+    /// assert_eq!(report.to_string(), "OK   (root) (offset 0): hash matches\n");
+    /// ```
+    #[cfg(feature = "hash")]
+    pub fn integrity_report(&self) -> IntegrityReport {
+        use sha2::{Digest, Sha256};
+
+        let mut report = IntegrityReport::default();
+        let mut stack = vec![(self, self.desc.label.unwrap_or("(root)").to_owned())];
+
+        while let Some((sbox, path)) = stack.pop() {
+            let desc_end = sbox.desc.offset + sbox.desc.original.len() - sbox.offset;
+
+            if let Some(hash) = sbox.desc.hash {
+                let content = sbox.original.get(desc_end..).unwrap_or_default();
+                report.hashes.push(HashCheck {
+                    path: path.clone(),
+                    offset: sbox.offset,
+                    expected: *hash,
+                    actual: Sha256::digest(content).into(),
+                });
+            }
+
+            let actual = desc_end
+                + sbox
+                    .child_boxes
+                    .iter()
+                    .map(|child| match child {
+                        ChildBox::SuperBox(sbox) => sbox.original.len(),
+                        ChildBox::DataBox(dbox) => dbox.original.len(),
+                    })
+                    .sum::<usize>();
+
+            if actual != sbox.original.len() {
+                report.size_mismatches.push(SizeMismatch {
+                    path: path.clone(),
+                    offset: sbox.offset,
+                    declared: sbox.original.len(),
+                    actual,
+                });
+            }
+
+            stack.extend(
+                sbox.child_boxes
+                    .iter()
+                    .rev()
+                    .filter_map(|child| match child {
+                        ChildBox::SuperBox(child) => Some((
+                            child,
+                            format!("{path}/{}", child.desc.label.unwrap_or("(unlabeled)")),
+                        )),
+                        ChildBox::DataBox(_) => None,
+                    }),
+            );
+        }
+
+        report
+    }
+
+    /// If this superbox has the XML content type ([`Uuid::XML`]), return
+    /// the content of its XML box (`xml `) as a string.
+    ///
+    /// The content is validated as UTF-8. If it begins with a UTF-8 byte
+    /// order mark, the byte order mark is removed.
+    ///
+    /// Will return an error if the superbox's content type is not XML, if the
+    /// first child box is not an XML box, or if the content is not valid
+    /// UTF-8.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::parser::SuperBox;
+    ///
+    /// let jumbf = hex!(
+    ///     "0000002f" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000019" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "786d6c2000110010800000aa00389b71" // UUID (XML)
+    ///         "00" // toggles
+    ///         // ----
+    ///         "0000000e" // box size
+    ///         "786d6c20" // box type = 'xml '
+    ///         "3c782f3e0a0a" // payload ("<x/>\n\n")
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    /// assert_eq!(sbox.xml_payload().unwrap(), "<x/>\n\n");
+    /// ```
+    pub fn xml_payload(&self) -> Result<&'a str, Error> {
+        if self.desc.uuid != Uuid::XML {
+            return Err(Error::InvalidContentType {
+                expected: Uuid::XML,
+                actual: self.desc.uuid,
+            });
+        }
+
+        let xml = match self.child_boxes.first() {
+            Some(ChildBox::DataBox(dbox)) if dbox.tbox == XML_BOX_TYPE => dbox.data,
+            _ => {
+                return Err(Error::MissingChildBox(XML_BOX_TYPE));
+            }
+        };
+
+        let xml = xml.strip_prefix(b"\xef\xbb\xbf").unwrap_or(xml);
+        from_utf8(xml).map_err(Error::Utf8Error)
+    }
+
+    /// If this superbox has the contiguous codestream content type
+    /// ([`Uuid::CODESTREAM`]), return the content of its contiguous
+    /// codestream box (`jp2c`).
+    ///
+    /// Will return an error if the superbox's content type is not
+    /// codestream or if the first child box is not a contiguous codestream
+    /// box. Use [`CodestreamFormat::detect()`] to identify the kind of
+    /// codestream.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::{codestream::CodestreamFormat, parser::SuperBox};
+    ///
+    /// let jumbf = hex!(
+    ///     "0000002d" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000019" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "6579d6fbdba2446bb2ac1b82feeb89d1" // UUID (codestream)
+    ///         "00" // toggles
+    ///         // ----
+    ///         "0000000c" // box size
+    ///         "6a703263" // box type = 'jp2c'
+    ///         "ffd8ffe0" // payload
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    /// let codestream = sbox.codestream_payload().unwrap();
+    ///
+    /// assert_eq!(codestream, [0xff, 0xd8, 0xff, 0xe0]);
+    /// assert_eq!(
+    ///     CodestreamFormat::detect(codestream),
+    ///     Some(CodestreamFormat::Jpeg)
+    /// );
+    /// ```
+    ///
+    /// [`CodestreamFormat::detect()`]: crate::codestream::CodestreamFormat::detect()
+    pub fn codestream_payload(&self) -> Result<&'a [u8], Error> {
+        if self.desc.uuid != Uuid::CODESTREAM {
+            return Err(Error::InvalidContentType {
+                expected: Uuid::CODESTREAM,
+                actual: self.desc.uuid,
+            });
+        }
+
+        match self.child_boxes.first() {
+            Some(ChildBox::DataBox(dbox)) if dbox.tbox == BoxType::CODESTREAM => Ok(dbox.data),
+            _ => Err(Error::MissingChildBox(BoxType::CODESTREAM)),
+        }
+    }
+
     /// If the first child box of this superbox is a data box, return it.
     /// Otherwise, return `None`.
     ///
@@ -185,32 +1367,598 @@ impl<'a> SuperBox<'a> {
                 _ => None,
             })
     }
+
+    /// Returns an iterator over the direct child data boxes of this superbox
+    /// which have the box type `tbox`, in the order they appear.
+    ///
+    /// Child superboxes are never returned, even if `tbox` is `jumb`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::{parser::SuperBox, BoxType};
+    ///
+    /// let jumbf = hex!(
+    ///     "0000003c" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000019" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "00000000000000000000000000000000" // UUID
+    ///         "00" // toggles
+    ///         // ----
+    ///         "00000009" // box size
+    ///         "63626f72" // box type = 'cbor'
+    ///         "01" // payload
+    ///         // ----
+    ///         "00000009" // box size
+    ///         "6a736f6e" // box type = 'json'
+    ///         "02" // payload
+    ///         // ----
+    ///         "00000009" // box size
+    ///         "63626f72" // box type = 'cbor'
+    ///         "03" // payload
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    ///
+    /// let cbor: Vec<_> = sbox
+    ///     .children_by_type(BoxType::CBOR)
+    ///     .map(|dbox| dbox.data)
+    ///     .collect();
+    ///
+    /// assert_eq!(cbor, [[1], [3]]);
+    /// ```
+    pub fn children_by_type<'s>(
+        &'s self,
+        tbox: BoxType,
+    ) -> impl Iterator<Item = &'s DataBox<'a>> + 's {
+        self.child_boxes
+            .iter()
+            .filter_map(move |child_box| match child_box {
+                ChildBox::DataBox(data_box) if data_box.tbox == tbox => Some(data_box),
+                _ => None,
+            })
+    }
+
+    /// Returns the first direct child of this superbox which is a JSON content
+    /// box (`json`), regardless of its position among the child boxes.
+    ///
+    /// Unlike [`data_box()`], this skips over any preceding boxes of other
+    /// types. This matches how C2PA readers typically locate an assertion's
+    /// payload.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::parser::SuperBox;
+    ///
+    /// let jumbf = hex!(
+    ///     "00000035" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000019" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "00000000000000000000000000000000" // UUID
+    ///         "00" // toggles
+    ///         // ----
+    ///         "0000000a" // box size
+    ///         "66726565" // box type = 'free'
+    ///         "0000" // padding
+    ///         // ----
+    ///         "0000000a" // box size
+    ///         "6a736f6e" // box type = 'json'
+    ///         "7b7d" // payload
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    ///
+    /// assert_eq!(sbox.data_box().unwrap().data, [0, 0]);
+    /// assert_eq!(sbox.json_box().unwrap().data, b"{}");
+    /// assert!(sbox.cbor_box().is_none());
+    /// ```
+    ///
+    /// [`data_box()`]: Self::data_box
+    pub fn json_box(&self) -> Option<&DataBox<'a>> {
+        self.children_by_type(BoxType::JSON).next()
+    }
+
+    /// Returns the first direct child of this superbox which is a CBOR content
+    /// box (`cbor`), regardless of its position among the child boxes.
+    ///
+    /// See [`json_box()`] for details.
+    ///
+    /// [`json_box()`]: Self::json_box
+    pub fn cbor_box(&self) -> Option<&DataBox<'a>> {
+        self.children_by_type(BoxType::CBOR).next()
+    }
+
+    /// Returns the first direct child of this superbox which is a UUID box
+    /// (`uuid`), regardless of its position among the child boxes.
+    ///
+    /// See [`json_box()`] for details.
+    ///
+    /// [`json_box()`]: Self::json_box
+    pub fn uuid_box(&self) -> Option<&DataBox<'a>> {
+        self.children_by_type(BoxType::UUID).next()
+    }
+
+    /// Returns an object which renders this superbox and its descendants as
+    /// an indented tree via [`Display`](std::fmt::Display).
+    ///
+    /// This is typically much easier to read than the [`Debug`] output for a
+    /// large JUMBF data structure.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::parser::SuperBox;
+    ///
+    /// let jumbf = hex!(
+    ///     "00000050" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000019" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "00000000000000000000000000000000" // UUID
+    ///         "00" // toggles
+    ///         // ----
+    ///         "0000002f" // box size
+    ///         "6a756d62" // box type = 'jumb'
+    ///             "0000001b" // box size
+    ///             "6a756d64" // box type = 'jumd'
+    ///             "6a736f6e00110010800000aa00389b71" // UUID (JSON)
+    ///             "02" // toggles
+    ///             "6100" // label ("a")
+    ///             // ----
+    ///             "0000000c" // box size
+    ///             "6a736f6e" // box type = 'json'
+    ///             "7b7d200a" // payload
+    ///     );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    ///
+    /// assert_eq!(
+    ///     sbox.display_tree().to_string(),
+    ///     concat!(
+    ///         "jumb (80 bytes, uuid 00000000-0000-0000-0000-000000000000)\n",
+    ///         "  jumb \"a\" (47 bytes, uuid 6a736f6e-0011-0010-8000-00aa00389b71)\n",
+    ///         "    json (12 bytes): 7b 7d 20 0a\n",
+    ///     )
+    /// );
+    /// ```
+    pub fn display_tree(&self) -> DisplayTree<'_, 'a> {
+        DisplayTree(self)
+    }
+
+    /// Returns `true` if this superbox has the same structure and content as
+    /// `other`.
+    ///
+    /// Unlike `==`, this ignores the [`original`] backing slices and source
+    /// offsets of each box, and therefore also ignores differences in how
+    /// box sizes were encoded. Superboxes are compared by UUID, label,
+    /// toggles, ID, hash, and private box; other boxes are compared by box
+    /// type and payload. Child boxes must appear in the same order.
+    ///
+    /// This is useful for comparing a freshly-built JUMBF data structure to
+    /// one that was parsed from elsewhere. See also [`StructuralEq`].
+    ///
+    /// [`original`]: Self::original
+    pub fn eq_structure(&self, other: &SuperBox) -> bool {
+        // Pairs of superboxes whose children remain to be compared.
+        let mut stack = vec![(self, other)];
+
+        while let Some((a, b)) = stack.pop() {
+            if !desc_eq_structure(&a.desc, &b.desc) || a.child_boxes.len() != b.child_boxes.len() {
+                return false;
+            }
+
+            for pair in a.child_boxes.iter().zip(b.child_boxes.iter()) {
+                match pair {
+                    (ChildBox::SuperBox(a), ChildBox::SuperBox(b)) => stack.push((a, b)),
+                    (ChildBox::DataBox(a), ChildBox::DataBox(b)) => {
+                        if !data_box_eq_structure(a, b) {
+                            return false;
+                        }
+                    }
+                    _ => return false,
+                }
+            }
+        }
+
+        true
+    }
 }
 
 impl<'a> Debug for SuperBox<'a> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
         f.debug_struct("SuperBox")
             .field("desc", &self.desc)
             .field("child_boxes", &self.child_boxes)
             .field("original", &DebugByteSlice(self.original))
+            .field("offset", &self.offset)
             .finish()
     }
 }
 
-// Parse boxes from slice until slice is empty.
-fn boxes_from_slice(i: &[u8]) -> ParseResult<Vec<DataBox<'_>>> {
-    let mut result: Vec<DataBox> = vec![];
+// Iterates over a superbox and all of its descendant superboxes in
+// depth-first order.
+struct SuperBoxes<'s, 'a> {
+    stack: Vec<&'s SuperBox<'a>>,
+}
+
+impl<'s, 'a> Iterator for SuperBoxes<'s, 'a> {
+    type Item = &'s SuperBox<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sbox = self.stack.pop()?;
+
+        self.stack.extend(
+            sbox.child_boxes
+                .iter()
+                .rev()
+                .filter_map(|child| match child {
+                    ChildBox::SuperBox(sbox) => Some(sbox),
+                    _ => None,
+                }),
+        );
+
+        Some(sbox)
+    }
+}
+
+// The default drop glue would recurse once per level of nesting, which could
+// overflow the stack for a deeply nested tree. Instead, detach descendant
+// superboxes and drop them one at a time.
+impl<'a> Drop for SuperBox<'a> {
+    fn drop(&mut self) {
+        let mut stack = core::mem::take(&mut self.child_boxes);
+
+        while let Some(child) = stack.pop() {
+            if let ChildBox::SuperBox(mut sbox) = child {
+                stack.append(&mut sbox.child_boxes);
+            }
+        }
+    }
+}
+
+// A superbox whose child superboxes are still being parsed by
+// `SuperBox::from_data_box_with_depth_limit()` or
+// `SuperBox::from_data_box_with_options()`.
+struct PendingSuperBox<'a> {
+    sbox: SuperBox<'a>,
+
+    // Index of the next child box to examine. The child at `next - 1` (if
+    // any) is the one currently being parsed.
+    next: usize,
+
+    // Remaining depth limit for this superbox's children.
+    depth_limit: usize,
+
+    // Input which has not yet been parsed into child boxes. Always empty for
+    // `from_data_box_with_depth_limit()`, which parses all child boxes of a
+    // superbox up front.
+    rest: &'a [u8],
+}
+
+impl<'a> PendingSuperBox<'a> {
+    fn new(sbox: SuperBox<'a>, depth_limit: usize) -> Self {
+        Self {
+            sbox,
+            next: 0,
+            depth_limit,
+            rest: &[],
+        }
+    }
+
+    // Returns the index of the next child box which should be parsed as a
+    // superbox, if any.
+    fn next_super_box(&mut self) -> Option<usize> {
+        if self.depth_limit == 0 {
+            return None;
+        }
+
+        let index = self
+            .sbox
+            .child_boxes
+            .iter()
+            .enumerate()
+            .skip(self.next)
+            .find(|(_, child)| matches!(child, ChildBox::DataBox(d) if d.tbox == SUPER_BOX_TYPE))?
+            .0;
+
+        self.next = index + 1;
+        Some(index)
+    }
+}
+
+// State shared by every level of `SuperBox::from_data_box_with_options()`.
+struct OptionsParser<'o, 'r> {
+    options: &'o ParseOptions,
+    box_count: usize,
+    report: Option<&'r mut ParseReport>,
+    observer: Option<&'r mut dyn ParseObserver>,
+}
+
+impl<'o, 'r> OptionsParser<'o, 'r> {
+    // Parse the description box of `data_box`, which is nested `depth`
+    // levels deep. Child boxes are left in `rest` to be parsed by
+    // `next_super_box()`.
+    fn start<'a>(
+        &mut self,
+        data_box: &DataBox<'a>,
+        depth: usize,
+    ) -> Result<PendingSuperBox<'a>, Error> {
+        if data_box.tbox != SUPER_BOX_TYPE {
+            return Err(Error::InvalidSuperBoxType(data_box.tbox));
+        }
+
+        check_limit(depth, self.options.max_depth, ParseLimit::Depth)?;
+        self.count_box(data_box.original)?;
+        self.notify(data_box, depth, data_box.data_offset());
+
+        let (rest, desc) = DescriptionBox::from_slice_with_report(
+            data_box.data,
+            data_box.data_offset(),
+            self.report.as_deref_mut(),
+        )?;
+        self.count_box(desc.original)?;
+
+        if let Some(observer) = self.observer.as_deref_mut() {
+            observer.box_parsed(&ParseProgress {
+                tbox: DESCRIPTION_BOX_TYPE,
+                offset: desc.offset,
+                size: desc.original.len(),
+                depth: depth + 1,
+                bytes_consumed: desc.offset + desc.original.len(),
+                boxes_parsed: self.box_count,
+            });
+        }
+
+        if let Some(report) = self.report.as_deref_mut() {
+            check_minimal_header(data_box.original, data_box.offset, report);
+            check_minimal_header(desc.original, desc.offset, report);
+            if let Some(private) = desc.private.as_ref() {
+                check_minimal_header(private.original, private.offset, report);
+            }
+        }
+
+        if let Some(label) = desc.label {
+            check_limit(
+                label.len(),
+                self.options.max_label_len,
+                ParseLimit::LabelLength,
+            )?;
+        }
+
+        Ok(PendingSuperBox {
+            sbox: SuperBox {
+                desc,
+                child_boxes: vec![],
+                original: data_box.original,
+                offset: data_box.offset,
+            },
+            next: 0,
+            depth_limit: 0,
+            rest,
+        })
+    }
+
+    // Parse child boxes of `pending`, which is nested `depth` levels deep,
+    // until a child superbox is found or the input is exhausted. Returns the
+    // child superbox, whose own child boxes have not yet been parsed.
+    //
+    // Errors are reported as occurring within `pending`, but not within any
+    // of its ancestors.
+    fn next_super_box<'a>(
+        &mut self,
+        pending: &mut PendingSuperBox<'a>,
+        depth: usize,
+    ) -> Result<Option<PendingSuperBox<'a>>, Error> {
+        let label = pending.sbox.desc.label;
+
+        while !pending.rest.is_empty() {
+            let offset = pending.sbox.offset + pending.sbox.original.len() - pending.rest.len();
+            let in_super_box = |e: Error| e.in_super_box(label, offset);
+
+            if pending.rest.len() < 8 {
+                if let Some(report) = self.report.as_deref_mut() {
+                    report.warn(ParseWarning::TrailingBytes {
+                        offset,
+                        len: pending.rest.len(),
+                    });
+                    break;
+                }
+            }
+
+            let (rest, d) = DataBox::from_slice_at(pending.rest, offset).map_err(in_super_box)?;
+            pending.rest = rest;
+
+            if self.options.skip_free_boxes && d.is_free() {
+                self.count_box(d.original).map_err(in_super_box)?;
+                self.notify(&d, depth + 1, d.offset + d.original.len());
+                continue;
+            }
+
+            check_limit(
+                pending.sbox.child_boxes.len() + 1,
+                self.options.max_children,
+                ParseLimit::Children,
+            )
+            .map_err(in_super_box)?;
+
+            if d.tbox == SUPER_BOX_TYPE {
+                return self.start(&d, depth + 1).map(Some).map_err(in_super_box);
+            }
+
+            self.count_box(d.original).map_err(in_super_box)?;
+            self.notify(&d, depth + 1, d.offset + d.original.len());
+            if let Some(report) = self.report.as_deref_mut() {
+                check_minimal_header(d.original, d.offset, report);
+            }
+            pending.sbox.child_boxes.push(ChildBox::DataBox(d));
+        }
+
+        Ok(None)
+    }
+
+    // Account for one more box and verify that it is within the size and
+    // count limits.
+    fn count_box(&mut self, original: &[u8]) -> Result<(), Error> {
+        self.box_count += 1;
+        check_limit(self.box_count, self.options.max_boxes, ParseLimit::Boxes)?;
+        check_limit(
+            original.len(),
+            self.options.max_box_size,
+            ParseLimit::BoxSize,
+        )
+    }
+
+    // Report a box which has just been parsed to the observer, if any.
+    fn notify(&mut self, dbox: &DataBox, depth: usize, bytes_consumed: usize) {
+        if let Some(observer) = self.observer.as_deref_mut() {
+            observer.box_parsed(&ParseProgress {
+                tbox: dbox.tbox,
+                offset: dbox.offset,
+                size: dbox.original.len(),
+                depth,
+                bytes_consumed,
+                boxes_parsed: self.box_count,
+            });
+        }
+    }
+}
+
+/// A wrapper which compares two [`SuperBox`]es via
+/// [`SuperBox::eq_structure()`] rather than `==`.
+///
+/// This is primarily intended for use with `assert_eq!` in tests, so that a
+/// mismatch still prints both trees.
+///
+/// ## Example
+///
+/// ```
+/// use hex_literal::hex;
+/// use jumbf::parser::{StructuralEq, SuperBox};
+///
+/// let short = hex!(
+///     "0000002d" // box size
+///     "6a756d62" // box type = 'jumb'
+///         "00000019" // box size
+///         "6a756d64" // box type = 'jumd'
+///         "00000000000000000000000000000000" // UUID
+///         "00" // toggles
+///         // ----
+///         "0000000c" // box size
+///         "61626364" // box type = 'abcd'
+///         "41424344" // payload
+/// );
+///
+/// let xlbox = hex!(
+///     "00000035" // box size
+///     "6a756d62" // box type = 'jumb'
+///         "00000019" // box size
+///         "6a756d64" // box type = 'jumd'
+///         "00000000000000000000000000000000" // UUID
+///         "00" // toggles
+///         // ----
+///         "00000001" // box size (XLBox)
+///         "61626364" // box type = 'abcd'
+///         "0000000000000014" // XLBox size
+///         "41424344" // payload
+/// );
+///
+/// let (_, a) = SuperBox::from_slice(&short).unwrap();
+/// let (_, b) = SuperBox::from_slice(&xlbox).unwrap();
+///
+/// assert_ne!(a, b);
+/// assert_eq!(StructuralEq(&a), StructuralEq(&b));
+/// ```
+#[derive(Clone, Copy)]
+pub struct StructuralEq<'r, 'a>(pub &'r SuperBox<'a>);
+
+impl<'r, 'a> PartialEq for StructuralEq<'r, 'a> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_structure(other.0)
+    }
+}
+
+impl<'r, 'a> Eq for StructuralEq<'r, 'a> {}
+
+impl<'r, 'a> Debug for StructuralEq<'r, 'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        self.0.fmt(f)
+    }
+}
+
+fn desc_eq_structure(a: &DescriptionBox, b: &DescriptionBox) -> bool {
+    a.uuid == b.uuid
+        && a.label == b.label
+        && a.requestable == b.requestable
+        && a.id == b.id
+        && a.hash == b.hash
+        && match (&a.private, &b.private) {
+            (Some(a), Some(b)) => data_box_eq_structure(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+}
+
+fn data_box_eq_structure(a: &DataBox, b: &DataBox) -> bool {
+    a.tbox == b.tbox && a.data == b.data
+}
+
+// Parse boxes from slice until slice is empty. The first box begins `offset`
+// bytes from the start of the source that was originally parsed. Errors are
+// reported as occurring within the superbox labeled `label`.
+//
+// Each box is converted via `f` as it is parsed so that callers which store
+// boxes in some other form need not allocate an intermediate `Vec`.
+pub(crate) fn boxes_from_slice<'a, T, F>(
+    i: &'a [u8],
+    offset: usize,
+    label: Option<&str>,
+    mut f: F,
+) -> ParseResult<'a, Vec<T>>
+where
+    F: FnMut(DataBox<'a>) -> T,
+{
+    let mut result: Vec<T> = vec![];
+    let mut offset = offset;
     let mut i = i;
 
     while !i.is_empty() {
-        let (x, data_box) = DataBox::from_slice(i)?;
+        let (x, data_box) =
+            DataBox::from_slice_at(i, offset).map_err(|e| e.in_super_box(label, offset))?;
+        offset += data_box.original.len();
         i = x;
-        result.push(data_box);
+        result.push(f(data_box));
     }
 
     Ok((i, result))
 }
 
+// Returns true if the box's length field gives its exact size using the
+// shortest possible encoding.
+fn has_minimal_header(original: &[u8]) -> bool {
+    let len = u32::try_from(original.len()).unwrap_or(1);
+    original.starts_with(&len.to_be_bytes())
+}
+
+fn check_minimal_header(original: &[u8], offset: usize, report: &mut ParseReport) {
+    if !has_minimal_header(original) {
+        report.warn(ParseWarning::NonMinimalLength { offset });
+    }
+}
+
+fn check_limit(value: usize, max: usize, limit: ParseLimit) -> Result<(), Error> {
+    if value > max {
+        Err(Error::LimitExceeded { limit, max })
+    } else {
+        Ok(())
+    }
+}
+
 /// This type represents a single box within a superbox,
 /// which may itself be a superbox or or a regular box.
 ///
@@ -246,4 +1994,15 @@ impl<'a> ChildBox<'a> {
             None
         }
     }
+
+    /// Write this box exactly as it was originally parsed to `to_stream`.
+    ///
+    /// This function is available when the `std` feature is enabled.
+    #[cfg(feature = "std")]
+    pub fn write_original(&self, to_stream: &mut impl std::io::Write) -> std::io::Result<()> {
+        match self {
+            Self::SuperBox(sbox) => sbox.write_original(to_stream),
+            Self::DataBox(dbox) => dbox.write_original(to_stream),
+        }
+    }
 }
@@ -0,0 +1,137 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use core::fmt::Debug;
+
+use crate::{
+    parser::{ChildBox, SuperBox},
+    Uuid,
+};
+
+/// A dyn-compatible view of a parsed superbox.
+///
+/// [`SuperBox`] carries the lifetime of the source it was parsed from. A
+/// `&dyn SuperBoxRef` erases that lifetime from the type, so that plugin
+/// systems and FFI layers can pass superboxes around (and navigate to their
+/// children) without naming the [`SuperBox`] type or its lifetime
+/// parameter.
+///
+/// ## Example
+///
+/// ```
+/// use jumbf::parser::{SuperBox, SuperBoxRef};
+///
+/// // A plugin which only knows about `SuperBoxRef`.
+/// fn signature_len(manifest: &dyn SuperBoxRef) -> Option<usize> {
+///     let signature = manifest.find_by_label("c2pa.signature")?;
+///     signature.payload().map(|payload| payload.len())
+/// }
+///
+/// let jumbf = include_bytes!("../tests/fixtures/C.c2pa");
+/// let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+///
+/// let manifest = sbox.child(0).unwrap();
+/// assert_eq!(
+///     manifest.label(),
+///     Some("contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9")
+/// );
+/// assert_eq!(signature_len(manifest), Some(13726));
+/// ```
+pub trait SuperBoxRef: Debug + Send + Sync {
+    /// Returns the content type of this superbox, as declared in its
+    /// description box.
+    fn uuid(&self) -> Uuid;
+
+    /// Returns the label of this superbox, if any.
+    fn label(&self) -> Option<&str>;
+
+    /// Returns `true` if this superbox is requestable.
+    fn is_requestable(&self) -> bool;
+
+    /// Returns the ID of this superbox, if any.
+    fn id(&self) -> Option<u32>;
+
+    /// Returns the payload of this superbox's first child box, if that box is
+    /// not itself a superbox. This matches [`SuperBox::data_box()`].
+    fn payload(&self) -> Option<&[u8]>;
+
+    /// Returns the original bytes of this superbox, including its header.
+    fn original(&self) -> &[u8];
+
+    /// Returns the offset of the start of this superbox from the start of
+    /// the source that was originally parsed.
+    fn offset(&self) -> usize;
+
+    /// Returns the number of child superboxes of this superbox. Child boxes
+    /// which are not superboxes are not counted.
+    fn child_count(&self) -> usize;
+
+    /// Returns the child superbox at position `index` among this superbox's
+    /// child superboxes, if any.
+    fn child(&self, index: usize) -> Option<&dyn SuperBoxRef>;
+
+    /// Find a descendant superbox by label, as described in
+    /// [`SuperBox::find_by_label()`].
+    fn find_by_label(&self, label: &str) -> Option<&dyn SuperBoxRef>;
+}
+
+impl<'a> SuperBoxRef for SuperBox<'a> {
+    fn uuid(&self) -> Uuid {
+        self.desc.uuid
+    }
+
+    fn label(&self) -> Option<&str> {
+        self.desc.label
+    }
+
+    fn is_requestable(&self) -> bool {
+        self.desc.requestable
+    }
+
+    fn id(&self) -> Option<u32> {
+        self.desc.id
+    }
+
+    fn payload(&self) -> Option<&[u8]> {
+        self.data_box().map(|dbox| dbox.data)
+    }
+
+    fn original(&self) -> &[u8] {
+        self.original
+    }
+
+    fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn child_count(&self) -> usize {
+        child_super_boxes(self).count()
+    }
+
+    fn child(&self, index: usize) -> Option<&dyn SuperBoxRef> {
+        child_super_boxes(self)
+            .nth(index)
+            .map(|sbox| sbox as &dyn SuperBoxRef)
+    }
+
+    fn find_by_label(&self, label: &str) -> Option<&dyn SuperBoxRef> {
+        SuperBox::find_by_label(self, label).map(|sbox| sbox as &dyn SuperBoxRef)
+    }
+}
+
+fn child_super_boxes<'s, 'a>(sbox: &'s SuperBox<'a>) -> impl Iterator<Item = &'s SuperBox<'a>> {
+    sbox.child_boxes.iter().filter_map(|child| match child {
+        ChildBox::SuperBox(sbox) => Some(sbox),
+        ChildBox::DataBox(_) => None,
+    })
+}
@@ -0,0 +1,88 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use core::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use crate::Uuid;
+
+/// Prefix of a UUID URN (RFC 4122).
+const URN_PREFIX: &str = "urn:uuid:";
+
+/// A superbox label which is made unique by a UUID URN, such as
+/// `contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9`.
+///
+/// C2PA uses labels of this form for manifests. Such a label can be written
+/// via [`SuperBoxBuilder::set_label_with_urn()`].
+///
+/// ## Example
+///
+/// ```
+/// use jumbf::parser::UuidLabel;
+///
+/// let label =
+///     UuidLabel::parse("contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9").unwrap();
+///
+/// assert_eq!(label.prefix, "contentauth");
+/// assert_eq!(label.urn, "urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9");
+/// assert_eq!(
+///     label.uuid.to_string(),
+///     "021b555e-5e02-4074-b444-43d7919d89b9"
+/// );
+/// ```
+///
+/// [`SuperBoxBuilder::set_label_with_urn()`]: crate::builder::SuperBoxBuilder::set_label_with_urn()
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UuidLabel<'l> {
+    /// The portion of the label before the URN, without the `:` separator.
+    /// Empty if the label consists of only the URN.
+    pub prefix: &'l str,
+
+    /// The URN (i.e. `urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9`).
+    pub urn: &'l str,
+
+    /// The UUID contained in the URN.
+    pub uuid: Uuid,
+}
+
+impl<'l> UuidLabel<'l> {
+    /// Split a label of the form `<prefix>:urn:uuid:<uuid>` or
+    /// `urn:uuid:<uuid>` into its components.
+    ///
+    /// Will return `None` if `label` does not end with a UUID URN in the
+    /// canonical hyphenated form.
+    pub fn parse(label: &'l str) -> Option<Self> {
+        let urn_start = label.len().checked_sub(URN_PREFIX.len() + 36)?;
+        let (prefix, urn) = (label.get(..urn_start)?, label.get(urn_start..)?);
+
+        let prefix = match prefix {
+            "" => "",
+            prefix => prefix.strip_suffix(':')?,
+        };
+
+        let uuid = Uuid::from_str(urn.strip_prefix(URN_PREFIX)?).ok()?;
+
+        Some(Self { prefix, urn, uuid })
+    }
+}
+
+impl<'l> Display for UuidLabel<'l> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        if !self.prefix.is_empty() {
+            write!(f, "{}:", self.prefix)?;
+        }
+        f.write_str(self.urn)
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Inventory of the boxes in a JUMBF data structure.
+//!
+//! [`scan()`] reads only the header of each box (and the description box of
+//! each superbox, to find its label). It never copies box payloads or builds
+//! a tree of boxes, which makes it suitable for tools that need to know which
+//! boxes exist and how large they are without otherwise examining them.
+
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    box_type::SUPER_BOX_TYPE,
+    parser::{DataBox, DescriptionBox, Error},
+    BoxType,
+};
+
+/// Summarizes a single box found by [`scan()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoxSummary<'a> {
+    /// Box type.
+    pub tbox: BoxType,
+
+    /// Offset of the start of this box (i.e. its header) from the start of
+    /// the source that was scanned.
+    pub offset: usize,
+
+    /// Size of this box, including its header.
+    pub size: usize,
+
+    /// Nesting depth of this box. Boxes at the top level of the source have
+    /// depth 0, their children have depth 1, and so on.
+    pub depth: usize,
+
+    /// Label of this superbox, if it is a superbox and has a label.
+    pub label: Option<&'a str>,
+}
+
+/// Scan a byte-slice containing one or more JUMBF boxes and summarize every
+/// box found, in the order in which the boxes appear.
+///
+/// Every box of type `jumb` is treated as a superbox: its description box is
+/// read for the label, but is not itself listed, and its child boxes are
+/// scanned in turn. All other boxes are listed without examining their
+/// contents.
+///
+/// ## Example
+///
+/// ```
+/// use hex_literal::hex;
+/// use jumbf::{scan::scan, BoxType};
+///
+/// let jumbf = hex!(
+///     "0000003b" // box size
+///     "6a756d62" // box type = 'jumb'
+///         "00000027" // box size
+///         "6a756d64" // box type = 'jumd'
+///         "00000000000000000000000000000000" // UUID
+///         "03" // toggles
+///         "746573742e7375706572626f7800" // label
+///         // ----
+///         "0000000c" // box size
+///         "6a736f6e" // box type = 'json'
+///         "7b7d200a" // payload
+/// );
+///
+/// let boxes = scan(&jumbf).unwrap();
+/// assert_eq!(boxes.len(), 2);
+///
+/// assert_eq!(boxes[0].tbox, BoxType(*b"jumb"));
+/// assert_eq!(boxes[0].label, Some("test.superbox"));
+///
+/// assert_eq!(boxes[1].tbox, BoxType(*b"json"));
+/// assert_eq!(boxes[1].offset, 47);
+/// assert_eq!(boxes[1].size, 12);
+/// assert_eq!(boxes[1].depth, 1);
+/// ```
+pub fn scan(source: &[u8]) -> Result<Vec<BoxSummary<'_>>, Error> {
+    let mut boxes = vec![];
+
+    // The top-level sequence of boxes, followed by the child boxes of each
+    // enclosing superbox. Nested superboxes are scanned via this explicit
+    // stack rather than by recursion so that deeply nested input can not
+    // overflow the thread's stack.
+    let mut stack = vec![Siblings {
+        rest: source,
+        offset: 0,
+        label: None,
+    }];
+
+    loop {
+        let depth = stack.len().saturating_sub(1);
+        let Some(siblings) = stack.last_mut() else {
+            break;
+        };
+
+        if siblings.rest.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        let offset = siblings.offset;
+
+        match scan_box(siblings, depth, &mut boxes) {
+            Ok(Some(children)) => stack.push(children),
+            Ok(None) => (),
+            Err(e) if depth == 0 => return Err(e),
+            Err(e) => {
+                // Errors are reported as occurring within every enclosing
+                // superbox, but not within the top-level sequence.
+                let labels = stack.iter().skip(1).map(|siblings| siblings.label);
+                return Err(e.in_super_boxes(labels, offset));
+            }
+        }
+    }
+
+    Ok(boxes)
+}
+
+// A sequence of sibling boxes which remain to be scanned.
+struct Siblings<'a> {
+    rest: &'a [u8],
+
+    // Offset of the start of `rest` from the start of the source.
+    offset: usize,
+
+    // Label of the enclosing superbox, if any.
+    label: Option<&'a str>,
+}
+
+// Scan the next box from `siblings`, which are nested `depth` levels deep.
+// If it is a superbox, returns its child boxes, which remain to be scanned.
+fn scan_box<'a>(
+    siblings: &mut Siblings<'a>,
+    depth: usize,
+    boxes: &mut Vec<BoxSummary<'a>>,
+) -> Result<Option<Siblings<'a>>, Error> {
+    let (rem, data_box) = DataBox::from_slice_at(siblings.rest, siblings.offset)?;
+    siblings.rest = rem;
+    siblings.offset += data_box.original.len();
+
+    if data_box.tbox != SUPER_BOX_TYPE {
+        boxes.push(summary(&data_box, depth, None));
+        return Ok(None);
+    }
+
+    let data_offset = data_box.data_offset();
+    let (i, desc) = DescriptionBox::from_slice_at(data_box.data, data_offset)?;
+
+    boxes.push(summary(&data_box, depth, desc.label));
+
+    Ok(Some(Siblings {
+        rest: i,
+        offset: data_offset + data_box.data.len() - i.len(),
+        label: desc.label,
+    }))
+}
+
+fn summary<'a>(data_box: &DataBox<'a>, depth: usize, label: Option<&'a str>) -> BoxSummary<'a> {
+    BoxSummary {
+        tbox: data_box.tbox,
+        offset: data_box.offset,
+        size: data_box.original.len(),
+        depth,
+        label,
+    }
+}
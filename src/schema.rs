@@ -0,0 +1,435 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Structural validation of parsed JUMBF trees.
+//!
+//! A [`Schema`] declares the expected shape of a superbox: its content type,
+//! the type of its payload, and which child superboxes it must (or may)
+//! contain. [`Schema::validate()`] checks a parsed [`SuperBox`] against the
+//! schema and returns a [`Violation`] for each way in which it differs.
+//!
+//! This module is available when the `parser` feature is enabled.
+//!
+//! ## Example
+//!
+//! A (partial) description of the C2PA manifest store format:
+//!
+//! ```
+//! use jumbf::{
+//!     c2pa::{MANIFEST_STORE_TYPE, MANIFEST_TYPE},
+//!     parser::SuperBox,
+//!     schema::{Cardinality, Schema},
+//!     BoxType, Uuid,
+//! };
+//!
+//! let signature = Schema::new()
+//!     .content_type(Uuid(
+//!         *b"c2cs\x00\x11\x00\x10\x80\x00\x00\xaa\x00\x38\x9b\x71",
+//!     ))
+//!     .payload(BoxType::CBOR);
+//!
+//! let manifest = Schema::new()
+//!     .child("c2pa.assertions", Cardinality::ONE, Schema::new())
+//!     .child("c2pa.signature", Cardinality::ONE, signature);
+//!
+//! let store = Schema::new()
+//!     .content_type(MANIFEST_STORE_TYPE)
+//!     .children_of_type(MANIFEST_TYPE, Cardinality::ONE_OR_MORE, manifest)
+//!     .deny_other_children();
+//!
+//! let jumbf = include_bytes!("tests/fixtures/C.c2pa");
+//! let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+//!
+//! assert!(store.validate(&sbox).is_empty());
+//! ```
+
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::{Display, Formatter};
+
+use crate::{
+    parser::{ChildBox, SuperBox},
+    BoxType, Uuid,
+};
+
+/// The expected shape of a superbox.
+///
+/// A newly-created schema accepts any superbox. Each builder method adds a
+/// constraint.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Schema {
+    content_type: Option<Uuid>,
+    payload: Option<BoxType>,
+    children: Vec<ChildRule>,
+    deny_other_children: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct ChildRule {
+    selector: ChildSelector,
+    cardinality: Cardinality,
+    schema: Schema,
+}
+
+impl Schema {
+    /// Create a schema which accepts any superbox.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require the superbox to have the content type `uuid`.
+    pub fn content_type(mut self, uuid: Uuid) -> Self {
+        self.content_type = Some(uuid);
+        self
+    }
+
+    /// Require the first child box of the superbox to be a data box of type
+    /// `tbox`.
+    pub fn payload(mut self, tbox: BoxType) -> Self {
+        self.payload = Some(tbox);
+        self
+    }
+
+    /// Require the number of child superboxes labeled `label` to be within
+    /// `cardinality`, and require each of them to match `schema`.
+    pub fn child<S: AsRef<str>>(
+        mut self,
+        label: S,
+        cardinality: Cardinality,
+        schema: Schema,
+    ) -> Self {
+        self.children.push(ChildRule {
+            selector: ChildSelector::Label(label.as_ref().to_owned()),
+            cardinality,
+            schema,
+        });
+        self
+    }
+
+    /// Require the number of child superboxes with content type `uuid` to be
+    /// within `cardinality`, and require each of them to match `schema`.
+    ///
+    /// This is useful for children whose labels are not known in advance,
+    /// such as C2PA manifests.
+    pub fn children_of_type(
+        mut self,
+        uuid: Uuid,
+        cardinality: Cardinality,
+        schema: Schema,
+    ) -> Self {
+        self.children.push(ChildRule {
+            selector: ChildSelector::ContentType(uuid),
+            cardinality,
+            schema,
+        });
+        self
+    }
+
+    /// Report any child superbox which is not matched by a rule added via
+    /// [`child()`] or [`children_of_type()`].
+    ///
+    /// Child data boxes are not affected by this setting.
+    ///
+    /// [`child()`]: Self::child()
+    /// [`children_of_type()`]: Self::children_of_type()
+    pub fn deny_other_children(mut self) -> Self {
+        self.deny_other_children = true;
+        self
+    }
+
+    /// Check `sbox` against this schema.
+    ///
+    /// Returns every violation that was found, in the order in which the
+    /// affected superboxes appear in the source. An empty result means that
+    /// `sbox` matches the schema.
+    pub fn validate(&self, sbox: &SuperBox) -> Vec<Violation> {
+        let mut violations = vec![];
+        let mut path = vec![sbox.desc.label.map(ToOwned::to_owned)];
+        self.validate_into(sbox, &mut path, &mut violations);
+        violations
+    }
+
+    // Recursion is bounded by the depth of the schema, not of the input.
+    fn validate_into(
+        &self,
+        sbox: &SuperBox,
+        path: &mut Vec<Option<String>>,
+        violations: &mut Vec<Violation>,
+    ) {
+        let mut report = |kind| {
+            violations.push(Violation {
+                path: path.clone(),
+                offset: sbox.offset,
+                kind,
+            })
+        };
+
+        if let Some(expected) = self.content_type {
+            if sbox.desc.uuid != expected {
+                report(ViolationKind::ContentType {
+                    expected,
+                    actual: sbox.desc.uuid,
+                });
+            }
+        }
+
+        if let Some(expected) = self.payload {
+            match sbox.child_boxes.first() {
+                Some(ChildBox::DataBox(dbox)) if dbox.tbox == expected => (),
+                Some(ChildBox::DataBox(dbox)) => report(ViolationKind::PayloadType {
+                    expected,
+                    actual: dbox.tbox,
+                }),
+                _ => report(ViolationKind::MissingPayload { expected }),
+            }
+        }
+
+        let child_sboxes: Vec<&SuperBox> = sbox
+            .child_boxes
+            .iter()
+            .filter_map(|child| match child {
+                ChildBox::SuperBox(sbox) => Some(sbox),
+                ChildBox::DataBox(_) => None,
+            })
+            .collect();
+
+        for rule in &self.children {
+            let found = child_sboxes
+                .iter()
+                .filter(|child| rule.selector.matches(child))
+                .count();
+
+            if !rule.cardinality.contains(found) {
+                report(ViolationKind::Cardinality {
+                    selector: rule.selector.clone(),
+                    cardinality: rule.cardinality,
+                    found,
+                });
+            }
+        }
+
+        if self.deny_other_children {
+            for child in &child_sboxes {
+                if !self
+                    .children
+                    .iter()
+                    .any(|rule| rule.selector.matches(child))
+                {
+                    violations.push(Violation {
+                        path: path.clone(),
+                        offset: child.offset,
+                        kind: ViolationKind::UnexpectedChild {
+                            label: child.desc.label.map(ToOwned::to_owned),
+                            uuid: child.desc.uuid,
+                        },
+                    });
+                }
+            }
+        }
+
+        for child in child_sboxes {
+            for rule in &self.children {
+                if rule.selector.matches(child) {
+                    path.push(child.desc.label.map(ToOwned::to_owned));
+                    rule.schema.validate_into(child, path, violations);
+                    path.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Identifies which child superboxes a [`Schema`] rule applies to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ChildSelector {
+    /// Child superboxes with this label.
+    Label(String),
+
+    /// Child superboxes with this content type.
+    ContentType(Uuid),
+}
+
+impl ChildSelector {
+    fn matches(&self, sbox: &SuperBox) -> bool {
+        match self {
+            Self::Label(label) => sbox.desc.label == Some(label.as_str()),
+            Self::ContentType(uuid) => sbox.desc.uuid == *uuid,
+        }
+    }
+}
+
+impl Display for ChildSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::Label(label) => write!(f, "superboxes labeled {label:?}"),
+            Self::ContentType(uuid) => write!(f, "superboxes of type {uuid}"),
+        }
+    }
+}
+
+/// The permitted number of child superboxes matched by a [`Schema`] rule.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cardinality {
+    /// Minimum number of matching child superboxes.
+    pub min: usize,
+
+    /// Maximum number of matching child superboxes, or `None` if there is no
+    /// maximum.
+    pub max: Option<usize>,
+}
+
+impl Cardinality {
+    /// Any number, including zero.
+    pub const ANY: Self = Self { min: 0, max: None };
+    /// Exactly one.
+    pub const ONE: Self = Self {
+        min: 1,
+        max: Some(1),
+    };
+    /// At least one.
+    pub const ONE_OR_MORE: Self = Self { min: 1, max: None };
+    /// Zero or one.
+    pub const OPTIONAL: Self = Self {
+        min: 0,
+        max: Some(1),
+    };
+
+    /// Returns `true` if `count` is within this cardinality.
+    pub fn contains(&self, count: usize) -> bool {
+        count >= self.min && self.max.map_or(true, |max| count <= max)
+    }
+}
+
+impl Display for Cardinality {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self.max {
+            Some(max) if max == self.min => write!(f, "exactly {max}"),
+            Some(max) => write!(f, "{} to {max}", self.min),
+            None => write!(f, "at least {}", self.min),
+        }
+    }
+}
+
+/// A way in which a superbox did not match a [`Schema`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Violation {
+    /// Labels of the superbox which did not match, preceded by those of its
+    /// enclosing superboxes, outermost first. An entry is `None` if that
+    /// superbox has no label.
+    pub path: Vec<Option<String>>,
+
+    /// Offset, from the start of the source that was originally parsed, of
+    /// the box which did not match. For [`ViolationKind::UnexpectedChild`],
+    /// this is the offset of the unexpected child.
+    pub offset: usize,
+
+    /// What did not match.
+    pub kind: ViolationKind,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        for (index, label) in self.path.iter().enumerate() {
+            if index > 0 {
+                f.write_str("/")?;
+            }
+            f.write_str(label.as_deref().unwrap_or("(unlabeled)"))?;
+        }
+
+        write!(f, " (offset {}): {}", self.offset, self.kind)
+    }
+}
+
+/// Describes a [`Violation`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ViolationKind {
+    /// The superbox did not have the expected content type.
+    ContentType {
+        /// Content type required by the schema.
+        expected: Uuid,
+
+        /// Content type of the superbox.
+        actual: Uuid,
+    },
+
+    /// The first child box of the superbox was not a data box.
+    MissingPayload {
+        /// Payload box type required by the schema.
+        expected: BoxType,
+    },
+
+    /// The first child box of the superbox was a data box of the wrong type.
+    PayloadType {
+        /// Payload box type required by the schema.
+        expected: BoxType,
+
+        /// Box type of the first child box.
+        actual: BoxType,
+    },
+
+    /// The number of matching child superboxes was not permitted.
+    Cardinality {
+        /// Which child superboxes were counted.
+        selector: ChildSelector,
+
+        /// The permitted number of matching child superboxes.
+        cardinality: Cardinality,
+
+        /// The number of matching child superboxes that were found.
+        found: usize,
+    },
+
+    /// A child superbox was not matched by any rule in a schema which
+    /// [denies other children](Schema::deny_other_children()).
+    UnexpectedChild {
+        /// Label of the child superbox, if any.
+        label: Option<String>,
+
+        /// Content type of the child superbox.
+        uuid: Uuid,
+    },
+}
+
+impl Display for ViolationKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            Self::ContentType { expected, actual } => {
+                write!(f, "content type should be {expected}, was {actual}")
+            }
+            Self::MissingPayload { expected } => {
+                write!(f, "missing '{expected}' payload")
+            }
+            Self::PayloadType { expected, actual } => {
+                write!(f, "payload box type should be '{expected}', was '{actual}'")
+            }
+            Self::Cardinality {
+                selector,
+                cardinality,
+                found,
+            } => {
+                write!(f, "expected {cardinality} {selector}, found {found}")
+            }
+            Self::UnexpectedChild { label, uuid } => {
+                let label = label
+                    .as_ref()
+                    .map_or_else(|| "(unlabeled)".to_string(), |label| format!("{label:?}"));
+                write!(f, "unexpected child superbox {label} of type {uuid}")
+            }
+        }
+    }
+}
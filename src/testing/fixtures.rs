@@ -0,0 +1,191 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Known JUMBF data structures for use as test fixtures and fuzzing corpora.
+//!
+//! Each function returns a complete, serialized superbox. Most are written
+//! with the [builder], but some intentionally use encodings that the builder
+//! never produces (such as a 64-bit box size or a box size of zero), so that
+//! parsers can be tested against them.
+//!
+//! [`corpus()`] returns all of the fixtures together with descriptive names:
+//!
+//! ```
+//! use jumbf::{parser::SuperBox, testing::fixtures};
+//!
+//! for (name, jumbf) in fixtures::corpus() {
+//!     assert!(SuperBox::from_slice(&jumbf).is_ok(), "{name}");
+//! }
+//! ```
+//!
+//! [builder]: crate::builder
+
+use std::io::Cursor;
+
+use crate::{
+    box_type::{BINARY_DATA_BOX_TYPE, DESCRIPTION_BOX_TYPE, SUPER_BOX_TYPE},
+    builder::{DataBoxBuilder, EmbeddedFileBoxBuilder, SuperBoxBuilder},
+    BoxType, Uuid,
+};
+
+/// Box type used for data boxes in these fixtures.
+const DATA_BOX_TYPE: BoxType = BoxType(*b"abcd");
+
+/// Nesting depth of the deeply-nested superbox included in [`corpus()`].
+const CORPUS_DEPTH: usize = 64;
+
+/// The C2PA manifest store which is used throughout this crate's tests.
+///
+/// This was produced by an implementation of the C2PA specification other
+/// than this crate, so it is useful as an example of real-world JUMBF.
+pub fn c2pa_manifest_store() -> &'static [u8] {
+    include_bytes!("../tests/fixtures/C.c2pa")
+}
+
+/// A superbox with an all-zero UUID, no label, and a single `abcd` box
+/// containing `"some data"`.
+pub fn simple() -> Vec<u8> {
+    to_jumbf(
+        &SuperBoxBuilder::new(Uuid([0; 16]))
+            .add_child_box(DataBoxBuilder::from_borrowed(DATA_BOX_TYPE, b"some data")),
+    )
+}
+
+/// A superbox whose only child box has an empty payload.
+pub fn empty_payload() -> Vec<u8> {
+    to_jumbf(
+        &SuperBoxBuilder::new(Uuid([0; 16]))
+            .set_label("empty")
+            .add_child_box(DataBoxBuilder::from_borrowed(DATA_BOX_TYPE, b"")),
+    )
+}
+
+/// A chain of `depth` nested superboxes, labeled `level0` (outermost)
+/// through `level{depth - 1}`. The innermost superbox contains a single `abcd`
+/// box.
+///
+/// If `depth` is 0, returns the same superbox as [`simple()`].
+pub fn deeply_nested(depth: usize) -> Vec<u8> {
+    if depth == 0 {
+        return simple();
+    }
+
+    let mut sbox = SuperBoxBuilder::new(Uuid([0; 16]))
+        .set_label(format!("level{}", depth - 1))
+        .add_child_box(DataBoxBuilder::from_borrowed(DATA_BOX_TYPE, b"deepest"));
+
+    for level in (0..depth - 1).rev() {
+        sbox = SuperBoxBuilder::new(Uuid([0; 16]))
+            .set_label(format!("level{level}"))
+            .add_child_box(sbox);
+    }
+
+    to_jumbf(&sbox)
+}
+
+/// A superbox in which every box, including the description box, uses the
+/// 64-bit extended box size (`XLBox`) even though a 32-bit size would do.
+///
+/// Its only child is an `abcd` box containing `"large"`.
+pub fn large_size() -> Vec<u8> {
+    let desc = large_box(DESCRIPTION_BOX_TYPE, &description(b"large"));
+    let child = large_box(DATA_BOX_TYPE, b"large");
+    large_box(SUPER_BOX_TYPE, &[desc, child].concat())
+}
+
+/// A superbox whose size and whose last child box's size are both given as
+/// zero, meaning that each extends to the end of the data.
+///
+/// Its children are an `abcd` box containing `"first"` (with a normal size)
+/// and an `abcd` box containing `"to end"`.
+pub fn zero_length() -> Vec<u8> {
+    let desc = small_box(DESCRIPTION_BOX_TYPE, &description(b"zero"));
+    let first = small_box(DATA_BOX_TYPE, b"first");
+    let last = zero_size_box(DATA_BOX_TYPE, b"to end");
+    zero_size_box(SUPER_BOX_TYPE, &[desc, first, last].concat())
+}
+
+/// An embedded file superbox (see [`EmbeddedFileBoxBuilder`]) labeled
+/// `embedded` which contains `data` directly.
+pub fn embedded_file(media_type: &str, file_name: Option<&str>, data: &[u8]) -> Vec<u8> {
+    let mut desc = EmbeddedFileBoxBuilder::new(media_type);
+    if let Some(file_name) = file_name {
+        desc = desc.set_file_name(file_name);
+    }
+
+    to_jumbf(
+        &SuperBoxBuilder::new(Uuid::EMBEDDED_FILE)
+            .set_label("embedded")
+            .add_child_box(desc)
+            .add_child_box(DataBoxBuilder::from_owned(
+                BINARY_DATA_BOX_TYPE,
+                data.to_vec(),
+            )),
+    )
+}
+
+/// Returns every fixture in this module, each with a short descriptive name.
+pub fn corpus() -> Vec<(&'static str, Vec<u8>)> {
+    vec![
+        ("c2pa_manifest_store", c2pa_manifest_store().to_vec()),
+        ("simple", simple()),
+        ("empty_payload", empty_payload()),
+        ("deeply_nested", deeply_nested(CORPUS_DEPTH)),
+        ("large_size", large_size()),
+        ("zero_length", zero_length()),
+        (
+            "embedded_file",
+            embedded_file(
+                "image/jpeg",
+                Some("thumbnail.jpg"),
+                &[0xff, 0xd8, 0xff, 0xe0, 0x00, 0x1a, 0x4a],
+            ),
+        ),
+    ]
+}
+
+// The labels used above are all valid, so writing to memory can not fail.
+#[allow(clippy::panic)]
+fn to_jumbf(sbox: &SuperBoxBuilder) -> Vec<u8> {
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    if let Err(e) = sbox.write_jumbf(&mut jumbf) {
+        panic!("Unable to write fixture: {e}");
+    }
+    jumbf.into_inner()
+}
+
+/// Payload of a description box with an all-zero UUID and the given
+/// requestable label.
+fn description(label: &[u8]) -> Vec<u8> {
+    [&[0; 16][..], &[0x03], label, &[0]].concat()
+}
+
+fn small_box(tbox: BoxType, payload: &[u8]) -> Vec<u8> {
+    let size = (payload.len() + 8) as u32;
+    [&size.to_be_bytes()[..], &tbox.0, payload].concat()
+}
+
+fn large_box(tbox: BoxType, payload: &[u8]) -> Vec<u8> {
+    let size = (payload.len() + 16) as u64;
+    [
+        &1u32.to_be_bytes()[..],
+        &tbox.0,
+        &size.to_be_bytes(),
+        payload,
+    ]
+    .concat()
+}
+
+fn zero_size_box(tbox: BoxType, payload: &[u8]) -> Vec<u8> {
+    [&0u32.to_be_bytes()[..], &tbox.0, payload].concat()
+}
@@ -0,0 +1,152 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! Generators and assertions for testing code which produces or consumes
+//! JUMBF data structures.
+//!
+//! This module is intended for use in the tests of this crate and of
+//! downstream crates. [`Generator`] produces a reproducible sequence of
+//! random but valid JUMBF trees (described as [`SuperBoxSpec`]s), and the
+//! `assert_*` functions check that JUMBF survives a parse/serialize round
+//! trip:
+//!
+//! ```
+//! use jumbf::testing::{assert_round_trip, Generator};
+//!
+//! for spec in Generator::new(42).take(100) {
+//!     assert_round_trip(&spec);
+//! }
+//! ```
+//!
+//! The [`fixtures`] module provides known JUMBF data structures, including
+//! some which use unusual but valid encodings.
+//!
+//! This module is available when the `testing` feature is enabled. Since the
+//! assertions panic on failure, it is best enabled only via
+//! `[dev-dependencies]`.
+
+pub mod fixtures;
+
+use arbitrary::{Arbitrary, Unstructured};
+
+pub use crate::fuzzing::{ChildBoxSpec, DataBoxSpec, RoundTripError, SuperBoxSpec};
+use crate::parser::SuperBox;
+
+/// Number of random bytes consumed to generate each tree.
+const BYTES_PER_TREE: usize = 4096;
+
+/// Generates a reproducible sequence of random, valid JUMBF trees.
+///
+/// The same seed always generates the same sequence, so a failing case can
+/// be reproduced by re-running with the seed reported by the test.
+#[derive(Clone, Debug)]
+pub struct Generator {
+    state: u64,
+}
+
+impl Generator {
+    /// Create a generator from the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            // xorshift gets stuck at zero, so force the low bit on.
+            state: seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1,
+        }
+    }
+
+    /// Generate the next random superbox description.
+    pub fn super_box(&mut self) -> SuperBoxSpec {
+        loop {
+            let bytes = self.bytes(BYTES_PER_TREE);
+            if let Ok(spec) = SuperBoxSpec::arbitrary(&mut Unstructured::new(&bytes)) {
+                return spec;
+            }
+        }
+    }
+
+    /// Generate the next random superbox, serialized as JUMBF.
+    pub fn jumbf(&mut self) -> Vec<u8> {
+        loop {
+            if let Ok(jumbf) = self.super_box().to_jumbf() {
+                return jumbf;
+            }
+        }
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        (0..len)
+            .map(|_| {
+                self.state ^= self.state << 13;
+                self.state ^= self.state >> 7;
+                self.state ^= self.state << 17;
+                self.state as u8
+            })
+            .collect()
+    }
+}
+
+impl Iterator for Generator {
+    type Item = SuperBoxSpec;
+
+    fn next(&mut self) -> Option<SuperBoxSpec> {
+        Some(self.super_box())
+    }
+}
+
+/// Assert that `spec` survives a build→parse→rebuild round trip, as
+/// described in [`SuperBoxSpec::check_round_trip()`].
+///
+/// # Panics
+///
+/// Panics if the round trip fails.
+#[allow(clippy::panic)]
+#[track_caller]
+pub fn assert_round_trip(spec: &SuperBoxSpec) {
+    if let Err(e) = spec.check_round_trip() {
+        panic!("JUMBF round trip failed: {e}\n\nOriginal: {spec:?}");
+    }
+}
+
+/// Assert that `jumbf` parses as a single superbox and that serializing the
+/// parsed superbox reproduces `jumbf` exactly.
+///
+/// Note that this requires `jumbf` to use the most compact encoding of each
+/// box (the same encoding that the builder produces).
+///
+/// # Panics
+///
+/// Panics if `jumbf` does not parse, has data following the superbox, or is
+/// not reproduced exactly.
+#[allow(clippy::panic)]
+#[track_caller]
+pub fn assert_jumbf_round_trip(jumbf: &[u8]) {
+    let (rem, sbox) = match SuperBox::from_slice(jumbf) {
+        Ok(result) => result,
+        Err(e) => panic!("Unable to parse JUMBF: {e:?}"),
+    };
+
+    if !rem.is_empty() {
+        panic!("Parser left {} bytes unread", rem.len());
+    }
+
+    let spec = SuperBoxSpec::from_super_box(&sbox);
+    let rebuilt = match spec.to_jumbf() {
+        Ok(rebuilt) => rebuilt,
+        Err(e) => panic!("Unable to rebuild JUMBF: {e}\n\nParsed: {spec:?}"),
+    };
+
+    if rebuilt != jumbf {
+        panic!(
+            "Rebuilt JUMBF differs from original\n\nOriginal: {jumbf:02x?}\n\nRebuilt: {rebuilt:02x?}"
+        );
+    }
+}
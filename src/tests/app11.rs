@@ -0,0 +1,143 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{
+    app11::{plan_app11_segments, plan_app11_segments_with_limit},
+    parser::Error,
+};
+
+// Reassemble the box from its segments as a JPEG reader would.
+fn reassemble(segments: &[crate::app11::App11Segment]) -> Vec<u8> {
+    let box_header = &segments[0].header[12..];
+
+    let mut jumbf = box_header.to_vec();
+    for segment in segments {
+        assert_eq!(&segment.header[12..], box_header);
+        jumbf.extend_from_slice(segment.payload);
+    }
+    jumbf
+}
+
+#[test]
+fn c2pa_fits_in_one_segment() {
+    let jumbf = include_bytes!("fixtures/C.c2pa");
+    let segments = plan_app11_segments(jumbf, 1).unwrap();
+
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0].len(), jumbf.len() + 12);
+    assert_eq!(reassemble(&segments), jumbf);
+}
+
+#[test]
+fn split_with_limit() {
+    let jumbf = include_bytes!("fixtures/C.c2pa");
+    let segments = plan_app11_segments_with_limit(jumbf, 7, 1018).unwrap();
+
+    // Each segment carries up to 1018 - 18 = 1000 bytes of payload.
+    assert_eq!(segments.len(), (jumbf.len() - 8).div_ceil(1000));
+    assert_eq!(reassemble(&segments), jumbf);
+
+    for (index, segment) in segments.iter().enumerate() {
+        let header = &segment.header;
+        assert_eq!(&header[0..2], [0xff, 0xeb]);
+
+        let len = u16::from_be_bytes([header[2], header[3]]) as usize;
+        assert_eq!(len, segment.len() - 2);
+        assert!(len <= 1018);
+
+        assert_eq!(&header[4..6], b"JP");
+        assert_eq!(&header[6..8], [0, 7]);
+        assert_eq!(
+            u32::from_be_bytes([header[8], header[9], header[10], header[11]]) as usize,
+            index + 1
+        );
+
+        assert_eq!(&jumbf[segment.range.clone()], segment.payload);
+    }
+
+    assert_eq!(segments[0].range, 8..1008);
+    assert_eq!(segments.last().unwrap().range.end, jumbf.len());
+}
+
+#[test]
+fn large_box_header_is_repeated() {
+    let jumbf = hex!(
+        "00000001" // box size (see large size)
+        "6a736f6e" // box type = 'json'
+        "0000000000000014" // large box size
+        "7b7d200a" // payload
+    );
+
+    let segments = plan_app11_segments_with_limit(&jumbf, 1, 28).unwrap();
+    assert_eq!(segments.len(), 2);
+
+    assert_eq!(
+        segments[1].header,
+        hex!(
+            "ffeb" // APP11 marker
+            "001c" // segment length
+            "4a50" // common identifier = 'JP'
+            "0001" // box instance number
+            "00000002" // packet sequence number
+            "00000001" // box size (see large size)
+            "6a736f6e" // box type = 'json'
+            "0000000000000014" // large box size
+        )
+    );
+
+    assert_eq!(segments[0].payload, b"{}");
+    assert_eq!(segments[1].payload, b" \n");
+}
+
+#[test]
+fn limit_too_small() {
+    let jumbf = hex!(
+        "0000000c" // box size
+        "6a736f6e" // box type = 'json'
+        "7b7d200a" // payload
+    );
+
+    // Each segment still carries one byte of payload.
+    let segments = plan_app11_segments_with_limit(&jumbf, 1, 0).unwrap();
+    assert_eq!(segments.len(), 4);
+    assert_eq!(reassemble(&segments), jumbf);
+}
+
+#[test]
+fn empty_payload() {
+    let jumbf = hex!(
+        "00000008" // box size
+        "66726565" // box type = 'free'
+    );
+
+    let segments = plan_app11_segments(&jumbf, 1).unwrap();
+    assert_eq!(segments.len(), 1);
+    assert!(segments[0].is_empty());
+    assert_eq!(reassemble(&segments), jumbf);
+}
+
+#[test]
+fn error_incomplete_box() {
+    let jumbf = hex!(
+        "00000010" // box size
+        "6a736f6e" // box type = 'json'
+    );
+
+    assert_eq!(
+        plan_app11_segments(&jumbf, 1).unwrap_err(),
+        Error::Incomplete(8)
+    );
+}
@@ -0,0 +1,60 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{
+    bench_support::{deep, huge_payload, to_jumbf, wide, DATA_BOX_TYPE},
+    parser::{ChildBox, SuperBox},
+};
+
+#[test]
+fn deep_nesting() {
+    let jumbf = to_jumbf(&deep(100));
+    let (rem, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    assert!(rem.is_empty());
+
+    let mut depth = 1;
+    let mut sbox = &sbox;
+    while let Some(ChildBox::SuperBox(child)) = sbox.child_boxes.first() {
+        depth += 1;
+        sbox = child;
+    }
+
+    assert_eq!(depth, 100);
+    assert_eq!(sbox.data_box().unwrap().tbox, DATA_BOX_TYPE);
+}
+
+#[test]
+fn deep_zero() {
+    assert_eq!(to_jumbf(&deep(0)), to_jumbf(&deep(1)));
+}
+
+#[test]
+fn many_siblings() {
+    let jumbf = to_jumbf(&wide(5000));
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+
+    assert_eq!(sbox.child_boxes.len(), 5000);
+    assert_eq!(sbox.children_by_type(DATA_BOX_TYPE).count(), 5000);
+}
+
+#[test]
+fn large_payload() {
+    let jumbf = to_jumbf(&huge_payload(1 << 20));
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+
+    let dbox = sbox.data_box().unwrap();
+    assert_eq!(dbox.data.len(), 1 << 20);
+    assert!(dbox.data.iter().all(|b| *b == 0x5a));
+}
@@ -11,7 +11,9 @@
 // specific language governing permissions and limitations under
 // each license.
 
-use crate::BoxType;
+use std::str::FromStr;
+
+use crate::{box_type, BoxType};
 
 #[test]
 fn impl_debug() {
@@ -24,3 +26,47 @@ fn impl_debug() {
     let x = BoxType([b'a', b'b', b'c', 0x7f]);
     assert_eq!(format!("{x:#?}"), "[0x61, 0x62, 0x63, 0x7f]");
 }
+
+#[test]
+fn impl_display() {
+    assert_eq!(BoxType(*b"json").to_string(), "json");
+    assert_eq!(BoxType(*b"xml ").to_string(), "xml ");
+    assert_eq!(BoxType([b'a', b'b', b'c', 0x7f]).to_string(), "abc\\x7f");
+}
+
+#[test]
+fn from_str() {
+    assert_eq!(BoxType::from_str("jumb").unwrap(), BoxType(*b"jumb"));
+    assert_eq!(BoxType::from_str("xml ").unwrap(), BoxType(*b"xml "));
+
+    for s in ["", "abc", "abcde", "jümb"] {
+        let err = BoxType::from_str(s).unwrap_err();
+        assert_eq!(err.to_string(), "Box type must be exactly four bytes");
+    }
+}
+
+#[test]
+fn new_and_macro() {
+    const JSON: BoxType = BoxType::new(b"json");
+    const CBOR: BoxType = box_type!("cbor");
+
+    assert_eq!(JSON, BoxType(*b"json"));
+    assert_eq!(CBOR, BoxType(*b"cbor"));
+    assert_eq!(box_type!("jumb"), crate::box_type::SUPER_BOX_TYPE);
+}
+
+#[test]
+fn is_standard() {
+    for tbox in [
+        b"bidb", b"cbor", b"jp2c", b"jumd", b"bfdb", b"free", b"json", b"jumb", b"uuid", b"xml ",
+    ] {
+        assert!(BoxType::new(tbox).is_standard(), "{tbox:?}");
+    }
+
+    assert_eq!(BoxType::JSON, BoxType(*b"json"));
+    assert_eq!(BoxType::XML, BoxType(*b"xml "));
+
+    assert!(!BoxType(*b"c2sh").is_standard());
+    assert!(!BoxType(*b"JSON").is_standard());
+    assert!(!BoxType(*b"xml\0").is_standard());
+}
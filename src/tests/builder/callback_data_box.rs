@@ -0,0 +1,76 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::{Cursor, Error};
+
+use hex_literal::hex;
+
+use crate::{
+    builder::{
+        to_box::{jumbf_size, write_jumbf},
+        CallbackDataBox, ToBox,
+    },
+    BoxType,
+};
+
+const RANDOM_BOX_TYPE: BoxType = BoxType(*b"abcd");
+
+#[test]
+fn simple_box() {
+    let expected_jumbf = hex!(
+        "0000000c" // box size
+        "61626364" // box type = 'abcd'
+        "41424344" // payload
+    );
+
+    let boxx = CallbackDataBox::new(RANDOM_BOX_TYPE, 4, |to_stream| {
+        to_stream.write_all(b"AB")?;
+        to_stream.write_all(b"CD")
+    });
+
+    assert_eq!(boxx.box_type(), RANDOM_BOX_TYPE);
+    assert_eq!(boxx.payload_size().unwrap(), 4);
+    assert_eq!(jumbf_size(&boxx).unwrap(), 12);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    write_jumbf(&boxx, &mut jumbf).unwrap();
+    assert_eq!(*jumbf.into_inner(), expected_jumbf);
+}
+
+#[test]
+fn error_size_mismatch() {
+    let boxx = CallbackDataBox::new(RANDOM_BOX_TYPE, 5, |to_stream| to_stream.write_all(b"ABCD"));
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let err = write_jumbf(&boxx, &mut jumbf).unwrap_err();
+
+    assert_eq!(
+        "Custom { kind: Other, error: \"write_jumbf: box type b\\\"abcd\\\" wrote 4 bytes of payload, but payload_size() reported 5 bytes\" }",
+        format!("{err:?}")
+    );
+}
+
+#[test]
+fn error_from_callback() {
+    let boxx = CallbackDataBox::new(RANDOM_BOX_TYPE, 4, |_to_stream| {
+        Err(Error::other("callback failed"))
+    });
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let err = write_jumbf(&boxx, &mut jumbf).unwrap_err();
+
+    assert_eq!(
+        "Custom { kind: Other, error: \"callback failed\" }",
+        format!("{err:?}")
+    );
+}
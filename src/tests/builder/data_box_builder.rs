@@ -74,3 +74,29 @@ fn simple_box_owned() {
     write_jumbf(&boxx, &mut jumbf).unwrap();
     assert_eq!(*jumbf.into_inner(), expected_jumbf);
 }
+
+#[cfg(feature = "compress")]
+#[test]
+fn compressed() {
+    use crate::{box_type, compression::Compression};
+
+    let payload = b"payload payload payload payload";
+
+    let boxx = DataBoxBuilder::compressed(box_type!("c2cb"), Compression::Brotli, payload).unwrap();
+    assert_eq!(boxx.box_type(), box_type!("c2cb"));
+
+    let mut compressed = Cursor::new(Vec::<u8>::new());
+    boxx.write_payload(&mut compressed).unwrap();
+
+    let compressed = compressed.into_inner();
+    assert!(compressed.len() < payload.len());
+    assert_eq!(boxx.payload_size().unwrap(), compressed.len());
+
+    assert_eq!(
+        Compression::Brotli
+            .decompress(&compressed, payload.len())
+            .unwrap()
+            .unwrap(),
+        payload
+    );
+}
@@ -0,0 +1,97 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::Cursor;
+
+use hex_literal::hex;
+
+use crate::{
+    builder::{
+        to_box::{jumbf_size, write_jumbf},
+        DataBoxBuilder, EmbeddedFileBoxBuilder, SuperBoxBuilder, ToBox,
+    },
+    BoxType, Uuid,
+};
+
+#[test]
+fn media_type_only() {
+    let expected_jumbf = hex!(
+        "00000014" // box size
+        "62666462" // box type = 'bfdb'
+        "00" // toggles
+        "696d6167652f6a70656700" // media type ("image/jpeg")
+    );
+
+    let boxx = EmbeddedFileBoxBuilder::new("image/jpeg");
+
+    assert_eq!(boxx.box_type(), BoxType(*b"bfdb"));
+    assert_eq!(boxx.payload_size().unwrap(), 12);
+    assert_eq!(jumbf_size(&boxx).unwrap(), 20);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    write_jumbf(&boxx, &mut jumbf).unwrap();
+    assert_eq!(*jumbf.into_inner(), expected_jumbf);
+}
+
+#[test]
+fn file_name_and_external() {
+    let expected_jumbf = hex!(
+        "0000001f" // box size
+        "62666462" // box type = 'bfdb'
+        "03" // toggles
+        "746578742f706c61696e00" // media type ("text/plain")
+        "726561646d652e74787400" // file name ("readme.txt")
+    );
+
+    let boxx = EmbeddedFileBoxBuilder::new("text/plain")
+        .set_file_name("readme.txt")
+        .set_external(true);
+
+    assert_eq!(jumbf_size(&boxx).unwrap(), 31);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    write_jumbf(&boxx, &mut jumbf).unwrap();
+    assert_eq!(*jumbf.into_inner(), expected_jumbf);
+}
+
+#[test]
+fn in_super_box() {
+    let expected_jumbf = hex!(
+        "00000044" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "40cb0c32bb8a489da70b2ad6f47f4369" // UUID (embedded file)
+            "00" // toggles
+            // ----
+            "00000014" // box size
+            "62666462" // box type = 'bfdb'
+            "00" // toggles
+            "696d6167652f6a70656700" // media type ("image/jpeg")
+            // ----
+            "0000000f" // box size
+            "62696462" // box type = 'bidb'
+            "ffd8ffe0001a4a" // payload (file content)
+    );
+
+    let sbox = SuperBoxBuilder::new(Uuid::EMBEDDED_FILE)
+        .add_child_box(EmbeddedFileBoxBuilder::new("image/jpeg"))
+        .add_child_box(DataBoxBuilder::from_owned(
+            BoxType(*b"bidb"),
+            hex!("ffd8ffe0001a4a").to_vec(),
+        ));
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+    assert_eq!(*jumbf.into_inner(), expected_jumbf);
+}
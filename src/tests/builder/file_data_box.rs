@@ -0,0 +1,96 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{
+    fs::File,
+    io::{Cursor, Seek, SeekFrom},
+};
+
+use crate::{
+    builder::{
+        to_box::{jumbf_size, write_jumbf},
+        FileDataBox, SuperBoxBuilder, ToBox,
+    },
+    BoxType,
+};
+
+const FIXTURE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests/fixtures/C.c2pa");
+const FIXTURE: &[u8] = include_bytes!("../fixtures/C.c2pa");
+
+const RANDOM_BOX_TYPE: BoxType = BoxType(*b"abcd");
+
+#[test]
+fn open() {
+    let boxx = FileDataBox::open(RANDOM_BOX_TYPE, FIXTURE_PATH).unwrap();
+
+    assert_eq!(boxx.box_type(), RANDOM_BOX_TYPE);
+    assert_eq!(boxx.payload_size().unwrap(), FIXTURE.len());
+    assert_eq!(jumbf_size(&boxx).unwrap(), FIXTURE.len() + 8);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    write_jumbf(&boxx, &mut jumbf).unwrap();
+
+    let jumbf = jumbf.into_inner();
+    assert_eq!(
+        jumbf[0..4],
+        u32::try_from(FIXTURE.len() + 8).unwrap().to_be_bytes()
+    );
+    assert_eq!(jumbf[4..8], *b"abcd");
+    assert_eq!(jumbf[8..], *FIXTURE);
+}
+
+#[test]
+fn try_clone() {
+    let boxx = FileDataBox::open(RANDOM_BOX_TYPE, FIXTURE_PATH).unwrap();
+    let clone = boxx.try_clone().unwrap();
+    drop(boxx);
+
+    let mut payload = Cursor::new(Vec::<u8>::new());
+    clone.write_payload(&mut payload).unwrap();
+    assert_eq!(payload.into_inner(), FIXTURE);
+}
+
+#[test]
+fn from_file_ignores_position() {
+    let mut file = File::open(FIXTURE_PATH).unwrap();
+    file.seek(SeekFrom::Start(100)).unwrap();
+
+    let boxx = FileDataBox::from_file(RANDOM_BOX_TYPE, file);
+
+    let mut payload = Cursor::new(Vec::<u8>::new());
+    boxx.write_payload(&mut payload).unwrap();
+    assert_eq!(*payload.into_inner(), *FIXTURE);
+}
+
+#[test]
+fn write_twice() {
+    let sbox = SuperBoxBuilder::new([0; 16])
+        .add_child_box(FileDataBox::open(RANDOM_BOX_TYPE, FIXTURE_PATH).unwrap());
+
+    let mut first = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut first).unwrap();
+
+    let mut second = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut second).unwrap();
+
+    assert_eq!(first.get_ref().len(), 8 + 25 + 8 + FIXTURE.len());
+    assert_eq!(first.into_inner(), second.into_inner());
+}
+
+#[test]
+fn error_file_not_found() {
+    let err = FileDataBox::open(RANDOM_BOX_TYPE, "does/not/exist")
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
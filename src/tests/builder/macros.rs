@@ -0,0 +1,144 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::Cursor;
+
+use hex_literal::hex;
+
+use crate::{
+    builder::{DataBoxBuilder, SuperBoxBuilder},
+    jumbf, BoxType, Uuid,
+};
+
+fn to_jumbf(sbox: &SuperBoxBuilder) -> Vec<u8> {
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+    jumbf.into_inner()
+}
+
+#[test]
+fn empty() {
+    let expected_jumbf = hex!(
+        "00000021" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+    );
+
+    let sbox = jumbf! { jumb([0; 16]) {} };
+    assert_eq!(to_jumbf(&sbox), expected_jumbf);
+}
+
+#[test]
+fn data_boxes() {
+    let expected_jumbf = hex!(
+        "0000004d" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000026" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "03" // toggles
+            "746573742e64657363626f7800" // label
+            // ---
+            "0000000a" // box size
+            "61626364" // box type = 'abcd'
+            "6869" // payload ("hi")
+            // ---
+            "0000000a" // box size
+            "6a736f6e" // box type = 'json'
+            "7b7d" // payload ("{}")
+            // ---
+            "0000000b" // box size
+            "78786d6c" // box type = 'xxml'
+            "010203" // payload
+    );
+
+    let sbox = jumbf! {
+        jumb([0; 16], label = "test.descbox") {
+            "abcd" => b"hi",
+            "json" => "{}",
+            "xxml" => vec![1u8, 2, 3],
+        }
+    };
+
+    assert_eq!(to_jumbf(&sbox), expected_jumbf);
+}
+
+#[test]
+fn all_settings() {
+    let hash = [0x55; 32];
+    let salt = hex!("000102030405060708090a0b0c0d0e0f");
+
+    let sbox = jumbf! {
+        jumb(Uuid::JSON, non_requestable_label = "a", id = 42, hash = &hash, canonical = true) {
+            jumb(Uuid::CBOR, label = "b", salt = &salt) {},
+            jumb(
+                Uuid::CBOR,
+                private = DataBoxBuilder::from_borrowed(BoxType(*b"priv"), b"private"),
+            ) {}
+        }
+    };
+
+    let expected = SuperBoxBuilder::new(Uuid::JSON)
+        .set_non_requestable_label("a")
+        .set_id(42)
+        .set_sha256_hash(&hash)
+        .set_canonical(true)
+        .add_child_box(
+            SuperBoxBuilder::new(Uuid::CBOR)
+                .set_label("b")
+                .set_salt(&salt),
+        )
+        .add_child_box(
+            SuperBoxBuilder::new(Uuid::CBOR)
+                .set_private_box(DataBoxBuilder::from_borrowed(BoxType(*b"priv"), b"private")),
+        );
+
+    assert_eq!(to_jumbf(&sbox), to_jumbf(&expected));
+}
+
+#[test]
+fn nested() {
+    let sbox = jumbf! {
+        jumb([1; 16], label = "outer") {
+            "abcd" => b"first",
+            jumb([2; 16], label = "middle") {
+                jumb([3; 16], label = "inner") {
+                    "abcd" => b"deepest",
+                },
+            },
+            "abcd" => b"last",
+        }
+    };
+
+    let expected = SuperBoxBuilder::new([1; 16])
+        .set_label("outer")
+        .add_child_box(DataBoxBuilder::from_borrowed(BoxType(*b"abcd"), b"first"))
+        .add_child_box(
+            SuperBoxBuilder::new([2; 16])
+                .set_label("middle")
+                .add_child_box(
+                    SuperBoxBuilder::new([3; 16])
+                        .set_label("inner")
+                        .add_child_box(DataBoxBuilder::from_borrowed(
+                            BoxType(*b"abcd"),
+                            b"deepest",
+                        )),
+                ),
+        )
+        .add_child_box(DataBoxBuilder::from_borrowed(BoxType(*b"abcd"), b"last"));
+
+    assert_eq!(to_jumbf(&sbox), to_jumbf(&expected));
+}
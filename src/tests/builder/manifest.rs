@@ -0,0 +1,145 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::{self, Cursor, ErrorKind};
+
+use crate::{
+    builder::{load_manifest, parse_manifest, DataBoxBuilder, ManifestError, SuperBoxBuilder},
+    BoxType, Uuid,
+};
+
+const MANIFEST: &str = "
+# A simple manifest
+jumb 00000000-0000-0000-0000-000000000000 c2pa
+  jumb 6a736f6e-0011-0010-8000-00aa00389b71 c2pa.assertion
+    json assertion.json
+
+  jumb 00000000-0000-0000-0000-000000000000 c2pa.signature
+    uuid signature.bin
+";
+
+const FIXTURE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests/fixtures");
+const FIXTURE: &[u8] = include_bytes!("../fixtures/C.c2pa");
+
+fn read_file(path: &str) -> io::Result<Vec<u8>> {
+    match path {
+        "assertion.json" => Ok(b"{}".to_vec()),
+        "signature.bin" => Ok(b"signature".to_vec()),
+        _ => Err(io::Error::new(ErrorKind::NotFound, "no such file")),
+    }
+}
+
+fn to_jumbf(sbox: &SuperBoxBuilder) -> Vec<u8> {
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+    jumbf.into_inner()
+}
+
+#[test]
+fn parse() {
+    let sbox = parse_manifest(MANIFEST, read_file).unwrap();
+
+    let expected = SuperBoxBuilder::new([0; 16])
+        .set_label("c2pa")
+        .add_child_box(
+            SuperBoxBuilder::new(Uuid::JSON)
+                .set_label("c2pa.assertion")
+                .add_child_box(DataBoxBuilder::from_borrowed(BoxType(*b"json"), b"{}")),
+        )
+        .add_child_box(
+            SuperBoxBuilder::new([0; 16])
+                .set_label("c2pa.signature")
+                .add_child_box(DataBoxBuilder::from_borrowed(
+                    BoxType(*b"uuid"),
+                    b"signature",
+                )),
+        );
+
+    assert_eq!(to_jumbf(&sbox), to_jumbf(&expected));
+}
+
+#[test]
+fn load() {
+    let sbox = load_manifest(format!("{FIXTURE_DIR}/manifest.txt")).unwrap();
+
+    let expected = SuperBoxBuilder::new([0; 16])
+        .set_label("test.manifest")
+        .add_child_box(DataBoxBuilder::from_borrowed(BoxType(*b"c2pa"), FIXTURE));
+
+    assert_eq!(to_jumbf(&sbox), to_jumbf(&expected));
+}
+
+#[test]
+fn error_load_missing() {
+    let err = load_manifest(format!("{FIXTURE_DIR}/missing.txt"))
+        .err()
+        .unwrap();
+
+    let ManifestError::ReadManifest(e) = &err else {
+        panic!("unexpected error {err:?}");
+    };
+
+    assert_eq!(e.kind(), ErrorKind::NotFound);
+    assert!(std::error::Error::source(&err).is_some());
+}
+
+#[test]
+fn error_read_file() {
+    let text = "jumb 00000000-0000-0000-0000-000000000000\n  json missing.json";
+    let err = parse_manifest(text, read_file).err().unwrap();
+
+    let ManifestError::ReadFile { line, path, error } = &err else {
+        panic!("unexpected error {err:?}");
+    };
+
+    assert_eq!(*line, 2);
+    assert_eq!(path, "missing.json");
+    assert_eq!(error.kind(), ErrorKind::NotFound);
+}
+
+#[test]
+fn manifest_errors() {
+    let errors = [
+        ("", "manifest description is empty"),
+        (
+            "json x.json",
+            "line 1: first box must be an unindented superbox",
+        ),
+        (
+            "jumb 00000000-0000-0000-0000-000000000000\n   json x",
+            "line 2: indentation must be a multiple of two spaces",
+        ),
+        (
+            "jumb 00000000-0000-0000-0000-000000000000\n    json x",
+            "line 2: unexpected indentation",
+        ),
+        (
+            "jumb 00000000-0000-0000-0000-000000000000\njumb 00000000-0000-0000-0000-000000000000",
+            "line 2: only one top-level superbox is allowed",
+        ),
+        (
+            "jumb 00000000-0000-0000-0000-000000000000\n  jsonx assertion.json",
+            "line 2: box type \"jsonx\" must be four bytes",
+        ),
+        (
+            "jumb 00000000-0000-0000-0000-000000000000\n  json missing.json",
+            "line 2: unable to read \"missing.json\": no such file",
+        ),
+        ("jumb", "line 1: expected `jumb <uuid> [label]`"),
+    ];
+
+    for (text, message) in errors {
+        let err = parse_manifest(text, read_file).err().unwrap();
+        assert_eq!(err.to_string(), message);
+    }
+}
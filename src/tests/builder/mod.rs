@@ -18,6 +18,18 @@
 #![allow(clippy::panic)]
 #![allow(clippy::unwrap_used)]
 
+mod callback_data_box;
 mod data_box_builder;
+mod embedded_file_box_builder;
+mod file_data_box;
+mod macros;
+mod manifest;
+mod padding_box_builder;
 mod placeholder_data_box;
+mod placeholder_registry;
+mod placeholder_super_box;
+mod prepared_super_box;
+mod streaming_super_box_writer;
 mod super_box_builder;
+mod to_box;
+mod write_only;
@@ -0,0 +1,95 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::Cursor;
+
+use hex_literal::hex;
+
+use crate::{
+    box_type::FREE_BOX_TYPE,
+    builder::{
+        to_box::{jumbf_size, write_jumbf},
+        PaddingBoxBuilder, ToBox,
+    },
+};
+
+#[test]
+fn simple_box() {
+    let expected_jumbf = hex!(
+        "00000010" // box size
+        "66726565" // box type = 'free'
+        "0000000000000000" // padding
+    );
+
+    let boxx = PaddingBoxBuilder::new(16);
+
+    assert_eq!(boxx.box_type(), FREE_BOX_TYPE);
+    assert_eq!(boxx.payload_size().unwrap(), 8);
+    assert_eq!(jumbf_size(&boxx).unwrap(), 16);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    write_jumbf(&boxx, &mut jumbf).unwrap();
+    assert_eq!(*jumbf.into_inner(), expected_jumbf);
+}
+
+#[test]
+fn header_only() {
+    let boxx = PaddingBoxBuilder::new(8);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    write_jumbf(&boxx, &mut jumbf).unwrap();
+    assert_eq!(*jumbf.into_inner(), hex!("00000008 66726565"));
+}
+
+#[test]
+fn error_size_too_small() {
+    let boxx = PaddingBoxBuilder::new(7);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let err = write_jumbf(&boxx, &mut jumbf).unwrap_err();
+
+    assert_eq!(
+        "Custom { kind: Other, error: \"PaddingBoxBuilder: size (7 bytes) must be at least 8 bytes\" }",
+        format!("{err:?}")
+    );
+}
+
+#[cfg(feature = "parser")]
+#[test]
+fn parser_recognizes_padding() {
+    use crate::{
+        builder::{DataBoxBuilder, SuperBoxBuilder},
+        parser::{ParseOptions, SuperBox},
+        BoxType,
+    };
+
+    let sbox = SuperBoxBuilder::new([0; 16])
+        .add_child_box(DataBoxBuilder::from_borrowed(BoxType(*b"abcd"), b"ABCD"))
+        .add_child_box(PaddingBoxBuilder::new(100));
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+    let jumbf = jumbf.into_inner();
+
+    let (_, parsed) = SuperBox::from_slice(&jumbf).unwrap();
+    assert_eq!(parsed.child_boxes.len(), 2);
+    assert!(parsed.child_boxes[1].as_data_box().unwrap().is_free());
+
+    let options = ParseOptions {
+        skip_free_boxes: true,
+        ..Default::default()
+    };
+
+    let (_, parsed) = SuperBox::from_source_with_options(&jumbf, &options).unwrap();
+    assert_eq!(parsed.child_boxes.len(), 1);
+}
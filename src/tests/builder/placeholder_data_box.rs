@@ -18,7 +18,7 @@ use hex_literal::hex;
 use crate::{
     builder::{
         to_box::{jumbf_size, write_jumbf},
-        PlaceholderDataBox, ToBox,
+        PlaceholderDataBox, SuperBoxBuilder, ToBox,
     },
     BoxType,
 };
@@ -147,3 +147,198 @@ fn offset_before_write() {
     assert_eq!(jumbf_size(&pbox).unwrap(), 24);
     assert_eq!(pbox.offset(), None);
 }
+
+#[test]
+fn offset_within() {
+    let pbox = PlaceholderDataBox::new(RANDOM_BOX_TYPE, 4);
+    assert_eq!(pbox.offset_within(0), None);
+
+    let sbox = SuperBoxBuilder::new([0; 16]).add_borrowed_child_box(&pbox);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    jumbf.write_all(b"ABCD").unwrap();
+    sbox.write_jumbf(&mut jumbf).unwrap();
+
+    assert_eq!(pbox.offset(), Some(45));
+    assert_eq!(pbox.offset_within(4), Some(41));
+    assert_eq!(pbox.offset_within(46), None);
+}
+
+#[test]
+fn relocate() {
+    let pbox = PlaceholderDataBox::new(RANDOM_BOX_TYPE, 4);
+    let sbox = SuperBoxBuilder::new([0; 16]).add_borrowed_child_box(&pbox);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+    let jumbf = jumbf.into_inner();
+
+    // Copy the JUMBF into the middle of a container.
+    let mut container = Cursor::new(vec![0xffu8; 20]);
+    container.get_mut().splice(10..10, jumbf.iter().copied());
+
+    pbox.relocate(10).unwrap();
+    assert_eq!(pbox.offset(), Some(51));
+
+    pbox.replace_payload(&mut container, b"WXYZ").unwrap();
+    assert_eq!(&container.get_ref()[51..55], b"WXYZ");
+    assert_eq!(&container.get_ref()[55..], &[0xff; 10]);
+
+    pbox.relocate(-51).unwrap();
+    assert_eq!(pbox.offset(), Some(0));
+}
+
+#[test]
+fn error_relocate() {
+    let pbox = PlaceholderDataBox::new(RANDOM_BOX_TYPE, 4);
+
+    let err = pbox.relocate(10).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "PlaceholderDataBox::relocate: no offset recorded; call write_jumbf() first"
+    );
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    jumbf.write_all(b"ABCD").unwrap();
+    write_jumbf(&pbox, &mut jumbf).unwrap();
+
+    let err = pbox.relocate(-13).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "PlaceholderDataBox::relocate: moving offset 12 by -13 bytes is out of range"
+    );
+    assert_eq!(pbox.offset(), Some(12));
+}
+
+#[test]
+fn finalize_payload_shrinks_box() {
+    let pbox = PlaceholderDataBox::new(RANDOM_BOX_TYPE, 24);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    write_jumbf(&pbox, &mut jumbf).unwrap();
+
+    pbox.finalize_payload(&mut jumbf, b"0123456789").unwrap();
+
+    let expected_jumbf = hex!(
+        "00000012" // box size
+        "61626364" // box type = 'abcd'
+        "30313233343536373839" // payload
+        // ---
+        "0000000e" // box size
+        "66726565" // box type = 'free'
+        "000000000000" // unused space
+    );
+
+    assert_eq!(*jumbf.get_ref(), expected_jumbf);
+}
+
+#[test]
+fn finalize_payload_exact_size() {
+    let pbox = PlaceholderDataBox::new(RANDOM_BOX_TYPE, 10);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    write_jumbf(&pbox, &mut jumbf).unwrap();
+
+    pbox.finalize_payload(&mut jumbf, b"0123456789").unwrap();
+
+    let expected_jumbf = hex!(
+        "00000012" // box size
+        "61626364" // box type = 'abcd'
+        "30313233343536373839" // payload
+    );
+
+    assert_eq!(*jumbf.get_ref(), expected_jumbf);
+}
+
+#[test]
+fn finalize_payload_too_little_space_for_free_box() {
+    let pbox = PlaceholderDataBox::new(RANDOM_BOX_TYPE, 16);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    write_jumbf(&pbox, &mut jumbf).unwrap();
+
+    // Fill with non-zero content first to verify that the padding is
+    // rewritten.
+    pbox.replace_payload(&mut jumbf, &[0xff; 16]).unwrap();
+    pbox.finalize_payload(&mut jumbf, b"0123456789").unwrap();
+
+    let expected_jumbf = hex!(
+        "00000018" // box size
+        "61626364" // box type = 'abcd'
+        "30313233343536373839" // payload
+        "000000000000" // zero padding
+    );
+
+    assert_eq!(*jumbf.get_ref(), expected_jumbf);
+}
+
+#[cfg(feature = "parser")]
+#[test]
+fn finalize_payload_within_super_box() {
+    use crate::{
+        builder::SuperBoxBuilder,
+        parser::{ChildBox, SuperBox},
+    };
+
+    let pbox = PlaceholderDataBox::new(RANDOM_BOX_TYPE, 32);
+
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .add_borrowed_child_box(&pbox);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+
+    let len_before = jumbf.get_ref().len();
+
+    pbox.finalize_payload(&mut jumbf, b"signature").unwrap();
+    assert_eq!(jumbf.get_ref().len(), len_before);
+
+    let jumbf = jumbf.into_inner();
+    let (rem, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    assert!(rem.is_empty());
+
+    let children: Vec<(BoxType, &[u8])> = sbox
+        .child_boxes
+        .iter()
+        .filter_map(ChildBox::as_data_box)
+        .map(|dbox| (dbox.tbox, dbox.data))
+        .collect();
+
+    assert_eq!(
+        children,
+        vec![
+            (RANDOM_BOX_TYPE, &b"signature"[..]),
+            (BoxType(*b"free"), &[0u8; 15][..]),
+        ]
+    );
+}
+
+#[test]
+fn error_finalize_payload_too_large() {
+    let pbox = PlaceholderDataBox::new(RANDOM_BOX_TYPE, 16);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    write_jumbf(&pbox, &mut jumbf).unwrap();
+
+    let err = pbox.finalize_payload(&mut jumbf, &[1u8; 17]).unwrap_err();
+
+    assert_eq!(
+            "Custom { kind: Other, error: \"finalize_payload: payload (17 bytes) is larger than reserved capacity (16 bytes)\" }",
+            format!("{err:?}")
+        );
+}
+
+#[test]
+fn error_finalize_payload_write_jumbf_not_called() {
+    let pbox = PlaceholderDataBox::new(RANDOM_BOX_TYPE, 16);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let err = pbox.finalize_payload(&mut jumbf, &[1u8; 8]).unwrap_err();
+
+    assert_eq!(
+            "Custom { kind: Other, error: \"finalize_payload: no offset recorded; call write_jumbf() first\" }",
+            format!("{err:?}")
+        );
+
+    assert_eq!(*jumbf.get_ref(), []);
+}
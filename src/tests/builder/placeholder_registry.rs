@@ -0,0 +1,160 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::Cursor;
+
+use hex_literal::hex;
+
+use crate::{
+    builder::{PlaceholderRegistry, SuperBoxBuilder, ToBox},
+    BoxType,
+};
+
+const SIG_BOX_TYPE: BoxType = BoxType(*b"uuid");
+const HASH_BOX_TYPE: BoxType = BoxType(*b"hash");
+
+#[test]
+fn nested_placeholders() {
+    let mut registry = PlaceholderRegistry::new();
+    registry.add("sig", SIG_BOX_TYPE, 8).unwrap();
+    registry.add("hash", HASH_BOX_TYPE, 4).unwrap();
+
+    assert_eq!(registry.get("sig").unwrap().box_type(), SIG_BOX_TYPE);
+    assert!(registry.get("nope").is_none());
+    assert_eq!(registry.offset("sig"), None);
+    assert_eq!(registry.offsets().count(), 0);
+
+    let inner = SuperBoxBuilder::new([0; 16]).add_borrowed_child_box(registry.get("hash").unwrap());
+
+    let outer = SuperBoxBuilder::new([0; 16])
+        .add_borrowed_child_box(registry.get("sig").unwrap())
+        .add_borrowed_child_box(&inner);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    outer.write_jumbf(&mut jumbf).unwrap();
+
+    assert_eq!(registry.offset("sig"), Some(41));
+    assert_eq!(registry.offset("hash"), Some(90));
+    assert_eq!(
+        registry.offsets().collect::<Vec<_>>(),
+        vec![("hash", 90), ("sig", 41)]
+    );
+
+    registry.replace(&mut jumbf, "sig", b"SIGNATUR").unwrap();
+    registry.replace(&mut jumbf, "hash", b"HASH").unwrap();
+
+    let expected_jumbf = hex!(
+        "0000005e" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ---
+            "00000010" // box size
+            "75756964" // box type = 'uuid'
+            "5349474e41545552" // payload
+            // ---
+            "0000002d" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "00" // toggles
+                // ---
+                "0000000c" // box size
+                "68617368" // box type = 'hash'
+                "48415348" // payload
+    );
+
+    assert_eq!(*jumbf.get_ref(), expected_jumbf);
+}
+
+#[test]
+fn error_duplicate_label() {
+    let mut registry = PlaceholderRegistry::new();
+    registry.add("sig", SIG_BOX_TYPE, 8).unwrap();
+
+    let err = registry.add("sig", SIG_BOX_TYPE, 16).unwrap_err();
+    assert_eq!(
+        "Custom { kind: Other, error: \"PlaceholderRegistry: duplicate label \\\"sig\\\"\" }",
+        format!("{err:?}")
+    );
+
+    // The original placeholder is unchanged.
+    assert_eq!(registry.get("sig").unwrap().payload_size().unwrap(), 8);
+}
+
+#[test]
+fn error_unknown_label() {
+    let registry = PlaceholderRegistry::new();
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let err = registry.replace(&mut jumbf, "sig", b"x").unwrap_err();
+
+    assert_eq!(
+        "Custom { kind: Other, error: \"PlaceholderRegistry: unknown label \\\"sig\\\"\" }",
+        format!("{err:?}")
+    );
+}
+
+#[test]
+fn error_replace_before_write() {
+    let mut registry = PlaceholderRegistry::new();
+    registry.add("sig", SIG_BOX_TYPE, 8).unwrap();
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let err = registry.replace(&mut jumbf, "sig", b"x").unwrap_err();
+
+    assert_eq!(
+        "Custom { kind: Other, error: \"replace_payload: no offset recorded; call write_jumbf() first\" }",
+        format!("{err:?}")
+    );
+}
+
+#[test]
+fn relocate() {
+    let mut registry = PlaceholderRegistry::new();
+    registry.add("a", HASH_BOX_TYPE, 4).unwrap();
+    registry.add("b", HASH_BOX_TYPE, 4).unwrap();
+    registry.add("unused", HASH_BOX_TYPE, 4).unwrap();
+
+    let sbox = SuperBoxBuilder::new([0; 16])
+        .add_borrowed_child_box(registry.get("a").unwrap())
+        .add_borrowed_child_box(registry.get("b").unwrap());
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+
+    assert_eq!(
+        registry.offsets().collect::<Vec<_>>(),
+        [("a", 41), ("b", 53)]
+    );
+
+    registry.relocate(100).unwrap();
+    assert_eq!(
+        registry.offsets().collect::<Vec<_>>(),
+        [("a", 141), ("b", 153)]
+    );
+
+    // No offset should change if any would be out of range.
+    let err = registry.relocate(-142).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "PlaceholderRegistry: moving offset 141 of \"a\" by -142 bytes is out of range"
+    );
+    assert_eq!(
+        registry.offsets().collect::<Vec<_>>(),
+        [("a", 141), ("b", 153)]
+    );
+}
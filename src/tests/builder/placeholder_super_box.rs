@@ -0,0 +1,241 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::Cursor;
+
+use hex_literal::hex;
+
+use crate::{
+    builder::{DataBoxBuilder, PlaceholderSuperBox, SuperBoxBuilder, ToBox},
+    BoxType,
+};
+
+const RANDOM_BOX_TYPE: BoxType = BoxType(*b"abcd");
+
+fn write_with_placeholder(placeholder: &PlaceholderSuperBox) -> Cursor<Vec<u8>> {
+    let outer = SuperBoxBuilder::new([0; 16]).add_borrowed_child_box(placeholder);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    outer.write_jumbf(&mut jumbf).unwrap();
+    jumbf
+}
+
+#[test]
+fn reserved_space() {
+    let placeholder = PlaceholderSuperBox::new(16);
+
+    assert_eq!(placeholder.box_type(), BoxType(*b"free"));
+    assert_eq!(placeholder.payload_size().unwrap(), 8);
+    assert_eq!(placeholder.offset(), None);
+
+    let jumbf = write_with_placeholder(&placeholder);
+
+    let expected_jumbf = hex!(
+        "00000031" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ---
+            "00000010" // box size
+            "66726565" // box type = 'free'
+            "0000000000000000" // reserved space
+    );
+
+    assert_eq!(*jumbf.get_ref(), expected_jumbf);
+    assert_eq!(placeholder.offset(), Some(33));
+}
+
+#[test]
+fn relocate() {
+    let placeholder = PlaceholderSuperBox::new(41);
+    assert!(placeholder.relocate(1).is_err());
+
+    let jumbf = write_with_placeholder(&placeholder);
+    assert_eq!(placeholder.offset_within(0), Some(33));
+
+    // Move the JUMBF 7 bytes into a container.
+    let mut container = Cursor::new([&[0xffu8; 7][..], jumbf.get_ref()].concat());
+    placeholder.relocate(7).unwrap();
+    assert_eq!(placeholder.offset(), Some(40));
+    assert_eq!(placeholder.offset_within(7), Some(33));
+
+    let sig = SuperBoxBuilder::new([0; 16]);
+    placeholder.replace_super_box(&mut container, &sig).unwrap();
+
+    let container = container.into_inner();
+    assert_eq!(container[..7], [0xff; 7]);
+    assert_eq!(container[7..11], hex!("0000004a"));
+    assert_eq!(&container[44..48], b"jumb");
+    assert_eq!(&container[77..81], b"free");
+    assert_eq!(container.len(), 7 + 74);
+}
+
+#[test]
+fn replace_exact_size() {
+    let placeholder = PlaceholderSuperBox::new(45);
+    let mut jumbf = write_with_placeholder(&placeholder);
+
+    let sbox = SuperBoxBuilder::new([0; 16])
+        .add_child_box(DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"ABCD"));
+
+    placeholder.replace_super_box(&mut jumbf, &sbox).unwrap();
+
+    let expected_jumbf = hex!(
+        "0000004e" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ---
+            "0000002d" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "00" // toggles
+                // ---
+                "0000000c" // box size
+                "61626364" // box type = 'abcd'
+                "41424344" // payload
+    );
+
+    assert_eq!(*jumbf.get_ref(), expected_jumbf);
+}
+
+#[test]
+fn replace_with_free_space() {
+    let placeholder = PlaceholderSuperBox::new(55);
+    let mut jumbf = write_with_placeholder(&placeholder);
+
+    let sbox = SuperBoxBuilder::new([0; 16])
+        .add_child_box(DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"ABCD"));
+
+    placeholder.replace_super_box(&mut jumbf, &sbox).unwrap();
+
+    let expected_jumbf = hex!(
+        "00000058" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ---
+            "0000002d" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "00" // toggles
+                // ---
+                "0000000c" // box size
+                "61626364" // box type = 'abcd'
+                "41424344" // payload
+            // ---
+            "0000000a" // box size
+            "66726565" // box type = 'free'
+            "0000" // unused space
+    );
+
+    assert_eq!(*jumbf.get_ref(), expected_jumbf);
+}
+
+#[test]
+fn error_too_large() {
+    let placeholder = PlaceholderSuperBox::new(40);
+    let mut jumbf = write_with_placeholder(&placeholder);
+    let before = jumbf.get_ref().clone();
+
+    let sbox = SuperBoxBuilder::new([0; 16])
+        .add_child_box(DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"ABCD"));
+
+    let err = placeholder
+        .replace_super_box(&mut jumbf, &sbox)
+        .unwrap_err();
+
+    assert_eq!(
+        "Custom { kind: Other, error: \"replace_super_box: superbox (45 bytes) is larger than reserved capacity (40 bytes)\" }",
+        format!("{err:?}")
+    );
+
+    assert_eq!(*jumbf.get_ref(), before);
+}
+
+#[test]
+fn error_unused_space_too_small() {
+    let placeholder = PlaceholderSuperBox::new(50);
+    let mut jumbf = write_with_placeholder(&placeholder);
+    let before = jumbf.get_ref().clone();
+
+    let sbox = SuperBoxBuilder::new([0; 16])
+        .add_child_box(DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"ABCD"));
+
+    let err = placeholder
+        .replace_super_box(&mut jumbf, &sbox)
+        .unwrap_err();
+
+    assert_eq!(
+        "Custom { kind: Other, error: \"replace_super_box: unused space (5 bytes) is too small for a free box\" }",
+        format!("{err:?}")
+    );
+
+    assert_eq!(*jumbf.get_ref(), before);
+}
+
+#[test]
+fn error_write_jumbf_not_called() {
+    let placeholder = PlaceholderSuperBox::new(45);
+    let sbox = SuperBoxBuilder::new([0; 16]);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let err = placeholder
+        .replace_super_box(&mut jumbf, &sbox)
+        .unwrap_err();
+
+    assert_eq!(
+        "Custom { kind: Other, error: \"replace_super_box: no offset recorded; call write_jumbf() first\" }",
+        format!("{err:?}")
+    );
+
+    assert_eq!(*jumbf.get_ref(), []);
+}
+
+#[test]
+fn error_reserved_size_too_small() {
+    let placeholder = PlaceholderSuperBox::new(7);
+
+    let outer = SuperBoxBuilder::new([0; 16]).add_borrowed_child_box(&placeholder);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let err = outer.write_jumbf(&mut jumbf).unwrap_err();
+
+    assert_eq!(
+        "Custom { kind: Other, error: \"PlaceholderSuperBox: reserved size (7 bytes) must be at least 8 bytes\" }",
+        format!("{err:?}")
+    );
+}
+
+#[test]
+fn error_write_payload_only() {
+    let placeholder = PlaceholderSuperBox::new(16);
+
+    let mut payload = Cursor::new(Vec::<u8>::new());
+    let err = placeholder.write_payload(&mut payload).unwrap_err();
+
+    assert_eq!(
+        "Custom { kind: Other, error: \"placeholder stream should have some data already\" }",
+        format!("{err:?}")
+    );
+}
@@ -0,0 +1,136 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{
+    io::{Cursor, Result},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{
+    builder::{DataBoxBuilder, SuperBoxBuilder, ToBox, WriteAndSeek},
+    BoxType,
+};
+
+const RANDOM_BOX_TYPE: BoxType = BoxType(*b"abcd");
+
+// A box which counts how often its size is requested. Its payload is
+// `len` bytes long.
+struct CountingBox {
+    len: AtomicUsize,
+    size_calls: AtomicUsize,
+}
+
+impl CountingBox {
+    fn new(len: usize) -> Self {
+        Self {
+            len: AtomicUsize::new(len),
+            size_calls: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl ToBox for CountingBox {
+    fn box_type(&self) -> BoxType {
+        RANDOM_BOX_TYPE
+    }
+
+    fn payload_size(&self) -> Result<usize> {
+        self.size_calls.fetch_add(1, Ordering::Relaxed);
+        Ok(self.len.load(Ordering::Relaxed))
+    }
+
+    fn write_payload(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
+        to_stream.write_all(&vec![0x5a; self.len.load(Ordering::Relaxed)])
+    }
+}
+
+#[test]
+fn sizes() {
+    let leaf = CountingBox::new(10);
+
+    let inner = SuperBoxBuilder::new([0; 16])
+        .set_label("inner")
+        .add_borrowed_child_box(&leaf);
+
+    let sbox = SuperBoxBuilder::new([0; 16])
+        .add_borrowed_child_box(&inner)
+        .add_child_box(DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"ABCD"));
+
+    let prepared = sbox.prepare().unwrap();
+
+    // Outer description box (25) + inner superbox (8 + 31 + 18) + data box
+    // (12).
+    assert_eq!(prepared.payload_size(), 94);
+    assert_eq!(prepared.jumbf_size(), 102);
+    assert_eq!(prepared.payload_size(), sbox.payload_size().unwrap());
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    prepared.write_jumbf(&mut jumbf).unwrap();
+    assert_eq!(jumbf.into_inner().len(), 102);
+}
+
+#[test]
+fn payload_size_called_once() {
+    let leaves: Vec<CountingBox> = (0..4).map(CountingBox::new).collect();
+
+    let mut inner = SuperBoxBuilder::new([0; 16]);
+    for leaf in &leaves[1..] {
+        inner = inner.add_borrowed_child_box(leaf);
+    }
+
+    let middle = SuperBoxBuilder::new([0; 16]).add_borrowed_child_box(&inner);
+
+    let sbox = SuperBoxBuilder::new([0; 16])
+        .add_borrowed_child_box(&leaves[0])
+        .add_borrowed_child_box(&middle);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+
+    for leaf in &leaves {
+        assert_eq!(leaf.size_calls.load(Ordering::Relaxed), 1);
+    }
+}
+
+#[test]
+fn write_twice() {
+    let sbox = SuperBoxBuilder::new([0; 16])
+        .add_child_box(DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"ABCD"));
+
+    let prepared = sbox.prepare().unwrap();
+
+    let mut first = Cursor::new(Vec::<u8>::new());
+    prepared.write_jumbf(&mut first).unwrap();
+
+    let mut second = Cursor::new(Vec::<u8>::new());
+    prepared.write_jumbf(&mut second).unwrap();
+
+    assert_eq!(first.into_inner(), second.into_inner());
+}
+
+#[test]
+fn error_size_changed_after_prepare() {
+    let leaf = CountingBox::new(4);
+    let sbox = SuperBoxBuilder::new([0; 16]).add_borrowed_child_box(&leaf);
+
+    let prepared = sbox.prepare().unwrap();
+    leaf.len.store(5, Ordering::Relaxed);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let err = prepared.write_jumbf(&mut jumbf).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "write_jumbf: box type b\"abcd\" wrote 5 bytes of payload, but payload_size() reported 4 bytes"
+    );
+}
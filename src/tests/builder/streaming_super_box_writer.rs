@@ -0,0 +1,217 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::{
+    io::{Cursor, Result, Write},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use hex_literal::hex;
+
+use crate::{
+    builder::{
+        DataBoxBuilder, PlaceholderDataBox, StreamingSuperBoxWriter, SuperBoxBuilder, ToBox,
+        WriteAndSeek,
+    },
+    BoxType,
+};
+
+const RANDOM_BOX_TYPE: BoxType = BoxType(*b"abcd");
+
+// A box whose payload is "expensive" to generate. Counts the number of times
+// the payload was generated.
+struct GeneratedBox {
+    calls: AtomicUsize,
+}
+
+impl ToBox for GeneratedBox {
+    fn box_type(&self) -> BoxType {
+        RANDOM_BOX_TYPE
+    }
+
+    fn write_payload(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        to_stream.write_all(b"ABCD")
+    }
+}
+
+#[test]
+fn matches_super_box_builder() {
+    let expected_jumbf = hex!(
+        "00000056" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000026" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "03" // toggles
+            "746573742e64657363626f7800" // label
+            // ---
+            "0000000c" // box size
+            "61626364" // box type = 'abcd'
+            "41424344" // payload
+            // ---
+            "0000001c" // box size
+            "61626364" // box type = 'abcd'
+            "0000000000000000000000000000000000000000" // payload
+    );
+
+    let desc = SuperBoxBuilder::new([0u8; 16]).set_label("test.descbox");
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let mut sbox = StreamingSuperBoxWriter::new(&mut jumbf, &desc).unwrap();
+    sbox.write_child_box(&DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"ABCD"))
+        .unwrap();
+    sbox.write_child_box(&DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, &[0u8; 20]))
+        .unwrap();
+    sbox.finish().unwrap();
+
+    assert_eq!(*jumbf.get_ref(), expected_jumbf);
+
+    let sbox = SuperBoxBuilder::new([0u8; 16])
+        .set_label("test.descbox")
+        .add_child_box(DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"ABCD"))
+        .add_child_box(DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, &[0u8; 20]));
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+    assert_eq!(*jumbf.into_inner(), expected_jumbf);
+}
+
+#[test]
+fn nested_super_box() {
+    let expected_jumbf = hex!(
+        "0000004e" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ---
+            "0000002d" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "01010101010101010101010101010101" // UUID
+                "00" // toggles
+                // ---
+                "0000000c" // box size
+                "61626364" // box type = 'abcd'
+                "41424344" // payload
+    );
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let mut sbox =
+        StreamingSuperBoxWriter::new(&mut jumbf, &SuperBoxBuilder::new([0u8; 16])).unwrap();
+
+    let mut nested = sbox
+        .begin_child_super_box(&SuperBoxBuilder::new([1u8; 16]))
+        .unwrap();
+    nested
+        .write_child_box(&DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"ABCD"))
+        .unwrap();
+    nested.finish().unwrap();
+
+    sbox.finish().unwrap();
+
+    assert_eq!(*jumbf.into_inner(), expected_jumbf);
+}
+
+#[test]
+fn payload_written_once() {
+    let gbox = GeneratedBox {
+        calls: AtomicUsize::new(0),
+    };
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let mut sbox =
+        StreamingSuperBoxWriter::new(&mut jumbf, &SuperBoxBuilder::new([0u8; 16])).unwrap();
+    sbox.write_child_box(&gbox).unwrap();
+    sbox.finish().unwrap();
+
+    assert_eq!(gbox.calls.load(Ordering::Relaxed), 1);
+
+    // By contrast, SuperBoxBuilder needs to call write_payload() twice.
+    let sbox = SuperBoxBuilder::new([0u8; 16]).add_borrowed_child_box(&gbox);
+
+    let mut jumbf2 = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf2).unwrap();
+
+    assert_eq!(gbox.calls.load(Ordering::Relaxed), 3);
+    assert_eq!(jumbf.into_inner(), jumbf2.into_inner());
+}
+
+#[test]
+fn children_from_builder() {
+    let gbox = GeneratedBox {
+        calls: AtomicUsize::new(0),
+    };
+
+    let desc = SuperBoxBuilder::new([0u8; 16]).add_borrowed_child_box(&gbox);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let sbox = StreamingSuperBoxWriter::new(&mut jumbf, &desc).unwrap();
+    sbox.finish().unwrap();
+
+    assert_eq!(gbox.calls.load(Ordering::Relaxed), 1);
+
+    let mut jumbf2 = Cursor::new(Vec::<u8>::new());
+    desc.write_jumbf(&mut jumbf2).unwrap();
+
+    assert_eq!(jumbf.into_inner(), jumbf2.into_inner());
+}
+
+#[test]
+fn nested_builder_streamed() {
+    let gbox = GeneratedBox {
+        calls: AtomicUsize::new(0),
+    };
+
+    let inner = SuperBoxBuilder::new([2u8; 16]).add_borrowed_child_box(&gbox);
+    let nested = SuperBoxBuilder::new([1u8; 16]).add_borrowed_child_box(&inner);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let mut sbox =
+        StreamingSuperBoxWriter::new(&mut jumbf, &SuperBoxBuilder::new([0u8; 16])).unwrap();
+    sbox.write_child_box(&nested).unwrap();
+    sbox.finish().unwrap();
+
+    assert_eq!(gbox.calls.load(Ordering::Relaxed), 1);
+
+    let sbox = SuperBoxBuilder::new([0u8; 16]).add_borrowed_child_box(&nested);
+
+    let mut jumbf2 = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf2).unwrap();
+
+    assert_eq!(jumbf.into_inner(), jumbf2.into_inner());
+}
+
+#[test]
+fn placeholder_offset() {
+    let pbox = PlaceholderDataBox::new(RANDOM_BOX_TYPE, 16);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    jumbf.write_all(b"ABCD").unwrap();
+
+    let mut sbox =
+        StreamingSuperBoxWriter::new(&mut jumbf, &SuperBoxBuilder::new([0u8; 16])).unwrap();
+    sbox.write_child_box(&pbox).unwrap();
+    sbox.finish().unwrap();
+
+    assert_eq!(pbox.offset(), Some(45));
+
+    pbox.replace_payload(&mut jumbf, b"0123456789abcdef")
+        .unwrap();
+
+    assert_eq!(&jumbf.get_ref()[45..], b"0123456789abcdef");
+    assert_eq!(&jumbf.get_ref()[4..8], &hex!("00000039"));
+}
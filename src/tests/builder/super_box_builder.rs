@@ -11,13 +11,16 @@
 // specific language governing permissions and limitations under
 // each license.
 
-use std::io::Cursor;
+use std::io::{Cursor, ErrorKind};
 
 use hex_literal::hex;
 
 use crate::{
-    builder::{DataBoxBuilder, PlaceholderDataBox, SuperBoxBuilder},
-    BoxType,
+    builder::{
+        BuilderError, CallbackDataBox, DataBoxBuilder, PaddingBoxBuilder, PlaceholderDataBox,
+        SuperBoxBuilder, ToBox,
+    },
+    BoxType, Toggles, Uuid,
 };
 
 // Used here as an illustration only. This crate does not parse JSON content.
@@ -38,7 +41,31 @@ fn basic_case() {
     );
 
     let sbox =
-        SuperBoxBuilder::new(&hex!("00000000000000000000000000000000")).set_label("test.descbox");
+        SuperBoxBuilder::new(hex!("00000000000000000000000000000000")).set_label("test.descbox");
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+    assert_eq!(*jumbf.into_inner(), expected_jumbf);
+}
+
+#[test]
+fn codestream() {
+    let expected_jumbf = hex!(
+        "0000002d" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "6579d6fbdba2446bb2ac1b82feeb89d1" // UUID (codestream)
+            "00" // toggles
+            // ----
+            "0000000c" // box size
+            "6a703263" // box type = 'jp2c'
+            "ffd8ffe0" // payload
+    );
+
+    let sbox = SuperBoxBuilder::codestream(vec![0xff, 0xd8, 0xff, 0xe0]);
+    assert_eq!(sbox.uuid(), Uuid::CODESTREAM);
+    assert_eq!(sbox.child_box_count(), 1);
 
     let mut jumbf = Cursor::new(Vec::<u8>::new());
     sbox.write_jumbf(&mut jumbf).unwrap();
@@ -57,7 +84,7 @@ fn non_requestable_label() {
             "746573742e64657363626f7800" // label
     );
 
-    let sbox = SuperBoxBuilder::new(&hex!("00000000000000000000000000000000"))
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
         .set_non_requestable_label("test.descbox");
 
     let mut jumbf = Cursor::new(Vec::<u8>::new());
@@ -77,7 +104,29 @@ fn with_id() {
             "00001000" // ID
     );
 
-    let sbox = SuperBoxBuilder::new(&hex!("00000000000000000000000000000000")).set_id(4096);
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000")).set_id(4096);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+    assert_eq!(*jumbf.into_inner(), expected_jumbf);
+}
+
+#[test]
+fn with_extra_toggles() {
+    let expected_jumbf = hex!(
+        "0000002e" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000026" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "83" // toggles
+            "746573742e64657363626f7800" // label
+    );
+
+    // HAS_ID and HAS_HASH are ignored since no ID or hash was provided.
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .set_label("test.descbox")
+        .set_toggles(Toggles(0x80) | Toggles::HAS_ID | Toggles::HAS_HASH);
 
     let mut jumbf = Cursor::new(Vec::<u8>::new());
     sbox.write_jumbf(&mut jumbf).unwrap();
@@ -98,7 +147,7 @@ fn with_hash() {
             "686173682e2e2e2e2e2e2e2e2e2e2e2e" // hash
     );
 
-    let sbox = SuperBoxBuilder::new(&hex!("00000000000000000000000000000000"))
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
         .set_label("test.descbox")
         .set_sha256_hash(b"This is a bogus hash............" as &[u8; 32]);
 
@@ -130,7 +179,7 @@ fn with_private_box() {
         .to_vec(),
     );
 
-    let sbox = SuperBoxBuilder::new(&hex!("00000000000000000000000000000000"))
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
         .set_label("test.descbox")
         .set_private_box(private);
 
@@ -139,6 +188,91 @@ fn with_private_box() {
     assert_eq!(*jumbf.into_inner(), expected_jumbf);
 }
 
+#[test]
+fn with_private_super_box() {
+    let expected_jumbf = hex!(
+        "0000005b" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000053" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "13" // toggles
+            "746573742e64657363626f7800" // label
+                "0000002d" // box size
+                "6a756d62" // box type = 'jumb'
+                    "0000001b" // box size
+                    "6a756d64" // box type = 'jumd'
+                    "00000000000000000000000000000000" // UUID
+                    "03" // toggles
+                    "7000" // label ("p")
+                    // ---
+                    "0000000a" // box size
+                    "61626364" // box type = 'abcd'
+                    "6869" // payload ("hi")
+    );
+
+    let private = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .set_label("p")
+        .add_child_box(DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"hi"));
+
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .set_label("test.descbox")
+        .set_private_box(private);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+    assert_eq!(*jumbf.into_inner(), expected_jumbf);
+}
+
+#[test]
+fn with_salt() {
+    let expected_jumbf = hex!(
+        "00000039" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000031" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "10" // toggles
+                "00000018" // box size
+                "63327368" // box type = 'c2sh'
+                "000102030405060708090a0b0c0d0e0f" // salt
+    );
+
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .set_salt(&hex!("000102030405060708090a0b0c0d0e0f"));
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+    assert_eq!(*jumbf.into_inner(), expected_jumbf);
+}
+
+#[cfg(all(feature = "parser", feature = "salt"))]
+#[test]
+fn with_random_salt() {
+    use crate::parser::SuperBox;
+
+    let write_salted = || {
+        let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+            .set_label("test.descbox")
+            .set_random_salt();
+
+        let mut jumbf = Cursor::new(Vec::<u8>::new());
+        sbox.write_jumbf(&mut jumbf).unwrap();
+        jumbf.into_inner()
+    };
+
+    let jumbf1 = write_salted();
+    let jumbf2 = write_salted();
+    assert_ne!(jumbf1, jumbf2);
+
+    let (rem, sbox) = SuperBox::from_slice(&jumbf1).unwrap();
+    assert!(rem.is_empty());
+
+    let salt = sbox.desc.private.as_ref().unwrap();
+    assert_eq!(salt.tbox, BoxType(*b"c2sh"));
+    assert_eq!(salt.data.len(), 16);
+}
+
 #[test]
 fn no_label() {
     let expected_jumbf = hex!(
@@ -150,7 +284,7 @@ fn no_label() {
             "00" // toggles
     );
 
-    let sbox = SuperBoxBuilder::new(&hex!("00000000000000000000000000000000"));
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"));
 
     let mut jumbf = Cursor::new(Vec::<u8>::new());
     sbox.write_jumbf(&mut jumbf).unwrap();
@@ -186,7 +320,7 @@ fn with_child_boxes() {
 
     let cbox2 = DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"ABCD");
 
-    let sbox = SuperBoxBuilder::new(&hex!("00000000000000000000000000000000"))
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
         .add_child_box(cbox1)
         .add_child_box(cbox2);
 
@@ -195,6 +329,79 @@ fn with_child_boxes() {
     assert_eq!(*jumbf.into_inner(), expected_jumbf);
 }
 
+#[test]
+fn with_nested_super_boxes() {
+    let expected_jumbf = hex!(
+        "0000005a" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ---
+            "0000002d" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "00" // toggles
+                // ---
+                "0000000c" // box size
+                "61626364" // box type = 'abcd'
+                "41424344" // payload
+            // ---
+            "0000000c" // box size
+            "61626364" // box type = 'abcd'
+            "45464748" // payload
+    );
+
+    let inner = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .add_child_box(DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"ABCD"));
+
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .add_borrowed_child_box(&inner)
+        .add_child_box(DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"EFGH"));
+
+    assert_eq!(sbox.payload_size().unwrap(), expected_jumbf.len() - 8);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+    assert_eq!(*jumbf.into_inner(), expected_jumbf);
+
+    // Writing the nested superbox as a payload should produce the same
+    // result.
+    let mut payload = Cursor::new(Vec::<u8>::new());
+    sbox.write_payload(&mut payload).unwrap();
+    assert_eq!(*payload.into_inner(), expected_jumbf[8..]);
+}
+
+#[test]
+fn deeply_nested() {
+    // Neither sizing, writing, nor dropping this tree should overflow the
+    // stack.
+    const DEPTH: usize = 100_000;
+    const LEVEL_LEN: usize = 8 + 25;
+
+    let mut sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"));
+    for _ in 1..DEPTH {
+        sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000")).add_child_box(sbox);
+    }
+
+    assert_eq!(sbox.payload_size().unwrap(), DEPTH * LEVEL_LEN - 8);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+
+    let jumbf = jumbf.into_inner();
+    assert_eq!(jumbf.len(), DEPTH * LEVEL_LEN);
+
+    for (level, header) in jumbf.chunks(LEVEL_LEN).enumerate() {
+        let size = ((DEPTH - level) * LEVEL_LEN) as u32;
+        assert_eq!(header[0..4], size.to_be_bytes());
+        assert_eq!(&header[4..8], b"jumb");
+    }
+}
+
 #[test]
 fn with_placeholder() {
     let expected_jumbf = hex!(
@@ -224,7 +431,7 @@ fn with_placeholder() {
 
     let pbox = PlaceholderDataBox::new(RANDOM_BOX_TYPE, 16);
 
-    let sbox = SuperBoxBuilder::new(&hex!("00000000000000000000000000000000"))
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
         .add_child_box(cbox)
         .add_borrowed_child_box(&pbox);
 
@@ -255,3 +462,592 @@ fn with_placeholder() {
 
     assert_eq!(*jumbf.get_ref(), expected_jumbf);
 }
+
+#[test]
+fn write_at_offset() {
+    let pbox = PlaceholderDataBox::new(RANDOM_BOX_TYPE, 8);
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .set_label("test.descbox")
+        .add_borrowed_child_box(&pbox);
+
+    let mut expected = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut expected).unwrap();
+    let expected = expected.into_inner();
+    assert_eq!(pbox.offset(), Some(54));
+
+    let mut container = Cursor::new(vec![0xffu8; 200]);
+    sbox.write_jumbf_at(&mut container, 100).unwrap();
+    assert_eq!(container.position(), 100 + expected.len() as u64);
+    assert_eq!(pbox.offset(), Some(154));
+
+    pbox.replace_payload(&mut container, b"ABCDEFGH").unwrap();
+
+    let container = container.into_inner();
+    assert!(container[..100].iter().all(|b| *b == 0xff));
+    assert_eq!(container[100..154], expected[..54]);
+    assert_eq!(&container[154..162], b"ABCDEFGH");
+    assert!(container[162..].iter().all(|b| *b == 0xff));
+}
+
+#[test]
+fn error_write_at_offset_invalid_label() {
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000")).set_label("a/b");
+
+    let mut container = Cursor::new(vec![0xffu8; 100]);
+    container.set_position(10);
+
+    let err = sbox.write_jumbf_at(&mut container, 50).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+    // Nothing should have been written and the stream should not have moved.
+    assert_eq!(container.position(), 10);
+    assert!(container.into_inner().iter().all(|b| *b == 0xff));
+}
+
+#[test]
+fn error_invalid_label() {
+    for (label, c) in [
+        ("test/descbox", '/'),
+        ("test\0descbox", '\0'),
+        ("test.descbox?hl=abc", '?'),
+        ("#test.descbox", '#'),
+    ] {
+        let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000")).set_label(label);
+
+        let mut jumbf = Cursor::new(Vec::<u8>::new());
+        let err = sbox.write_jumbf(&mut jumbf).unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(
+            err.get_ref()
+                .and_then(|e| e.downcast_ref::<BuilderError>())
+                .unwrap(),
+            &BuilderError::InvalidLabelCharacter {
+                label: label.to_owned(),
+                c
+            }
+        );
+
+        // Nothing should have been written.
+        assert!(jumbf.into_inner().is_empty());
+    }
+}
+
+#[test]
+fn error_invalid_label_in_nested_super_box() {
+    let inner = SuperBoxBuilder::new(hex!("00000000000000000000000000000000")).set_label("a/b");
+
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .add_child_box(DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"ABCD"))
+        .add_child_box(
+            SuperBoxBuilder::new(hex!("00000000000000000000000000000000")).add_child_box(inner),
+        );
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let err = sbox.write_jumbf(&mut jumbf).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+    // Sizes are computed before anything is written.
+    assert!(jumbf.into_inner().is_empty());
+}
+
+#[test]
+fn error_invalid_non_requestable_label() {
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .set_non_requestable_label("test/descbox");
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let err = sbox.write_jumbf(&mut jumbf).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "Label \"test/descbox\" contains invalid character '/'"
+    );
+}
+
+#[test]
+fn canonical() {
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .set_canonical(true)
+        .add_child_box(DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"ABCD"));
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+
+    let expected_jumbf = hex!(
+        "0000002d" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ---
+            "0000000c" // box size
+            "61626364" // box type = 'abcd'
+            "41424344" // payload
+    );
+
+    assert_eq!(*jumbf.into_inner(), expected_jumbf);
+}
+
+#[test]
+fn error_canonical_with_padding() {
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .set_canonical(true)
+        .add_child_box(PaddingBoxBuilder::new(16));
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let err = sbox.write_jumbf(&mut jumbf).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert_eq!(
+        err.get_ref()
+            .and_then(|e| e.downcast_ref::<BuilderError>())
+            .unwrap(),
+        &BuilderError::NonCanonicalChildBox {
+            tbox: BoxType(*b"free")
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Box type b\"free\" is not permitted in canonical output"
+    );
+
+    // Nothing should have been written.
+    assert!(jumbf.into_inner().is_empty());
+
+    // Padding is allowed when canonical mode is off.
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .add_child_box(PaddingBoxBuilder::new(16));
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+}
+
+#[cfg(feature = "parser")]
+#[test]
+fn canonical_round_trip() {
+    use crate::parser::SuperBox;
+
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .set_canonical(true)
+        .set_label("outer")
+        .add_child_box(
+            SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+                .set_canonical(true)
+                .set_label("inner")
+                .add_child_box(DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"ABCD")),
+        );
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+    let jumbf = jumbf.into_inner();
+
+    let (_, parsed) = SuperBox::from_slice(&jumbf).unwrap();
+    assert!(parsed.is_canonical());
+}
+
+#[test]
+fn with_trailing_description_data() {
+    let expected_jumbf = hex!(
+        "00000027" // box size
+        "6a756d62" // box type = 'jumb'
+            "0000001f" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            "667574757265" // trailing data ("future")
+    );
+
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .set_trailing_description_data(b"future");
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+    assert_eq!(*jumbf.into_inner(), expected_jumbf);
+}
+
+#[test]
+fn shared_across_threads() {
+    let pbox = PlaceholderDataBox::new(RANDOM_BOX_TYPE, 4);
+
+    let sbox = SuperBoxBuilder::new([0; 16])
+        .set_label("test.threads")
+        .add_child_box(DataBoxBuilder::from_borrowed(JSON_BOX_TYPE, b"{}"))
+        .add_borrowed_child_box(&pbox);
+
+    let outputs: Vec<Vec<u8>> = std::thread::scope(|s| {
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut jumbf = Cursor::new(Vec::<u8>::new());
+                    sbox.write_jumbf(&mut jumbf).unwrap();
+                    jumbf.into_inner()
+                })
+            })
+            .collect();
+
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    for jumbf in &outputs {
+        assert_eq!(jumbf, &outputs[0]);
+    }
+
+    assert_eq!(pbox.offset(), Some(outputs[0].len() as u64 - 4));
+}
+
+fn to_jumbf(sbox: &SuperBoxBuilder) -> Vec<u8> {
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+    jumbf.into_inner()
+}
+
+#[test]
+fn try_clone() {
+    let template = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .set_label("template")
+        .set_id(42)
+        .set_private_box(DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"private"))
+        .add_child_box(DataBoxBuilder::from_borrowed(JSON_BOX_TYPE, b"{}"))
+        .add_child_box(
+            SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+                .set_canonical(true)
+                .add_child_box(DataBoxBuilder::from_owned(RANDOM_BOX_TYPE, vec![1, 2, 3])),
+        )
+        .add_child_box(PaddingBoxBuilder::new(12));
+
+    let clone = template.try_clone().unwrap();
+    assert_eq!(to_jumbf(&clone), to_jumbf(&template));
+
+    // Changing the clone doesn't affect the template.
+    let original = to_jumbf(&template);
+    let clone = clone
+        .set_label("asset")
+        .add_child_box(DataBoxBuilder::from_borrowed(JSON_BOX_TYPE, b"[]"));
+
+    assert_ne!(to_jumbf(&clone), original);
+    assert_eq!(to_jumbf(&template), original);
+}
+
+#[test]
+fn try_clone_shares_borrowed_children() {
+    let pbox = PlaceholderDataBox::new(RANDOM_BOX_TYPE, 4);
+
+    let template = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .add_borrowed_child_box(&pbox)
+        .add_child_box(PlaceholderDataBox::new(RANDOM_BOX_TYPE, 4));
+
+    template
+        .write_jumbf(&mut Cursor::new(Vec::<u8>::new()))
+        .unwrap();
+    assert_eq!(pbox.offset(), Some(41));
+
+    let clone = template.try_clone().unwrap();
+    let mut jumbf = Cursor::new(vec![0u8; 100]);
+    clone.write_jumbf_at(&mut jumbf, 100).unwrap();
+
+    // The borrowed placeholder is the same one, so it records the most
+    // recent write.
+    assert_eq!(pbox.offset(), Some(141));
+}
+
+#[test]
+fn try_clone_deeply_nested() {
+    // Cloning this tree should not overflow the stack.
+    const DEPTH: usize = 100_000;
+
+    let mut sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"));
+    for _ in 1..DEPTH {
+        sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000")).add_child_box(sbox);
+    }
+
+    let clone = sbox.try_clone().unwrap();
+    assert_eq!(clone.payload_size().unwrap(), sbox.payload_size().unwrap());
+}
+
+#[test]
+fn error_try_clone_unsupported() {
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000")).add_child_box(
+        SuperBoxBuilder::new(hex!("00000000000000000000000000000000")).add_child_box(
+            CallbackDataBox::new(RANDOM_BOX_TYPE, 2, |to_stream| to_stream.write_all(b"hi")),
+        ),
+    );
+
+    let err = sbox.try_clone().err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+    assert_eq!(err.to_string(), "box of type b\"abcd\" can not be cloned");
+}
+
+#[test]
+fn accessors() {
+    let pbox = PlaceholderDataBox::new(RANDOM_BOX_TYPE, 4);
+
+    let sbox = SuperBoxBuilder::new(hex!("00112233445566778899aabbccddeeff"))
+        .set_label("test.accessors")
+        .set_id(42)
+        .add_child_box(DataBoxBuilder::from_borrowed(JSON_BOX_TYPE, b"{}"))
+        .add_child_box(
+            SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+                .set_non_requestable_label("nested")
+                .set_canonical(true),
+        )
+        .add_borrowed_child_box(&pbox);
+
+    assert_eq!(sbox.uuid(), hex!("00112233445566778899aabbccddeeff"));
+    assert_eq!(sbox.label(), Some("test.accessors"));
+    assert!(sbox.is_requestable());
+    assert_eq!(sbox.id(), Some(42));
+    assert!(!sbox.is_canonical());
+    assert_eq!(sbox.child_box_count(), 3);
+
+    let types: Vec<BoxType> = sbox.child_boxes().map(|child| child.box_type()).collect();
+    assert_eq!(types, [JSON_BOX_TYPE, BoxType(*b"jumb"), RANDOM_BOX_TYPE]);
+
+    let nested: Vec<&SuperBoxBuilder> = sbox
+        .child_boxes()
+        .filter_map(|child| child.as_super_box_builder())
+        .collect();
+
+    assert_eq!(nested.len(), 1);
+    assert_eq!(nested[0].label(), Some("nested"));
+    assert!(!nested[0].is_requestable());
+    assert_eq!(nested[0].id(), None);
+    assert!(nested[0].is_canonical());
+    assert_eq!(nested[0].child_box_count(), 0);
+}
+
+#[test]
+fn insert_remove_replace_child() {
+    let pbox = PlaceholderDataBox::new(RANDOM_BOX_TYPE, 4);
+
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .add_child_box(DataBoxBuilder::from_borrowed(JSON_BOX_TYPE, b"1"))
+        .add_child_box(DataBoxBuilder::from_borrowed(JSON_BOX_TYPE, b"2"))
+        .insert_child_box(0, DataBoxBuilder::from_borrowed(JSON_BOX_TYPE, b"0"))
+        .insert_borrowed_child_box(3, &pbox)
+        .remove_child(1)
+        .replace_child(1, DataBoxBuilder::from_borrowed(JSON_BOX_TYPE, b"3"));
+
+    let types: Vec<BoxType> = sbox.child_boxes().map(|child| child.box_type()).collect();
+    assert_eq!(types, [JSON_BOX_TYPE, JSON_BOX_TYPE, RANDOM_BOX_TYPE]);
+
+    let expected_jumbf = hex!(
+        "0000003f" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ---
+            "00000009" // box size
+            "6a736f6e" // box type = 'json'
+            "30" // payload ("0")
+            // ---
+            "00000009" // box size
+            "6a736f6e" // box type = 'json'
+            "33" // payload ("3")
+            // ---
+            "0000000c" // box size
+            "61626364" // box type = 'abcd'
+            "00000000" // placeholder
+    );
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+    assert_eq!(*jumbf.into_inner(), expected_jumbf);
+
+    let sbox = sbox.replace_borrowed_child(2, &pbox).remove_child(0);
+    assert_eq!(sbox.child_box_count(), 2);
+}
+
+#[test]
+#[should_panic(expected = "insertion index")]
+fn insert_child_box_out_of_bounds() {
+    let _ = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .insert_child_box(1, DataBoxBuilder::from_borrowed(JSON_BOX_TYPE, b"{}"));
+}
+
+#[test]
+fn sort_children_by_label() {
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .add_child_box(DataBoxBuilder::from_borrowed(JSON_BOX_TYPE, b"{}"))
+        .add_child_box(
+            SuperBoxBuilder::new(hex!("00000000000000000000000000000000")).set_label("c2pa.b"),
+        )
+        .add_child_box(SuperBoxBuilder::new(hex!(
+            "00000000000000000000000000000000"
+        )))
+        .add_child_box(
+            SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+                .set_non_requestable_label("c2pa.a"),
+        )
+        .add_child_box(DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b""))
+        .sort_children_by_label();
+
+    let children: Vec<(BoxType, Option<&str>)> = sbox
+        .child_boxes()
+        .map(|child| {
+            (
+                child.box_type(),
+                child
+                    .as_super_box_builder()
+                    .and_then(SuperBoxBuilder::label),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        children,
+        [
+            (BoxType(*b"jumb"), Some("c2pa.a")),
+            (BoxType(*b"jumb"), Some("c2pa.b")),
+            (JSON_BOX_TYPE, None),
+            (BoxType(*b"jumb"), None),
+            (RANDOM_BOX_TYPE, None),
+        ]
+    );
+}
+
+#[test]
+fn sort_children_by() {
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .add_child_box(DataBoxBuilder::from_borrowed(JSON_BOX_TYPE, b"123"))
+        .add_child_box(DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"1"))
+        .add_child_box(DataBoxBuilder::from_borrowed(JSON_BOX_TYPE, b"12"))
+        .sort_children_by(|a, b| {
+            a.payload_size()
+                .unwrap()
+                .cmp(&b.payload_size().unwrap())
+                .reverse()
+        });
+
+    let sizes: Vec<usize> = sbox
+        .child_boxes()
+        .map(|child| child.payload_size().unwrap())
+        .collect();
+
+    assert_eq!(sizes, [3, 2, 1]);
+}
+
+#[test]
+fn error_strict_duplicate_label() {
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .set_strict(true)
+        .add_child_box(
+            SuperBoxBuilder::new(hex!("00000000000000000000000000000000")).set_label("c2pa.a"),
+        )
+        .add_child_box(
+            SuperBoxBuilder::new(hex!("00000000000000000000000000000000")).set_label("c2pa.a"),
+        );
+
+    assert!(sbox.is_strict());
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let err = sbox.write_jumbf(&mut jumbf).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert_eq!(
+        err.get_ref()
+            .and_then(|e| e.downcast_ref::<BuilderError>())
+            .unwrap(),
+        &BuilderError::DuplicateLabel {
+            label: "c2pa.a".to_owned()
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "More than one requestable child superbox is labeled \"c2pa.a\""
+    );
+
+    // Nothing should have been written.
+    assert!(jumbf.into_inner().is_empty());
+
+    // Duplicate labels are allowed when strict mode is off.
+    let sbox = sbox.set_strict(false);
+    sbox.write_jumbf(&mut Cursor::new(Vec::<u8>::new()))
+        .unwrap();
+}
+
+#[test]
+fn strict_ignores_non_requestable_labels() {
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+        .set_strict(true)
+        .add_child_box(
+            SuperBoxBuilder::new(hex!("00000000000000000000000000000000")).set_label("c2pa.a"),
+        )
+        .add_child_box(
+            SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+                .set_non_requestable_label("c2pa.a"),
+        )
+        .add_child_box(
+            SuperBoxBuilder::new(hex!("00000000000000000000000000000000")).set_label("c2pa.b"),
+        );
+
+    sbox.write_jumbf(&mut Cursor::new(Vec::<u8>::new()))
+        .unwrap();
+}
+
+#[test]
+fn error_strict_duplicate_label_in_nested_super_box() {
+    let sbox = SuperBoxBuilder::new(hex!("00000000000000000000000000000000")).add_child_box(
+        SuperBoxBuilder::new(hex!("00000000000000000000000000000000"))
+            .set_strict(true)
+            .add_child_box(
+                SuperBoxBuilder::new(hex!("00000000000000000000000000000000")).set_label("x"),
+            )
+            .add_child_box(
+                SuperBoxBuilder::new(hex!("00000000000000000000000000000000")).set_label("x"),
+            ),
+    );
+
+    let err = sbox.prepare().err().unwrap();
+    assert_eq!(
+        err.get_ref()
+            .and_then(|e| e.downcast_ref::<BuilderError>())
+            .unwrap(),
+        &BuilderError::DuplicateLabel {
+            label: "x".to_owned()
+        }
+    );
+}
+
+#[cfg(feature = "parser")]
+#[test]
+fn label_with_urn() {
+    use crate::parser::UuidLabel;
+
+    let uuid = hex!("021b555e5e024074b44443d7919d89b9");
+
+    let sbox = SuperBoxBuilder::new([0; 16]).set_label_with_urn("contentauth", uuid);
+    assert_eq!(
+        sbox.label(),
+        Some("contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9")
+    );
+    assert!(sbox.is_requestable());
+
+    let label = UuidLabel::parse(sbox.label().unwrap()).unwrap();
+    assert_eq!(label.prefix, "contentauth");
+    assert_eq!(label.uuid, uuid);
+
+    let sbox = SuperBoxBuilder::new([0; 16]).set_label_with_urn("", uuid);
+    assert_eq!(
+        sbox.label(),
+        Some("urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9")
+    );
+}
+
+#[cfg(all(feature = "parser", feature = "salt"))]
+#[test]
+fn label_with_uuid() {
+    use crate::parser::UuidLabel;
+
+    let sbox1 = SuperBoxBuilder::new([0; 16]).set_label_with_uuid("contentauth");
+    let sbox2 = SuperBoxBuilder::new([0; 16]).set_label_with_uuid("contentauth");
+    assert_ne!(sbox1.label(), sbox2.label());
+
+    let label = UuidLabel::parse(sbox1.label().unwrap()).unwrap();
+    assert_eq!(label.prefix, "contentauth");
+    assert_eq!(label.uuid.0[6] >> 4, 4);
+    assert_eq!(label.uuid.0[8] >> 6, 2);
+}
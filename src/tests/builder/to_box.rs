@@ -0,0 +1,172 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::{Cursor, Result, SeekFrom};
+
+use crate::{
+    builder::{to_box::write_jumbf, SuperBoxBuilder, ToBox, WriteAndSeek},
+    BoxType,
+};
+
+const RANDOM_BOX_TYPE: BoxType = BoxType(*b"abcd");
+
+// A box which misreports its payload size.
+struct MisreportedSizeBox {
+    reported_size: usize,
+}
+
+impl ToBox for MisreportedSizeBox {
+    fn box_type(&self) -> BoxType {
+        RANDOM_BOX_TYPE
+    }
+
+    fn payload_size(&self) -> Result<usize> {
+        Ok(self.reported_size)
+    }
+
+    fn write_payload(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
+        to_stream.write_all(b"ABCD")
+    }
+}
+
+#[test]
+fn exact_size() {
+    let boxx = MisreportedSizeBox { reported_size: 4 };
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    write_jumbf(&boxx, &mut jumbf).unwrap();
+    assert_eq!(*jumbf.into_inner(), *b"\0\0\0\x0cabcdABCD");
+}
+
+#[test]
+fn error_payload_too_short() {
+    let boxx = MisreportedSizeBox { reported_size: 5 };
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let err = write_jumbf(&boxx, &mut jumbf).unwrap_err();
+
+    assert_eq!(
+        "Custom { kind: Other, error: \"write_jumbf: box type b\\\"abcd\\\" wrote 4 bytes of payload, but payload_size() reported 5 bytes\" }",
+        format!("{err:?}")
+    );
+}
+
+#[test]
+fn error_payload_too_long() {
+    let boxx = MisreportedSizeBox { reported_size: 3 };
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    let err = write_jumbf(&boxx, &mut jumbf).unwrap_err();
+
+    assert_eq!(
+        "Custom { kind: Other, error: \"write_jumbf: box type b\\\"abcd\\\" wrote 4 bytes of payload, but payload_size() reported 3 bytes\" }",
+        format!("{err:?}")
+    );
+}
+
+#[test]
+fn error_nested_in_super_box() {
+    let sbox =
+        SuperBoxBuilder::new([0u8; 16]).add_child_box(MisreportedSizeBox { reported_size: 2 });
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    assert!(sbox.write_jumbf(&mut jumbf).is_err());
+}
+
+// A box which writes a placeholder length, then its content, and then seeks
+// back to fill in the length.
+struct BackPatchingBox {
+    content: &'static [u8],
+}
+
+impl ToBox for BackPatchingBox {
+    fn box_type(&self) -> BoxType {
+        RANDOM_BOX_TYPE
+    }
+
+    fn write_payload(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
+        let start = to_stream.stream_position()?;
+        to_stream.write_all(&[0; 4])?;
+        to_stream.write_all(self.content)?;
+
+        let end = to_stream.stream_position()?;
+        to_stream.seek(SeekFrom::Start(start))?;
+        to_stream.write_all(&(self.content.len() as u32).to_be_bytes())?;
+        to_stream.seek(SeekFrom::Start(end))?;
+        Ok(())
+    }
+}
+
+#[test]
+fn payload_size_with_seek() {
+    let boxx = BackPatchingBox { content: b"ABCD" };
+    assert_eq!(boxx.payload_size().unwrap(), 8);
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    write_jumbf(&boxx, &mut jumbf).unwrap();
+    assert_eq!(*jumbf.into_inner(), *b"\0\0\0\x10abcd\0\0\0\x04ABCD");
+}
+
+#[test]
+fn payload_size_with_seek_in_super_box() {
+    let sbox = SuperBoxBuilder::new([0u8; 16])
+        .add_child_box(BackPatchingBox { content: b"ABCD" })
+        .add_child_box(BackPatchingBox { content: b"EFGHIJ" });
+
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+
+    let jumbf = jumbf.into_inner();
+    assert_eq!(jumbf.len(), 8 + 25 + 16 + 18);
+    assert_eq!(jumbf[33..49], *b"\0\0\0\x10abcd\0\0\0\x04ABCD");
+    assert_eq!(jumbf[49..], *b"\0\0\0\x12abcd\0\0\0\x06EFGHIJ");
+}
+
+// A box which seeks past the end of its payload before writing.
+struct SparseBox;
+
+impl ToBox for SparseBox {
+    fn box_type(&self) -> BoxType {
+        RANDOM_BOX_TYPE
+    }
+
+    fn write_payload(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
+        to_stream.seek(SeekFrom::Current(3))?;
+        to_stream.write_all(b"A")
+    }
+}
+
+#[test]
+fn payload_size_counts_skipped_bytes() {
+    assert_eq!(SparseBox.payload_size().unwrap(), 4);
+}
+
+// A box which seeks before the start of the stream.
+struct BadSeekBox;
+
+impl ToBox for BadSeekBox {
+    fn box_type(&self) -> BoxType {
+        RANDOM_BOX_TYPE
+    }
+
+    fn write_payload(&self, to_stream: &mut dyn WriteAndSeek) -> Result<()> {
+        to_stream.seek(SeekFrom::Current(-1))?;
+        Ok(())
+    }
+}
+
+#[test]
+fn error_payload_size_seek_before_start() {
+    let err = BadSeekBox.payload_size().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
@@ -0,0 +1,120 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::{Cursor, ErrorKind, Seek, SeekFrom, Write};
+
+use hex_literal::hex;
+
+use crate::{
+    builder::{
+        DataBoxBuilder, PlaceholderDataBox, StreamingSuperBoxWriter, SuperBoxBuilder, WriteOnly,
+    },
+    BoxType,
+};
+
+const RANDOM_BOX_TYPE: BoxType = BoxType(*b"abcd");
+
+#[test]
+fn matches_seekable_output() {
+    let sbox = SuperBoxBuilder::new([0u8; 16])
+        .set_label("test.descbox")
+        .add_child_box(DataBoxBuilder::from_borrowed(RANDOM_BOX_TYPE, b"ABCD"));
+
+    let mut seekable = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut seekable).unwrap();
+
+    let mut write_only = WriteOnly::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut write_only).unwrap();
+
+    assert_eq!(write_only.get_ref().len(), 58);
+    assert_eq!(write_only.into_inner(), seekable.into_inner());
+}
+
+#[test]
+fn stream_position() {
+    let mut write_only = WriteOnly::new(Vec::<u8>::new());
+    assert_eq!(write_only.stream_position().unwrap(), 0);
+
+    write_only.write_all(b"ABCD").unwrap();
+    assert_eq!(write_only.stream_position().unwrap(), 4);
+    assert_eq!(write_only.seek(SeekFrom::Start(4)).unwrap(), 4);
+
+    let err = write_only.seek(SeekFrom::Start(0)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+    let err = write_only.seek(SeekFrom::End(0)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+}
+
+#[test]
+fn placeholder() {
+    let expected_jumbf = hex!(
+        "41424344" // arbitrary prefix = 'ABCD'
+        "00000039" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ---
+            "00000018" // box size
+            "61626364" // box type = 'abcd'
+            "00000000000000000000000000000000" // placeholder
+    );
+
+    let pbox = PlaceholderDataBox::new(RANDOM_BOX_TYPE, 16);
+    let sbox = SuperBoxBuilder::new([0u8; 16]).add_borrowed_child_box(&pbox);
+
+    let mut write_only = WriteOnly::new(Vec::<u8>::new());
+    write_only.write_all(b"ABCD").unwrap();
+    sbox.write_jumbf(&mut write_only).unwrap();
+
+    // Offset is still recorded ...
+    assert_eq!(pbox.offset(), Some(45));
+
+    // ... but the placeholder can't be replaced in this stream.
+    let err = pbox
+        .replace_payload(&mut write_only, b"0123456789abcdef")
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+    assert_eq!(*write_only.into_inner(), expected_jumbf);
+}
+
+#[test]
+fn error_streaming_writer() {
+    let mut write_only = WriteOnly::new(Vec::<u8>::new());
+
+    let sbox =
+        StreamingSuperBoxWriter::new(&mut write_only, &SuperBoxBuilder::new([0u8; 16])).unwrap();
+
+    let err = sbox.finish().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+}
+
+#[test]
+fn with_offset() {
+    let pbox = PlaceholderDataBox::new(RANDOM_BOX_TYPE, 16);
+    let sbox = SuperBoxBuilder::new([0u8; 16]).add_borrowed_child_box(&pbox);
+
+    let mut write_only = WriteOnly::with_offset(Vec::<u8>::new(), 1000);
+    assert_eq!(write_only.stream_position().unwrap(), 1000);
+
+    sbox.write_jumbf(&mut write_only).unwrap();
+    assert_eq!(write_only.stream_position().unwrap(), 1000 + 0x39);
+
+    // The placeholder's offset is relative to the base offset, not to the
+    // start of the underlying stream.
+    assert_eq!(pbox.offset(), Some(1000 + 41));
+    assert_eq!(write_only.into_inner().len(), 0x39);
+}
@@ -0,0 +1,242 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{
+    c2pa::{Manifest, ManifestStore, SignatureRef, MANIFEST_STORE_TYPE, MANIFEST_TYPE},
+    parser::{Error, SuperBox},
+    BoxType, Uuid,
+};
+
+const MANIFEST: &str = "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9";
+
+#[test]
+fn manifest_store_c2pa() {
+    let jumbf = include_bytes!("fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let store = ManifestStore::from_super_box(&sbox).unwrap();
+    assert_eq!(store.super_box(), &sbox);
+    assert_eq!(store.manifests().count(), 1);
+
+    let manifest = store.active_manifest().unwrap();
+    assert_eq!(manifest.super_box(), sbox.find_by_label(MANIFEST).unwrap());
+    assert_eq!(manifest.label(), Some(MANIFEST));
+    assert_eq!(
+        manifest.urn(),
+        Some("urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9")
+    );
+    assert_eq!(manifest.uuid_label().unwrap().prefix, "contentauth");
+    assert!(!manifest.is_update_manifest());
+
+    assert_eq!(
+        manifest.signature().unwrap(),
+        SignatureRef::from_manifest(manifest.super_box()).unwrap()
+    );
+}
+
+#[test]
+fn manifest_store_multiple() {
+    let jumbf = hex!(
+        "000000e9" // box size
+        "6a756d62" // box type = 'jumb'
+            "0000001e" // box size
+            "6a756d64" // box type = 'jumd'
+            "6332706100110010800000aa00389b71" // UUID = c2pa
+            "03" // toggles
+            "6332706100" // label = "c2pa"
+            // ----
+            "0000004f" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000047" // box size
+                "6a756d64" // box type = 'jumd'
+                "63326d6100110010800000aa00389b71" // UUID = c2ma
+                "03" // toggles
+                "75726e3a757569643a30303030303030302d303030302d303030302d303030302d30303030303030303030303100"
+                // label = "urn:uuid:00000000-0000-0000-0000-000000000001"
+            // ----
+            "00000023" // box size
+            "6a756d62" // box type = 'jumb'
+                "0000001b" // box size
+                "6a756d64" // box type = 'jumd'
+                "63626f7200110010800000aa00389b71" // UUID = cbor
+                "03" // toggles
+                "7800" // label = "x"
+            // ----
+            "00000051" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000049" // box size
+                "6a756d64" // box type = 'jumd'
+                "6332756d00110010800000aa00389b71" // UUID = c2um
+                "03" // toggles
+                "613a75726e3a757569643a30303030303030302d303030302d303030302d303030302d30303030303030303030303200"
+                // label = "a:urn:uuid:00000000-0000-0000-0000-000000000002"
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    let store = ManifestStore::from_super_box(&sbox).unwrap();
+
+    let urns: Vec<_> = store.manifests().filter_map(|m| m.urn()).collect();
+    assert_eq!(
+        urns,
+        [
+            "urn:uuid:00000000-0000-0000-0000-000000000001",
+            "urn:uuid:00000000-0000-0000-0000-000000000002"
+        ]
+    );
+
+    let updates: Vec<_> = store.manifests().map(|m| m.is_update_manifest()).collect();
+    assert_eq!(updates, [false, true]);
+
+    let active = store.active_manifest().unwrap();
+    assert_eq!(active.uuid_label().unwrap().prefix, "a");
+    assert!(active.signature().is_err());
+}
+
+#[test]
+fn error_not_manifest_store() {
+    let jumbf = include_bytes!("fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+    let manifest = sbox.find_by_label(MANIFEST).unwrap();
+
+    assert_eq!(
+        ManifestStore::from_super_box(manifest).unwrap_err(),
+        Error::InvalidContentType {
+            expected: MANIFEST_STORE_TYPE,
+            actual: MANIFEST_TYPE,
+        }
+    );
+
+    assert_eq!(
+        Manifest::from_super_box(&sbox).unwrap_err(),
+        Error::InvalidContentType {
+            expected: MANIFEST_TYPE,
+            actual: MANIFEST_STORE_TYPE,
+        }
+    );
+
+    let claim = manifest.find_by_label("c2pa.claim").unwrap();
+    assert_eq!(
+        Manifest::from_super_box(claim).unwrap_err(),
+        Error::InvalidContentType {
+            expected: MANIFEST_TYPE,
+            actual: Uuid(*b"c2cl\x00\x11\x00\x10\x80\x00\x00\xaa\x00\x38\x9b\x71"),
+        }
+    );
+}
+
+#[test]
+fn from_manifest() {
+    let jumbf = include_bytes!("fixtures/C.c2pa");
+    let (_, store) = SuperBox::from_slice(jumbf).unwrap();
+
+    let manifest = store.find_by_label(MANIFEST).unwrap();
+
+    let sig = SignatureRef::from_manifest(manifest).unwrap();
+
+    let signature = manifest.find_by_label("c2pa.signature").unwrap();
+    assert_eq!(sig.signature_bytes(), signature.cbor_box().unwrap().data);
+    assert_eq!(sig.signature_bytes().len(), 13726);
+
+    let claim = manifest.find_by_label("c2pa.claim").unwrap();
+    assert_eq!(sig.claim_bytes(), claim.cbor_box().unwrap().data);
+    assert_eq!(sig.claim_bytes().len(), 632);
+
+    assert_eq!(&jumbf[sig.protected_range()], sig.claim_bytes());
+}
+
+#[test]
+fn error_no_claim() {
+    let jumbf = hex!(
+        "00000023" // box size
+        "6a756d62" // box type = 'jumb'
+            "0000001b" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "03" // toggles
+            "6d00" // label = "m"
+    );
+
+    let (_, manifest) = SuperBox::from_slice(&jumbf).unwrap();
+
+    assert_eq!(
+        SignatureRef::from_manifest(&manifest).unwrap_err(),
+        Error::Malformed("C2PA manifest with a claim")
+    );
+}
+
+#[test]
+fn error_no_signature() {
+    let jumbf = hex!(
+        "0000004f" // box size
+        "6a756d62" // box type = 'jumb'
+            "0000001b" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "03" // toggles
+            "6d00" // label = "m"
+            // ----
+            "0000002c" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000024" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "03" // toggles
+                "633270612e636c61696d00" // label = "c2pa.claim"
+    );
+
+    let (_, manifest) = SuperBox::from_slice(&jumbf).unwrap();
+
+    assert_eq!(
+        SignatureRef::from_manifest(&manifest).unwrap_err(),
+        Error::Malformed("C2PA manifest with a claim signature")
+    );
+}
+
+#[test]
+fn error_no_cbor_box() {
+    let jumbf = hex!(
+        "0000007f" // box size
+        "6a756d62" // box type = 'jumb'
+            "0000001b" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "03" // toggles
+            "6d00" // label = "m"
+            // ----
+            "0000002c" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000024" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "03" // toggles
+                "633270612e636c61696d00" // label = "c2pa.claim"
+            // ----
+            "00000030" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000028" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "03" // toggles
+                "633270612e7369676e617475726500" // label = "c2pa.signature"
+    );
+
+    let (_, manifest) = SuperBox::from_slice(&jumbf).unwrap();
+
+    assert_eq!(
+        SignatureRef::from_manifest(&manifest).unwrap_err(),
+        Error::MissingChildBox(BoxType::CBOR)
+    );
+}
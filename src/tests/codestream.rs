@@ -0,0 +1,51 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use pretty_assertions_sorted::assert_eq;
+
+use crate::codestream::CodestreamFormat;
+
+#[test]
+fn detect() {
+    assert_eq!(
+        CodestreamFormat::detect(&[0xff, 0x4f, 0xff, 0x51]),
+        Some(CodestreamFormat::Jpeg2000)
+    );
+    assert_eq!(
+        CodestreamFormat::detect(&[0xff, 0xd8]),
+        Some(CodestreamFormat::Jpeg)
+    );
+    assert_eq!(
+        CodestreamFormat::detect(&[0xff, 0x10, 0xff, 0x50]),
+        Some(CodestreamFormat::JpegXs)
+    );
+}
+
+#[test]
+fn detect_unrecognized() {
+    assert_eq!(CodestreamFormat::detect(&[]), None);
+    assert_eq!(CodestreamFormat::detect(&[0xff]), None);
+
+    // SOC must be followed by SIZ.
+    assert_eq!(CodestreamFormat::detect(&[0xff, 0x4f]), None);
+    assert_eq!(CodestreamFormat::detect(&[0xff, 0x4f, 0xff, 0x52]), None);
+
+    assert_eq!(CodestreamFormat::detect(b"\x00\x00\x00\x0cjP  "), None);
+}
+
+#[test]
+fn media_type() {
+    assert_eq!(CodestreamFormat::Jpeg2000.media_type(), "image/j2c");
+    assert_eq!(CodestreamFormat::Jpeg.media_type(), "image/jpeg");
+    assert_eq!(CodestreamFormat::JpegXs.media_type(), "image/jxsc");
+}
@@ -0,0 +1,169 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{
+    compact, compact_with_options, compact_with_report, parser::SuperBox, CompactOptions,
+    CompactReport,
+};
+
+#[test]
+fn c2pa_unchanged() {
+    let jumbf = include_bytes!("fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let (compacted, report) = compact_with_report(&sbox);
+
+    assert_eq!(compacted, jumbf);
+    assert_eq!(report.bytes_saved(), 0);
+}
+
+#[test]
+fn compact_all() {
+    let jumbf = hex!(
+        "00000001" // box size (see XLBox)
+        "6a756d62" // box type = 'jumb'
+        "0000000000000053" // XLBox
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "00000001" // box size (see XLBox)
+            "6a736f6e" // box type = 'json'
+            "0000000000000012" // XLBox
+            "7b7d" // payload
+            // ----
+            "0000000c" // box size
+            "66726565" // box type = 'free'
+            "00000000" // padding
+            // ----
+            "0000000c" // box size
+            "63626f72" // box type = 'cbor'
+            "00000000" // unfilled placeholder
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    let options = CompactOptions {
+        trim_zero_payloads: true,
+    };
+    let (compacted, report) = compact_with_options(&sbox, &options);
+
+    assert_eq!(
+        compacted,
+        hex!(
+            "00000033" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "00" // toggles
+                // ----
+                "0000000a" // box size
+                "6a736f6e" // box type = 'json'
+                "7b7d" // payload
+                // ----
+                "00000008" // box size
+                "63626f72" // box type = 'cbor'
+        )
+    );
+
+    assert_eq!(
+        report,
+        CompactReport {
+            original_size: 0x53,
+            compacted_size: 0x33,
+            free_boxes_removed: 1,
+            placeholders_trimmed: 1,
+            headers_shortened: 2,
+        }
+    );
+    assert_eq!(report.bytes_saved(), 0x20);
+}
+
+#[test]
+fn zero_payload_preserved() {
+    let jumbf = hex!(
+        "0000002a" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "00000009" // box size
+            "63626f72" // box type = 'cbor'
+            "00" // payload (the integer 0)
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    let (compacted, report) = compact_with_report(&sbox);
+
+    assert_eq!(compacted, jumbf);
+    assert_eq!(report.placeholders_trimmed, 0);
+    assert_eq!(compact(&sbox), jumbf);
+}
+
+#[test]
+fn nested() {
+    let jumbf = hex!(
+        "00000059" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "0000002c" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "00" // toggles
+                // ----
+                "0000000b" // box size
+                "66726565" // box type = 'free'
+                "000000" // padding
+            // ----
+            "0000000c" // box size
+            "6a736f6e" // box type = 'json'
+            "7b22227d" // payload
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+
+    assert_eq!(
+        compact(&sbox),
+        hex!(
+            "0000004e" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "00" // toggles
+                // ----
+                "00000021" // box size
+                "6a756d62" // box type = 'jumb'
+                    "00000019" // box size
+                    "6a756d64" // box type = 'jumd'
+                    "00000000000000000000000000000000" // UUID
+                    "00" // toggles
+                // ----
+                "0000000c" // box size
+                "6a736f6e" // box type = 'json'
+                "7b22227d" // payload
+        )
+    );
+}
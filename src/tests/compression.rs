@@ -0,0 +1,68 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use pretty_assertions_sorted::assert_eq;
+
+use crate::compression::Compression;
+
+const DATA: &[u8] = b"the quick brown fox jumps over the lazy dog, the lazy dog sleeps";
+
+#[test]
+fn round_trip() {
+    for compression in [Compression::Brotli, Compression::Zlib] {
+        let compressed = compression.compress(DATA).unwrap();
+        assert_ne!(compressed, DATA, "{compression:?}");
+
+        assert_eq!(
+            compression.decompress(&compressed, DATA.len()).unwrap(),
+            Some(DATA.to_vec()),
+            "{compression:?}"
+        );
+    }
+}
+
+#[test]
+fn empty() {
+    for compression in [Compression::Brotli, Compression::Zlib] {
+        let compressed = compression.compress(&[]).unwrap();
+
+        assert_eq!(
+            compression.decompress(&compressed, 0).unwrap(),
+            Some(vec![]),
+            "{compression:?}"
+        );
+    }
+}
+
+#[test]
+fn exceeds_max_size() {
+    for compression in [Compression::Brotli, Compression::Zlib] {
+        let compressed = compression.compress(DATA).unwrap();
+
+        assert_eq!(
+            compression.decompress(&compressed, DATA.len() - 1).unwrap(),
+            None,
+            "{compression:?}"
+        );
+    }
+}
+
+#[test]
+fn error_malformed() {
+    for compression in [Compression::Brotli, Compression::Zlib] {
+        assert!(
+            compression.decompress(&[0xff; 16], 1024).is_err(),
+            "{compression:?}"
+        );
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::{ContentType, Uuid};
+
+#[test]
+fn from_uuid() {
+    assert_eq!(ContentType::from(Uuid::JSON), ContentType::Json);
+    assert_eq!(ContentType::from(Uuid::CBOR), ContentType::Cbor);
+    assert_eq!(ContentType::from(Uuid::CODESTREAM), ContentType::Codestream);
+    assert_eq!(
+        ContentType::from(Uuid::EMBEDDED_FILE),
+        ContentType::EmbeddedFile
+    );
+    assert_eq!(ContentType::from(Uuid::UUID), ContentType::Uuid);
+    assert_eq!(ContentType::from(Uuid::XML), ContentType::Xml);
+    assert_eq!(ContentType::from(Uuid::PROTOBUF), ContentType::Protobuf);
+
+    let app_uuid = Uuid(*b"c2pa\x00\x11\x00\x10\x80\x00\x00\xaa\x00\x38\x9b\x71");
+    assert_eq!(ContentType::from(app_uuid), ContentType::Unknown(app_uuid));
+}
+
+#[test]
+fn round_trip() {
+    for uuid in [
+        Uuid::JSON,
+        Uuid::CBOR,
+        Uuid::CODESTREAM,
+        Uuid::EMBEDDED_FILE,
+        Uuid::UUID,
+        Uuid::XML,
+        Uuid::PROTOBUF,
+        Uuid([0; 16]),
+    ] {
+        assert_eq!(ContentType::from(uuid).uuid(), uuid);
+        assert_eq!(Uuid::from(ContentType::from(uuid)), uuid);
+    }
+}
@@ -0,0 +1,258 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::Cursor;
+
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{
+    builder::{DataBoxBuilder, SuperBoxBuilder},
+    diff::{diff, ByteDiff, DiffEntry, DiffKind},
+    parser::SuperBox,
+    tests::parser::super_box_depth_limit::pathologically_deep,
+    BoxType,
+};
+
+const JSON_BOX_TYPE: BoxType = BoxType(*b"json");
+
+fn to_jumbf(sbox: &SuperBoxBuilder) -> Vec<u8> {
+    let mut jumbf = Cursor::new(Vec::<u8>::new());
+    sbox.write_jumbf(&mut jumbf).unwrap();
+    jumbf.into_inner()
+}
+
+fn json_box(json: &'static [u8]) -> DataBoxBuilder<'static> {
+    DataBoxBuilder::from_borrowed(JSON_BOX_TYPE, json)
+}
+
+fn entry(path: &str, kind: DiffKind) -> DiffEntry {
+    DiffEntry {
+        path: path.to_owned(),
+        kind,
+    }
+}
+
+#[test]
+fn identical() {
+    let jumbf = include_bytes!("fixtures/C.c2pa");
+    let (_, old) = SuperBox::from_slice(jumbf).unwrap();
+    let (_, new) = SuperBox::from_slice(jumbf).unwrap();
+
+    assert_eq!(diff(&old, &new), vec![]);
+}
+
+#[test]
+fn payload_modified() {
+    let old = to_jumbf(
+        &SuperBoxBuilder::new([0; 16])
+            .set_label("manifest")
+            .add_child_box(
+                SuperBoxBuilder::new([0; 16])
+                    .set_label("assertion")
+                    .add_child_box(json_box(b"{\"a\":1}")),
+            ),
+    );
+
+    let new = to_jumbf(
+        &SuperBoxBuilder::new([0; 16])
+            .set_label("manifest")
+            .add_child_box(
+                SuperBoxBuilder::new([0; 16])
+                    .set_label("assertion")
+                    .add_child_box(json_box(b"{\"a\":23}")),
+            ),
+    );
+
+    let (_, old) = SuperBox::from_slice(&old).unwrap();
+    let (_, new) = SuperBox::from_slice(&new).unwrap();
+
+    assert_eq!(
+        diff(&old, &new),
+        vec![entry(
+            "assertion/json",
+            DiffKind::PayloadModified(ByteDiff {
+                old_len: 7,
+                new_len: 8,
+                first_difference: 5,
+                bytes_changed: 3,
+            })
+        )]
+    );
+}
+
+#[test]
+fn added_and_removed() {
+    let old = to_jumbf(
+        &SuperBoxBuilder::new([0; 16])
+            .add_child_box(SuperBoxBuilder::new([0; 16]).set_label("a"))
+            .add_child_box(SuperBoxBuilder::new([0; 16]).set_label("b")),
+    );
+
+    let new = to_jumbf(
+        &SuperBoxBuilder::new([0; 16])
+            .add_child_box(SuperBoxBuilder::new([0; 16]).set_label("b"))
+            .add_child_box(SuperBoxBuilder::new([0; 16]).set_label("c"))
+            .add_child_box(json_box(b"{}")),
+    );
+
+    let (_, old) = SuperBox::from_slice(&old).unwrap();
+    let (_, new) = SuperBox::from_slice(&new).unwrap();
+
+    assert_eq!(
+        diff(&old, &new),
+        vec![
+            entry("a", DiffKind::Removed),
+            entry("c", DiffKind::Added),
+            entry("json", DiffKind::Added),
+        ]
+    );
+
+    assert_eq!(
+        diff(&new, &old),
+        vec![
+            entry("c", DiffKind::Removed),
+            entry("json", DiffKind::Removed),
+            entry("a", DiffKind::Added),
+        ]
+    );
+}
+
+#[test]
+fn description_modified() {
+    let old = to_jumbf(
+        &SuperBoxBuilder::new([0; 16])
+            .add_child_box(SuperBoxBuilder::new([0; 16]).set_label("a").set_id(1)),
+    );
+
+    let new = to_jumbf(
+        &SuperBoxBuilder::new([1; 16])
+            .add_child_box(SuperBoxBuilder::new([0; 16]).set_label("a").set_id(2)),
+    );
+
+    let (_, old) = SuperBox::from_slice(&old).unwrap();
+    let (_, new) = SuperBox::from_slice(&new).unwrap();
+
+    assert_eq!(
+        diff(&old, &new),
+        vec![
+            entry("", DiffKind::DescriptionModified),
+            entry("a", DiffKind::DescriptionModified),
+        ]
+    );
+}
+
+#[test]
+fn duplicate_siblings() {
+    let old = to_jumbf(
+        &SuperBoxBuilder::new([0; 16])
+            .add_child_box(json_box(b"1"))
+            .add_child_box(json_box(b"2"))
+            .add_child_box(SuperBoxBuilder::new([0; 16]))
+            .add_child_box(SuperBoxBuilder::new([0; 16]).add_child_box(json_box(b"3"))),
+    );
+
+    let new = to_jumbf(
+        &SuperBoxBuilder::new([0; 16])
+            .add_child_box(json_box(b"1"))
+            .add_child_box(json_box(b"4"))
+            .add_child_box(SuperBoxBuilder::new([0; 16]))
+            .add_child_box(SuperBoxBuilder::new([0; 16]).add_child_box(json_box(b"5"))),
+    );
+
+    let (_, old) = SuperBox::from_slice(&old).unwrap();
+    let (_, new) = SuperBox::from_slice(&new).unwrap();
+
+    let one_byte = ByteDiff {
+        old_len: 1,
+        new_len: 1,
+        first_difference: 0,
+        bytes_changed: 1,
+    };
+
+    assert_eq!(
+        diff(&old, &new),
+        vec![
+            entry("json[1]", DiffKind::PayloadModified(one_byte.clone())),
+            entry("jumb[1]/json", DiffKind::PayloadModified(one_byte)),
+        ]
+    );
+}
+
+#[test]
+fn box_kind_changed() {
+    let old = to_jumbf(&SuperBoxBuilder::new([0; 16]).add_child_box(json_box(b"{}")));
+
+    let new = to_jumbf(
+        &SuperBoxBuilder::new([0; 16])
+            .add_child_box(SuperBoxBuilder::new([0; 16]).set_label("json")),
+    );
+
+    let (_, old) = SuperBox::from_slice(&old).unwrap();
+    let (_, new) = SuperBox::from_slice(&new).unwrap();
+
+    assert_eq!(
+        diff(&old, &new),
+        vec![
+            entry("json", DiffKind::Removed),
+            entry("json", DiffKind::Added),
+        ]
+    );
+}
+
+#[test]
+fn byte_diff() {
+    assert_eq!(ByteDiff::new(b"abc", b"abc"), None);
+    assert_eq!(ByteDiff::new(b"", b""), None);
+
+    assert_eq!(
+        ByteDiff::new(b"abc", b"abcde"),
+        Some(ByteDiff {
+            old_len: 3,
+            new_len: 5,
+            first_difference: 3,
+            bytes_changed: 2,
+        })
+    );
+
+    assert_eq!(
+        ByteDiff::new(b"xbcy", b"abc"),
+        Some(ByteDiff {
+            old_len: 4,
+            new_len: 3,
+            first_difference: 0,
+            bytes_changed: 2,
+        })
+    );
+}
+
+#[test]
+fn pathological_depth() {
+    const DEPTH: usize = 100_000;
+
+    // Change the UUID of the innermost description box.
+    let old = pathologically_deep(DEPTH);
+    let mut new = old.clone();
+    new[(DEPTH - 1) * 33 + 16] = 1;
+
+    let (_, old) = SuperBox::from_slice(&old).unwrap();
+    let (_, new) = SuperBox::from_slice(&new).unwrap();
+
+    let entries = diff(&old, &new);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].kind, DiffKind::DescriptionModified);
+    assert_eq!(entries[0].path.split('/').count(), DEPTH - 1);
+    assert!(entries[0].path.split('/').all(|key| key == "jumb"));
+
+    drop(old);
+    drop(new);
+}
@@ -0,0 +1,164 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{embed::jxl, parser::Error, BoxType};
+
+const JUMBF: [u8; 33] = hex!(
+    "00000021" // box size
+    "6a756d62" // box type = 'jumb'
+        "00000019" // box size
+        "6a756d64" // box type = 'jumd'
+        "00000000000000000000000000000000" // UUID
+        "00" // toggles
+);
+
+const CONTAINER: [u8; 56] = hex!(
+    "0000000c" // box size
+    "4a584c20" // box type = 'JXL '
+    "0d0a870a" // signature
+    // ----
+    "00000014" // box size
+    "66747970" // box type = 'ftyp'
+    "6a786c20" // major brand = 'jxl '
+    "00000000" // minor version
+    "6a786c20" // compatible brand = 'jxl '
+    // ----
+    "0000000c" // box size
+    "4578696600000000" // box type = 'Exif', payload
+    // ----
+    "0000000c" // box size
+    "6a786c63" // box type = 'jxlc'
+    "ff0afa1f" // codestream
+);
+
+#[test]
+fn container_without_jumbf() {
+    assert!(jxl::is_container(&CONTAINER));
+    assert!(!jxl::is_codestream(&CONTAINER));
+    assert!(jxl::extract_jumbf(&CONTAINER).unwrap().is_empty());
+    assert_eq!(jxl::remove_jumbf(&CONTAINER).unwrap(), CONTAINER);
+}
+
+#[test]
+fn insert_into_container() {
+    let file = jxl::insert_jumbf(&CONTAINER, &JUMBF).unwrap();
+
+    // The superbox goes immediately before the codestream box.
+    assert_eq!(&file[..44], &CONTAINER[..44]);
+    assert_eq!(&file[44..77], JUMBF);
+    assert_eq!(&file[77..], &CONTAINER[44..]);
+
+    assert_eq!(jxl::extract_jumbf(&file).unwrap(), [&JUMBF[..]]);
+    assert_eq!(jxl::remove_jumbf(&file).unwrap(), CONTAINER);
+
+    // Inserting again keeps the existing superbox.
+    let file = jxl::insert_jumbf(&file, &JUMBF).unwrap();
+    assert_eq!(jxl::extract_jumbf(&file).unwrap(), [&JUMBF[..], &JUMBF[..]]);
+    assert_eq!(jxl::remove_jumbf(&file).unwrap(), CONTAINER);
+}
+
+#[test]
+fn insert_before_partial_codestream() {
+    let mut container = CONTAINER[..44].to_vec();
+    container.extend_from_slice(&hex!(
+        "0000000e" // box size
+        "6a786c70" // box type = 'jxlp'
+        "00000000" // partial codestream index
+        "ff0a" // codestream
+        // ----
+        "0000000e" // box size
+        "6a786c70" // box type = 'jxlp'
+        "80000001" // partial codestream index (last)
+        "fa1f" // codestream
+    ));
+
+    let file = jxl::insert_jumbf(&container, &JUMBF).unwrap();
+    assert_eq!(&file[44..77], JUMBF);
+    assert_eq!(&file[77..], &container[44..]);
+}
+
+#[test]
+fn insert_into_codestream() {
+    let codestream = hex!("ff0afa1f");
+    assert!(jxl::is_codestream(&codestream));
+    assert!(jxl::extract_jumbf(&codestream).unwrap().is_empty());
+    assert_eq!(jxl::remove_jumbf(&codestream).unwrap(), codestream);
+
+    let file = jxl::insert_jumbf(&codestream, &JUMBF).unwrap();
+    assert_eq!(&file[..32], &CONTAINER[..32]);
+    assert_eq!(&file[32..65], JUMBF);
+    assert_eq!(&file[65..], &CONTAINER[44..]);
+
+    assert_eq!(jxl::extract_jumbf(&file).unwrap(), [&JUMBF[..]]);
+}
+
+#[test]
+fn c2pa() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+
+    let file = jxl::insert_jumbf(&CONTAINER, jumbf).unwrap();
+    assert_eq!(file.len(), CONTAINER.len() + jumbf.len());
+    assert_eq!(jxl::extract_jumbf(&file).unwrap(), [&jumbf[..]]);
+}
+
+#[test]
+fn error_not_jxl() {
+    let png = hex!("89504e470d0a1a0a");
+
+    assert_eq!(
+        jxl::extract_jumbf(&png).unwrap_err(),
+        Error::Malformed("JPEG XL container or codestream")
+    );
+    assert_eq!(
+        jxl::insert_jumbf(&png, &JUMBF).unwrap_err(),
+        Error::Malformed("JPEG XL container or codestream")
+    );
+    assert_eq!(
+        jxl::remove_jumbf(&png).unwrap_err(),
+        Error::Malformed("JPEG XL container or codestream")
+    );
+}
+
+#[test]
+fn error_truncated_container() {
+    assert!(matches!(
+        jxl::extract_jumbf(&CONTAINER[..54]).unwrap_err(),
+        Error::Incomplete(_)
+    ));
+}
+
+#[test]
+fn error_no_codestream_box() {
+    assert_eq!(
+        jxl::insert_jumbf(&CONTAINER[..44], &JUMBF).unwrap_err(),
+        Error::MissingChildBox(jxl::CODESTREAM)
+    );
+}
+
+#[test]
+fn error_not_superbox() {
+    assert_eq!(
+        jxl::insert_jumbf(&CONTAINER, &CONTAINER[32..44]).unwrap_err(),
+        Error::InvalidSuperBoxType(BoxType(*b"Exif"))
+    );
+
+    let mut jumbf = JUMBF.to_vec();
+    jumbf.push(0);
+    assert_eq!(
+        jxl::insert_jumbf(&CONTAINER, &jumbf).unwrap_err(),
+        Error::Malformed("a single JUMBF superbox")
+    );
+}
@@ -0,0 +1,322 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{
+    embed::mp3::{self, Id3Frame},
+    parser::Error,
+    BoxType,
+};
+
+const JUMBF: [u8; 33] = hex!(
+    "00000021" // box size
+    "6a756d62" // box type = 'jumb'
+        "00000019" // box size
+        "6a756d64" // box type = 'jumd'
+        "00000000000000000000000000000000" // UUID
+        "00" // toggles
+);
+
+const AUDIO: [u8; 4] = hex!("fffb9000");
+
+// An ID3v2.3 tag with a title frame and padding, followed by audio.
+const TAGGED: [u8; 36] = hex!(
+    "494433" // 'ID3'
+    "0300" // version 2.3.0
+    "00" // flags
+    "00000016" // tag size (syncsafe)
+    "54495432" // frame ID = 'TIT2'
+    "00000006" // frame size
+    "0000" // frame flags
+    "00" // text encoding
+    "7469746c65" // "title"
+    "00" // padding
+    "0000000000" // padding
+    "fffb9000" // audio
+);
+
+#[test]
+fn insert_geob_without_tag() {
+    let file = mp3::insert_jumbf(&AUDIO, &JUMBF).unwrap();
+
+    let mut expected = hex!(
+        "494433" // 'ID3'
+        "0400" // version 2.4.0
+        "00" // flags
+        "00000050" // tag size (syncsafe)
+        "47454f42" // frame ID = 'GEOB'
+        "00000046" // frame size (syncsafe)
+        "0000" // frame flags
+        "00" // text encoding
+    )
+    .to_vec();
+    expected.extend_from_slice(b"application/x-c2pa-manifest-store\0");
+    expected.extend_from_slice(b"\0\0"); // file name, description
+    expected.extend_from_slice(&JUMBF);
+    expected.extend_from_slice(&AUDIO);
+
+    assert_eq!(file, expected);
+    assert_eq!(mp3::extract_jumbf(&file).unwrap(), [&JUMBF[..]]);
+
+    // Removing the only frame removes the tag.
+    assert_eq!(mp3::remove_jumbf(&file).unwrap(), AUDIO);
+}
+
+#[test]
+fn insert_priv_without_tag() {
+    let file = mp3::insert_jumbf_with_frame(&AUDIO, &JUMBF, Id3Frame::Priv).unwrap();
+
+    assert_eq!(
+        &file[..25],
+        hex!(
+            "494433" // 'ID3'
+            "0400" // version 2.4.0
+            "00" // flags
+            "00000030" // tag size (syncsafe)
+            "50524956" // frame ID = 'PRIV'
+            "00000026" // frame size (syncsafe)
+            "0000" // frame flags
+            "6332706100" // owner identifier ("c2pa")
+        )
+    );
+    assert_eq!(&file[25..58], JUMBF);
+    assert_eq!(&file[58..], AUDIO);
+
+    assert_eq!(mp3::extract_jumbf(&file).unwrap(), [&JUMBF[..]]);
+    assert_eq!(mp3::remove_jumbf(&file).unwrap(), AUDIO);
+}
+
+#[test]
+fn insert_into_v23_tag() {
+    assert!(mp3::extract_jumbf(&TAGGED).unwrap().is_empty());
+
+    let file = mp3::insert_jumbf(&TAGGED, &JUMBF).unwrap();
+
+    // The existing frame is kept and the padding is removed. Frame sizes
+    // in ID3v2.3 are not syncsafe.
+    assert_eq!(
+        &file[..26],
+        hex!(
+            "494433" // 'ID3'
+            "0300" // version 2.3.0
+            "00" // flags
+            "00000060" // tag size (syncsafe)
+            "54495432" // frame ID = 'TIT2'
+            "00000006" // frame size
+            "0000" // frame flags
+            "00" // text encoding
+            "7469746c65" // "title"
+        )
+    );
+    assert_eq!(&file[26..36], hex!("47454f42000000460000"));
+    assert_eq!(&file[file.len() - 4..], AUDIO);
+
+    let file = mp3::insert_jumbf_with_frame(&file, &JUMBF, Id3Frame::Priv).unwrap();
+    assert_eq!(mp3::extract_jumbf(&file).unwrap(), [&JUMBF[..], &JUMBF[..]]);
+
+    let file = mp3::remove_jumbf(&file).unwrap();
+    assert!(mp3::extract_jumbf(&file).unwrap().is_empty());
+    assert_eq!(&file[..10], hex!("494433 0300 00 00000010"));
+    assert_eq!(&file[10..26], &TAGGED[10..26]);
+    assert_eq!(&file[26..], AUDIO);
+}
+
+#[test]
+fn footer_is_kept() {
+    let tag = hex!(
+        "494433" // 'ID3'
+        "0400" // version 2.4.0
+        "10" // flags (footer)
+        "00000010" // tag size (syncsafe)
+        "54495432" // frame ID = 'TIT2'
+        "00000006" // frame size (syncsafe)
+        "0000" // frame flags
+        "00" // text encoding
+        "7469746c65" // "title"
+        "334449" // '3DI'
+        "0400" // version 2.4.0
+        "10" // flags (footer)
+        "00000010" // tag size (syncsafe)
+    );
+    let mut tagged = tag.to_vec();
+    tagged.extend_from_slice(&AUDIO);
+
+    let file = mp3::insert_jumbf(&tagged, &JUMBF).unwrap();
+    assert_eq!(mp3::extract_jumbf(&file).unwrap(), [&JUMBF[..]]);
+
+    let footer = &file[file.len() - 14..file.len() - 4];
+    assert_eq!(&footer[..6], b"3DI\x04\x00\x10");
+    assert_eq!(&footer[6..], &file[6..10]);
+
+    assert_eq!(mp3::remove_jumbf(&file).unwrap(), tagged);
+}
+
+#[test]
+fn extended_header_is_removed() {
+    let tagged = hex!(
+        "494433" // 'ID3'
+        "0300" // version 2.3.0
+        "40" // flags (extended header)
+        "0000001a" // tag size (syncsafe)
+        "00000006" // extended header size
+        "0000" // extended flags
+        "00000000" // padding size
+        "54495432" // frame ID = 'TIT2'
+        "00000006" // frame size
+        "0000" // frame flags
+        "00" // text encoding
+        "7469746c65" // "title"
+        "fffb9000" // audio
+    );
+
+    let file = mp3::insert_jumbf(&tagged, &JUMBF).unwrap();
+    assert_eq!(file[5], 0);
+    assert_eq!(&file[10..26], &tagged[20..36]);
+    assert_eq!(mp3::extract_jumbf(&file).unwrap(), [&JUMBF[..]]);
+}
+
+#[test]
+fn utf16_geob() {
+    let mut content = vec![1]; // text encoding (UTF-16)
+    content.extend_from_slice(b"application/x-c2pa-manifest-store\0");
+    content.extend_from_slice(&hex!("fffe6100 0000")); // file name ("a")
+    content.extend_from_slice(&hex!("fffe 0000")); // description
+    content.extend_from_slice(&JUMBF);
+
+    let mut file = hex!("494433 0400 00 00000000 47454f42 00000000 0000").to_vec();
+    file[17] = content.len() as u8;
+    file[9] = (content.len() + 10) as u8;
+    file.extend_from_slice(&content);
+
+    assert_eq!(mp3::extract_jumbf(&file).unwrap(), [&JUMBF[..]]);
+}
+
+#[test]
+fn other_frames_ignored() {
+    let file = hex!(
+        "494433" // 'ID3'
+        "0400" // version 2.4.0
+        "00" // flags
+        "00000032" // tag size (syncsafe)
+        "47454f42" // frame ID = 'GEOB'
+        "0000000f" // frame size (syncsafe)
+        "0000" // frame flags
+        "00" // text encoding
+        "696d6167652f706e6700" // MIME type ("image/png")
+        "0000" // file name, description
+        "0102" // object
+        "50524956" // frame ID = 'PRIV'
+        "0000000f" // frame size (syncsafe)
+        "0008" // frame flags (compressed)
+        "6332706100" // owner identifier ("c2pa")
+        "00000000000000000000" // compressed data
+    );
+
+    assert!(mp3::extract_jumbf(&file).unwrap().is_empty());
+    assert_eq!(mp3::remove_jumbf(&file).unwrap(), file);
+}
+
+#[test]
+fn c2pa() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+
+    let file = mp3::insert_jumbf(&TAGGED, jumbf).unwrap();
+    assert_eq!(mp3::extract_jumbf(&file).unwrap(), [&jumbf[..]]);
+}
+
+#[test]
+fn error_unsupported_tag() {
+    let v22 = hex!("494433 0200 00 00000000");
+    assert_eq!(
+        mp3::extract_jumbf(&v22).unwrap_err(),
+        Error::Malformed("ID3v2.3 or ID3v2.4 tag")
+    );
+
+    let unsynchronised = hex!("494433 0400 80 00000000");
+    assert_eq!(
+        mp3::insert_jumbf(&unsynchronised, &JUMBF).unwrap_err(),
+        Error::Malformed("ID3v2 tag without unsynchronisation")
+    );
+
+    let bad_size = hex!("494433 0400 00 00000080");
+    assert_eq!(
+        mp3::remove_jumbf(&bad_size).unwrap_err(),
+        Error::Malformed("ID3v2 size field")
+    );
+}
+
+#[test]
+fn error_truncated() {
+    assert_eq!(
+        mp3::extract_jumbf(b"ID3\x04").unwrap_err(),
+        Error::Incomplete(6)
+    );
+    assert_eq!(
+        mp3::extract_jumbf(&TAGGED[..20]).unwrap_err(),
+        Error::Incomplete(12)
+    );
+
+    let frame_too_long = hex!(
+        "494433" // 'ID3'
+        "0400" // version 2.4.0
+        "00" // flags
+        "0000000a" // tag size (syncsafe)
+        "54495432" // frame ID = 'TIT2'
+        "00000006" // frame size (syncsafe)
+        "0000" // frame flags
+    );
+    assert_eq!(
+        mp3::extract_jumbf(&frame_too_long).unwrap_err(),
+        Error::Malformed("ID3v2 frame within its tag")
+    );
+}
+
+#[test]
+fn error_size_overflow() {
+    let huge_frame = hex!(
+        "494433" // 'ID3'
+        "0300" // version 2.3.0
+        "00" // flags
+        "0000000a" // tag size (syncsafe)
+        "54495432" // frame ID = 'TIT2'
+        "ffffffff" // frame size
+        "0000" // frame flags
+    );
+    assert_eq!(
+        mp3::extract_jumbf(&huge_frame).unwrap_err(),
+        Error::Malformed("ID3v2 frame within its tag")
+    );
+
+    let huge_extended_header = hex!(
+        "494433" // 'ID3'
+        "0300" // version 2.3.0
+        "40" // flags (extended header)
+        "0000000a" // tag size (syncsafe)
+        "ffffffff" // extended header size
+        "000000000000" // extended header
+    );
+    assert_eq!(
+        mp3::extract_jumbf(&huge_extended_header).unwrap_err(),
+        Error::Malformed("ID3v2 extended header")
+    );
+}
+
+#[test]
+fn error_not_superbox() {
+    assert_eq!(
+        mp3::insert_jumbf(&AUDIO, &hex!("000000096162636478")).unwrap_err(),
+        Error::InvalidSuperBoxType(BoxType(*b"abcd"))
+    );
+}
@@ -0,0 +1,118 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{embed::riff, parser::Error};
+
+#[test]
+fn round_trip() {
+    let file = riff::write(b"TEST", [(b"abcd", &b"x"[..]), (b"efgh", &b"yz"[..])]).unwrap();
+
+    assert_eq!(
+        file,
+        hex!(
+            "52494646" // 'RIFF'
+            "18000000" // RIFF size
+            "54455354" // form type = 'TEST'
+            "61626364" // chunk ID = 'abcd'
+            "01000000" // chunk size
+            "78" // payload
+            "00" // padding
+            "65666768" // chunk ID = 'efgh'
+            "02000000" // chunk size
+            "797a" // payload
+        )
+    );
+
+    let chunks = riff::parse(&file, b"TEST").unwrap();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(&chunks[0].id, b"abcd");
+    assert_eq!(chunks[0].data, b"x");
+    assert_eq!(&chunks[1].id, b"efgh");
+    assert_eq!(chunks[1].data, b"yz");
+}
+
+#[test]
+fn missing_final_padding() {
+    let file = hex!(
+        "52494646" // 'RIFF'
+        "0d000000" // RIFF size
+        "54455354" // form type = 'TEST'
+        "61626364" // chunk ID = 'abcd'
+        "01000000" // chunk size
+        "78" // payload (no padding)
+    );
+
+    let chunks = riff::parse(&file, b"TEST").unwrap();
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].data, b"x");
+}
+
+#[test]
+fn trailing_data_ignored() {
+    let mut file = riff::write(b"TEST", [(b"abcd", &b"xy"[..])]).unwrap();
+    file.extend_from_slice(b"junk");
+
+    let chunks = riff::parse(&file, b"TEST").unwrap();
+    assert_eq!(chunks.len(), 1);
+}
+
+#[test]
+fn error_wrong_form_type() {
+    let file = riff::write(b"TEST", []).unwrap();
+    assert_eq!(
+        riff::parse(&file, b"WEBP").err(),
+        Some(Error::Malformed("RIFF file"))
+    );
+    assert_eq!(
+        riff::parse(b"RIFX", b"TEST").err(),
+        Some(Error::Malformed("RIFF file"))
+    );
+}
+
+#[test]
+fn error_truncated() {
+    let file = riff::write(b"TEST", [(b"abcd", &b"xyz"[..])]).unwrap();
+
+    assert_eq!(
+        riff::parse(&file[..file.len() - 2], b"TEST").err(),
+        Some(Error::Incomplete(2))
+    );
+
+    // A chunk whose size exceeds the RIFF chunk.
+    let file = hex!(
+        "52494646" // 'RIFF'
+        "0c000000" // RIFF size
+        "54455354" // form type = 'TEST'
+        "61626364" // chunk ID = 'abcd'
+        "01000000" // chunk size
+    );
+    assert_eq!(
+        riff::parse(&file, b"TEST").err(),
+        Some(Error::Incomplete(1))
+    );
+
+    // A partial chunk header.
+    let file = hex!(
+        "52494646" // 'RIFF'
+        "08000000" // RIFF size
+        "54455354" // form type = 'TEST'
+        "61626364" // chunk ID = 'abcd'
+    );
+    assert_eq!(
+        riff::parse(&file, b"TEST").err(),
+        Some(Error::Incomplete(4))
+    );
+}
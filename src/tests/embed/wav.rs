@@ -0,0 +1,105 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{embed::wav, parser::Error, BoxType};
+
+const JUMBF: [u8; 33] = hex!(
+    "00000021" // box size
+    "6a756d62" // box type = 'jumb'
+        "00000019" // box size
+        "6a756d64" // box type = 'jumd'
+        "00000000000000000000000000000000" // UUID
+        "00" // toggles
+);
+
+const AUDIO: [u8; 30] = hex!(
+    "52494646" // 'RIFF'
+    "16000000" // RIFF size
+    "57415645" // form type = 'WAVE'
+    "666d7420" // chunk ID = 'fmt '
+    "02000000" // chunk size
+    "0100" // payload (truncated)
+    "64617461" // chunk ID = 'data'
+    "00000000" // chunk size
+);
+
+#[test]
+fn insert_and_remove() {
+    assert!(wav::extract_jumbf(&AUDIO).unwrap().is_empty());
+
+    let file = wav::insert_jumbf(&AUDIO, &JUMBF).unwrap();
+
+    let mut expected = hex!(
+        "52494646" // 'RIFF'
+        "40000000" // RIFF size
+        "57415645" // form type = 'WAVE'
+    )
+    .to_vec();
+    expected.extend_from_slice(&AUDIO[12..]);
+    expected.extend_from_slice(&hex!(
+        "43325041" // chunk ID = 'C2PA'
+        "21000000" // chunk size
+    ));
+    expected.extend_from_slice(&JUMBF);
+    expected.push(0); // padding
+
+    assert_eq!(file, expected);
+    assert_eq!(wav::extract_jumbf(&file).unwrap(), [&JUMBF[..]]);
+
+    // Inserting again keeps the existing chunk.
+    let file = wav::insert_jumbf(&file, &JUMBF).unwrap();
+    assert_eq!(wav::extract_jumbf(&file).unwrap(), [&JUMBF[..], &JUMBF[..]]);
+
+    assert_eq!(wav::remove_jumbf(&file).unwrap(), AUDIO);
+}
+
+#[test]
+fn c2pa() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+
+    let file = wav::insert_jumbf(&AUDIO, jumbf).unwrap();
+    assert_eq!(wav::extract_jumbf(&file).unwrap(), [&jumbf[..]]);
+}
+
+#[test]
+fn error_not_wav() {
+    let webp = hex!(
+        "52494646" // 'RIFF'
+        "04000000" // RIFF size
+        "57454250" // form type = 'WEBP'
+    );
+
+    assert_eq!(
+        wav::extract_jumbf(&webp).unwrap_err(),
+        Error::Malformed("RIFF file")
+    );
+    assert_eq!(
+        wav::insert_jumbf(&webp, &JUMBF).unwrap_err(),
+        Error::Malformed("RIFF file")
+    );
+    assert_eq!(
+        wav::remove_jumbf(&webp).unwrap_err(),
+        Error::Malformed("RIFF file")
+    );
+}
+
+#[test]
+fn error_not_superbox() {
+    assert_eq!(
+        wav::insert_jumbf(&AUDIO, &hex!("000000096162636478")).unwrap_err(),
+        Error::InvalidSuperBoxType(BoxType(*b"abcd"))
+    );
+}
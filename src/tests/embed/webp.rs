@@ -0,0 +1,225 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{embed::webp, parser::Error, BoxType};
+
+const JUMBF: [u8; 33] = hex!(
+    "00000021" // box size
+    "6a756d62" // box type = 'jumb'
+        "00000019" // box size
+        "6a756d64" // box type = 'jumd'
+        "00000000000000000000000000000000" // UUID
+        "00" // toggles
+);
+
+const LOSSY: [u8; 30] = hex!(
+    "52494646" // 'RIFF'
+    "16000000" // RIFF size
+    "57454250" // form type = 'WEBP'
+    "56503820" // chunk ID = 'VP8 '
+    "0a000000" // chunk size
+    "f00100" // frame tag
+    "9d012a" // start code
+    "0200" // width = 2
+    "0300" // height = 3
+);
+
+#[test]
+fn insert_into_lossy() {
+    let file = webp::insert_jumbf(&LOSSY, &JUMBF).unwrap();
+
+    let mut expected = hex!(
+        "52494646" // 'RIFF'
+        "52000000" // RIFF size
+        "57454250" // form type = 'WEBP'
+        "56503858" // chunk ID = 'VP8X'
+        "0a000000" // chunk size
+        "00" // flags
+        "000000" // reserved
+        "010000" // canvas width - 1
+        "020000" // canvas height - 1
+    )
+    .to_vec();
+    expected.extend_from_slice(&LOSSY[12..]);
+    expected.extend_from_slice(&hex!(
+        "43325041" // chunk ID = 'C2PA'
+        "21000000" // chunk size
+    ));
+    expected.extend_from_slice(&JUMBF);
+    expected.push(0); // padding
+
+    assert_eq!(file, expected);
+    assert_eq!(webp::extract_jumbf(&file).unwrap(), [&JUMBF[..]]);
+}
+
+#[test]
+fn insert_into_lossless() {
+    let image = hex!(
+        "52494646" // 'RIFF'
+        "12000000" // RIFF size
+        "57454250" // form type = 'WEBP'
+        "5650384c" // chunk ID = 'VP8L'
+        "05000000" // chunk size
+        "2f0fc00110" // lossless bitstream header (16x8, alpha)
+        "00" // padding
+    );
+
+    let file = webp::insert_jumbf(&image, &JUMBF).unwrap();
+
+    assert_eq!(
+        &file[12..30],
+        hex!(
+            "56503858" // chunk ID = 'VP8X'
+            "0a000000" // chunk size
+            "10" // flags (alpha)
+            "000000" // reserved
+            "0f0000" // canvas width - 1
+            "070000" // canvas height - 1
+        )
+    );
+    assert_eq!(&file[30..44], &image[12..]);
+    assert_eq!(webp::extract_jumbf(&file).unwrap(), [&JUMBF[..]]);
+}
+
+#[test]
+fn insert_into_extended() {
+    let image = hex!(
+        "52494646" // 'RIFF'
+        "34000000" // RIFF size
+        "57454250" // form type = 'WEBP'
+        "56503858" // chunk ID = 'VP8X'
+        "0a000000" // chunk size
+        "08" // flags (EXIF)
+        "000000" // reserved
+        "010000" // canvas width - 1
+        "020000" // canvas height - 1
+        "56503820" // chunk ID = 'VP8 '
+        "0a000000" // chunk size
+        "f001009d012a02000300" // lossy bitstream
+        "45584946" // chunk ID = 'EXIF'
+        "04000000" // chunk size
+        "49492a00" // payload
+    );
+
+    assert!(webp::extract_jumbf(&image).unwrap().is_empty());
+
+    let file = webp::insert_jumbf(&image, &JUMBF).unwrap();
+    assert_eq!(&file[12..], {
+        let mut expected = image[12..].to_vec();
+        expected.extend_from_slice(&hex!("4332504121000000"));
+        expected.extend_from_slice(&JUMBF);
+        expected.push(0);
+        expected
+    });
+    assert_eq!(
+        u32::from_le_bytes([file[4], file[5], file[6], file[7]]) as usize,
+        file.len() - 8
+    );
+
+    // Inserting again keeps the existing chunk.
+    let file = webp::insert_jumbf(&file, &JUMBF).unwrap();
+    assert_eq!(
+        webp::extract_jumbf(&file).unwrap(),
+        [&JUMBF[..], &JUMBF[..]]
+    );
+
+    assert_eq!(webp::remove_jumbf(&file).unwrap(), image);
+}
+
+#[test]
+fn remove_keeps_vp8x() {
+    let file = webp::insert_jumbf(&LOSSY, &JUMBF).unwrap();
+    let file = webp::remove_jumbf(&file).unwrap();
+
+    assert_eq!(&file[12..16], b"VP8X");
+    assert_eq!(&file[30..], &LOSSY[12..]);
+    assert!(webp::extract_jumbf(&file).unwrap().is_empty());
+}
+
+#[test]
+fn c2pa() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+
+    let file = webp::insert_jumbf(&LOSSY, jumbf).unwrap();
+    assert_eq!(webp::extract_jumbf(&file).unwrap(), [&jumbf[..]]);
+}
+
+#[test]
+fn error_not_webp() {
+    let wav = hex!(
+        "52494646" // 'RIFF'
+        "04000000" // RIFF size
+        "57415645" // form type = 'WAVE'
+    );
+
+    assert_eq!(
+        webp::extract_jumbf(&wav).unwrap_err(),
+        Error::Malformed("RIFF file")
+    );
+    assert_eq!(
+        webp::insert_jumbf(&wav, &JUMBF).unwrap_err(),
+        Error::Malformed("RIFF file")
+    );
+    assert_eq!(
+        webp::remove_jumbf(&wav).unwrap_err(),
+        Error::Malformed("RIFF file")
+    );
+}
+
+#[test]
+fn error_no_image_data() {
+    let empty = hex!(
+        "52494646" // 'RIFF'
+        "04000000" // RIFF size
+        "57454250" // form type = 'WEBP'
+    );
+    assert_eq!(
+        webp::insert_jumbf(&empty, &JUMBF).unwrap_err(),
+        Error::Malformed("WebP image data")
+    );
+}
+
+#[test]
+fn error_bad_bitstream_header() {
+    let mut lossy = LOSSY;
+    lossy[23] = 0;
+    assert_eq!(
+        webp::insert_jumbf(&lossy, &JUMBF).unwrap_err(),
+        Error::Malformed("WebP lossy bitstream header")
+    );
+
+    let lossless = hex!(
+        "52494646" // 'RIFF'
+        "12000000" // RIFF size
+        "57454250" // form type = 'WEBP'
+        "5650384c" // chunk ID = 'VP8L'
+        "05000000" // chunk size
+        "0000000000" // not a lossless bitstream header
+        "00" // padding
+    );
+    assert_eq!(
+        webp::insert_jumbf(&lossless, &JUMBF).unwrap_err(),
+        Error::Malformed("WebP lossless bitstream header")
+    );
+}
+
+#[test]
+fn error_not_superbox() {
+    assert_eq!(
+        webp::insert_jumbf(&LOSSY, &hex!("000000096162636478")).unwrap_err(),
+        Error::InvalidSuperBoxType(BoxType(*b"abcd"))
+    );
+}
@@ -0,0 +1,61 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::error::Error as _;
+
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{builder::BuilderError, parser, BoxType, Error};
+
+#[test]
+fn from_parser_error() {
+    let err: Error = parser::Error::MissingChildBox(BoxType::CBOR).into();
+
+    assert_eq!(
+        err.parse_error(),
+        Some(&parser::Error::MissingChildBox(BoxType::CBOR))
+    );
+    assert!(err.builder_error().is_none());
+    assert!(err.source().is_some());
+
+    assert_eq!(
+        err.to_string(),
+        "Unable to parse JUMBF: Superbox is missing required child box 'b\"cbor\"'"
+    );
+}
+
+#[test]
+fn from_builder_error() {
+    let builder_err = BuilderError::DuplicateLabel {
+        label: "x".to_owned(),
+    };
+    let err: Error = builder_err.clone().into();
+
+    assert_eq!(err.builder_error(), Some(&builder_err));
+    assert!(err.parse_error().is_none());
+    assert!(matches!(err, Error::Io(ref e) if e.kind() == std::io::ErrorKind::InvalidInput));
+
+    assert_eq!(
+        err.to_string(),
+        "Unable to build JUMBF: More than one requestable child superbox is labeled \"x\""
+    );
+}
+
+#[test]
+fn from_io_error() {
+    let err: Error = std::io::Error::new(std::io::ErrorKind::WriteZero, "full").into();
+
+    assert!(err.builder_error().is_none());
+    assert!(err.parse_error().is_none());
+    assert_eq!(err.to_string(), "Unable to build JUMBF: full");
+}
@@ -0,0 +1,151 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use arbitrary::{Arbitrary, Unstructured};
+
+use crate::{
+    fuzzing::{ChildBoxSpec, DataBoxSpec, RoundTripError, SuperBoxSpec},
+    BoxType, Uuid,
+};
+
+// Deterministic pseudo-random bytes (xorshift) so that failures reproduce.
+fn random_bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+#[test]
+fn arbitrary_round_trip() {
+    let mut max_children = 0;
+
+    for seed in 0..500 {
+        let bytes = random_bytes(seed, 4096);
+        let mut u = Unstructured::new(&bytes);
+        let spec = SuperBoxSpec::arbitrary(&mut u).unwrap();
+
+        max_children = max_children.max(spec.child_boxes.len());
+
+        if let Err(e) = spec.check_round_trip() {
+            panic!("seed {seed}: {e}");
+        }
+    }
+
+    assert!(max_children > 0);
+}
+
+#[test]
+fn never_generates_superbox_data_box() {
+    let bytes = b"jumbjumbjumbjumb";
+    let mut u = Unstructured::new(bytes);
+    let dbox = DataBoxSpec::arbitrary(&mut u).unwrap();
+    assert_ne!(dbox.tbox, BoxType(*b"jumb"));
+}
+
+#[test]
+fn nested_round_trip() {
+    let spec = SuperBoxSpec {
+        uuid: Uuid::JSON,
+        label: Some("outer".to_owned()),
+        requestable: true,
+        id: Some(42),
+        hash: Some([7; 32]),
+        private: Some(DataBoxSpec {
+            tbox: BoxType(*b"priv"),
+            data: b"private".to_vec(),
+        }),
+        unknown_toggles: 0x80,
+        trailing_description_data: b"future".to_vec(),
+        child_boxes: vec![
+            ChildBoxSpec::DataBox(DataBoxSpec {
+                tbox: BoxType(*b"json"),
+                data: b"{}".to_vec(),
+            }),
+            ChildBoxSpec::SuperBox(SuperBoxSpec {
+                uuid: Uuid([0; 16]),
+                label: Some("inner".to_owned()),
+                requestable: false,
+                id: None,
+                hash: None,
+                private: None,
+                unknown_toggles: 0,
+                trailing_description_data: vec![],
+                child_boxes: vec![],
+            }),
+        ],
+    };
+
+    spec.check_round_trip().unwrap();
+}
+
+#[test]
+fn invalid_label() {
+    let spec = SuperBoxSpec {
+        uuid: Uuid([0; 16]),
+        label: Some("a/b".to_owned()),
+        requestable: true,
+        id: None,
+        hash: None,
+        private: None,
+        unknown_toggles: 0,
+        trailing_description_data: vec![],
+        child_boxes: vec![],
+    };
+
+    assert!(matches!(
+        spec.check_round_trip(),
+        Err(RoundTripError::Build(_))
+    ));
+}
+
+#[test]
+fn mismatch() {
+    // A data box of type `jumb` is parsed back as a superbox.
+    let spec = SuperBoxSpec {
+        uuid: Uuid([0; 16]),
+        label: None,
+        requestable: false,
+        id: None,
+        hash: None,
+        private: None,
+        unknown_toggles: 0,
+        trailing_description_data: vec![],
+        child_boxes: vec![ChildBoxSpec::DataBox(DataBoxSpec {
+            tbox: BoxType(*b"jumb"),
+            data: SuperBoxSpec {
+                uuid: Uuid([0; 16]),
+                label: None,
+                requestable: false,
+                id: None,
+                hash: None,
+                private: None,
+                unknown_toggles: 0,
+                trailing_description_data: vec![],
+                child_boxes: vec![],
+            }
+            .to_jumbf()
+            .unwrap()[8..]
+                .to_vec(),
+        })],
+    };
+
+    let err = spec.check_round_trip().unwrap_err();
+    assert!(matches!(err, RoundTripError::Mismatch(_)));
+    assert!(err.to_string().starts_with("Parsed superbox differs"));
+}
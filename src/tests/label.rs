@@ -0,0 +1,78 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::borrow::Cow;
+
+use crate::label;
+
+#[test]
+fn invalid_character() {
+    assert_eq!(label::invalid_character("c2pa.assertions"), None);
+    assert_eq!(label::invalid_character("a\0b"), Some('\0'));
+    assert_eq!(label::invalid_character("a?b#c"), Some('?'));
+    assert_eq!(label::invalid_character("a#b"), Some('#'));
+
+    assert!(label::is_valid(""));
+    assert!(label::is_valid("contentauth:urn:uuid:021b555e"));
+    assert!(!label::is_valid("c2pa/c2pa.claim"));
+}
+
+#[test]
+fn normalize() {
+    assert!(matches!(
+        label::normalize("c2pa.claim"),
+        Cow::Borrowed("c2pa.claim")
+    ));
+    assert_eq!(label::normalize("A/B?C#D\0É"), "a_b_c_d_É");
+}
+
+#[test]
+fn matches() {
+    assert!(label::matches("c2pa.claim", "c2pa.claim"));
+    assert!(!label::matches("c2pa.claim", "C2PA.claim"));
+    assert!(!label::matches("c2pa.claim", "c2pa.claim "));
+}
+
+#[test]
+fn percent_encode() {
+    assert!(matches!(
+        label::percent_encode("contentauth:urn:uuid:021b555e"),
+        Cow::Borrowed(_)
+    ));
+    assert_eq!(label::percent_encode("50% off"), "50%25%20off");
+    assert_eq!(label::percent_encode("日"), "%E6%97%A5");
+}
+
+#[test]
+fn percent_decode() {
+    assert!(matches!(
+        label::percent_decode("c2pa.claim"),
+        Some(Cow::Borrowed("c2pa.claim"))
+    ));
+    assert_eq!(label::percent_decode("50%25%20off").unwrap(), "50% off");
+    assert_eq!(label::percent_decode("%e6%97%a5").unwrap(), "日");
+    assert_eq!(label::percent_decode("a%2Fb").unwrap(), "a/b");
+
+    assert_eq!(label::percent_decode("%"), None);
+    assert_eq!(label::percent_decode("%2"), None);
+    assert_eq!(label::percent_decode("%zz"), None);
+    assert_eq!(label::percent_decode("%ff"), None);
+}
+
+#[test]
+fn percent_round_trip() {
+    for text in ["c2pa.claim", "my label", "100%", "日本", "a&b=c;d"] {
+        let encoded = label::percent_encode(text);
+        assert_eq!(label::percent_decode(&encoded).unwrap(), text);
+    }
+}
@@ -18,11 +18,65 @@
 #![allow(clippy::panic)]
 #![allow(clippy::unwrap_used)]
 
+#[cfg(feature = "parser")]
+mod app11;
+
+#[cfg(all(feature = "parser", feature = "std"))]
+mod bench_support;
+
 mod box_type;
+
+#[cfg(feature = "std")]
 mod builder;
 
+#[cfg(feature = "parser")]
+mod c2pa;
+
+#[cfg(feature = "parser")]
+mod compact;
+
+mod codestream;
+
+#[cfg(feature = "compress")]
+mod compression;
+
+mod content_type;
+
 #[cfg(feature = "parser")]
 mod debug;
 
+#[cfg(all(feature = "parser", feature = "std"))]
+mod diff;
+
+#[cfg(all(feature = "parser", feature = "std"))]
+mod error;
+
+#[cfg(feature = "parser")]
+mod embed;
+
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
+
+mod label;
+
 #[cfg(feature = "parser")]
 mod parser;
+
+#[cfg(feature = "parser")]
+mod scan;
+
+#[cfg(feature = "parser")]
+mod schema;
+
+#[cfg(feature = "testing")]
+mod testing;
+
+mod toggles;
+
+#[cfg(feature = "parser")]
+mod uri;
+
+mod uuid;
+
+#[cfg(feature = "wasm")]
+mod wasm;
@@ -0,0 +1,150 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::{Cursor, ErrorKind, Read, Seek, SeekFrom};
+
+use crate::parser::{CachedReader, SuperBox};
+
+/// Wraps a reader and counts how many times it is read from.
+struct CountingReader<R> {
+    inner: R,
+    reads: usize,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.reads += 1;
+        self.inner.read(buf)
+    }
+}
+
+impl<R: Seek> Seek for CountingReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+fn counting(data: &[u8]) -> CountingReader<Cursor<&[u8]>> {
+    CountingReader {
+        inner: Cursor::new(data),
+        reads: 0,
+    }
+}
+
+#[test]
+fn read_all() {
+    let data: Vec<u8> = (0..=255).collect();
+    let mut reader = CachedReader::with_block_size(Cursor::new(&data), 10, 2);
+
+    let mut out = vec![];
+    reader.read_to_end(&mut out).unwrap();
+    assert_eq!(out, data);
+}
+
+#[test]
+fn repeated_reads_use_cache() {
+    let data: Vec<u8> = (0..=255).collect();
+    let mut reader = CachedReader::with_block_size(counting(&data), 16, 4);
+
+    let mut buf = [0u8; 4];
+    for _ in 0..10 {
+        reader.seek(SeekFrom::Start(20)).unwrap();
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [20, 21, 22, 23]);
+    }
+
+    assert_eq!(reader.get_ref().reads, 1);
+}
+
+#[test]
+fn least_recently_used_block_is_evicted() {
+    let data: Vec<u8> = (0..=255).collect();
+    let mut reader = CachedReader::with_block_size(counting(&data), 16, 2);
+
+    fn read_at(reader: &mut CachedReader<CountingReader<Cursor<&[u8]>>>, pos: u64) -> usize {
+        let mut buf = [0u8; 1];
+        reader.seek(SeekFrom::Start(pos)).unwrap();
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf[0], pos as u8);
+        reader.get_ref().reads
+    }
+
+    let after_a = read_at(&mut reader, 0);
+    let after_b = read_at(&mut reader, 16);
+    assert!(after_b > after_a);
+
+    // Block 0 is cached and becomes most recently used.
+    assert_eq!(read_at(&mut reader, 1), after_b);
+
+    // Reading block 2 evicts block 1, not block 0.
+    let after_c = read_at(&mut reader, 32);
+    assert!(after_c > after_b);
+    assert_eq!(read_at(&mut reader, 2), after_c);
+    assert!(read_at(&mut reader, 17) > after_c);
+}
+
+#[test]
+fn read_across_blocks() {
+    let data: Vec<u8> = (0..=255).collect();
+    let mut reader = CachedReader::with_block_size(Cursor::new(&data), 7, 1);
+
+    reader.seek(SeekFrom::Start(5)).unwrap();
+
+    let mut buf = [0u8; 20];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf.to_vec(), (5..25).collect::<Vec<u8>>());
+}
+
+#[test]
+fn seek() {
+    let data: Vec<u8> = (0..100).collect();
+    let mut reader = CachedReader::new(Cursor::new(&data));
+
+    assert_eq!(reader.seek(SeekFrom::End(-10)).unwrap(), 90);
+    assert_eq!(reader.seek(SeekFrom::Current(5)).unwrap(), 95);
+    assert_eq!(reader.stream_position().unwrap(), 95);
+
+    let mut out = vec![];
+    reader.read_to_end(&mut out).unwrap();
+    assert_eq!(out, [95, 96, 97, 98, 99]);
+
+    // Reading past the end returns no data.
+    reader.seek(SeekFrom::Start(1000)).unwrap();
+    assert_eq!(reader.read(&mut [0u8; 4]).unwrap(), 0);
+
+    assert_eq!(
+        reader.seek(SeekFrom::Current(-2000)).unwrap_err().kind(),
+        ErrorKind::InvalidInput
+    );
+}
+
+#[test]
+fn zero_block_size_and_capacity() {
+    let data: Vec<u8> = (0..10).collect();
+    let mut reader = CachedReader::with_block_size(Cursor::new(&data), 0, 0);
+
+    let mut out = vec![];
+    reader.read_to_end(&mut out).unwrap();
+    assert_eq!(out, data);
+    assert_eq!(reader.into_inner().position(), 10);
+}
+
+#[test]
+fn parse_super_box() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let mut reader = CachedReader::with_block_size(Cursor::new(jumbf), 512, 4);
+
+    let mut buf = vec![];
+    let sbox = SuperBox::from_reader(&mut reader, &mut buf).unwrap();
+    assert_eq!(sbox, SuperBox::from_slice(jumbf).unwrap().1);
+}
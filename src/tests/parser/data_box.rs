@@ -11,8 +11,9 @@
 // specific language governing permissions and limitations under
 // each license.
 
+use std::borrow::Cow;
+
 use hex_literal::hex;
-use nom::{error::ErrorKind, Needed};
 use pretty_assertions_sorted::assert_eq;
 
 use crate::{
@@ -42,10 +43,11 @@ fn simple_box() {
                 101, 115, 99, 98, 111, 120, 0,
             ],
             original: &jumbf,
+            offset: 0,
         }
     );
 
-    assert_eq!(format!("{boxx:#?}"), "DataBox {\n    tbox: b\"jumd\",\n    data: 30 bytes starting with [00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 03, 74, 65, 73],\n    original: 38 bytes starting with [00, 00, 00, 26, 6a, 75, 6d, 64, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n}");
+    assert_eq!(format!("{boxx:#?}"), "DataBox {\n    tbox: b\"jumd\",\n    data: 30 bytes starting with [00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 03, 74, 65, 73],\n    original: 38 bytes starting with [00, 00, 00, 26, 6a, 75, 6d, 64, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    offset: 0,\n}");
 }
 
 #[test]
@@ -56,7 +58,7 @@ fn error_incomplete_box_length() {
 
     assert_eq!(
         DataBox::from_slice(&jumbf).unwrap_err(),
-        nom::Err::Error(Error::NomError(ErrorKind::Eof))
+        Error::UnexpectedEof
     );
 }
 
@@ -69,7 +71,7 @@ fn error_incomplete_box_type() {
 
     assert_eq!(
         DataBox::from_slice(&jumbf).unwrap_err(),
-        nom::Err::Error(Error::Incomplete(Needed::new(4)))
+        Error::Incomplete(4)
     );
 }
 
@@ -82,7 +84,7 @@ fn error_invalid_box_length() {
 
     assert_eq!(
         DataBox::from_slice(&jumbf).unwrap_err(),
-        nom::Err::Error(Error::InvalidBoxLength(2,),)
+        Error::InvalidBoxLength(2,),
     );
 }
 
@@ -108,6 +110,7 @@ fn read_to_eof() {
                 101, 115, 99, 98, 111, 120, 0,
             ],
             original: &jumbf,
+            offset: 0,
         }
     );
 }
@@ -135,6 +138,7 @@ fn read_xlbox_size() {
                 101, 115, 99, 98, 111, 120, 0,
             ],
             original: &jumbf,
+            offset: 0,
         }
     );
 }
@@ -152,7 +156,7 @@ fn error_xlbox_size_too_small() {
 
     assert_eq!(
         DataBox::from_slice(&jumbf).unwrap_err(),
-        nom::Err::Error(Error::InvalidBoxLength(14,),)
+        Error::InvalidBoxLength(14,),
     );
 }
 
@@ -168,7 +172,112 @@ fn error_incorrect_length() {
 
     assert_eq!(
         DataBox::from_slice(&jumbf).unwrap_err(),
-        nom::Err::Error(Error::Incomplete(Needed::new(30)))
+        Error::Incomplete(30)
+    );
+}
+
+#[test]
+fn as_str() {
+    let jumbf = hex!(
+        "0000000f" // box size
+        "61626364" // box type = 'abcd'
+        "7061796c6f6164" // payload ("payload")
+    );
+
+    let (_, dbox) = DataBox::from_slice(&jumbf).unwrap();
+    assert_eq!(dbox.as_str().unwrap(), "payload");
+    assert!(matches!(dbox.to_string_lossy(), Cow::Borrowed("payload")));
+}
+
+#[test]
+fn as_str_invalid_utf8() {
+    let jumbf = hex!(
+        "0000000c" // box size
+        "61626364" // box type = 'abcd'
+        "6869ff21" // payload ("hi\xff!")
+    );
+
+    let (_, dbox) = DataBox::from_slice(&jumbf).unwrap();
+
+    let Error::Utf8Error(e) = dbox.as_str().unwrap_err() else {
+        panic!("expected a UTF-8 error");
+    };
+    assert_eq!(e.valid_up_to(), 2);
+
+    assert_eq!(dbox.to_string_lossy(), "hi\u{fffd}!");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn copy_payload_to() {
+    let jumbf = hex!(
+        "0000000f" // box size
+        "61626364" // box type = 'abcd'
+        "7061796c6f6164" // payload ("payload")
+    );
+
+    let (_, dbox) = DataBox::from_slice(&jumbf).unwrap();
+
+    let mut out = vec![];
+    dbox.copy_payload_to(&mut out).unwrap();
+    assert_eq!(out, b"payload");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn reader() {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let jumbf = hex!(
+        "0000000f" // box size
+        "61626364" // box type = 'abcd'
+        "7061796c6f6164" // payload ("payload")
+    );
+
+    let (_, dbox) = DataBox::from_slice(&jumbf).unwrap();
+    let mut reader = dbox.reader();
+
+    let mut buf = [0; 3];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"pay");
+
+    // Seeking is relative to the payload, not the box header.
+    assert_eq!(reader.seek(SeekFrom::End(-2)).unwrap(), 5);
+    reader.read_exact(&mut buf[..2]).unwrap();
+    assert_eq!(&buf[..2], b"ad");
+
+    assert_eq!(reader.read(&mut buf).unwrap(), 0);
+
+    // The reader borrows the payload rather than copying it.
+    assert!(core::ptr::eq(*reader.get_ref(), dbox.data));
+}
+
+#[cfg(feature = "compress")]
+#[test]
+fn decompress() {
+    use crate::{compression::Compression, parser::ParseLimit};
+
+    let jumbf = hex!(
+        "00000017" // box size
+        "61626364" // box type = 'abcd'
+        "789c2b48acccc94f4c01000bdd02eb" // payload ("payload", zlib-compressed)
+    );
+
+    let (_, dbox) = DataBox::from_slice(&jumbf).unwrap();
+
+    assert_eq!(dbox.decompress(Compression::Zlib, 7).unwrap(), b"payload");
+
+    assert_eq!(
+        dbox.decompress(Compression::Zlib, 6).unwrap_err(),
+        Error::LimitExceeded {
+            limit: ParseLimit::BoxSize,
+            max: 6,
+        }
+    );
+
+    assert_eq!(
+        dbox.decompress(Compression::Brotli, 7).unwrap_err(),
+        Error::Malformed("compressed payload")
     );
 }
 
@@ -339,3 +448,41 @@ mod offset_within_superbox {
         assert!(claim_dbox.offset_within_superbox(sig_sbox).is_none());
     }
 }
+
+mod offset_within {
+    use hex_literal::hex;
+    use pretty_assertions_sorted::assert_eq;
+
+    use crate::parser::SuperBox;
+
+    #[test]
+    fn abuse_read_to_eof() {
+        // As with the corresponding `offset_within_superbox` test, the data box
+        // from the longer parse run overruns the superbox from the shorter
+        // parse run and should not be considered to be contained within it.
+
+        let jumbf = hex!(
+        "00000000" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000028" // box size
+            "6a756d64" // box type = 'jumd'
+            "6332637300110010800000aa00389b71" // UUID
+            "03" // toggles
+            "633270612e7369676e617475726500" // label
+            // ----
+            "00000000" // box size
+            "75756964" // box type = 'uuid'
+            "6332637300110010800000aa00389b717468697320776f756c64206e6f726d616c6c792062652062696e617279207369676e617475726520646174612e2e2e" // data (type unknown)
+        );
+
+        let (_, sbox_full) = SuperBox::from_slice(&jumbf).unwrap();
+        let (_, sbox_short) = SuperBox::from_slice(&jumbf[0..118]).unwrap();
+
+        let dbox_from_full = sbox_full.data_box().unwrap();
+
+        assert_eq!(dbox_from_full.offset_within(&sbox_full), Some(48));
+        assert_eq!(dbox_from_full.offset_within(&sbox_short), None);
+        assert_eq!(sbox_full.offset_within(&sbox_short), None);
+        assert_eq!(sbox_short.offset_within(&sbox_full), Some(0));
+    }
+}
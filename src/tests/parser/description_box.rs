@@ -12,12 +12,11 @@
 // each license.
 
 use hex_literal::hex;
-use nom::Needed;
 use pretty_assertions_sorted::assert_eq;
 
 use crate::{
-    parser::{DataBox, DescriptionBox, Error},
-    BoxType,
+    parser::{ChildBox, DataBox, DescriptionBox, Error},
+    BoxType, Toggles, Uuid,
 };
 
 #[test]
@@ -36,17 +35,18 @@ fn from_slice() {
     assert_eq!(
         dbox,
         DescriptionBox {
-            uuid: &[0; 16],
+            uuid: Uuid([0; 16]),
             label: Some("test.descbox",),
             requestable: true,
             id: None,
             hash: None,
             private: None,
             original: &jumbf,
+            offset: 0,
         }
     );
 
-    assert_eq!(format!("{dbox:#?}"), "DescriptionBox {\n    uuid: [00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    label: Some(\n        \"test.descbox\",\n    ),\n    requestable: true,\n    id: None,\n    hash: None,\n    private: None,\n    original: 38 bytes starting with [00, 00, 00, 26, 6a, 75, 6d, 64, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n}");
+    assert_eq!(format!("{dbox:#?}"), "DescriptionBox {\n    uuid: [00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    label: Some(\n        \"test.descbox\",\n    ),\n    requestable: true,\n    id: None,\n    hash: None,\n    private: None,\n    original: 38 bytes starting with [00, 00, 00, 26, 6a, 75, 6d, 64, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    offset: 0,\n}");
 }
 
 #[test]
@@ -68,17 +68,18 @@ fn from_box() {
     assert_eq!(
         dbox,
         DescriptionBox {
-            uuid: &[0; 16],
+            uuid: Uuid([0; 16]),
             label: Some("test.descbox",),
             requestable: true,
             id: None,
             hash: None,
             private: None,
             original: &jumbf,
+            offset: 0,
         }
     );
 
-    assert_eq!(format!("{dbox:#?}"), "DescriptionBox {\n    uuid: [00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    label: Some(\n        \"test.descbox\",\n    ),\n    requestable: true,\n    id: None,\n    hash: None,\n    private: None,\n    original: 38 bytes starting with [00, 00, 00, 26, 6a, 75, 6d, 64, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n}");
+    assert_eq!(format!("{dbox:#?}"), "DescriptionBox {\n    uuid: [00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    label: Some(\n        \"test.descbox\",\n    ),\n    requestable: true,\n    id: None,\n    hash: None,\n    private: None,\n    original: 38 bytes starting with [00, 00, 00, 26, 6a, 75, 6d, 64, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    offset: 0,\n}");
 }
 
 #[test]
@@ -100,17 +101,18 @@ fn with_id() {
     assert_eq!(
         dbox,
         DescriptionBox {
-            uuid: &[0; 16],
+            uuid: Uuid([0; 16]),
             label: None,
             requestable: false,
             id: Some(4096),
             hash: None,
             private: None,
             original: &jumbf,
+            offset: 0,
         }
     );
 
-    assert_eq!(format!("{dbox:#?}"), "DescriptionBox {\n    uuid: [00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    label: None,\n    requestable: false,\n    id: Some(\n        4096,\n    ),\n    hash: None,\n    private: None,\n    original: 29 bytes starting with [00, 00, 00, 1d, 6a, 75, 6d, 64, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n}");
+    assert_eq!(format!("{dbox:#?}"), "DescriptionBox {\n    uuid: [00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    label: None,\n    requestable: false,\n    id: Some(\n        4096,\n    ),\n    hash: None,\n    private: None,\n    original: 29 bytes starting with [00, 00, 00, 1d, 6a, 75, 6d, 64, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    offset: 0,\n}");
 }
 
 #[test]
@@ -125,7 +127,7 @@ fn error_incomplete_id() {
 
     assert_eq!(
         DescriptionBox::from_slice(&jumbf).unwrap_err(),
-        nom::Err::Error(Error::NomError(nom::error::ErrorKind::Eof))
+        Error::UnexpectedEof
     );
 }
 
@@ -150,17 +152,18 @@ fn with_hash() {
     assert_eq!(
         dbox,
         DescriptionBox {
-            uuid: &[0; 16],
+            uuid: Uuid([0; 16]),
             label: Some("test.descbox",),
             requestable: true,
             id: None,
             hash: Some(b"This is a bogus hash............" as &[u8; 32]),
             private: None,
             original: &jumbf,
+            offset: 0,
         }
     );
 
-    assert_eq!(format!("{dbox:#?}"), "DescriptionBox {\n    uuid: [00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    label: Some(\n        \"test.descbox\",\n    ),\n    requestable: true,\n    id: None,\n    hash: Some(32 bytes starting with [54, 68, 69, 73, 20, 69, 73, 20, 61, 20, 62, 6f, 67, 75, 73, 20, 68, 61, 73, 68]),\n    private: None,\n    original: 70 bytes starting with [00, 00, 00, 46, 6a, 75, 6d, 64, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n}");
+    assert_eq!(format!("{dbox:#?}"), "DescriptionBox {\n    uuid: [00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    label: Some(\n        \"test.descbox\",\n    ),\n    requestable: true,\n    id: None,\n    hash: Some(32 bytes starting with [54, 68, 69, 73, 20, 69, 73, 20, 61, 20, 62, 6f, 67, 75, 73, 20, 68, 61, 73, 68]),\n    private: None,\n    original: 70 bytes starting with [00, 00, 00, 46, 6a, 75, 6d, 64, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    offset: 0,\n}");
 }
 
 #[test]
@@ -186,7 +189,7 @@ fn with_private_box() {
     assert_eq!(
         dbox,
         DescriptionBox {
-            uuid: &[0; 16],
+            uuid: Uuid([0; 16]),
             label: Some("test.descbox",),
             requestable: true,
             id: None,
@@ -198,12 +201,107 @@ fn with_private_box() {
                     103, 97, 116, 101, 32, 67, 105, 116, 121, 44, 32, 78, 74, 34, 125,
                 ],
                 original: &jumbf[38..79],
+                offset: 38,
             }),
             original: &jumbf,
+            offset: 0,
         }
     );
 
-    assert_eq!(format!("{dbox:#?}"), "DescriptionBox {\n    uuid: [00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    label: Some(\n        \"test.descbox\",\n    ),\n    requestable: true,\n    id: None,\n    hash: None,\n    private: Some(\n        DataBox {\n            tbox: b\"json\",\n            data: 33 bytes starting with [7b, 20, 22, 6c, 6f, 63, 61, 74, 69, 6f, 6e, 22, 3a, 20, 22, 4d, 61, 72, 67, 61],\n            original: 41 bytes starting with [00, 00, 00, 29, 6a, 73, 6f, 6e, 7b, 20, 22, 6c, 6f, 63, 61, 74, 69, 6f, 6e, 22],\n        },\n    ),\n    original: 79 bytes starting with [00, 00, 00, 4f, 6a, 75, 6d, 64, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n}");
+    assert_eq!(format!("{dbox:#?}"), "DescriptionBox {\n    uuid: [00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    label: Some(\n        \"test.descbox\",\n    ),\n    requestable: true,\n    id: None,\n    hash: None,\n    private: Some(\n        DataBox {\n            tbox: b\"json\",\n            data: 33 bytes starting with [7b, 20, 22, 6c, 6f, 63, 61, 74, 69, 6f, 6e, 22, 3a, 20, 22, 4d, 61, 72, 67, 61],\n            original: 41 bytes starting with [00, 00, 00, 29, 6a, 73, 6f, 6e, 7b, 20, 22, 6c, 6f, 63, 61, 74, 69, 6f, 6e, 22],\n            offset: 38,\n        },\n    ),\n    original: 79 bytes starting with [00, 00, 00, 4f, 6a, 75, 6d, 64, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    offset: 0,\n}");
+}
+
+#[test]
+fn private_super_box() {
+    let jumbf = hex!(
+        "00000053" // box size
+        "6a756d64" // box type = 'jumd'
+        "00000000000000000000000000000000" // UUID
+        "13" // toggles
+        "746573742e64657363626f7800" // label
+            "0000002d" // box size
+            "6a756d62" // box type = 'jumb'
+                "0000001b" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "03" // toggles
+                "7000" // label ("p")
+                // ---
+                "0000000a" // box size
+                "61626364" // box type = 'abcd'
+                "6869" // payload ("hi")
+    );
+
+    let (rem, dbox) = DescriptionBox::from_slice(&jumbf).unwrap();
+    assert!(rem.is_empty());
+
+    let private = dbox.private_super_box().unwrap().unwrap();
+    assert_eq!(private.offset, 38);
+    assert_eq!(private.desc.label, Some("p"));
+    assert_eq!(private.desc.offset, 46);
+    assert_eq!(private.child_boxes.len(), 1);
+
+    let ChildBox::DataBox(child) = &private.child_boxes[0] else {
+        panic!("expected a data box");
+    };
+
+    assert_eq!(child.tbox, BoxType(*b"abcd"));
+    assert_eq!(child.data, b"hi");
+    assert_eq!(child.offset, 73);
+}
+
+#[test]
+fn private_super_box_not_super_box() {
+    let jumbf = hex!(
+        "00000030" // box size
+        "6a756d64" // box type = 'jumd'
+        "00000000000000000000000000000000" // UUID
+        "13" // toggles
+        "746573742e64657363626f7800" // label
+            "0000000a" // box size
+            "6a736f6e" // box type = 'json'
+            "7b7d" // payload ("{}")
+    );
+
+    let (_, dbox) = DescriptionBox::from_slice(&jumbf).unwrap();
+    assert!(dbox.private.is_some());
+    assert!(dbox.private_super_box().is_none());
+}
+
+#[test]
+fn private_super_box_none() {
+    let jumbf = hex!(
+        "00000026" // box size
+        "6a756d64" // box type = 'jumd'
+        "00000000000000000000000000000000" // UUID
+        "03" // toggles
+        "746573742e64657363626f7800" // label
+    );
+
+    let (_, dbox) = DescriptionBox::from_slice(&jumbf).unwrap();
+    assert!(dbox.private_super_box().is_none());
+}
+
+#[test]
+fn error_private_super_box() {
+    let jumbf = hex!(
+        "00000036" // box size
+        "6a756d64" // box type = 'jumd'
+        "00000000000000000000000000000000" // UUID
+        "13" // toggles
+        "746573742e64657363626f7800" // label
+            "00000010" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000008" // box size
+                "61626364" // box type = 'abcd' (INCORRECT)
+    );
+
+    let (_, dbox) = DescriptionBox::from_slice(&jumbf).unwrap();
+
+    assert_eq!(
+        dbox.private_super_box().unwrap().unwrap_err(),
+        Error::InvalidDescriptionBoxType(BoxType(*b"abcd")).in_super_box(Some("test.descbox"), 38)
+    );
 }
 
 #[test]
@@ -218,7 +316,7 @@ fn error_wrong_box_type() {
 
     assert_eq!(
         DescriptionBox::from_slice(&jumbf).unwrap_err(),
-        nom::Err::Error(Error::InvalidDescriptionBoxType(BoxType(*b"jumc")))
+        Error::InvalidDescriptionBoxType(BoxType(*b"jumc"))
     );
 }
 
@@ -232,10 +330,63 @@ fn error_incomplete_uuid() {
 
     assert_eq!(
         DescriptionBox::from_slice(&jumbf).unwrap_err(),
-        nom::Err::Error(Error::Incomplete(Needed::new(16)))
+        Error::Incomplete(16)
     );
 }
 
+#[test]
+fn label_borrows_from_source() {
+    let jumbf = hex!(
+        "00000026" // box size
+        "6a756d64" // box type = 'jumd'
+        "00000000000000000000000000000000" // UUID
+        "03" // toggles
+        "746573742e64657363626f7800" // label
+    );
+
+    let (_, dbox) = DescriptionBox::from_slice(&jumbf).unwrap();
+    let label = dbox.label.unwrap();
+
+    // The label is a view into the original slice, not a copy.
+    assert_eq!(label.as_ptr(), jumbf[25..].as_ptr());
+    assert_eq!(label, "test.descbox");
+}
+
+#[test]
+fn toggles() {
+    let jumbf = hex!(
+        "00000026" // box size
+        "6a756d64" // box type = 'jumd'
+        "00000000000000000000000000000000" // UUID
+        "43" // toggles
+        "746573742e64657363626f7800" // label
+    );
+
+    let (_, dbox) = DescriptionBox::from_slice(&jumbf).unwrap();
+    assert_eq!(dbox.label, Some("test.descbox"));
+    assert!(dbox.requestable);
+
+    assert_eq!(
+        dbox.toggles(),
+        Toggles::REQUESTABLE | Toggles::HAS_LABEL | Toggles(0x40)
+    );
+    assert_eq!(dbox.toggles().unknown(), Toggles(0x40));
+}
+
+#[test]
+fn toggles_with_large_box_header() {
+    let jumbf = hex!(
+        "00000001" // box size (see large size)
+        "6a756d64" // box type = 'jumd'
+        "0000000000000021" // large box size
+        "00000000000000000000000000000000" // UUID
+        "00" // toggles
+    );
+
+    let (_, dbox) = DescriptionBox::from_slice(&jumbf).unwrap();
+    assert_eq!(dbox.toggles(), Toggles::default());
+}
+
 #[test]
 fn no_label() {
     let jumbf = hex!(
@@ -251,17 +402,18 @@ fn no_label() {
     assert_eq!(
         dbox,
         DescriptionBox {
-            uuid: &[0; 16],
+            uuid: Uuid([0; 16]),
             label: None,
             requestable: false,
             id: None,
             hash: None,
             private: None,
             original: &jumbf,
+            offset: 0,
         }
     );
 
-    assert_eq!(format!("{dbox:#?}"), "DescriptionBox {\n    uuid: [00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    label: None,\n    requestable: false,\n    id: None,\n    hash: None,\n    private: None,\n    original: 25 bytes starting with [00, 00, 00, 19, 6a, 75, 6d, 64, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n}");
+    assert_eq!(format!("{dbox:#?}"), "DescriptionBox {\n    uuid: [00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    label: None,\n    requestable: false,\n    id: None,\n    hash: None,\n    private: None,\n    original: 25 bytes starting with [00, 00, 00, 19, 6a, 75, 6d, 64, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    offset: 0,\n}");
 }
 
 #[test]
@@ -278,6 +430,39 @@ fn error_incomplete_hash() {
 
     assert_eq!(
         DescriptionBox::from_slice(&jumbf).unwrap_err(),
-        nom::Err::Error(Error::Incomplete(Needed::new(32)))
+        Error::Incomplete(32)
     );
 }
+
+#[test]
+fn trailing() {
+    let jumbf = hex!(
+        "0000002b" // box size
+        "6a756d64" // box type = 'jumd'
+        "00000000000000000000000000000000" // UUID
+        "e3" // toggles
+        "746573742e64657363626f7800" // label
+        "6675747572" // trailing data ("futur")
+    );
+
+    let (rem, dbox) = DescriptionBox::from_slice(&jumbf).unwrap();
+    assert!(rem.is_empty());
+
+    assert_eq!(dbox.label, Some("test.descbox"));
+    assert_eq!(dbox.toggles().unknown(), Toggles(0xe0));
+    assert_eq!(dbox.trailing(), b"futur");
+}
+
+#[test]
+fn no_trailing() {
+    let jumbf = hex!(
+        "0000001d" // box size
+        "6a756d64" // box type = 'jumd'
+        "00000000000000000000000000000000" // UUID
+        "04" // toggles
+        "00001000" // ID
+    );
+
+    let (_, dbox) = DescriptionBox::from_slice(&jumbf).unwrap();
+    assert_eq!(dbox.trailing(), b"");
+}
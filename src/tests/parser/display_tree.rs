@@ -0,0 +1,143 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::parser::SuperBox;
+
+#[test]
+fn truncated_preview() {
+    let jumbf = hex!(
+        "00000047" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000026" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "03" // toggles
+            "746573742e64617461626f7800" // label
+            // ----
+            "00000019" // box size
+            "61626364" // box type = 'abcd'
+            "000102030405060708090a0b0c0d0e0f10" // payload
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+
+    assert_eq!(
+        sbox.display_tree().to_string(),
+        concat!(
+            "jumb \"test.databox\" (71 bytes, uuid 00000000-0000-0000-0000-000000000000)\n",
+            "  abcd (25 bytes): 00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f ...\n",
+        )
+    );
+}
+
+#[test]
+fn empty_payload_and_unprintable_box_type() {
+    let jumbf = hex!(
+        "00000029" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "00000008" // box size
+            "00010203" // box type (unprintable)
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+
+    assert_eq!(
+        sbox.display_tree().to_string(),
+        concat!(
+            "jumb (41 bytes, uuid 00000000-0000-0000-0000-000000000000)\n",
+            "  [0x00, 0x01, 0x02, 0x03] (8 bytes)\n",
+        )
+    );
+}
+
+#[test]
+fn c2pa_manifest() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    assert_eq!(
+        sbox.display_tree().to_string(),
+        concat!(
+            "jumb \"c2pa\" (46948 bytes, uuid 63327061-0011-0010-8000-00aa00389b71)\n",
+            "  jumb \"contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9\" (46910 bytes, uuid 63326d61-0011-0010-8000-00aa00389b71)\n",
+            "    jumb \"c2pa.assertions\" (32353 bytes, uuid 63326173-0011-0010-8000-00aa00389b71)\n",
+            "      jumb \"c2pa.thumbnail.claim.jpeg\" (31798 bytes, uuid 40cb0c32-bb8a-489d-a70b-2ad6f47f4369)\n",
+            "        bfdb (20 bytes): 00 69 6d 61 67 65 2f 6a 70 65 67 00\n",
+            "        bidb (31719 bytes): ff d8 ff e0 00 10 4a 46 49 46 00 01 02 00 00 01 ...\n",
+            "      jumb \"stds.schema-org.CreativeWork\" (203 bytes, uuid 6a736f6e-0011-0010-8000-00aa00389b71)\n",
+            "        json (117 bytes): 7b 22 40 63 6f 6e 74 65 78 74 22 3a 22 68 74 74 ...\n",
+            "      jumb \"c2pa.actions\" (132 bytes, uuid 63626f72-0011-0010-8000-00aa00389b71)\n",
+            "        cbor (86 bytes): a1 67 61 63 74 69 6f 6e 73 82 a1 66 61 63 74 69 ...\n",
+            "      jumb \"c2pa.hash.data\" (171 bytes, uuid 63626f72-0011-0010-8000-00aa00389b71)\n",
+            "        cbor (123 bytes): a5 6a 65 78 63 6c 75 73 69 6f 6e 73 81 a2 65 73 ...\n",
+            "    jumb \"c2pa.claim\" (684 bytes, uuid 6332636c-0011-0010-8000-00aa00389b71)\n",
+            "      cbor (640 bytes): a8 68 64 63 3a 74 69 74 6c 65 65 43 2e 6a 70 67 ...\n",
+            "    jumb \"c2pa.signature\" (13782 bytes, uuid 63326373-0011-0010-8000-00aa00389b71)\n",
+            "      cbor (13734 bytes): d2 84 43 a1 01 26 a3 67 78 35 63 68 61 69 6e 83 ...\n",
+        )
+    );
+}
+
+#[test]
+fn pathological_depth() {
+    use core::fmt::Write;
+
+    use crate::tests::parser::super_box_depth_limit::pathologically_deep;
+
+    // Deep enough that the innermost indent exceeds `u16::MAX` spaces. The
+    // output is over a gigabyte, so only the line count and the last line
+    // are retained.
+    const DEPTH: usize = 33_000;
+
+    #[derive(Default)]
+    struct LastLine {
+        lines: usize,
+        last: String,
+    }
+
+    impl Write for LastLine {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            if self.last.ends_with('\n') {
+                self.lines += 1;
+                self.last.clear();
+            }
+            self.last.push_str(s);
+            Ok(())
+        }
+    }
+
+    let jumbf = pathologically_deep(DEPTH);
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+
+    let mut output = LastLine::default();
+    write!(output, "{}", sbox.display_tree()).unwrap();
+
+    assert_eq!(output.lines, DEPTH - 1);
+    assert_eq!(
+        output.last,
+        format!(
+            "{}jumb (33 bytes, uuid 00000000-0000-0000-0000-000000000000)\n",
+            " ".repeat((DEPTH - 1) * 2)
+        )
+    );
+
+    drop(sbox);
+}
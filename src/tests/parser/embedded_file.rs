@@ -0,0 +1,253 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::borrow::Cow;
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{
+    parser::{
+        DataBox, EmbeddedFile, EmbeddedFileDescriptionBox, Error, ReferenceResolver, SuperBox,
+    },
+    BoxType, Uuid,
+};
+
+#[test]
+fn description_box() {
+    let jumbf = hex!(
+        "0000001f" // box size
+        "62666462" // box type = 'bfdb'
+        "03" // toggles
+        "746578742f706c61696e00" // media type ("text/plain")
+        "726561646d652e74787400" // file name ("readme.txt")
+    );
+
+    let (rem, desc) = EmbeddedFileDescriptionBox::from_slice(&jumbf).unwrap();
+    assert!(rem.is_empty());
+
+    assert_eq!(
+        desc,
+        EmbeddedFileDescriptionBox {
+            media_type: "text/plain",
+            file_name: Some("readme.txt"),
+            external: true,
+            original: &jumbf,
+        }
+    );
+
+    assert_eq!(format!("{desc:#?}"), "EmbeddedFileDescriptionBox {\n    media_type: \"text/plain\",\n    file_name: Some(\n        \"readme.txt\",\n    ),\n    external: true,\n    original: 31 bytes starting with [00, 00, 00, 1f, 62, 66, 64, 62, 03, 74, 65, 78, 74, 2f, 70, 6c, 61, 69, 6e, 00],\n}");
+}
+
+#[test]
+fn c2pa_thumbnail() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+    let thumbnail = sbox
+        .find_by_label("contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9/c2pa.assertions/c2pa.thumbnail.claim.jpeg")
+        .unwrap();
+
+    let file = EmbeddedFile::from_super_box(thumbnail).unwrap();
+
+    assert_eq!(
+        file,
+        EmbeddedFile {
+            desc: EmbeddedFileDescriptionBox {
+                media_type: "image/jpeg",
+                file_name: None,
+                external: false,
+                original: &jumbf[237..257],
+            },
+            data: DataBox {
+                tbox: BoxType(*b"bidb"),
+                data: &jumbf[265..31976],
+                original: &jumbf[257..31976],
+                offset: 257,
+            },
+        }
+    );
+}
+
+#[test]
+fn error_wrong_content_type() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    assert_eq!(
+        EmbeddedFile::from_super_box(&sbox).unwrap_err(),
+        Error::InvalidContentType {
+            expected: Uuid::EMBEDDED_FILE,
+            actual: Uuid(hex!("63 32 70 61 00 11 00 10 80 00 00 aa 00 38 9b 71")),
+        }
+    );
+}
+
+#[test]
+fn error_wrong_description_box_type() {
+    let jumbf = hex!(
+        "00000030" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "40cb0c32bb8a489da70b2ad6f47f4369" // UUID (embedded file)
+            "00" // toggles
+            // ----
+            "0000000f" // box size
+            "62696462" // box type = 'bidb'
+            "ffd8ffe0001a4a" // payload
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+
+    assert_eq!(
+        EmbeddedFile::from_super_box(&sbox).unwrap_err(),
+        Error::InvalidEmbeddedFileDescriptionBoxType(BoxType(*b"bidb"))
+    );
+}
+
+#[test]
+fn error_wrong_binary_data_box_type() {
+    let jumbf = hex!(
+        "00000044" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "40cb0c32bb8a489da70b2ad6f47f4369" // UUID (embedded file)
+            "00" // toggles
+            // ----
+            "00000014" // box size
+            "62666462" // box type = 'bfdb'
+            "00" // toggles
+            "696d6167652f6a70656700" // media type ("image/jpeg")
+            // ----
+            "0000000f" // box size
+            "61626364" // box type = 'abcd'
+            "ffd8ffe0001a4a" // payload
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+
+    assert_eq!(
+        EmbeddedFile::from_super_box(&sbox).unwrap_err(),
+        Error::InvalidBinaryDataBoxType(BoxType(*b"abcd"))
+    );
+}
+
+#[test]
+fn error_missing_binary_data_box() {
+    let jumbf = hex!(
+        "00000035" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "40cb0c32bb8a489da70b2ad6f47f4369" // UUID (embedded file)
+            "00" // toggles
+            // ----
+            "00000014" // box size
+            "62666462" // box type = 'bfdb'
+            "00" // toggles
+            "696d6167652f6a70656700" // media type ("image/jpeg")
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+
+    assert_eq!(
+        EmbeddedFile::from_super_box(&sbox).unwrap_err(),
+        Error::MissingChildBox(BoxType(*b"bidb"))
+    );
+}
+
+const EXTERNAL_FILE: [u8; 87] = hex!(
+    "00000057" // box size
+    "6a756d62" // box type = 'jumb'
+        "00000019" // box size
+        "6a756d64" // box type = 'jumd'
+        "40cb0c32bb8a489da70b2ad6f47f4369" // UUID (embedded file)
+        "00" // toggles
+        // ----
+        "00000014" // box size
+        "62666462" // box type = 'bfdb'
+        "02" // toggles (external)
+        "696d6167652f6a70656700" // media type ("image/jpeg")
+        // ----
+        "00000022" // box size
+        "62696462" // box type = 'bidb'
+        "68747470733a2f2f6578616d706c652e636f6d2f782e6a706700" // URL
+);
+
+#[test]
+fn payload_bytes_inline() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+    let thumbnail = sbox
+        .find_by_label("contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9/c2pa.assertions/c2pa.thumbnail.claim.jpeg")
+        .unwrap();
+
+    let file = EmbeddedFile::from_super_box(thumbnail).unwrap();
+    assert_eq!(file.external_url().unwrap(), None);
+
+    let resolver = |_url: &str| -> Result<Vec<u8>, Error> {
+        panic!("resolver should not be called for inline content");
+    };
+
+    let payload = file.payload_bytes(&resolver).unwrap();
+    assert!(matches!(payload, Cow::Borrowed(_)));
+    assert_eq!(*payload, jumbf[265..31976]);
+}
+
+#[test]
+fn payload_bytes_external() {
+    let (_, sbox) = SuperBox::from_slice(&EXTERNAL_FILE).unwrap();
+    let file = EmbeddedFile::from_super_box(&sbox).unwrap();
+
+    assert!(file.desc.external);
+    assert_eq!(
+        file.external_url().unwrap(),
+        Some("https://example.com/x.jpg")
+    );
+
+    let resolver = |url: &str| -> Result<Vec<u8>, Error> {
+        assert_eq!(url, "https://example.com/x.jpg");
+        Ok(vec![0xff, 0xd8])
+    };
+
+    let payload = file.payload_bytes(&resolver).unwrap();
+    assert!(matches!(payload, Cow::Owned(_)));
+    assert_eq!(*payload, [0xff, 0xd8]);
+}
+
+struct FailingResolver;
+
+impl ReferenceResolver for FailingResolver {
+    fn resolve(&self, url: &str) -> Result<Vec<u8>, Error> {
+        Err(Error::UnresolvedReference {
+            url: url.to_owned(),
+            message: "network unavailable".to_owned(),
+        })
+    }
+}
+
+#[test]
+fn error_payload_bytes_unresolved() {
+    let (_, sbox) = SuperBox::from_slice(&EXTERNAL_FILE).unwrap();
+    let file = EmbeddedFile::from_super_box(&sbox).unwrap();
+
+    let err = file.payload_bytes(&FailingResolver).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Unable to resolve external reference 'https://example.com/x.jpg': network unavailable"
+    );
+}
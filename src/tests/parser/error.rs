@@ -0,0 +1,118 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::parser::{DescriptionBox, Error};
+
+#[test]
+fn display() {
+    assert_eq!(
+        Error::Incomplete(4).to_string(),
+        "Incomplete data, missing at least 4 bytes"
+    );
+
+    assert_eq!(Error::UnexpectedEof.to_string(), "Unexpected end of input");
+
+    assert_eq!(
+        Error::Malformed("NUL-terminated string").to_string(),
+        "Malformed input: NUL-terminated string"
+    );
+}
+
+#[test]
+fn missing_label_terminator() {
+    let jumbf = hex!(
+        "0000001b" // box size
+        "6a756d64" // box type = 'jumd'
+        "00000000000000000000000000000000" // UUID
+        "03" // toggles
+        "6162" // label (no NUL terminator)
+    );
+
+    assert_eq!(
+        DescriptionBox::from_slice(&jumbf).unwrap_err(),
+        Error::Malformed("NUL-terminated string")
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn source() {
+    use std::error::Error as _;
+
+    let jumbf = hex!(
+        "0000001c" // box size
+        "6a756d64" // box type = 'jumd'
+        "00000000000000000000000000000000" // UUID
+        "03" // toggles
+        "ff6100" // label (invalid UTF-8)
+    );
+
+    let err = DescriptionBox::from_slice(&jumbf).unwrap_err();
+    assert!(matches!(err, Error::Utf8Error(_)));
+    assert!(err.source().is_some());
+
+    let err = Error::InSuperBox {
+        offset: 8,
+        path: vec![None],
+        source: Box::new(Error::UnexpectedEof),
+    };
+
+    assert_eq!(err.source().unwrap().to_string(), "Unexpected end of input");
+
+    assert!(Error::UnexpectedEof.source().is_none());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn question_mark_with_std_error() {
+    use crate::parser::{DataBox, SuperBox};
+
+    fn parse(jumbf: &[u8]) -> Result<usize, Box<dyn std::error::Error>> {
+        let (_, dbox) = DataBox::from_slice(jumbf)?;
+        let (_, sbox) = SuperBox::from_data_box(&dbox)?;
+        Ok(sbox.child_boxes.len())
+    }
+
+    assert_eq!(
+        parse(b"\0\0").unwrap_err().to_string(),
+        "Unexpected end of input"
+    );
+}
+
+#[test]
+fn in_super_boxes() {
+    let nested = Error::UnexpectedEof
+        .in_super_box(Some("c"), 40)
+        .in_super_box(None, 20)
+        .in_super_box(Some("a"), 0);
+
+    assert_eq!(
+        Error::UnexpectedEof.in_super_boxes([Some("a"), None, Some("c")], 40),
+        nested
+    );
+
+    assert_eq!(
+        Error::UnexpectedEof
+            .in_super_box(Some("c"), 40)
+            .in_super_boxes([Some("a"), None], 0),
+        nested
+    );
+
+    assert_eq!(
+        nested.to_string(),
+        "Unexpected end of input at offset 40 inside a/(unlabeled)/c"
+    );
+}
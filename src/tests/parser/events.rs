@@ -0,0 +1,282 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::ops::ControlFlow;
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{
+    parser::{
+        events::{parse_events, EventSink},
+        ChildBox, DataBox, DescriptionBox, Error, SuperBox,
+    },
+    BoxType,
+};
+
+#[derive(Debug, Eq, PartialEq)]
+enum Event<'a> {
+    StartSuperBox(usize, usize),
+    Description(Option<&'a str>, usize),
+    DataBox(BoxType, &'a [u8], usize),
+    EndSuperBox(usize),
+}
+
+#[derive(Default)]
+struct Recorder<'a> {
+    events: Vec<Event<'a>>,
+    stop_after: Option<usize>,
+}
+
+impl<'a> Recorder<'a> {
+    fn push(&mut self, event: Event<'a>) -> ControlFlow<()> {
+        self.events.push(event);
+        if self.stop_after == Some(self.events.len()) {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+}
+
+impl<'a> EventSink<'a> for Recorder<'a> {
+    fn start_super_box(&mut self, original: &'a [u8], depth: usize) -> ControlFlow<()> {
+        self.push(Event::StartSuperBox(original.len(), depth))
+    }
+
+    fn description(&mut self, desc: DescriptionBox<'a>, depth: usize) -> ControlFlow<()> {
+        self.push(Event::Description(desc.label, depth))
+    }
+
+    fn data_box(&mut self, data_box: DataBox<'a>, depth: usize) -> ControlFlow<()> {
+        self.push(Event::DataBox(data_box.tbox, data_box.data, depth))
+    }
+
+    fn end_super_box(&mut self, depth: usize) -> ControlFlow<()> {
+        self.push(Event::EndSuperBox(depth))
+    }
+}
+
+#[test]
+fn simple_super_box() {
+    let jumbf = hex!(
+        "0000003b" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000027" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "03" // toggles
+            "746573742e7375706572626f7800" // label
+            // ---
+            "0000000c" // box size
+            "61626364" // box type = 'abcd'
+            "41424344" // payload
+        "5859" // remainder
+    );
+
+    let mut sink = Recorder::default();
+    let (rem, flow) = parse_events(&jumbf, &mut sink).unwrap();
+
+    assert_eq!(rem, b"XY");
+    assert!(flow.is_continue());
+
+    assert_eq!(
+        sink.events,
+        vec![
+            Event::StartSuperBox(59, 0),
+            Event::Description(Some("test.superbox"), 0),
+            Event::DataBox(BoxType(*b"abcd"), b"ABCD", 0),
+            Event::EndSuperBox(0),
+        ]
+    );
+}
+
+#[test]
+fn nested_super_boxes() {
+    let jumbf = hex!(
+        "00000065" // box size
+        "6a756d62" // box type = 'jumb'
+            "0000002f" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "03" // toggles
+            "746573742e7375706572626f785f64617461626f7800" // label
+            // ------
+            "0000002e" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000026" // box size
+                "6a756d64" // box type = 'jumbd'
+                "00000000000000000000000000000000" // UUID
+                "03" // toggles
+                "746573742e64617461626f7800"
+    );
+
+    let mut sink = Recorder::default();
+    let (rem, flow) = parse_events(&jumbf, &mut sink).unwrap();
+
+    assert!(rem.is_empty());
+    assert!(flow.is_continue());
+
+    assert_eq!(
+        sink.events,
+        vec![
+            Event::StartSuperBox(101, 0),
+            Event::Description(Some("test.superbox_databox"), 0),
+            Event::StartSuperBox(46, 1),
+            Event::Description(Some("test.databox"), 1),
+            Event::EndSuperBox(1),
+            Event::EndSuperBox(0),
+        ]
+    );
+}
+
+#[test]
+fn stop_early() {
+    let jumbf = hex!(
+        "00000065" // box size
+        "6a756d62" // box type = 'jumb'
+            "0000002f" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "03" // toggles
+            "746573742e7375706572626f785f64617461626f7800" // label
+            // ------
+            "0000002e" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000026" // box size
+                "6a756d64" // box type = 'jumbd'
+                "00000000000000000000000000000000" // UUID
+                "03" // toggles
+                "746573742e64617461626f7800"
+    );
+
+    let mut sink = Recorder {
+        stop_after: Some(4),
+        ..Default::default()
+    };
+
+    let (rem, flow) = parse_events(&jumbf, &mut sink).unwrap();
+
+    assert!(rem.is_empty());
+    assert!(flow.is_break());
+
+    assert_eq!(
+        sink.events,
+        vec![
+            Event::StartSuperBox(101, 0),
+            Event::Description(Some("test.superbox_databox"), 0),
+            Event::StartSuperBox(46, 1),
+            Event::Description(Some("test.databox"), 1),
+        ]
+    );
+}
+
+#[test]
+fn default_sink_methods() {
+    struct CountDataBoxes(usize);
+
+    impl<'a> EventSink<'a> for CountDataBoxes {
+        fn data_box(&mut self, _data_box: DataBox<'a>, _depth: usize) -> ControlFlow<()> {
+            self.0 += 1;
+            ControlFlow::Continue(())
+        }
+    }
+
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+
+    let mut sink = CountDataBoxes(0);
+    let (rem, flow) = parse_events(jumbf, &mut sink).unwrap();
+
+    assert!(rem.is_empty());
+    assert!(flow.is_continue());
+
+    fn count_data_boxes(sbox: &SuperBox) -> usize {
+        sbox.child_boxes
+            .iter()
+            .map(|child| match child {
+                ChildBox::SuperBox(sbox) => count_data_boxes(sbox),
+                ChildBox::DataBox(_) => 1,
+            })
+            .sum()
+    }
+
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+    assert_eq!(sink.0, count_data_boxes(&sbox));
+}
+
+#[test]
+fn error_wrong_box_type() {
+    let jumbf = hex!(
+        "0000000c" // box size
+        "61626364" // box type = 'abcd'
+        "41424344" // payload
+    );
+
+    let mut sink = Recorder::default();
+
+    assert_eq!(
+        parse_events(&jumbf, &mut sink).unwrap_err(),
+        Error::InvalidSuperBoxType(BoxType(*b"abcd"))
+    );
+
+    assert!(sink.events.is_empty());
+}
+
+#[test]
+fn error_truncated_child() {
+    let jumbf = hex!(
+        "0000002d" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ---
+            "00000010" // box size (too large)
+            "61626364" // box type = 'abcd'
+            "41424344" // payload
+    );
+
+    let mut sink = Recorder::default();
+    assert!(parse_events(&jumbf, &mut sink).is_err());
+
+    assert_eq!(
+        sink.events,
+        vec![Event::StartSuperBox(45, 0), Event::Description(None, 0),]
+    );
+}
+
+#[test]
+fn pathological_depth() {
+    use crate::tests::parser::super_box_depth_limit::pathologically_deep;
+
+    const DEPTH: usize = 100_000;
+
+    let jumbf = pathologically_deep(DEPTH);
+    let mut sink = Recorder::default();
+
+    let (rem, flow) = parse_events(&jumbf, &mut sink).unwrap();
+    assert!(rem.is_empty());
+    assert!(flow.is_continue());
+
+    assert_eq!(sink.events.len(), 3 * DEPTH);
+    assert_eq!(
+        sink.events[2 * DEPTH - 2..2 * DEPTH + 1],
+        [
+            Event::StartSuperBox(33, DEPTH - 1),
+            Event::Description(None, DEPTH - 1),
+            Event::EndSuperBox(DEPTH - 1),
+        ]
+    );
+    assert_eq!(sink.events.last(), Some(&Event::EndSuperBox(0)));
+}
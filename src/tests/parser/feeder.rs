@@ -0,0 +1,310 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{
+    parser::{Error, FeedEvent, JumbfFeeder},
+    BoxType, Uuid,
+};
+
+fn feed_in_chunks(jumbf: &[u8], chunk_size: usize) -> Result<Vec<FeedEvent>, Error> {
+    let mut feeder = JumbfFeeder::new();
+    let mut events: Vec<FeedEvent> = vec![];
+
+    for chunk in jumbf.chunks(chunk_size) {
+        events.append(&mut feeder.feed(chunk)?);
+    }
+
+    assert_eq!(feeder.position(), jumbf.len() as u64);
+
+    events.append(&mut feeder.finish()?);
+    Ok(events)
+}
+
+#[test]
+fn simple_super_box() {
+    let jumbf = hex!(
+        "0000003b" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000027" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "03" // toggles
+            "746573742e7375706572626f7800" // label
+            // ---
+            "0000000c" // box size
+            "61626364" // box type = 'abcd'
+            "41424344" // payload
+    );
+
+    let expected = vec![
+        FeedEvent::BoxStart {
+            tbox: BoxType(*b"jumb"),
+            offset: 0,
+            len: Some(59),
+            depth: 0,
+        },
+        FeedEvent::BoxStart {
+            tbox: BoxType(*b"jumd"),
+            offset: 8,
+            len: Some(39),
+            depth: 1,
+        },
+        FeedEvent::Description {
+            uuid: Uuid([0; 16]),
+            label: Some("test.superbox".to_owned()),
+            requestable: true,
+            id: None,
+            hash: None,
+        },
+        FeedEvent::BoxEnd {
+            tbox: BoxType(*b"jumd"),
+            offset: 47,
+        },
+        FeedEvent::BoxStart {
+            tbox: BoxType(*b"abcd"),
+            offset: 47,
+            len: Some(12),
+            depth: 1,
+        },
+        FeedEvent::BoxEnd {
+            tbox: BoxType(*b"abcd"),
+            offset: 59,
+        },
+        FeedEvent::BoxEnd {
+            tbox: BoxType(*b"jumb"),
+            offset: 59,
+        },
+    ];
+
+    for chunk_size in [1, 3, 8, 100] {
+        assert_eq!(feed_in_chunks(&jumbf, chunk_size).unwrap(), expected);
+    }
+}
+
+#[test]
+fn events_arrive_early() {
+    let jumbf = hex!(
+        "0000003b" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000027" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "03" // toggles
+            "746573742e7375706572626f7800" // label
+    );
+
+    let mut feeder = JumbfFeeder::new();
+
+    assert_eq!(feeder.feed(&jumbf[0..7]).unwrap(), vec![]);
+    assert_eq!(
+        feeder.feed(&jumbf[7..8]).unwrap(),
+        vec![FeedEvent::BoxStart {
+            tbox: BoxType(*b"jumb"),
+            offset: 0,
+            len: Some(59),
+            depth: 0,
+        }]
+    );
+
+    let events = feeder.feed(&jumbf[8..]).unwrap();
+    assert_eq!(events.len(), 3);
+    assert!(matches!(events[1], FeedEvent::Description { .. }));
+}
+
+#[test]
+fn extends_to_end() {
+    let jumbf = hex!(
+        "00000000" // box size (extends to end)
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ---
+            "00000000" // box size (extends to end)
+            "61626364" // box type = 'abcd'
+            "41424344" // payload
+    );
+
+    let events = feed_in_chunks(&jumbf, 5).unwrap();
+    assert_eq!(
+        events[events.len() - 2..],
+        [
+            FeedEvent::BoxEnd {
+                tbox: BoxType(*b"abcd"),
+                offset: 45,
+            },
+            FeedEvent::BoxEnd {
+                tbox: BoxType(*b"jumb"),
+                offset: 45,
+            },
+        ]
+    );
+}
+
+#[test]
+fn xlbox_size() {
+    let jumbf = hex!(
+        "00000001" // box size (see XLBox)
+        "61626364" // box type = 'abcd'
+        "0000000000000014" // XLBox size
+        "41424344" // payload
+    );
+
+    assert_eq!(
+        feed_in_chunks(&jumbf, 3).unwrap(),
+        vec![
+            FeedEvent::BoxStart {
+                tbox: BoxType(*b"abcd"),
+                offset: 0,
+                len: Some(20),
+                depth: 0,
+            },
+            FeedEvent::BoxEnd {
+                tbox: BoxType(*b"abcd"),
+                offset: 20,
+            },
+        ]
+    );
+}
+
+#[test]
+fn c2pa_manifest() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+
+    let all_at_once = feed_in_chunks(jumbf, jumbf.len()).unwrap();
+    assert_eq!(feed_in_chunks(jumbf, 1000).unwrap(), all_at_once);
+
+    let labels: Vec<&str> = all_at_once
+        .iter()
+        .filter_map(|e| match e {
+            FeedEvent::Description { label, .. } => label.as_deref(),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(labels[0], "c2pa");
+    assert!(labels.contains(&"c2pa.claim"));
+    assert!(labels.contains(&"c2pa.thumbnail.claim.jpeg"));
+
+    let starts = all_at_once
+        .iter()
+        .filter(|e| matches!(e, FeedEvent::BoxStart { .. }))
+        .count();
+    let ends = all_at_once
+        .iter()
+        .filter(|e| matches!(e, FeedEvent::BoxEnd { .. }))
+        .count();
+    assert_eq!(starts, ends);
+}
+
+#[test]
+fn error_invalid_box_length() {
+    let jumbf = hex!(
+        "00000002" // box size (invalid)
+        "6a756d62" // box type = 'jumb'
+    );
+
+    assert_eq!(
+        feed_in_chunks(&jumbf, 3).unwrap_err(),
+        Error::InvalidBoxLength(2)
+    );
+}
+
+#[test]
+fn error_no_description_box() {
+    let jumbf = hex!(
+        "00000014" // box size
+        "6a756d62" // box type = 'jumb'
+            "0000000c" // box size
+            "61626364" // box type = 'abcd'
+            "41424344" // payload
+    );
+
+    assert_eq!(
+        feed_in_chunks(&jumbf, 3).unwrap_err(),
+        Error::InvalidDescriptionBoxType(BoxType(*b"abcd"))
+    );
+}
+
+#[test]
+fn error_child_larger_than_parent() {
+    let jumbf = hex!(
+        "00000014" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+    );
+
+    assert_eq!(
+        feed_in_chunks(&jumbf, 3).unwrap_err(),
+        Error::Malformed("child box within its parent box")
+    );
+}
+
+#[test]
+fn error_xlbox_size_overflow() {
+    let jumbf = hex!(
+        "00000008" // box size
+        "61626364" // box type = 'abcd'
+        // ---
+        "00000001" // box size (see XLBox)
+        "61626364" // box type = 'abcd'
+        "ffffffffffffffff" // XLBox size
+    );
+
+    assert_eq!(
+        feed_in_chunks(&jumbf, 3).unwrap_err(),
+        Error::Malformed("box length within 64-bit range")
+    );
+}
+
+#[test]
+fn error_description_box_too_long() {
+    let mut jumbf = hex!(
+        "00000000" // box size (extends to end)
+        "6a756d62" // box type = 'jumb'
+            "00000000" // box size (extends to end)
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+    )
+    .to_vec();
+    jumbf.resize(JumbfFeeder::MAX_DESCRIPTION_BOX_LEN + 16, 0);
+
+    assert_eq!(
+        feed_in_chunks(&jumbf, 4096).unwrap_err(),
+        Error::Malformed("description box no longer than 64 KiB")
+    );
+}
+
+#[test]
+fn error_incomplete() {
+    let jumbf = hex!(
+        "00000014" // box size
+        "61626364" // box type = 'abcd'
+        "4142" // payload (incomplete)
+    );
+
+    assert_eq!(
+        feed_in_chunks(&jumbf, 3).unwrap_err(),
+        Error::Incomplete(10)
+    );
+
+    let mut feeder = JumbfFeeder::new();
+    feeder.feed(&jumbf[0..6]).unwrap();
+    assert_eq!(feeder.finish().unwrap_err(), Error::Incomplete(2));
+}
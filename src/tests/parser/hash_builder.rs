@@ -0,0 +1,95 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use pretty_assertions_sorted::assert_eq;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use crate::parser::{Error, HashBuilder, SuperBox};
+
+const ASSERTIONS: &str =
+    "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9/c2pa.assertions";
+
+#[test]
+fn whole_superbox() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let hasher = HashBuilder::new();
+
+    assert_eq!(hasher.ranges(&sbox).unwrap(), vec![0..jumbf.len()]);
+    assert_eq!(hasher.hash::<Sha256>(&sbox).unwrap(), Sha256::digest(jumbf));
+}
+
+#[test]
+fn include_and_exclude() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let assertions = sbox.find_by_label(ASSERTIONS).unwrap();
+    let assertions = assertions.offset..assertions.offset + assertions.original.len();
+
+    let creative_work = format!("{ASSERTIONS}/stds.schema-org.CreativeWork");
+    let excluded = sbox.find_by_label(&creative_work).unwrap();
+    let excluded = excluded.offset..excluded.offset + excluded.original.len();
+
+    let hasher = HashBuilder::new()
+        .include(ASSERTIONS)
+        .exclude(&creative_work)
+        .exclude("c2pa.missing");
+
+    let expected_ranges = vec![
+        assertions.start..excluded.start,
+        excluded.end..assertions.end,
+    ];
+    assert_eq!(hasher.ranges(&sbox).unwrap(), expected_ranges);
+
+    let mut expected = Sha384::new();
+    for range in &expected_ranges {
+        expected.update(&jumbf[range.clone()]);
+    }
+    assert_eq!(hasher.hash::<Sha384>(&sbox).unwrap(), expected.finalize());
+}
+
+#[test]
+fn overlapping_includes() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let assertions = sbox.find_by_label(ASSERTIONS).unwrap();
+    let assertions = assertions.offset..assertions.offset + assertions.original.len();
+
+    let thumbnail = format!("{ASSERTIONS}/c2pa.thumbnail.claim.jpeg");
+    let hasher = HashBuilder::new().include(&thumbnail).include(ASSERTIONS);
+
+    assert_eq!(hasher.ranges(&sbox).unwrap(), vec![assertions.clone()]);
+    assert_eq!(
+        hasher.hash::<Sha512>(&sbox).unwrap(),
+        Sha512::digest(&jumbf[assertions])
+    );
+}
+
+#[test]
+fn error_unresolved_include() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let hasher = HashBuilder::new().include("c2pa.missing");
+
+    assert_eq!(
+        hasher.hash::<Sha256>(&sbox).unwrap_err(),
+        Error::UnresolvedReference {
+            url: "c2pa.missing".to_owned(),
+            message: "no unique superbox with this label path".to_owned(),
+        }
+    );
+}
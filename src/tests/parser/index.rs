@@ -0,0 +1,93 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use pretty_assertions_sorted::assert_eq;
+
+use crate::parser::{BoxRef, ChildBox, SuperBox, SuperBoxIndex};
+
+const MANIFEST: &str = "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9";
+
+#[test]
+fn c2pa() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+    let index = SuperBoxIndex::new(&sbox);
+
+    let assertions = sbox
+        .find_by_label(&format!("{MANIFEST}/c2pa.assertions"))
+        .unwrap();
+
+    let hash_data = sbox
+        .find_by_label(&format!("{MANIFEST}/c2pa.assertions/c2pa.hash.data"))
+        .unwrap();
+
+    let ChildBox::DataBox(cbor) = &hash_data.child_boxes[0] else {
+        unreachable!();
+    };
+
+    assert_eq!(index.get(0), Some(BoxRef::SuperBox(&sbox)));
+    assert_eq!(index.get(cbor.offset), Some(BoxRef::DataBox(cbor)));
+
+    assert_eq!(index.parent_of(0), None);
+    assert_eq!(index.parent_of(cbor.offset).unwrap(), hash_data);
+    assert_eq!(index.parent_of(hash_data.offset).unwrap(), assertions);
+
+    let path: Vec<Option<&str>> = index
+        .path_to(cbor.offset)
+        .unwrap()
+        .iter()
+        .map(|b| b.as_super_box().and_then(|sbox| sbox.desc.label))
+        .collect();
+
+    assert_eq!(
+        path,
+        [
+            Some("c2pa"),
+            Some(MANIFEST),
+            Some("c2pa.assertions"),
+            Some("c2pa.hash.data"),
+            None
+        ]
+    );
+
+    assert_eq!(index.path_to(0).unwrap(), [BoxRef::SuperBox(&sbox)]);
+}
+
+#[test]
+fn iter_in_offset_order() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+    let index = SuperBoxIndex::new(&sbox);
+
+    let offsets: Vec<usize> = index.iter().map(|b| b.offset()).collect();
+    assert_eq!(offsets.len(), index.len());
+    assert!(offsets.windows(2).all(|w| w[0] < w[1]));
+
+    for b in index.iter() {
+        assert_eq!(index.get(b.offset()), Some(b));
+        assert!(b.range().end <= jumbf.len());
+    }
+}
+
+#[test]
+fn unknown_offset() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+    let index = SuperBoxIndex::new(&sbox);
+
+    // Offset 8 is the root superbox's description box, which is not indexed.
+    assert_eq!(index.get(8), None);
+    assert_eq!(index.parent_of(8), None);
+    assert_eq!(index.path_to(8), None);
+    assert_eq!(index.get(usize::MAX), None);
+}
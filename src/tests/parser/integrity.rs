@@ -0,0 +1,112 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::parser::{HashCheck, IntegrityReport, SizeMismatch, SuperBox};
+
+const EMPTY_HASH: [u8; 32] =
+    hex!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+
+#[test]
+fn c2pa() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let report = sbox.integrity_report();
+
+    assert_eq!(report, IntegrityReport::default());
+    assert!(report.is_valid());
+    assert_eq!(report.to_string(), "");
+}
+
+#[test]
+fn hash_mismatch() {
+    let jumbf = hex!(
+        "00000064" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "00000043" // box size
+            "6a756d62" // box type = 'jumb'
+                "0000003b" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "0b" // toggles
+                "6100" // label = "a"
+                "0000000000000000000000000000000000000000000000000000000000000000" // hash
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    let report = sbox.integrity_report();
+
+    assert_eq!(
+        report,
+        IntegrityReport {
+            hashes: vec![HashCheck {
+                path: "(root)/a".to_owned(),
+                offset: 33,
+                expected: [0; 32],
+                actual: EMPTY_HASH,
+            }],
+            size_mismatches: vec![],
+        }
+    );
+
+    assert!(!report.is_valid());
+    assert_eq!(
+        report.to_string(),
+        "FAIL (root)/a (offset 33): hash does not match\n"
+    );
+}
+
+#[test]
+fn size_mismatch() {
+    let jumbf = hex!(
+        "00000024" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "000000" // trailing bytes (too short for a box)
+    );
+
+    // Trailing bytes are only tolerated when parsing with a report.
+    let (_, sbox, _) = SuperBox::from_source_with_report(&jumbf).unwrap();
+    let report = sbox.integrity_report();
+
+    assert_eq!(
+        report,
+        IntegrityReport {
+            hashes: vec![],
+            size_mismatches: vec![SizeMismatch {
+                path: "(root)".to_owned(),
+                offset: 0,
+                declared: 36,
+                actual: 33,
+            }],
+        }
+    );
+
+    assert!(!report.is_valid());
+    assert_eq!(
+        report.to_string(),
+        "FAIL (root) (offset 0): declared size is 36 bytes but contents span 33 bytes\n"
+    );
+}
@@ -0,0 +1,170 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::parser::{Error, LazyChildBox, LazySuperBox, SuperBox};
+
+const MANIFEST: &str = "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9";
+
+#[test]
+fn find_by_label_matches_eager_parser() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (rem, lazy) = LazySuperBox::from_slice(jumbf).unwrap();
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    assert!(rem.is_empty());
+    assert_eq!(lazy.desc, sbox.desc);
+    assert_eq!(lazy.range(), sbox.range());
+    assert_eq!(lazy.child_boxes.len(), sbox.child_boxes.len());
+
+    for label in [
+        MANIFEST.to_owned(),
+        format!("{MANIFEST}/c2pa.assertions/c2pa.hash.data"),
+        format!("{MANIFEST}/c2pa.signature"),
+        "nonexistent".to_owned(),
+    ] {
+        let lazy_child = lazy.find_by_label(&label).unwrap();
+        let eager_child = sbox.find_by_label(&label);
+
+        assert_eq!(
+            lazy_child.map(LazySuperBox::range),
+            eager_child.map(SuperBox::range),
+            "{label}"
+        );
+    }
+}
+
+#[test]
+fn to_super_box() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, lazy) = LazySuperBox::from_slice(jumbf).unwrap();
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    assert_eq!(lazy.to_super_box().unwrap(), sbox);
+
+    let lazy_child = lazy
+        .find_by_label(&format!("{MANIFEST}/c2pa.assertions"))
+        .unwrap()
+        .unwrap();
+
+    let child = sbox
+        .find_by_label(&format!("{MANIFEST}/c2pa.assertions"))
+        .unwrap();
+
+    assert_eq!(&lazy_child.to_super_box().unwrap(), child);
+}
+
+#[test]
+fn children() {
+    let jumbf = hex!(
+        "00000050" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "0000000c" // box size
+            "6a736f6e" // box type = 'json'
+            "7b7d200a" // payload
+            // ----
+            "00000023" // box size
+            "6a756d62" // box type = 'jumb'
+                "0000001b" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "03" // toggles
+                "6100" // label ("a")
+    );
+
+    let (_, sbox) = LazySuperBox::from_slice(&jumbf).unwrap();
+    assert!(sbox.child(2).is_none());
+
+    let children: Vec<LazyChildBox> = sbox.children().map(Result::unwrap).collect();
+    assert_eq!(children.len(), 2);
+
+    let LazyChildBox::DataBox(json) = children[0] else {
+        unreachable!();
+    };
+    assert_eq!(json.data, b"{} \n");
+
+    let LazyChildBox::SuperBox(a) = children[1] else {
+        unreachable!();
+    };
+    assert_eq!(a.desc.label, Some("a"));
+    assert_eq!(a.offset, 45);
+    assert!(a.child_boxes.is_empty());
+}
+
+#[test]
+fn child_added_after_parsing() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, mut sbox) = LazySuperBox::from_slice(jumbf).unwrap();
+
+    let manifest = sbox.child_boxes[0].clone();
+    sbox.child_boxes.push(manifest);
+
+    assert!(matches!(sbox.child(0), Some(Ok(LazyChildBox::SuperBox(_)))));
+    assert!(sbox.child(1).is_none());
+    assert_eq!(sbox.children().count(), 1);
+}
+
+#[test]
+fn error_in_child_is_deferred() {
+    let jumbf = hex!(
+        "00000039" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "00000018" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000010" // box size
+                "6a756d64" // box type = 'jumd'
+                "0000000000000000" // truncated description box
+    );
+
+    // The malformed child superbox isn't looked at until it is accessed.
+    let (_, sbox) = LazySuperBox::from_slice(&jumbf).unwrap();
+    assert_eq!(sbox.child_boxes.len(), 1);
+
+    let err = sbox.child(0).unwrap().unwrap_err();
+    assert_eq!(
+        err,
+        SuperBox::from_slice(&jumbf).unwrap_err(),
+        "lazy and eager parsers should report the same error"
+    );
+
+    // Repeated access returns the cached error.
+    assert_eq!(sbox.child(0).unwrap().unwrap_err(), err);
+    assert_eq!(sbox.find_by_label("a").unwrap_err(), err);
+    assert!(matches!(err, Error::InSuperBox { offset: 33, .. }));
+}
+
+#[test]
+fn error_not_super_box() {
+    let jumbf = hex!(
+        "0000000c" // box size
+        "6a736f6e" // box type = 'json'
+        "7b7d200a" // payload
+    );
+
+    assert_eq!(
+        LazySuperBox::from_slice(&jumbf).unwrap_err(),
+        Error::InvalidSuperBoxType(crate::BoxType(*b"json"))
+    );
+}
@@ -11,7 +11,38 @@
 // specific language governing permissions and limitations under
 // each license.
 
+#[cfg(feature = "std")]
+mod cached_reader;
+
 mod data_box;
 mod description_box;
+mod display_tree;
+mod embedded_file;
+mod error;
+mod events;
+mod feeder;
+
+#[cfg(feature = "hash")]
+mod hash_builder;
+
+mod index;
+
+#[cfg(feature = "hash")]
+mod integrity;
+
+mod lazy;
+mod observer;
+mod options;
+
+#[cfg(feature = "parallel")]
+mod parallel;
+
+#[cfg(feature = "std")]
+mod read;
+
+mod registry;
+mod report;
 mod super_box;
-mod super_box_depth_limit;
+pub(crate) mod super_box_depth_limit;
+mod super_box_ref;
+mod uuid_label;
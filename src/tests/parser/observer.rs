@@ -0,0 +1,158 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{
+    parser::{ParseOptions, ParseProgress, SuperBox},
+    BoxType,
+};
+
+#[test]
+fn nested() {
+    let jumbf = hex!(
+        "0000004a" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "00000021" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "00" // toggles
+            // ----
+            "00000008" // box size
+            "61626364" // box type = 'abcd'
+    );
+
+    let mut events: Vec<ParseProgress> = vec![];
+    let mut observer = |progress: &ParseProgress| events.push(progress.clone());
+
+    let (rem, sbox) =
+        SuperBox::from_source_with_observer(&jumbf, &ParseOptions::default(), &mut observer)
+            .unwrap();
+
+    assert!(rem.is_empty());
+    assert_eq!(sbox, SuperBox::from_slice(&jumbf).unwrap().1);
+
+    let summary: Vec<(BoxType, usize, usize, usize, usize, usize)> = events
+        .iter()
+        .map(|p| {
+            (
+                p.tbox,
+                p.offset,
+                p.size,
+                p.depth,
+                p.bytes_consumed,
+                p.boxes_parsed,
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        [
+            (BoxType(*b"jumb"), 0, 0x4a, 0, 8, 1),
+            (BoxType(*b"jumd"), 8, 0x19, 1, 0x21, 2),
+            (BoxType(*b"jumb"), 0x21, 0x21, 1, 0x29, 3),
+            (BoxType(*b"jumd"), 0x29, 0x19, 2, 0x42, 4),
+            (BoxType(*b"abcd"), 0x42, 8, 1, 0x4a, 5),
+        ]
+    );
+}
+
+#[test]
+fn free_boxes_skipped() {
+    let jumbf = hex!(
+        "00000029" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "00000008" // box size
+            "66726565" // box type = 'free'
+    );
+
+    let options = ParseOptions {
+        skip_free_boxes: true,
+        ..Default::default()
+    };
+
+    let mut types = vec![];
+    let mut observer = |progress: &ParseProgress| types.push(progress.tbox);
+
+    let (_, sbox) = SuperBox::from_source_with_observer(&jumbf, &options, &mut observer).unwrap();
+
+    assert!(sbox.child_boxes.is_empty());
+    assert_eq!(
+        types,
+        [BoxType(*b"jumb"), BoxType(*b"jumd"), BoxType(*b"free")]
+    );
+}
+
+#[test]
+fn c2pa_manifest_store() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+
+    let mut events: Vec<ParseProgress> = vec![];
+    let mut observer = |progress: &ParseProgress| events.push(progress.clone());
+
+    let (_, sbox) =
+        SuperBox::from_source_with_observer(jumbf, &ParseOptions::default(), &mut observer)
+            .unwrap();
+
+    assert_eq!(sbox, SuperBox::from_slice(jumbf).unwrap().1);
+
+    for (index, progress) in events.iter().enumerate() {
+        assert_eq!(progress.boxes_parsed, index + 1);
+    }
+
+    assert!(events
+        .windows(2)
+        .all(|w| w[0].bytes_consumed <= w[1].bytes_consumed));
+
+    let last = events.last().unwrap();
+    assert_eq!(last.bytes_consumed, jumbf.len());
+    assert!(events.iter().any(|p| p.depth > 1));
+}
+
+#[test]
+fn error() {
+    let jumbf = hex!(
+        "00000029" // box size
+        "6a756d63" // box type = 'jumc'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "00000008" // box size
+            "61626364" // box type = 'abcd'
+    );
+
+    let mut count = 0;
+    let mut observer = |_: &ParseProgress| count += 1;
+
+    assert!(
+        SuperBox::from_source_with_observer(&jumbf, &ParseOptions::default(), &mut observer)
+            .is_err()
+    );
+    assert_eq!(count, 0);
+}
@@ -0,0 +1,270 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{
+    parser::{Error, ParseLimit, ParseOptions, SuperBox},
+    BoxType,
+};
+
+const NESTED: [u8; 0x71] = hex!(
+    "00000071" // box size
+    "6a756d62" // box type = 'jumb'
+        "0000002f" // box size
+        "6a756d64" // box type = 'jumd'
+        "00000000000000000000000000000000" // UUID
+        "03" // toggles
+        "746573742e7375706572626f785f64617461626f7800" // label
+        // ------
+        "0000002e" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000026" // box size
+            "6a756d64" // box type = 'jumbd'
+            "00000000000000000000000000000000" // UUID
+            "03" // toggles
+            "746573742e64617461626f7800"
+        // ------
+        "0000000c" // box size
+        "61626364" // box type = 'abcd'
+        "41424344" // payload
+);
+
+fn limit_error(limit: ParseLimit, max: usize) -> Error {
+    Error::LimitExceeded { limit, max }
+}
+
+fn nested_limit_error(limit: ParseLimit, max: usize, offset: usize, label: Option<&str>) -> Error {
+    Error::InSuperBox {
+        offset,
+        path: vec![label.map(str::to_owned)],
+        source: Box::new(Error::LimitExceeded { limit, max }),
+    }
+}
+
+#[test]
+fn default_options_match_from_slice() {
+    let (rem, sbox) =
+        SuperBox::from_source_with_options(&NESTED, &ParseOptions::default()).unwrap();
+    assert!(rem.is_empty());
+
+    assert_eq!(sbox, SuperBox::from_slice(&NESTED).unwrap().1);
+}
+
+#[test]
+fn c2pa_manifest() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+
+    let (rem, sbox) = SuperBox::from_source_with_options(jumbf, &ParseOptions::default()).unwrap();
+    assert!(rem.is_empty());
+
+    assert_eq!(sbox, SuperBox::from_slice(jumbf).unwrap().1);
+}
+
+#[test]
+fn limits_at_boundary() {
+    let options = ParseOptions {
+        max_boxes: 5,
+        max_children: 2,
+        max_label_len: 21,
+        max_depth: 1,
+        max_box_size: 0x71,
+        skip_free_boxes: false,
+    };
+
+    assert!(SuperBox::from_source_with_options(&NESTED, &options).is_ok());
+}
+
+#[test]
+fn max_boxes() {
+    let options = ParseOptions {
+        max_boxes: 4,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        SuperBox::from_source_with_options(&NESTED, &options).unwrap_err(),
+        nested_limit_error(ParseLimit::Boxes, 4, 101, Some("test.superbox_databox"))
+    );
+}
+
+#[test]
+fn max_children() {
+    let options = ParseOptions {
+        max_children: 1,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        SuperBox::from_source_with_options(&NESTED, &options).unwrap_err(),
+        nested_limit_error(ParseLimit::Children, 1, 101, Some("test.superbox_databox"))
+    );
+}
+
+#[test]
+fn max_label_len() {
+    let options = ParseOptions {
+        max_label_len: 20,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        SuperBox::from_source_with_options(&NESTED, &options).unwrap_err(),
+        limit_error(ParseLimit::LabelLength, 20)
+    );
+}
+
+#[test]
+fn max_depth() {
+    let options = ParseOptions {
+        max_depth: 0,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        SuperBox::from_source_with_options(&NESTED, &options).unwrap_err(),
+        nested_limit_error(ParseLimit::Depth, 0, 55, Some("test.superbox_databox"))
+    );
+}
+
+#[test]
+fn default_max_depth() {
+    // A chain of superboxes, each with an empty description box, one level
+    // deeper than the default limit permits.
+    let levels = ParseOptions::DEFAULT_MAX_DEPTH + 2;
+    let mut jumbf = vec![];
+    for level in 0..levels {
+        let size = ((levels - level) * 33) as u32;
+        jumbf.extend_from_slice(&size.to_be_bytes());
+        jumbf.extend_from_slice(b"jumb");
+        jumbf.extend_from_slice(&hex!(
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+        ));
+    }
+
+    let err = SuperBox::from_source_with_options(&jumbf, &ParseOptions::default()).unwrap_err();
+
+    let Error::InSuperBox { offset, path, .. } = &err else {
+        panic!("expected InSuperBox, got {err:?}");
+    };
+
+    assert_eq!(*offset, (levels - 1) * 33);
+    assert_eq!(path.len(), levels - 1);
+    assert_eq!(
+        err.root_cause(),
+        &limit_error(ParseLimit::Depth, ParseOptions::DEFAULT_MAX_DEPTH)
+    );
+
+    // Without the outermost superbox, the innermost is nested exactly as
+    // deeply as the default limit permits.
+    let (rem, _) =
+        SuperBox::from_source_with_options(&jumbf[33..], &ParseOptions::default()).unwrap();
+    assert!(rem.is_empty());
+}
+
+#[test]
+fn max_box_size() {
+    let options = ParseOptions {
+        max_box_size: 0x70,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        SuperBox::from_source_with_options(&NESTED, &options).unwrap_err(),
+        limit_error(ParseLimit::BoxSize, 0x70)
+    );
+}
+
+#[test]
+fn error_wrong_box_type() {
+    let jumbf = hex!(
+        "0000000c" // box size
+        "61626364" // box type = 'abcd'
+        "41424344" // payload
+    );
+
+    assert_eq!(
+        SuperBox::from_source_with_options(&jumbf, &ParseOptions::default()).unwrap_err(),
+        Error::InvalidSuperBoxType(BoxType(*b"abcd"))
+    );
+}
+
+const WITH_FREE: [u8; 0x3d] = hex!(
+    "0000003d" // box size
+    "6a756d62" // box type = 'jumb'
+        "00000019" // box size
+        "6a756d64" // box type = 'jumd'
+        "00000000000000000000000000000000" // UUID
+        "00" // toggles
+        // ------
+        "0000000c" // box size
+        "61626364" // box type = 'abcd'
+        "41424344" // payload
+        // ------
+        "00000010" // box size
+        "66726565" // box type = 'free'
+        "0000000000000000" // padding
+);
+
+#[test]
+fn free_boxes_retained_by_default() {
+    let (_, sbox) =
+        SuperBox::from_source_with_options(&WITH_FREE, &ParseOptions::default()).unwrap();
+
+    assert_eq!(sbox.child_boxes.len(), 2);
+
+    let free = sbox.child_boxes[1].as_data_box().unwrap();
+    assert!(free.is_free());
+    assert_eq!(free.range(), 45..61);
+
+    assert!(!sbox.child_boxes[0].as_data_box().unwrap().is_free());
+}
+
+#[test]
+fn skip_free_boxes() {
+    let options = ParseOptions {
+        skip_free_boxes: true,
+        max_children: 1,
+        ..Default::default()
+    };
+
+    let (rem, sbox) = SuperBox::from_source_with_options(&WITH_FREE, &options).unwrap();
+    assert!(rem.is_empty());
+
+    assert_eq!(sbox.child_boxes.len(), 1);
+    assert_eq!(
+        sbox.child_boxes[0].as_data_box().unwrap().tbox,
+        BoxType(*b"abcd")
+    );
+
+    // The free box is still preserved in the original slice.
+    assert_eq!(sbox.original, &WITH_FREE);
+}
+
+#[test]
+fn skipped_free_boxes_count_towards_max_boxes() {
+    let options = ParseOptions {
+        skip_free_boxes: true,
+        max_boxes: 3,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        SuperBox::from_source_with_options(&WITH_FREE, &options).unwrap_err(),
+        nested_limit_error(ParseLimit::Boxes, 3, 45, None)
+    );
+}
@@ -0,0 +1,96 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::parser::SuperBox;
+
+#[test]
+fn matches_serial_parser() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+
+    assert_eq!(
+        SuperBox::from_slice_parallel(jumbf).unwrap(),
+        SuperBox::from_slice(jumbf).unwrap()
+    );
+}
+
+#[test]
+fn reports_first_error() {
+    let jumbf = hex!(
+        "00000051" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "00000018" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000010" // box size
+                "6a756d64" // box type = 'jumd'
+                "0000000000000000" // truncated description box
+            // ----
+            "00000018" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000010" // box size
+                "6a756d64" // box type = 'jumd'
+                "0000000000000000" // truncated description box
+    );
+
+    assert_eq!(
+        SuperBox::from_slice_parallel(&jumbf).unwrap_err(),
+        SuperBox::from_slice(&jumbf).unwrap_err()
+    );
+}
+
+#[test]
+fn pathological_depth() {
+    use crate::{parser::ChildBox, tests::parser::super_box_depth_limit::pathologically_deep};
+
+    const DEPTH: usize = 100_000;
+
+    let jumbf = pathologically_deep(DEPTH);
+    let (rem, sbox) = SuperBox::from_slice_parallel(&jumbf).unwrap();
+    assert!(rem.is_empty());
+
+    let mut depth = 1;
+    let mut innermost = &sbox;
+    while let Some(ChildBox::SuperBox(child)) = innermost.child_boxes.first() {
+        depth += 1;
+        innermost = child;
+    }
+
+    assert_eq!(depth, DEPTH);
+    assert_eq!(innermost.offset, (DEPTH - 1) * 33);
+
+    drop(sbox);
+}
+
+#[test]
+fn error_at_pathological_depth() {
+    use crate::tests::parser::super_box_depth_limit::pathologically_deep;
+
+    const DEPTH: usize = 10_000;
+
+    // Corrupt the type of the innermost description box.
+    let mut jumbf = pathologically_deep(DEPTH);
+    let desc_type = (DEPTH - 1) * 33 + 12;
+    jumbf[desc_type..desc_type + 4].copy_from_slice(b"jumc");
+
+    assert_eq!(
+        SuperBox::from_slice_parallel(&jumbf).unwrap_err(),
+        SuperBox::from_slice(&jumbf).unwrap_err()
+    );
+}
@@ -0,0 +1,359 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::io::{Cursor, ErrorKind};
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{
+    parser::{DataBox, DescriptionBox, Error, JumbfReader, SuperBox},
+    BoxType,
+};
+
+#[test]
+fn data_box() {
+    let jumbf = hex!(
+        "0000000a" // box size
+        "61626364" // box type = 'abcd'
+        "6869" // payload ("hi")
+        // ---
+        "0000000a" // box size
+        "65666768" // box type = 'efgh'
+        "6f6b" // payload ("ok")
+    );
+
+    let mut reader = Cursor::new(&jumbf);
+
+    let mut buf = vec![];
+    let dbox = DataBox::from_reader(&mut reader, &mut buf).unwrap();
+    assert_eq!(dbox, DataBox::from_slice(&jumbf[0..10]).unwrap().1);
+
+    let mut buf = vec![];
+    let dbox = DataBox::from_reader(&mut reader, &mut buf).unwrap();
+    assert_eq!(dbox.tbox, BoxType(*b"efgh"));
+    assert_eq!(dbox.data, b"ok");
+
+    let mut buf = vec![];
+    assert_eq!(
+        DataBox::from_reader(&mut reader, &mut buf)
+            .unwrap_err()
+            .kind(),
+        ErrorKind::UnexpectedEof
+    );
+}
+
+#[test]
+fn data_box_large_size() {
+    let jumbf = hex!(
+        "00000001" // box size (large)
+        "61626364" // box type = 'abcd'
+        "0000000000000012" // large box size
+        "6869" // payload ("hi")
+    );
+
+    let mut buf = vec![];
+    let dbox = DataBox::from_reader(&mut Cursor::new(&jumbf), &mut buf).unwrap();
+    assert_eq!(dbox.original, jumbf);
+    assert_eq!(dbox.data, b"hi");
+}
+
+#[test]
+fn data_box_to_end() {
+    let jumbf = hex!(
+        "00000000" // box size (to end of stream)
+        "61626364" // box type = 'abcd'
+        "6869" // payload ("hi")
+    );
+
+    let mut buf = vec![];
+    let dbox = DataBox::from_reader(&mut Cursor::new(&jumbf), &mut buf).unwrap();
+    assert_eq!(dbox.data, b"hi");
+}
+
+#[test]
+fn description_box() {
+    let jumbf = hex!(
+        "00000026" // box size
+        "6a756d64" // box type = 'jumd'
+        "00000000000000000000000000000000" // UUID
+        "03" // toggles
+        "746573742e64657363626f7800" // label
+    );
+
+    let mut buf = vec![];
+    let dbox = DescriptionBox::from_reader(&mut Cursor::new(&jumbf), &mut buf).unwrap();
+    assert_eq!(dbox, DescriptionBox::from_slice(&jumbf).unwrap().1);
+}
+
+#[test]
+fn super_box() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+
+    let mut buf = vec![];
+    let sbox = SuperBox::from_reader(&mut Cursor::new(jumbf), &mut buf).unwrap();
+    assert_eq!(sbox, SuperBox::from_slice(jumbf).unwrap().1);
+}
+
+#[test]
+fn error_truncated() {
+    let jumbf = hex!(
+        "0000002f" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000027" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+    );
+
+    let mut buf = vec![];
+    assert_eq!(
+        SuperBox::from_reader(&mut Cursor::new(&jumbf), &mut buf)
+            .unwrap_err()
+            .kind(),
+        ErrorKind::UnexpectedEof
+    );
+}
+
+#[test]
+fn error_parse() {
+    let jumbf = hex!(
+        "00000026" // box size
+        "6a756d63" // box type = 'jumc' (INCORRECT)
+        "00000000000000000000000000000000" // UUID
+        "03" // toggles
+        "746573742e64657363626f7800" // label
+    );
+
+    let mut buf = vec![];
+    let err = DescriptionBox::from_reader(&mut Cursor::new(&jumbf), &mut buf).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+    assert_eq!(
+        err.into_inner().unwrap().downcast::<Error>().unwrap(),
+        Box::new(Error::InvalidDescriptionBoxType(BoxType(*b"jumc")))
+    );
+}
+
+#[test]
+fn jumbf_reader() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+
+    let reader = JumbfReader::new(Cursor::new(jumbf)).unwrap();
+    assert_eq!(reader.as_bytes(), jumbf);
+    assert_eq!(
+        reader.root().unwrap(),
+        SuperBox::from_slice(jumbf).unwrap().1
+    );
+
+    let path = "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9/c2pa.signature";
+    let sig = reader.get(path).unwrap().unwrap();
+    assert_eq!(sig.desc.label, Some("c2pa.signature"));
+
+    assert_eq!(
+        reader.payload(path).unwrap(),
+        sig.data_box().map(|dbox| dbox.data)
+    );
+    drop(sig);
+
+    assert!(reader.get("no.such.label").unwrap().is_none());
+    assert!(reader.payload("no.such.label").unwrap().is_none());
+
+    assert_eq!(reader.into_inner(), jumbf);
+}
+
+#[test]
+fn jumbf_reader_no_payload() {
+    let jumbf = hex!(
+        "00000021" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+    );
+
+    let reader = JumbfReader::new(Cursor::new(&jumbf)).unwrap();
+    assert!(reader.root().unwrap().child_boxes.is_empty());
+    assert!(reader.payload("").unwrap().is_none());
+}
+
+#[test]
+fn jumbf_reader_payload_after_super_box() {
+    let jumbf = hex!(
+        "0000004c" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "00000021" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "00" // toggles
+            // ----
+            "0000000a" // box size
+            "61626364" // box type = 'abcd'
+            "6869" // payload ("hi")
+    );
+
+    // Only the first child box is considered, as with `SuperBox::data_box()`.
+    let reader = JumbfReader::new(Cursor::new(&jumbf)).unwrap();
+    assert_eq!(reader.root().unwrap().child_boxes.len(), 2);
+    assert!(reader.payload("").unwrap().is_none());
+}
+
+#[test]
+fn error_jumbf_reader_not_super_box() {
+    let jumbf = hex!(
+        "0000000a" // box size
+        "61626364" // box type = 'abcd'
+        "6869" // payload ("hi")
+    );
+
+    let err = JumbfReader::new(Cursor::new(&jumbf)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn jumbf_reader_from_buffer() {
+    use std::{rc::Rc, sync::Arc};
+
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let path = "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9/c2pa.signature";
+    let sig = SuperBox::from_slice(jumbf)
+        .unwrap()
+        .1
+        .find_by_label(path)
+        .unwrap()
+        .clone();
+
+    let reader = JumbfReader::from_buffer(jumbf.to_vec()).unwrap();
+    assert_eq!(reader.as_bytes(), jumbf);
+    assert_eq!(reader.get(path).unwrap(), Some(sig.clone()));
+    assert_eq!(reader.into_inner(), jumbf);
+
+    let arc: Arc<[u8]> = Arc::from(&jumbf[..]);
+    let reader = JumbfReader::from_buffer(arc.clone()).unwrap();
+    assert_eq!(reader.get(path).unwrap(), Some(sig.clone()));
+    assert_eq!(Arc::strong_count(&arc), 2);
+
+    let shared = reader.clone();
+    assert_eq!(Arc::strong_count(&arc), 3);
+    std::thread::spawn(move || assert!(shared.get(path).unwrap().is_some()))
+        .join()
+        .unwrap();
+
+    let reader = JumbfReader::from_buffer(&jumbf[..]).unwrap();
+    assert_eq!(reader.get(path).unwrap(), Some(sig.clone()));
+    assert_eq!(reader.into_inner(), jumbf);
+
+    let rc: Rc<[u8]> = Rc::from(&jumbf[..]);
+    let reader = JumbfReader::from_buffer(rc.clone()).unwrap();
+    assert_eq!(
+        reader.payload(path).unwrap(),
+        sig.data_box().map(|dbox| dbox.data)
+    );
+    assert!(Rc::ptr_eq(&reader.into_inner(), &rc));
+}
+
+#[test]
+fn jumbf_reader_from_buffer_trailing_data() {
+    let mut jumbf = hex!(
+        "00000021" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+    )
+    .to_vec();
+    jumbf.extend_from_slice(b"trailing");
+
+    let cursor = Cursor::new(jumbf.clone());
+    let reader = JumbfReader::from_buffer(cursor.into_inner()).unwrap();
+
+    assert_eq!(reader.as_bytes(), &jumbf[..0x21]);
+    assert!(reader.root().unwrap().child_boxes.is_empty());
+    assert_eq!(reader.into_inner(), jumbf);
+}
+
+#[test]
+fn error_jumbf_reader_from_buffer_not_super_box() {
+    let jumbf = hex!(
+        "0000000a" // box size
+        "61626364" // box type = 'abcd'
+        "6869" // payload ("hi")
+    );
+
+    assert_eq!(
+        JumbfReader::from_buffer(jumbf).unwrap_err(),
+        Error::InvalidSuperBoxType(BoxType(*b"abcd"))
+    );
+}
+
+#[test]
+fn error_jumbf_reader_buffer_changed() {
+    use std::cell::Cell;
+
+    // A buffer whose contents are no longer a valid superbox after the
+    // first time they are read.
+    struct Fickle {
+        jumbf: Vec<u8>,
+        reads: Cell<usize>,
+    }
+
+    impl AsRef<[u8]> for Fickle {
+        fn as_ref(&self) -> &[u8] {
+            self.reads.set(self.reads.get() + 1);
+            if self.reads.get() > 1 {
+                &self.jumbf[8..]
+            } else {
+                &self.jumbf
+            }
+        }
+    }
+
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let reader = JumbfReader::from_buffer(Fickle {
+        jumbf: jumbf.to_vec(),
+        reads: Cell::new(0),
+    })
+    .unwrap();
+
+    let path = "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9/c2pa.signature";
+
+    assert!(reader.root().is_err());
+    assert!(reader.get(path).is_err());
+    assert!(reader.payload(path).is_err());
+}
+
+#[test]
+fn jumbf_reader_pathological_depth() {
+    use crate::tests::parser::super_box_depth_limit::pathologically_deep;
+
+    const DEPTH: usize = 100_000;
+
+    let jumbf = pathologically_deep(DEPTH);
+
+    let reader = JumbfReader::new(Cursor::new(&jumbf)).unwrap();
+    assert_eq!(reader.as_bytes(), jumbf);
+    assert!(reader.get("no.such.label").unwrap().is_none());
+    drop(reader.root().unwrap());
+
+    let reader = JumbfReader::from_buffer(&jumbf[..]).unwrap();
+    assert_eq!(reader.as_bytes(), jumbf);
+    drop(reader.root().unwrap());
+}
@@ -0,0 +1,131 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{
+    c2pa::MANIFEST_TYPE,
+    parser::{BoxRegistry, Error, SuperBox},
+    BoxType,
+};
+
+const MANIFEST: &str = "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9";
+
+#[derive(Debug, PartialEq)]
+struct Manifest(String);
+
+#[derive(Debug, PartialEq)]
+struct Cbor(usize);
+
+#[test]
+fn empty() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let registry = BoxRegistry::new();
+    assert!(registry.decode(&sbox).unwrap().is_empty());
+    assert!(registry.decode_super_box(&sbox).is_none());
+}
+
+#[test]
+fn c2pa() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let mut registry = BoxRegistry::new();
+    registry
+        .register_box_type(BoxType::CBOR, |dbox| Ok(Cbor(dbox.data.len())))
+        .register_content_type(MANIFEST_TYPE, |sbox| {
+            Ok(Manifest(sbox.desc.label.unwrap_or_default().to_owned()))
+        });
+
+    let payloads = registry.decode(&sbox).unwrap();
+
+    // The manifest is decoded before the boxes it contains.
+    let manifest = &payloads[0];
+    assert_eq!(manifest.tbox, BoxType::SUPER_BOX);
+    assert_eq!(
+        manifest.downcast_ref::<Manifest>(),
+        Some(&Manifest(MANIFEST.to_owned()))
+    );
+    assert!(manifest.downcast_ref::<Cbor>().is_none());
+
+    let cbor: Vec<usize> = payloads[1..]
+        .iter()
+        .map(|payload| {
+            assert_eq!(payload.tbox, BoxType::CBOR);
+            payload.downcast_ref::<Cbor>().unwrap().0
+        })
+        .collect();
+
+    assert!(cbor.contains(&632));
+    assert!(cbor.contains(&13726));
+
+    // Payloads are reported in source order.
+    let mut offsets: Vec<usize> = payloads.iter().map(|payload| payload.offset).collect();
+    let unsorted = offsets.clone();
+    offsets.sort_unstable();
+    assert_eq!(offsets, unsorted);
+}
+
+#[test]
+fn replace_handler() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let mut registry = BoxRegistry::new();
+    registry.register_content_type(sbox.desc.uuid, |_| Ok(1u8));
+    registry.register_content_type(sbox.desc.uuid, |_| Ok(2u8));
+
+    let payload = registry.decode_super_box(&sbox).unwrap().unwrap();
+    assert_eq!(payload.downcast_ref::<u8>(), Some(&2));
+    assert_eq!(payload.offset, 0);
+
+    assert_eq!(
+        format!("{registry:?}"),
+        format!(
+            "BoxRegistry {{ box_types: [], content_types: [{:?}] }}",
+            sbox.desc.uuid
+        )
+    );
+}
+
+#[test]
+fn handler_error() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let signature = sbox
+        .find_by_label(&format!("{MANIFEST}/c2pa.signature"))
+        .unwrap();
+
+    let mut registry = BoxRegistry::new();
+    registry.register_content_type(signature.desc.uuid, |sbox| {
+        if sbox.desc.label == Some("c2pa.signature") {
+            Err(Error::Malformed("signature"))
+        } else {
+            Ok(())
+        }
+    });
+
+    let err = registry.decode(&sbox).unwrap_err();
+
+    assert_eq!(
+        err,
+        Error::InSuperBox {
+            offset: signature.offset,
+            path: vec![Some("c2pa".to_owned()), Some(MANIFEST.to_owned())],
+            source: Box::new(Error::Malformed("signature")),
+        }
+    );
+}
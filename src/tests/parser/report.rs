@@ -0,0 +1,150 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::parser::{Error, ParseReport, ParseWarning, SuperBox};
+
+#[test]
+fn clean() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+
+    let (rem, sbox, report) = SuperBox::from_source_with_report(jumbf).unwrap();
+    assert!(rem.is_empty());
+    assert!(report.is_clean());
+    assert_eq!(report, ParseReport::default());
+
+    assert_eq!(sbox, SuperBox::from_slice(jumbf).unwrap().1);
+}
+
+#[test]
+fn non_minimal_length() {
+    let jumbf = hex!(
+        "00000035" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "00000001" // box size (XLBox)
+            "61626364" // box type = 'abcd'
+            "0000000000000014" // XLBox size
+            "41424344" // payload
+    );
+
+    let (_, sbox, report) = SuperBox::from_source_with_report(&jumbf).unwrap();
+
+    assert_eq!(sbox, SuperBox::from_slice(&jumbf).unwrap().1);
+    assert_eq!(
+        report.warnings,
+        vec![ParseWarning::NonMinimalLength { offset: 33 }]
+    );
+}
+
+#[test]
+fn unterminated_label() {
+    let jumbf = hex!(
+        "00000023" // box size
+        "6a756d62" // box type = 'jumb'
+            "0000001b" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "03" // toggles
+            "6162" // label ("ab", no NUL terminator)
+    );
+
+    assert_eq!(
+        SuperBox::from_slice(&jumbf).unwrap_err(),
+        Error::Malformed("NUL-terminated string")
+    );
+
+    let (_, sbox, report) = SuperBox::from_source_with_report(&jumbf).unwrap();
+
+    assert_eq!(sbox.desc.label, Some("ab"));
+    assert_eq!(
+        report.warnings,
+        vec![ParseWarning::UnterminatedLabel { offset: 8 }]
+    );
+}
+
+#[test]
+fn trailing_bytes_in_child() {
+    let jumbf = hex!(
+        "00000052" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "00000031" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "00" // toggles
+                // ----
+                "0000000c" // box size
+                "61626364" // box type = 'abcd'
+                "41424344" // payload
+                // ----
+                "00000000" // trailing bytes
+    );
+
+    assert!(SuperBox::from_slice(&jumbf).is_err());
+
+    let (rem, sbox, report) = SuperBox::from_source_with_report(&jumbf).unwrap();
+    assert!(rem.is_empty());
+
+    let child = sbox.child_boxes[0].as_super_box().unwrap();
+    assert_eq!(child.child_boxes.len(), 1);
+
+    assert_eq!(
+        report.warnings,
+        vec![ParseWarning::TrailingBytes { offset: 78, len: 4 }]
+    );
+}
+
+#[test]
+fn errors_are_still_errors() {
+    let jumbf = hex!(
+        "0000000c" // box size
+        "61626364" // box type = 'abcd'
+        "41424344" // payload
+    );
+
+    assert_eq!(
+        SuperBox::from_source_with_report(&jumbf).unwrap_err(),
+        Error::InvalidSuperBoxType(crate::BoxType(*b"abcd"))
+    );
+}
+
+#[test]
+fn display() {
+    assert_eq!(
+        ParseWarning::NonMinimalLength { offset: 33 }.to_string(),
+        "Box at offset 33 has a non-minimal length encoding"
+    );
+
+    assert_eq!(
+        ParseWarning::TrailingBytes { offset: 70, len: 4 }.to_string(),
+        "Ignored 4 trailing bytes at offset 70"
+    );
+
+    assert_eq!(
+        ParseWarning::UnterminatedLabel { offset: 8 }.to_string(),
+        "Label in description box at offset 8 is not NUL-terminated"
+    );
+}
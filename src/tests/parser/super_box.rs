@@ -15,8 +15,8 @@ use hex_literal::hex;
 use pretty_assertions_sorted::assert_eq;
 
 use crate::{
-    parser::{ChildBox, DataBox, DescriptionBox, Error, SuperBox},
-    BoxType,
+    parser::{ChildBox, DataBox, DescriptionBox, Error, ParseOptions, SuperBox, SuperBoxIndex},
+    BoxType, ContentType, Uuid,
 };
 
 #[test]
@@ -38,20 +38,22 @@ fn simple_super_box() {
         sbox,
         SuperBox {
             desc: DescriptionBox {
-                uuid: &[0; 16],
+                uuid: Uuid([0; 16]),
                 label: Some("test.superbox"),
                 requestable: true,
                 id: None,
                 hash: None,
                 private: None,
                 original: &jumbf[8..47],
+                offset: 8,
             },
             child_boxes: vec!(),
             original: &jumbf,
+            offset: 0,
         }
     );
 
-    assert_eq!(format!("{sbox:#?}"), "SuperBox {\n    desc: DescriptionBox {\n        uuid: [00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n        label: Some(\n            \"test.superbox\",\n        ),\n        requestable: true,\n        id: None,\n        hash: None,\n        private: None,\n        original: 39 bytes starting with [00, 00, 00, 27, 6a, 75, 6d, 64, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n    },\n    child_boxes: [],\n    original: 47 bytes starting with [00, 00, 00, 2f, 6a, 75, 6d, 62, 00, 00, 00, 27, 6a, 75, 6d, 64, 00, 00, 00, 00],\n}");
+    assert_eq!(format!("{sbox:#?}"), "SuperBox {\n    desc: DescriptionBox {\n        uuid: [00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n        label: Some(\n            \"test.superbox\",\n        ),\n        requestable: true,\n        id: None,\n        hash: None,\n        private: None,\n        original: 39 bytes starting with [00, 00, 00, 27, 6a, 75, 6d, 64, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00, 00],\n        offset: 8,\n    },\n    child_boxes: [],\n    original: 47 bytes starting with [00, 00, 00, 2f, 6a, 75, 6d, 62, 00, 00, 00, 27, 6a, 75, 6d, 64, 00, 00, 00, 00],\n    offset: 0,\n}");
 }
 
 #[test]
@@ -81,28 +83,32 @@ fn nested_super_boxes() {
         sbox,
         SuperBox {
             desc: DescriptionBox {
-                uuid: &[0; 16],
+                uuid: Uuid([0; 16]),
                 label: Some("test.superbox_databox"),
                 requestable: true,
                 id: None,
                 hash: None,
                 private: None,
                 original: &jumbf[8..55],
+                offset: 8,
             },
             child_boxes: vec!(ChildBox::SuperBox(SuperBox {
                 desc: DescriptionBox {
-                    uuid: &[0; 16],
+                    uuid: Uuid([0; 16]),
                     label: Some("test.databox"),
                     requestable: true,
                     id: None,
                     hash: None,
                     private: None,
                     original: &jumbf[63..101],
+                    offset: 63,
                 },
                 child_boxes: vec!(),
                 original: &jumbf[55..101],
+                offset: 55,
             })),
             original: &jumbf,
+            offset: 0,
         }
     );
 }
@@ -133,28 +139,32 @@ fn child_superbox_without_label() {
         sbox,
         SuperBox {
             desc: DescriptionBox {
-                uuid: &[0; 16],
+                uuid: Uuid([0; 16]),
                 label: Some("test.superbox_databox"),
                 requestable: true,
                 id: None,
                 hash: None,
                 private: None,
                 original: &jumbf[8..55],
+                offset: 8,
             },
             child_boxes: vec!(ChildBox::SuperBox(SuperBox {
                 desc: DescriptionBox {
-                    uuid: &[0; 16],
+                    uuid: Uuid([0; 16]),
                     label: None,
                     requestable: false,
                     id: None,
                     hash: None,
                     private: None,
                     original: &jumbf[63..88],
+                    offset: 63,
                 },
                 child_boxes: vec!(),
                 original: &jumbf[55..88],
+                offset: 55,
             })),
             original: &jumbf,
+            offset: 0,
         }
     );
 
@@ -166,16 +176,18 @@ fn child_superbox_without_label() {
         dbox_as_child.as_super_box().unwrap(),
         &SuperBox {
             desc: DescriptionBox {
-                uuid: &[0; 16],
+                uuid: Uuid([0; 16]),
                 label: None,
                 requestable: false,
                 id: None,
                 hash: None,
                 private: None,
                 original: &jumbf[63..88],
+                offset: 63,
             },
             child_boxes: vec!(),
             original: &jumbf[55..88],
+            offset: 55,
         }
     );
 }
@@ -203,13 +215,14 @@ fn data_box_sample() {
         sbox,
         SuperBox {
             desc: DescriptionBox {
-                uuid: &[99, 50, 99, 115, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,],
+                uuid: Uuid([99, 50, 99, 115, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,]),
                 label: Some("c2pa.signature"),
                 requestable: true,
                 id: None,
                 hash: None,
                 private: None,
                 original: &jumbf[8..48],
+                offset: 8,
             },
             child_boxes: vec!(ChildBox::DataBox(DataBox {
                 tbox: BoxType(*b"uuid"),
@@ -220,8 +233,10 @@ fn data_box_sample() {
                     117, 114, 101, 32, 100, 97, 116, 97, 46, 46, 46,
                 ],
                 original: &jumbf[48..119],
+                offset: 48,
             })),
             original: &jumbf,
+            offset: 0,
         }
     );
 
@@ -241,6 +256,7 @@ fn data_box_sample() {
                 101, 32, 100, 97, 116, 97, 46, 46, 46,
             ],
             original: &jumbf[48..119],
+            offset: 48,
         }
     );
 }
@@ -334,49 +350,53 @@ fn complex_example() {
         sbox,
         SuperBox {
             desc: DescriptionBox {
-                uuid: &[99, 50, 112, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,],
+                uuid: Uuid([99, 50, 112, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,]),
                 label: Some("c2pa"),
                 requestable: true,
                 id: None,
                 hash: None,
                 private: None,
                 original: &jumbf[8..38],
+                offset: 8,
             },
             child_boxes: vec!(ChildBox::SuperBox(SuperBox {
                 desc: DescriptionBox {
-                    uuid: &[99, 50, 109, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,],
+                    uuid: Uuid([99, 50, 109, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,]),
                     label: Some("cb.adobe_1"),
                     requestable: true,
                     id: None,
                     hash: None,
                     private: None,
                     original: &jumbf[46..82],
+                    offset: 46,
                 },
                 child_boxes: vec!(
                     ChildBox::SuperBox(SuperBox {
                         desc: DescriptionBox {
-                            uuid: &[
+                            uuid: Uuid([
                                 99, 50, 97, 115, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,
-                            ],
+                            ]),
                             label: Some("c2pa.assertions",),
                             requestable: true,
                             id: None,
                             hash: None,
                             private: None,
                             original: &jumbf[90..131],
+                            offset: 90,
                         },
                         child_boxes: vec![ChildBox::SuperBox(SuperBox {
                             desc: DescriptionBox {
-                                uuid: &[
+                                uuid: Uuid([
                                     106, 115, 111, 110, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155,
                                     113,
-                                ],
+                                ]),
                                 label: Some("c2pa.location.broad",),
                                 requestable: true,
                                 id: None,
                                 hash: None,
                                 private: None,
                                 original: &jumbf[139..184],
+                                offset: 139,
                             },
                             child_boxes: vec![ChildBox::DataBox(DataBox {
                                 tbox: BoxType(*b"json"),
@@ -386,22 +406,26 @@ fn complex_example() {
                                     32, 78, 74, 34, 125,
                                 ],
                                 original: &jumbf[184..225],
+                                offset: 184,
                             },),],
                             original: &jumbf[131..225],
+                            offset: 131,
                         },),],
                         original: &jumbf[82..225],
+                        offset: 82,
                     },),
                     ChildBox::SuperBox(SuperBox {
                         desc: DescriptionBox {
-                            uuid: &[
+                            uuid: Uuid([
                                 99, 50, 99, 108, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,
-                            ],
+                            ]),
                             label: Some("c2pa.claim",),
                             requestable: true,
                             id: None,
                             hash: None,
                             private: None,
                             original: &jumbf[233..269],
+                            offset: 233,
                         },
                         child_boxes: vec![ChildBox::DataBox(DataBox {
                             tbox: BoxType(*b"json"),
@@ -423,20 +447,23 @@ fn complex_example() {
                                 32, 32, 32, 125,
                             ],
                             original: &jumbf[269..496],
+                            offset: 269,
                         },),],
                         original: &jumbf[225..496],
+                        offset: 225,
                     },),
                     ChildBox::SuperBox(SuperBox {
                         desc: DescriptionBox {
-                            uuid: &[
+                            uuid: Uuid([
                                 99, 50, 99, 115, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,
-                            ],
+                            ]),
                             label: Some("c2pa.signature",),
                             requestable: true,
                             id: None,
                             hash: None,
                             private: None,
                             original: &jumbf[504..544],
+                            offset: 504,
                         },
                         child_boxes: vec![ChildBox::DataBox(DataBox {
                             tbox: BoxType(*b"uuid"),
@@ -448,13 +475,17 @@ fn complex_example() {
                                 116, 97, 46, 46, 46,
                             ],
                             original: &jumbf[544..615],
+                            offset: 544,
                         },),],
                         original: &jumbf[496..615],
+                        offset: 496,
                     },),
                 ),
                 original: &jumbf[38..615],
+                offset: 38,
             })),
             original: &jumbf,
+            offset: 0,
         }
     );
 
@@ -462,13 +493,14 @@ fn complex_example() {
         sbox.find_by_label("cb.adobe_1/c2pa.signature"),
         Some(&SuperBox {
             desc: DescriptionBox {
-                uuid: &[99, 50, 99, 115, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,],
+                uuid: Uuid([99, 50, 99, 115, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,]),
                 label: Some("c2pa.signature",),
                 requestable: true,
                 id: None,
                 hash: None,
                 private: None,
                 original: &jumbf[504..544],
+                offset: 504,
             },
             child_boxes: vec![ChildBox::DataBox(DataBox {
                 tbox: BoxType(*b"uuid"),
@@ -479,8 +511,10 @@ fn complex_example() {
                     117, 114, 101, 32, 100, 97, 116, 97, 46, 46, 46,
                 ],
                 original: &jumbf[544..615],
+                offset: 544,
             },),],
             original: &jumbf[496..615],
+            offset: 496,
         })
     );
 
@@ -500,6 +534,7 @@ fn complex_example() {
                 101, 32, 100, 97, 116, 97, 46, 46, 46,
             ],
             original: &jumbf[544..615],
+            offset: 544,
         })
     );
 
@@ -511,6 +546,44 @@ fn complex_example() {
         552
     );
 
+    let cb = sbox.find_by_label("cb.adobe_1").unwrap();
+    let sig = cb.find_by_label("c2pa.signature").unwrap();
+    let sig_dbox = sig.data_box().unwrap();
+
+    assert_eq!(sig.offset_within(&sbox), Some(496));
+    assert_eq!(sig.offset_within(cb), Some(458));
+    assert_eq!(sig.offset_within(sig), Some(0));
+    assert_eq!(sig_dbox.offset_within(&sbox), Some(544));
+    assert_eq!(sig_dbox.offset_within(cb), Some(506));
+    assert_eq!(sig_dbox.offset_within(sig), Some(48));
+
+    assert_eq!(sbox.offset_within(cb), None);
+    assert_eq!(
+        cb.find_by_label("c2pa.claim")
+            .and_then(|claim| claim.data_box())
+            .and_then(|claim| claim.offset_within(sig)),
+        None
+    );
+
+    assert_eq!(
+        sbox.exclusion_ranges(&["cb.adobe_1/c2pa.signature"]),
+        vec![496..615]
+    );
+    assert_eq!(
+        sbox.exclusion_ranges(&[
+            "cb.adobe_1/c2pa.signature",
+            "cb.adobe_1/c2pa.assertions",
+            "cb.adobe_1/c2pa.claim",
+        ]),
+        vec![82..225, 225..496, 496..615]
+    );
+    assert_eq!(
+        sbox.exclusion_ranges(&["cb.adobe_1/c2pa.signature", "cb.adobe_1", "no.such.box"]),
+        vec![38..615]
+    );
+    assert_eq!(cb.exclusion_ranges(&["c2pa.signature"]), vec![458..577]);
+    assert!(sbox.exclusion_ranges(&["no.such.box"]).is_empty());
+
     assert_eq!(sbox.data_box(), None);
 }
 
@@ -526,7 +599,85 @@ fn error_wrong_box_type() {
 
     assert_eq!(
         SuperBox::from_slice(&jumbf).unwrap_err(),
-        nom::Err::Error(Error::InvalidSuperBoxType(BoxType(*b"jumc")))
+        Error::InvalidSuperBoxType(BoxType(*b"jumc"))
+    );
+}
+
+const NESTED_INVALID_BOX_LENGTH: [u8; 0x4e] = hex!(
+    "0000004e" // box size
+    "6a756d62" // box type = 'jumb'
+        "0000001b" // box size
+        "6a756d64" // box type = 'jumd'
+        "00000000000000000000000000000000" // UUID
+        "03" // toggles
+        "6100" // label ("a")
+        // ------
+        "0000002b" // box size
+        "6a756d62" // box type = 'jumb'
+            "0000001b" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "03" // toggles
+            "6200" // label ("b")
+            // ------
+            "00000002" // box size (INVALID)
+            "61626364" // box type = 'abcd'
+);
+
+#[test]
+fn error_context_nested() {
+    let expected = Error::InSuperBox {
+        offset: 70,
+        path: vec![Some("a".to_owned()), Some("b".to_owned())],
+        source: Box::new(Error::InvalidBoxLength(2)),
+    };
+
+    let err = SuperBox::from_slice(&NESTED_INVALID_BOX_LENGTH).unwrap_err();
+    assert_eq!(err, expected);
+    assert_eq!(err.root_cause(), &Error::InvalidBoxLength(2));
+    assert_eq!(
+        err.to_string(),
+        "Box length value 2 is reserved at offset 70 inside a/b"
+    );
+
+    assert_eq!(
+        SuperBox::from_source_with_options(&NESTED_INVALID_BOX_LENGTH, &ParseOptions::default())
+            .unwrap_err(),
+        expected
+    );
+}
+
+#[test]
+fn error_context_invalid_child_description_box() {
+    let jumbf = hex!(
+        "00000033" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ------
+            "00000012" // box size
+            "6a756d62" // box type = 'jumb'
+                "0000000a" // box size
+                "6a756d63" // box type = 'jumc' (INCORRECT)
+                "0000" // payload
+    );
+
+    let err = SuperBox::from_slice(&jumbf).unwrap_err();
+
+    assert_eq!(
+        err,
+        Error::InSuperBox {
+            offset: 33,
+            path: vec![None],
+            source: Box::new(Error::InvalidDescriptionBoxType(BoxType(*b"jumc"))),
+        }
+    );
+
+    assert_eq!(
+        err.to_string(),
+        "Description box type should be 'jumd', was 'b\"jumc\"' at offset 33 inside (unlabeled)"
     );
 }
 
@@ -565,47 +716,114 @@ fn find_by_label_avoids_confict() {
         sbox,
         SuperBox {
             desc: DescriptionBox {
-                uuid: &[0; 16],
+                uuid: Uuid([0; 16]),
                 label: Some("test.superbox_databox"),
                 requestable: true,
                 id: None,
                 hash: None,
                 private: None,
                 original: &jumbf[8..55],
+                offset: 8,
             },
             child_boxes: vec!(
                 ChildBox::SuperBox(SuperBox {
                     desc: DescriptionBox {
-                        uuid: &[0; 16],
+                        uuid: Uuid([0; 16]),
                         label: Some("test.databox"),
                         requestable: true,
                         id: None,
                         hash: None,
                         private: None,
                         original: &jumbf[63..101],
+                        offset: 63,
                     },
                     child_boxes: vec!(),
                     original: &jumbf[55..101],
+                    offset: 55,
                 }),
                 ChildBox::SuperBox(SuperBox {
                     desc: DescriptionBox {
-                        uuid: &[0; 16],
+                        uuid: Uuid([0; 16]),
                         label: Some("test.databox"),
                         requestable: true,
                         id: None,
                         hash: None,
                         private: None,
                         original: &jumbf[109..147],
+                        offset: 109,
                     },
                     child_boxes: vec!(),
                     original: &jumbf[101..147],
+                    offset: 101,
                 })
             ),
             original: &jumbf,
+            offset: 0,
         }
     );
 
     assert_eq!(sbox.find_by_label("test.databox"), None);
+    assert_eq!(sbox.duplicate_labels(), vec!["test.databox"]);
+}
+
+#[test]
+fn duplicate_labels() {
+    let jumbf = hex!(
+        "000000d0" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ------
+            "00000023" // box size
+            "6a756d62" // box type = 'jumb'
+                "0000001b" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "03" // toggles
+                "6200" // label = "b"
+            // ------
+            "00000023" // box size
+            "6a756d62" // box type = 'jumb'
+                "0000001b" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "02" // toggles (not requestable)
+                "6100" // label = "a"
+            // ------
+            "00000023" // box size
+            "6a756d62" // box type = 'jumb'
+                "0000001b" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "03" // toggles
+                "6100" // label = "a"
+            // ------
+            "00000023" // box size
+            "6a756d62" // box type = 'jumb'
+                "0000001b" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "03" // toggles
+                "6200" // label = "b"
+            // ------
+            "00000023" // box size
+            "6a756d62" // box type = 'jumb'
+                "0000001b" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "03" // toggles
+                "6200" // label = "b"
+    );
+
+    let (rem, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    assert!(rem.is_empty());
+
+    // Only one requestable "a", so it can be found.
+    assert!(sbox.find_by_label("a").is_some());
+    assert_eq!(sbox.find_by_label("b"), None);
+    assert_eq!(sbox.duplicate_labels(), vec!["b"]);
 }
 
 #[test]
@@ -643,43 +861,49 @@ fn find_by_label_skips_non_requestable_boxes() {
         sbox,
         SuperBox {
             desc: DescriptionBox {
-                uuid: &[0; 16],
+                uuid: Uuid([0; 16]),
                 label: Some("test.superbox_databox"),
                 requestable: true,
                 id: None,
                 hash: None,
                 private: None,
                 original: &jumbf[8..55],
+                offset: 8,
             },
             child_boxes: vec!(
                 ChildBox::SuperBox(SuperBox {
                     desc: DescriptionBox {
-                        uuid: &[0; 16],
+                        uuid: Uuid([0; 16]),
                         label: Some("test.databox"),
                         requestable: false,
                         id: None,
                         hash: None,
                         private: None,
                         original: &jumbf[63..101],
+                        offset: 63,
                     },
                     child_boxes: vec!(),
                     original: &jumbf[55..101],
+                    offset: 55,
                 }),
                 ChildBox::SuperBox(SuperBox {
                     desc: DescriptionBox {
-                        uuid: &[0; 16],
+                        uuid: Uuid([0; 16]),
                         label: Some("test.databoz"),
                         requestable: true,
                         id: None,
                         hash: None,
                         private: None,
                         original: &jumbf[109..147],
+                        offset: 109,
                     },
                     child_boxes: vec!(),
                     original: &jumbf[101..147],
+                    offset: 101,
                 })
             ),
             original: &jumbf,
+            offset: 0,
         }
     );
 
@@ -689,88 +913,310 @@ fn find_by_label_skips_non_requestable_boxes() {
         sbox.find_by_label("test.databoz"),
         Some(&SuperBox {
             desc: DescriptionBox {
-                uuid: &[0; 16],
+                uuid: Uuid([0; 16]),
                 label: Some("test.databoz"),
                 requestable: true,
                 id: None,
                 hash: None,
                 private: None,
                 original: &jumbf[109..147],
+                offset: 109,
             },
             child_boxes: vec!(),
             original: &jumbf[101..147],
+            offset: 101,
         })
     );
 }
 
+#[test]
+fn find_any_by_label_includes_non_requestable_boxes() {
+    let jumbf = hex!(
+        "0000005d" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000027" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "03" // toggles
+            "746573742e7375706572626f7800" // label = "test.superbox"
+            // ------
+            "0000002e" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000026" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "02" // toggles
+                "746573742e64617461626f7800" // label = "test.databox"
+    );
+
+    let (rem, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    assert!(rem.is_empty());
+
+    assert_eq!(sbox.find_by_label("test.databox"), None);
+
+    let found = sbox.find_any_by_label("test.databox").unwrap();
+    assert_eq!(found.desc.label, Some("test.databox"));
+    assert!(!found.desc.requestable);
+
+    assert_eq!(sbox.find_any_by_label("test.databoz"), None);
+}
+
+#[test]
+fn find_recursive_by_label() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let hash_data = sbox
+        .find_by_label(
+            "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9/c2pa.assertions/c2pa.hash.data",
+        )
+        .unwrap();
+
+    let found: Vec<&SuperBox> = sbox.find_recursive_by_label("c2pa.hash.data").collect();
+    assert_eq!(found, [hash_data]);
+
+    // The search includes the superbox itself.
+    let found: Vec<&SuperBox> = sbox.find_recursive_by_label("c2pa").collect();
+    assert_eq!(found, [&sbox]);
+
+    assert_eq!(sbox.find_recursive_by_label("c2pa.hash").count(), 0);
+}
+
+#[test]
+fn find_recursive_by_label_suffix() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let labels: Vec<&str> = sbox
+        .find_recursive_by_label_suffix("43d7919d89b9")
+        .filter_map(|sbox| sbox.desc.label)
+        .collect();
+
+    assert_eq!(
+        labels,
+        ["contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9"]
+    );
+
+    // Every label ends with an empty suffix.
+    let all: Vec<&SuperBox> = sbox.find_recursive_by_label_suffix("").collect();
+    assert_eq!(all[0], &sbox);
+    assert!(all.len() > 1);
+    assert!(all.iter().all(|sbox| sbox.desc.requestable));
+}
+
+#[test]
+fn find_recursive_by_label_skips_non_requestable_boxes() {
+    let jumbf = hex!(
+        "00000093" // box size
+        "6a756d62" // box type = 'jumb'
+            "0000002f" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "03" // toggles
+            "746573742e7375706572626f785f64617461626f7800" // label
+            // ------
+            "0000002e" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000026" // box size
+                "6a756d64" // box type = 'jumbd'
+                "00000000000000000000000000000000" // UUID
+                "02" // toggles
+                "746573742e64617461626f7800" // label = "test.databox"
+            // ------
+            "0000002e" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000026" // box size
+                "6a756d64" // box type = 'jumbd'
+                "00000000000000000000000000000000" // UUID
+                "03" // toggles
+                "746573742e64617461626f7a00" // label = "test.databoz"
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+
+    assert_eq!(sbox.find_recursive_by_label("test.databox").count(), 0);
+    assert_eq!(sbox.find_recursive_by_label("test.databoz").count(), 1);
+
+    let labels: Vec<&str> = sbox
+        .find_recursive_by_label_suffix("databo")
+        .filter_map(|sbox| sbox.desc.label)
+        .collect();
+    assert!(labels.is_empty());
+
+    let labels: Vec<&str> = sbox
+        .find_recursive_by_label_suffix("box")
+        .filter_map(|sbox| sbox.desc.label)
+        .collect();
+    assert_eq!(labels, ["test.superbox_databox"]);
+}
+
+#[test]
+fn find_by_id() {
+    let jumbf = hex!(
+        "00000090" // box size
+        "6a756d62" // box type = 'jumb'
+            "0000001d" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "04" // toggles
+            "00000001" // ID
+            // ------
+            "00000046" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "00" // toggles
+                // ------
+                "00000025" // box size
+                "6a756d62" // box type = 'jumb'
+                    "0000001d" // box size
+                    "6a756d64" // box type = 'jumd'
+                    "00000000000000000000000000000000" // UUID
+                    "04" // toggles
+                    "00000007" // ID
+            // ------
+            "00000025" // box size
+            "6a756d62" // box type = 'jumb'
+                "0000001d" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "04" // toggles
+                "00000007" // ID
+    );
+
+    let (rem, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    assert!(rem.is_empty());
+
+    assert_eq!(sbox.find_by_id(1), Some(&sbox));
+
+    // Depth-first: the nested superbox is found before its parent's sibling.
+    assert_eq!(sbox.find_by_id(7).unwrap().offset, 70);
+
+    assert_eq!(sbox.find_by_id(9), None);
+
+    // Superboxes beyond a depth limit are not searched.
+    let (_, sbox) = SuperBox::from_slice_with_depth_limit(&jumbf, 0).unwrap();
+    assert_eq!(sbox.find_by_id(7), None);
+}
+
+#[test]
+fn find_by_uuid() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let labels = |uuid: Uuid| -> Vec<&str> {
+        sbox.find_by_uuid(uuid)
+            .map(|sbox| sbox.desc.label.unwrap())
+            .collect()
+    };
+
+    assert_eq!(
+        labels(Uuid(hex!(
+            "63 32 70 61 00 11 00 10 80 00 00 aa 00 38 9b 71"
+        ))),
+        vec!["c2pa"]
+    );
+
+    assert_eq!(
+        labels(Uuid(hex!(
+            "63 32 61 73 00 11 00 10 80 00 00 aa 00 38 9b 71"
+        ))),
+        vec!["c2pa.assertions"]
+    );
+
+    assert_eq!(labels(Uuid::JSON), vec!["stds.schema-org.CreativeWork"]);
+    assert_eq!(labels(Uuid::CBOR), vec!["c2pa.actions", "c2pa.hash.data"]);
+    assert!(labels(Uuid::XML).is_empty());
+
+    // Accepts a ContentType or a byte array.
+    assert_eq!(
+        sbox.find_by_uuid(ContentType::Json).count(),
+        labels(Uuid::JSON).len()
+    );
+    assert_eq!(sbox.find_by_uuid([0; 16]).count(), 0);
+}
+
 #[test]
 fn parse_c2pa_manifest() {
     let jumbf = include_bytes!("../fixtures/C.c2pa");
 
     let (rem, sbox) = SuperBox::from_slice(jumbf).unwrap();
     assert!(rem.is_empty());
+    assert!(sbox.duplicate_labels().is_empty());
 
     assert_eq!(
         sbox,
         SuperBox {
             desc: DescriptionBox {
-                uuid: &hex!("63 32 70 61 00 11 00 10 80 00 00 aa 00 38 9b 71"),
+                uuid: Uuid(hex!("63 32 70 61 00 11 00 10 80 00 00 aa 00 38 9b 71")),
                 label: Some("c2pa",),
                 requestable: true,
                 id: None,
                 hash: None,
                 private: None,
                 original: &jumbf[8..38],
+                offset: 8,
             },
             child_boxes: vec![ChildBox::SuperBox(SuperBox {
                 desc: DescriptionBox {
-                    uuid: &hex!("63 32 6d 61 00 11 00 10 80 00 00 aa 00 38 9b 71"),
+                    uuid: Uuid(hex!("63 32 6d 61 00 11 00 10 80 00 00 aa 00 38 9b 71")),
                     label: Some("contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9",),
                     requestable: true,
                     id: None,
                     hash: None,
                     private: None,
                     original: &jumbf[46..129],
+                    offset: 46,
                 },
                 child_boxes: vec![
                     ChildBox::SuperBox(SuperBox {
                         desc: DescriptionBox {
-                            uuid: &hex!("63 32 61 73 00 11 00 10 80 00 00 aa 00 38 9b 71"),
+                            uuid: Uuid(hex!("63 32 61 73 00 11 00 10 80 00 00 aa 00 38 9b 71")),
                             label: Some("c2pa.assertions",),
                             requestable: true,
                             id: None,
                             hash: None,
                             private: None,
                             original: &jumbf[137..178],
+                            offset: 137,
                         },
                         child_boxes: vec![
                             ChildBox::SuperBox(SuperBox {
                                 desc: DescriptionBox {
-                                    uuid: &hex!("40 cb 0c 32 bb 8a 48 9d a7 0b 2a d6 f4 7f 43 69"),
+                                    uuid: Uuid(hex!(
+                                        "40 cb 0c 32 bb 8a 48 9d a7 0b 2a d6 f4 7f 43 69"
+                                    )),
                                     label: Some("c2pa.thumbnail.claim.jpeg",),
                                     requestable: true,
                                     id: None,
                                     hash: None,
                                     private: None,
                                     original: &jumbf[186..237],
+                                    offset: 186,
                                 },
                                 child_boxes: vec![
                                     ChildBox::DataBox(DataBox {
                                         tbox: BoxType(*b"bfdb"),
                                         data: &jumbf[245..257],
                                         original: &jumbf[237..257],
+                                        offset: 237,
                                     },),
                                     ChildBox::DataBox(DataBox {
                                         tbox: BoxType(*b"bidb"),
                                         data: &jumbf[265..31976],
                                         original: &jumbf[257..31976],
+                                        offset: 257,
                                     },),
                                 ],
                                 original: &jumbf[178..31976],
+                                offset: 178,
                             },),
                             ChildBox::SuperBox(SuperBox {
                                 desc: DescriptionBox {
-                                    uuid: &hex!("6a 73 6f 6e 00 11 00 10 80 00 00 aa 00 38 9b 71"),
+                                    uuid: Uuid(hex!(
+                                        "6a 73 6f 6e 00 11 00 10 80 00 00 aa 00 38 9b 71"
+                                    )),
                                     label: Some("stds.schema-org.CreativeWork",),
                                     requestable: true,
                                     id: None,
@@ -779,91 +1225,751 @@ fn parse_c2pa_manifest() {
                                         tbox: BoxType(*b"c2sh"),
                                         data: &jumbf[32046..32062],
                                         original: &jumbf[32038..32062],
+                                        offset: 32038,
                                     },),
                                     original: &jumbf[31984..32062],
+                                    offset: 31984,
                                 },
                                 child_boxes: vec![ChildBox::DataBox(DataBox {
                                     tbox: BoxType(*b"json"),
                                     data: &jumbf[32070..32179],
                                     original: &jumbf[32062..32179],
+                                    offset: 32062,
                                 },),],
                                 original: &jumbf[31976..32179],
+                                offset: 31976,
                             },),
                             ChildBox::SuperBox(SuperBox {
                                 desc: DescriptionBox {
-                                    uuid: &hex!("63 62 6f 72 00 11 00 10 80 00 00 aa 00 38 9b 71"),
+                                    uuid: Uuid(hex!(
+                                        "63 62 6f 72 00 11 00 10 80 00 00 aa 00 38 9b 71"
+                                    )),
                                     label: Some("c2pa.actions",),
                                     requestable: true,
                                     id: None,
                                     hash: None,
                                     private: None,
                                     original: &jumbf[32187..32225],
+                                    offset: 32187,
                                 },
                                 child_boxes: vec![ChildBox::DataBox(DataBox {
                                     tbox: BoxType(*b"cbor"),
                                     data: &jumbf[32233..32311],
                                     original: &jumbf[32225..32311],
+                                    offset: 32225,
                                 },),],
                                 original: &jumbf[32179..32311],
+                                offset: 32179,
                             },),
                             ChildBox::SuperBox(SuperBox {
                                 desc: DescriptionBox {
-                                    uuid: &hex!("63 62 6f 72 00 11 00 10 80 00 00 aa 00 38 9b 71"),
+                                    uuid: Uuid(hex!(
+                                        "63 62 6f 72 00 11 00 10 80 00 00 aa 00 38 9b 71"
+                                    )),
                                     label: Some("c2pa.hash.data",),
                                     requestable: true,
                                     id: None,
                                     hash: None,
                                     private: None,
                                     original: &jumbf[32319..32359],
+                                    offset: 32319,
                                 },
                                 child_boxes: vec![ChildBox::DataBox(DataBox {
                                     tbox: BoxType(*b"cbor"),
                                     data: &jumbf[32367..32482],
                                     original: &jumbf[32359..32482],
+                                    offset: 32359,
                                 },),],
                                 original: &jumbf[32311..32482],
+                                offset: 32311,
                             },),
                         ],
                         original: &jumbf[129..32482],
+                        offset: 129,
                     },),
                     ChildBox::SuperBox(SuperBox {
                         desc: DescriptionBox {
-                            uuid: &hex!("63 32 63 6c 00 11 00 10 80 00 00 aa 00 38 9b 71"),
+                            uuid: Uuid(hex!("63 32 63 6c 00 11 00 10 80 00 00 aa 00 38 9b 71")),
                             label: Some("c2pa.claim",),
                             requestable: true,
                             id: None,
                             hash: None,
                             private: None,
                             original: &jumbf[32490..32526],
+                            offset: 32490,
                         },
                         child_boxes: vec![ChildBox::DataBox(DataBox {
                             tbox: BoxType(*b"cbor"),
                             data: &jumbf[32534..33166],
                             original: &jumbf[32526..33166],
+                            offset: 32526,
                         },),],
                         original: &jumbf[32482..33166],
+                        offset: 32482,
                     },),
                     ChildBox::SuperBox(SuperBox {
                         desc: DescriptionBox {
-                            uuid: &hex!("63 32 63 73 00 11 00 10 80 00 00 aa 00 38 9b 71"),
+                            uuid: Uuid(hex!("63 32 63 73 00 11 00 10 80 00 00 aa 00 38 9b 71")),
                             label: Some("c2pa.signature",),
                             requestable: true,
                             id: None,
                             hash: None,
                             private: None,
                             original: &jumbf[33174..33214],
+                            offset: 33174,
                         },
                         child_boxes: vec![ChildBox::DataBox(DataBox {
                             tbox: BoxType(*b"cbor"),
                             data: &jumbf[33222..46948],
                             original: &jumbf[33214..46948],
+                            offset: 33214,
                         },),],
                         original: &jumbf[33166..46948],
+                        offset: 33166,
                     },),
                 ],
                 original: &jumbf[38..46948],
+                offset: 38,
             },),],
             original: &jumbf[0..46948],
+            offset: 0,
+        }
+    );
+}
+
+#[test]
+fn content_type() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    assert_eq!(
+        sbox.content_type(),
+        ContentType::Unknown(Uuid(hex!(
+            "63 32 70 61 00 11 00 10 80 00 00 aa 00 38 9b 71"
+        )))
+    );
+
+    let assertions = sbox
+        .find_by_label("contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9/c2pa.assertions")
+        .unwrap();
+
+    assert_eq!(
+        assertions
+            .find_by_label("c2pa.thumbnail.claim.jpeg")
+            .unwrap()
+            .content_type(),
+        ContentType::EmbeddedFile
+    );
+
+    assert_eq!(
+        assertions
+            .find_by_label("stds.schema-org.CreativeWork")
+            .unwrap()
+            .content_type(),
+        ContentType::Json
+    );
+
+    assert_eq!(
+        assertions
+            .find_by_label("c2pa.actions")
+            .unwrap()
+            .content_type(),
+        ContentType::Cbor
+    );
+}
+
+#[test]
+fn xml_payload() {
+    let jumbf = hex!(
+        "00000032" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "786d6c2000110010800000aa00389b71" // UUID (XML)
+            "00" // toggles
+            // ----
+            "00000011" // box size
+            "786d6c20" // box type = 'xml '
+            "efbbbf" // byte order mark
+            "3c782f3e0a0a" // payload ("<x/>\n\n")
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    assert_eq!(sbox.content_type(), ContentType::Xml);
+    assert_eq!(sbox.xml_payload().unwrap(), "<x/>\n\n");
+}
+
+#[test]
+fn error_xml_payload_not_utf8() {
+    let jumbf = hex!(
+        "0000002d" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "786d6c2000110010800000aa00389b71" // UUID (XML)
+            "00" // toggles
+            // ----
+            "0000000c" // box size
+            "786d6c20" // box type = 'xml '
+            "3c78ff3e" // payload (invalid UTF-8)
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    assert!(matches!(
+        sbox.xml_payload().unwrap_err(),
+        Error::Utf8Error(_)
+    ));
+}
+
+#[test]
+fn error_xml_payload_wrong_content_type() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+    assert_eq!(
+        sbox.xml_payload().unwrap_err(),
+        Error::InvalidContentType {
+            expected: Uuid::XML,
+            actual: sbox.desc.uuid,
         }
     );
 }
+
+#[test]
+fn error_xml_payload_missing_xml_box() {
+    let jumbf = hex!(
+        "0000002d" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "786d6c2000110010800000aa00389b71" // UUID (XML)
+            "00" // toggles
+            // ----
+            "0000000c" // box size
+            "61626364" // box type = 'abcd'
+            "3c782f3e" // payload
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    assert_eq!(
+        sbox.xml_payload().unwrap_err(),
+        Error::MissingChildBox(BoxType(*b"xml "))
+    );
+}
+
+#[test]
+fn codestream_payload() {
+    let jumbf = hex!(
+        "0000002f" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "6579d6fbdba2446bb2ac1b82feeb89d1" // UUID (codestream)
+            "00" // toggles
+            // ----
+            "0000000e" // box size
+            "6a703263" // box type = 'jp2c'
+            "ff4fff51002f" // payload
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    assert_eq!(sbox.content_type(), ContentType::Codestream);
+    assert_eq!(
+        sbox.codestream_payload().unwrap(),
+        hex!("ff4fff51002f").as_slice()
+    );
+}
+
+#[test]
+fn error_codestream_payload_wrong_content_type() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+    assert_eq!(
+        sbox.codestream_payload().unwrap_err(),
+        Error::InvalidContentType {
+            expected: Uuid::CODESTREAM,
+            actual: sbox.desc.uuid,
+        }
+    );
+}
+
+#[test]
+fn error_codestream_payload_missing_codestream_box() {
+    let jumbf = hex!(
+        "00000021" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "6579d6fbdba2446bb2ac1b82feeb89d1" // UUID (codestream)
+            "00" // toggles
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    assert_eq!(
+        sbox.codestream_payload().unwrap_err(),
+        Error::MissingChildBox(BoxType::CODESTREAM)
+    );
+}
+
+#[test]
+fn ranges_match_original() {
+    fn check(jumbf: &[u8], sbox: &SuperBox) {
+        assert_eq!(&jumbf[sbox.range()], sbox.original);
+        assert_eq!(&jumbf[sbox.desc.range()], sbox.desc.original);
+
+        if let Some(private) = &sbox.desc.private {
+            assert_eq!(&jumbf[private.range()], private.original);
+        }
+
+        for child in &sbox.child_boxes {
+            match child {
+                ChildBox::SuperBox(sbox) => check(jumbf, sbox),
+                ChildBox::DataBox(dbox) => {
+                    assert_eq!(&jumbf[dbox.range()], dbox.original);
+                }
+            }
+        }
+    }
+
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+    check(jumbf, &sbox);
+
+    let options = ParseOptions::default();
+    let (_, sbox) = SuperBox::from_source_with_options(jumbf, &options).unwrap();
+    check(jumbf, &sbox);
+}
+
+mod is_canonical {
+    use hex_literal::hex;
+
+    use crate::parser::SuperBox;
+
+    #[test]
+    fn canonical() {
+        let jumbf = include_bytes!("../fixtures/C.c2pa");
+        let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+        assert!(sbox.is_canonical());
+    }
+
+    #[test]
+    fn free_box() {
+        let jumbf = hex!(
+            "0000003d" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "00" // toggles
+                // ----
+                "0000000c" // box size
+                "61626364" // box type = 'abcd'
+                "41424344" // payload
+                // ----
+                "00000010" // box size
+                "66726565" // box type = 'free'
+                "0000000000000000" // padding
+        );
+
+        let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+        assert!(!sbox.is_canonical());
+    }
+
+    #[test]
+    fn nested_free_box() {
+        let jumbf = hex!(
+            "0000004a" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "00" // toggles
+                // ----
+                "00000029" // box size
+                "6a756d62" // box type = 'jumb'
+                    "00000019" // box size
+                    "6a756d64" // box type = 'jumd'
+                    "00000000000000000000000000000000" // UUID
+                    "00" // toggles
+                    // ----
+                    "00000008" // box size
+                    "66726565" // box type = 'free'
+        );
+
+        let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+        assert!(!sbox.is_canonical());
+    }
+
+    #[test]
+    fn length_to_end_of_input() {
+        let jumbf = hex!(
+            "0000002d" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "00" // toggles
+                // ----
+                "00000000" // box size (to end of input)
+                "61626364" // box type = 'abcd'
+                "41424344" // payload
+        );
+
+        let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+        assert!(!sbox.is_canonical());
+    }
+
+    #[test]
+    fn unnecessary_xlbox_length() {
+        let jumbf = hex!(
+            "00000035" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "00" // toggles
+                // ----
+                "00000001" // box size (XLBox)
+                "61626364" // box type = 'abcd'
+                "0000000000000014" // XLBox size
+                "41424344" // payload
+        );
+
+        let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+        assert!(!sbox.is_canonical());
+    }
+}
+
+mod eq_structure {
+    use hex_literal::hex;
+    use pretty_assertions_sorted::assert_eq;
+
+    use crate::parser::{StructuralEq, SuperBox};
+
+    const NESTED: [u8; 0x50] = hex!(
+        "00000050" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "0000002f" // box size
+            "6a756d62" // box type = 'jumb'
+                "0000001b" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "02" // toggles
+                "6100" // label ("a")
+                // ----
+                "0000000c" // box size
+                "61626364" // box type = 'abcd'
+                "41424344" // payload
+    );
+
+    #[test]
+    fn same_source() {
+        let (_, a) = SuperBox::from_slice(&NESTED).unwrap();
+        let (_, b) = SuperBox::from_slice(&NESTED).unwrap();
+
+        assert!(a.eq_structure(&b));
+        assert_eq!(StructuralEq(&a), StructuralEq(&b));
+    }
+
+    #[test]
+    fn ignores_offset_and_size_encoding() {
+        // Same structure, preceded by unrelated data and with the innermost
+        // box using an XLBox length and the last box extending to the end of
+        // the input.
+        let jumbf = hex!(
+            "0102030405" // unrelated data
+            "00000000" // box size (to end of input)
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "00" // toggles
+                // ----
+                "00000037" // box size
+                "6a756d62" // box type = 'jumb'
+                    "0000001b" // box size
+                    "6a756d64" // box type = 'jumd'
+                    "00000000000000000000000000000000" // UUID
+                    "02" // toggles
+                    "6100" // label ("a")
+                    // ----
+                    "00000001" // box size (XLBox)
+                    "61626364" // box type = 'abcd'
+                    "0000000000000014" // XLBox size
+                    "41424344" // payload
+        );
+
+        let (_, a) = SuperBox::from_slice(&NESTED).unwrap();
+        let (_, b) = SuperBox::from_slice(&jumbf[5..]).unwrap();
+
+        assert_ne!(a, b);
+        assert!(a.eq_structure(&b));
+        assert!(b.eq_structure(&a));
+    }
+
+    #[test]
+    fn payload_differs() {
+        let mut other = NESTED;
+        other[0x4f] = b'E';
+
+        let (_, a) = SuperBox::from_slice(&NESTED).unwrap();
+        let (_, b) = SuperBox::from_slice(&other).unwrap();
+
+        assert!(!a.eq_structure(&b));
+        assert_ne!(StructuralEq(&a), StructuralEq(&b));
+    }
+
+    #[test]
+    fn label_differs() {
+        let mut other = NESTED;
+        other[0x42] = b'b';
+
+        let (_, a) = SuperBox::from_slice(&NESTED).unwrap();
+        let (_, b) = SuperBox::from_slice(&other).unwrap();
+
+        assert!(!a.eq_structure(&b));
+    }
+
+    #[test]
+    fn depth_limited_child_differs() {
+        let (_, a) = SuperBox::from_slice(&NESTED).unwrap();
+        let (_, b) = SuperBox::from_slice_with_depth_limit(&NESTED, 0).unwrap();
+
+        assert!(!a.eq_structure(&b));
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn write_original() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let mut out = vec![];
+    sbox.write_original(&mut out).unwrap();
+    assert_eq!(out, jumbf);
+
+    // Writing the description box and each child box in turn reproduces the
+    // payload of the superbox.
+    let mut out = vec![];
+    sbox.desc.write_original(&mut out).unwrap();
+    for child in &sbox.child_boxes {
+        child.write_original(&mut out).unwrap();
+    }
+    assert_eq!(out, &jumbf[8..]);
+
+    let hash_data = sbox
+        .find_by_label(
+            "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9/c2pa.assertions/c2pa.hash.data",
+        )
+        .unwrap();
+
+    let ChildBox::DataBox(dbox) = &hash_data.child_boxes[0] else {
+        unreachable!();
+    };
+
+    let mut out = vec![];
+    dbox.write_original(&mut out).unwrap();
+    assert_eq!(out, &jumbf[dbox.range()]);
+}
+
+#[test]
+fn size_breakdown_c2pa() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    assert_eq!(sbox.total_size(), jumbf.len());
+    assert_eq!(sbox.payload_size(), jumbf.len() - 8);
+
+    let index = SuperBoxIndex::new(&sbox);
+    for sbox in index.iter().filter_map(|b| b.as_super_box()) {
+        let sizes = sbox.size_breakdown();
+        assert_eq!(sizes.total(), sbox.total_size());
+        assert_eq!(sizes.children.len(), sbox.child_boxes.len());
+    }
+
+    let manifest = sbox.child_boxes[0].as_super_box().unwrap();
+    let sizes = sbox.size_breakdown();
+    assert_eq!(sizes.children[0].label, manifest.desc.label);
+    assert_eq!(sizes.children[0].offset, manifest.offset);
+    assert_eq!(sizes.children[0].size, manifest.total_size());
+}
+
+#[test]
+fn size_breakdown_large_header() {
+    let jumbf = hex!(
+        "00000001" // box size (see large size)
+        "6a756d62" // box type = 'jumb'
+        "0000000000000049" // large box size
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "00000001" // box size (see large size)
+            "6a736f6e" // box type = 'json'
+            "0000000000000014" // large box size
+            "7b7d200a" // payload
+            // ----
+            "0000000c" // box size
+            "6a736f6e" // box type = 'json'
+            "7b7d200a" // payload
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    assert_eq!(sbox.total_size(), 73);
+    assert_eq!(sbox.payload_size(), 57);
+
+    let sizes = sbox.size_breakdown();
+    assert_eq!(sizes.header, 16);
+    assert_eq!(sizes.description, 25);
+    assert_eq!(sizes.children[0].header, 16);
+    assert_eq!(sizes.children[0].size, 20);
+    assert_eq!(sizes.children[1].header, 8);
+    assert_eq!(sizes.children[1].size, 12);
+    assert_eq!(sizes.total(), 73);
+    assert_eq!(sizes.overhead(), 16 + 25 + 16 + 8);
+}
+
+#[test]
+fn children_by_type() {
+    let jumbf = hex!(
+        "0000005d" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "00000009" // box size
+            "63626f72" // box type = 'cbor'
+            "01" // payload
+            // ----
+            "0000002a" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "00" // toggles
+                // ----
+                "00000009" // box size
+                "63626f72" // box type = 'cbor'
+                "02" // payload
+            // ----
+            "00000009" // box size
+            "63626f72" // box type = 'cbor'
+            "03" // payload
+    );
+
+    let (rem, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    assert!(rem.is_empty());
+
+    let cbor: Vec<&DataBox> = sbox.children_by_type(BoxType::CBOR).collect();
+    assert_eq!(cbor.len(), 2);
+    assert_eq!(cbor[0].data, [1]);
+    assert_eq!(cbor[0].offset, 33);
+    assert_eq!(cbor[1].data, [3]);
+    assert_eq!(cbor[1].offset, 84);
+
+    assert_eq!(sbox.children_by_type(BoxType::JSON).count(), 0);
+    assert_eq!(sbox.children_by_type(BoxType::SUPER_BOX).count(), 0);
+}
+
+#[test]
+fn typed_child_accessors() {
+    let jumbf = hex!(
+        "00000057" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "00000009" // box size
+            "61626364" // box type = 'abcd'
+            "00" // payload
+            // ----
+            "00000009" // box size
+            "63626f72" // box type = 'cbor'
+            "01" // payload
+            // ----
+            "00000009" // box size
+            "6a736f6e" // box type = 'json'
+            "02" // payload
+            // ----
+            "00000009" // box size
+            "63626f72" // box type = 'cbor'
+            "03" // payload
+            // ----
+            "00000009" // box size
+            "6a736f6e" // box type = 'json'
+            "04" // payload
+            // ----
+            "00000009" // box size
+            "75756964" // box type = 'uuid'
+            "05" // payload
+    );
+
+    let (rem, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    assert!(rem.is_empty());
+
+    assert_eq!(sbox.data_box().unwrap().data, [0]);
+    assert_eq!(sbox.cbor_box().unwrap().data, [1]);
+    assert_eq!(sbox.json_box().unwrap().data, [2]);
+    assert_eq!(sbox.uuid_box().unwrap().data, [5]);
+}
+
+#[test]
+fn typed_child_accessors_c2pa() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    assert!(sbox.json_box().is_none());
+    assert!(sbox.cbor_box().is_none());
+    assert!(sbox.uuid_box().is_none());
+
+    let signature = sbox
+        .find_by_label("contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9/c2pa.signature")
+        .unwrap();
+
+    assert_eq!(signature.cbor_box(), signature.data_box());
+    assert!(signature.cbor_box().is_some());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn extract() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let label =
+        "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9/c2pa.assertions/c2pa.hash.data";
+
+    let mut out = vec![];
+    sbox.extract(label, &mut out).unwrap();
+    assert_eq!(
+        out,
+        sbox.find_by_label(label).unwrap().data_box().unwrap().data
+    );
+
+    let mut out = vec![];
+    let err = sbox.extract("no.such.label", &mut out).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    assert!(out.is_empty());
+
+    // The manifest superbox contains only superboxes.
+    let err = sbox
+        .extract(
+            "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9",
+            &mut out,
+        )
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
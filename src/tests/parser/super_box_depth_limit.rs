@@ -15,8 +15,10 @@ use hex_literal::hex;
 use pretty_assertions_sorted::assert_eq;
 
 use crate::{
-    parser::{ChildBox, DataBox, DescriptionBox, SuperBox},
-    BoxType,
+    parser::{
+        ChildBox, DataBox, DescriptionBox, Error, ParseLimit, ParseOptions, ParseProgress, SuperBox,
+    },
+    BoxType, Uuid,
 };
 
 const JUMBF: [u8; 615] = hex!(
@@ -108,20 +110,23 @@ fn depth_limit_0() {
         sbox,
         SuperBox {
             desc: DescriptionBox {
-                uuid: &[99, 50, 112, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,],
+                uuid: Uuid([99, 50, 112, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,]),
                 label: Some("c2pa"),
                 requestable: true,
                 id: None,
                 hash: None,
                 private: None,
                 original: &JUMBF[8..38],
+                offset: 8,
             },
             child_boxes: vec!(ChildBox::DataBox(DataBox {
                 tbox: BoxType(*b"jumb"),
                 original: &JUMBF[38..615],
+                offset: 38,
                 data: &JUMBF[46..615],
             })),
             original: &JUMBF,
+            offset: 0,
         }
     );
 
@@ -137,6 +142,7 @@ fn depth_limit_0() {
         &DataBox {
             tbox: BoxType(*b"jumb"),
             original: &JUMBF[38..615],
+            offset: 38,
             data: &JUMBF[46..615],
         }
     );
@@ -147,36 +153,41 @@ fn depth_limit_0() {
         nested_box,
         SuperBox {
             desc: DescriptionBox {
-                uuid: &[99, 50, 109, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,],
+                uuid: Uuid([99, 50, 109, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,]),
                 label: Some("cb.adobe_1"),
                 requestable: true,
                 id: None,
                 hash: None,
                 private: None,
                 original: &JUMBF[46..82],
+                offset: 46,
             },
             child_boxes: vec!(
                 ChildBox::SuperBox(SuperBox {
                     desc: DescriptionBox {
-                        uuid: &[99, 50, 97, 115, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,],
+                        uuid: Uuid([
+                            99, 50, 97, 115, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,
+                        ]),
                         label: Some("c2pa.assertions",),
                         requestable: true,
                         id: None,
                         hash: None,
                         private: None,
                         original: &JUMBF[90..131],
+                        offset: 90,
                     },
                     child_boxes: vec![ChildBox::SuperBox(SuperBox {
                         desc: DescriptionBox {
-                            uuid: &[
+                            uuid: Uuid([
                                 106, 115, 111, 110, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,
-                            ],
+                            ]),
                             label: Some("c2pa.location.broad",),
                             requestable: true,
                             id: None,
                             hash: None,
                             private: None,
                             original: &JUMBF[139..184],
+                            offset: 139,
                         },
                         child_boxes: vec![ChildBox::DataBox(DataBox {
                             tbox: BoxType(*b"json"),
@@ -186,20 +197,26 @@ fn depth_limit_0() {
                                 74, 34, 125,
                             ],
                             original: &JUMBF[184..225],
+                            offset: 184,
                         },),],
                         original: &JUMBF[131..225],
+                        offset: 131,
                     },),],
                     original: &JUMBF[82..225],
+                    offset: 82,
                 },),
                 ChildBox::SuperBox(SuperBox {
                     desc: DescriptionBox {
-                        uuid: &[99, 50, 99, 108, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,],
+                        uuid: Uuid([
+                            99, 50, 99, 108, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,
+                        ]),
                         label: Some("c2pa.claim",),
                         requestable: true,
                         id: None,
                         hash: None,
                         private: None,
                         original: &JUMBF[233..269],
+                        offset: 233,
                     },
                     child_boxes: vec![ChildBox::DataBox(DataBox {
                         tbox: BoxType(*b"json"),
@@ -220,18 +237,23 @@ fn depth_limit_0() {
                             32, 32, 32, 32, 32, 32, 32, 125,
                         ],
                         original: &JUMBF[269..496],
+                        offset: 269,
                     },),],
                     original: &JUMBF[225..496],
+                    offset: 225,
                 },),
                 ChildBox::SuperBox(SuperBox {
                     desc: DescriptionBox {
-                        uuid: &[99, 50, 99, 115, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,],
+                        uuid: Uuid([
+                            99, 50, 99, 115, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,
+                        ]),
                         label: Some("c2pa.signature",),
                         requestable: true,
                         id: None,
                         hash: None,
                         private: None,
                         original: &JUMBF[504..544],
+                        offset: 504,
                     },
                     child_boxes: vec![ChildBox::DataBox(DataBox {
                         tbox: BoxType(*b"uuid"),
@@ -243,11 +265,14 @@ fn depth_limit_0() {
                             46,
                         ],
                         original: &JUMBF[544..615],
+                        offset: 544,
                     },),],
                     original: &JUMBF[496..615],
+                    offset: 496,
                 },),
             ),
             original: &JUMBF[38..615],
+            offset: 38,
         }
     );
 }
@@ -261,44 +286,51 @@ fn depth_limit_1() {
         sbox,
         SuperBox {
             desc: DescriptionBox {
-                uuid: &[99, 50, 112, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,],
+                uuid: Uuid([99, 50, 112, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,]),
                 label: Some("c2pa"),
                 requestable: true,
                 id: None,
                 hash: None,
                 private: None,
                 original: &JUMBF[8..38],
+                offset: 8,
             },
             child_boxes: vec!(ChildBox::SuperBox(SuperBox {
                 desc: DescriptionBox {
-                    uuid: &[99, 50, 109, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,],
+                    uuid: Uuid([99, 50, 109, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,]),
                     label: Some("cb.adobe_1"),
                     requestable: true,
                     id: None,
                     hash: None,
                     private: None,
                     original: &JUMBF[46..82],
+                    offset: 46,
                 },
                 child_boxes: vec!(
                     ChildBox::DataBox(DataBox {
                         tbox: BoxType(*b"jumb"),
                         original: &JUMBF[82..225],
+                        offset: 82,
                         data: &JUMBF[90..225],
                     }),
                     ChildBox::DataBox(DataBox {
                         tbox: BoxType(*b"jumb"),
                         original: &JUMBF[225..496],
+                        offset: 225,
                         data: &JUMBF[233..496],
                     }),
                     ChildBox::DataBox(DataBox {
                         tbox: BoxType(*b"jumb"),
                         original: &JUMBF[496..615],
+                        offset: 496,
                         data: &JUMBF[504..615],
                     }),
                 ),
                 original: &JUMBF[38..615],
+                offset: 38,
             })),
             original: &JUMBF,
+            offset: 0,
         }
     );
 
@@ -316,55 +348,61 @@ fn depth_limit_2() {
         sbox,
         SuperBox {
             desc: DescriptionBox {
-                uuid: &[99, 50, 112, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,],
+                uuid: Uuid([99, 50, 112, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,]),
                 label: Some("c2pa"),
                 requestable: true,
                 id: None,
                 hash: None,
                 private: None,
                 original: &JUMBF[8..38],
+                offset: 8,
             },
             child_boxes: vec!(ChildBox::SuperBox(SuperBox {
                 desc: DescriptionBox {
-                    uuid: &[99, 50, 109, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,],
+                    uuid: Uuid([99, 50, 109, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,]),
                     label: Some("cb.adobe_1"),
                     requestable: true,
                     id: None,
                     hash: None,
                     private: None,
                     original: &JUMBF[46..82],
+                    offset: 46,
                 },
                 child_boxes: vec!(
                     ChildBox::SuperBox(SuperBox {
                         desc: DescriptionBox {
-                            uuid: &[
+                            uuid: Uuid([
                                 99, 50, 97, 115, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,
-                            ],
+                            ]),
                             label: Some("c2pa.assertions",),
                             requestable: true,
                             id: None,
                             hash: None,
                             private: None,
                             original: &JUMBF[90..131],
+                            offset: 90,
                         },
                         child_boxes: vec![ChildBox::DataBox(DataBox {
                             tbox: BoxType(*b"jumb"),
                             data: &JUMBF[139..225],
                             original: &JUMBF[131..225],
+                            offset: 131,
                         },),],
                         original: &JUMBF[82..225],
+                        offset: 82,
                     },),
                     ChildBox::SuperBox(SuperBox {
                         desc: DescriptionBox {
-                            uuid: &[
+                            uuid: Uuid([
                                 99, 50, 99, 108, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,
-                            ],
+                            ]),
                             label: Some("c2pa.claim",),
                             requestable: true,
                             id: None,
                             hash: None,
                             private: None,
                             original: &JUMBF[233..269],
+                            offset: 233,
                         },
                         child_boxes: vec![ChildBox::DataBox(DataBox {
                             tbox: BoxType(*b"json"),
@@ -386,20 +424,23 @@ fn depth_limit_2() {
                                 32, 32, 32, 125,
                             ],
                             original: &JUMBF[269..496],
+                            offset: 269,
                         },),],
                         original: &JUMBF[225..496],
+                        offset: 225,
                     },),
                     ChildBox::SuperBox(SuperBox {
                         desc: DescriptionBox {
-                            uuid: &[
+                            uuid: Uuid([
                                 99, 50, 99, 115, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,
-                            ],
+                            ]),
                             label: Some("c2pa.signature",),
                             requestable: true,
                             id: None,
                             hash: None,
                             private: None,
                             original: &JUMBF[504..544],
+                            offset: 504,
                         },
                         child_boxes: vec![ChildBox::DataBox(DataBox {
                             tbox: BoxType(*b"uuid"),
@@ -411,13 +452,17 @@ fn depth_limit_2() {
                                 116, 97, 46, 46, 46,
                             ],
                             original: &JUMBF[544..615],
+                            offset: 544,
                         },),],
                         original: &JUMBF[496..615],
+                        offset: 496,
                     },),
                 ),
                 original: &JUMBF[38..615],
+                offset: 38,
             })),
             original: &JUMBF,
+            offset: 0,
         }
     );
 
@@ -425,13 +470,14 @@ fn depth_limit_2() {
         sbox.find_by_label("cb.adobe_1/c2pa.signature"),
         Some(&SuperBox {
             desc: DescriptionBox {
-                uuid: &[99, 50, 99, 115, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,],
+                uuid: Uuid([99, 50, 99, 115, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,]),
                 label: Some("c2pa.signature",),
                 requestable: true,
                 id: None,
                 hash: None,
                 private: None,
                 original: &JUMBF[504..544],
+                offset: 504,
             },
             child_boxes: vec![ChildBox::DataBox(DataBox {
                 tbox: BoxType(*b"uuid"),
@@ -442,8 +488,10 @@ fn depth_limit_2() {
                     117, 114, 101, 32, 100, 97, 116, 97, 46, 46, 46,
                 ],
                 original: &JUMBF[544..615],
+                offset: 544,
             },),],
             original: &JUMBF[496..615],
+            offset: 496,
         })
     );
 
@@ -463,6 +511,7 @@ fn depth_limit_2() {
                 101, 32, 100, 97, 116, 97, 46, 46, 46,
             ],
             original: &JUMBF[544..615],
+            offset: 544,
         })
     );
 
@@ -478,49 +527,53 @@ fn depth_limit_3() {
         sbox,
         SuperBox {
             desc: DescriptionBox {
-                uuid: &[99, 50, 112, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,],
+                uuid: Uuid([99, 50, 112, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,]),
                 label: Some("c2pa"),
                 requestable: true,
                 id: None,
                 hash: None,
                 private: None,
                 original: &JUMBF[8..38],
+                offset: 8,
             },
             child_boxes: vec!(ChildBox::SuperBox(SuperBox {
                 desc: DescriptionBox {
-                    uuid: &[99, 50, 109, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,],
+                    uuid: Uuid([99, 50, 109, 97, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,]),
                     label: Some("cb.adobe_1"),
                     requestable: true,
                     id: None,
                     hash: None,
                     private: None,
                     original: &JUMBF[46..82],
+                    offset: 46,
                 },
                 child_boxes: vec!(
                     ChildBox::SuperBox(SuperBox {
                         desc: DescriptionBox {
-                            uuid: &[
+                            uuid: Uuid([
                                 99, 50, 97, 115, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,
-                            ],
+                            ]),
                             label: Some("c2pa.assertions",),
                             requestable: true,
                             id: None,
                             hash: None,
                             private: None,
                             original: &JUMBF[90..131],
+                            offset: 90,
                         },
                         child_boxes: vec![ChildBox::SuperBox(SuperBox {
                             desc: DescriptionBox {
-                                uuid: &[
+                                uuid: Uuid([
                                     106, 115, 111, 110, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155,
                                     113,
-                                ],
+                                ]),
                                 label: Some("c2pa.location.broad",),
                                 requestable: true,
                                 id: None,
                                 hash: None,
                                 private: None,
                                 original: &JUMBF[139..184],
+                                offset: 139,
                             },
                             child_boxes: vec![ChildBox::DataBox(DataBox {
                                 tbox: BoxType(*b"json"),
@@ -530,22 +583,26 @@ fn depth_limit_3() {
                                     32, 78, 74, 34, 125,
                                 ],
                                 original: &JUMBF[184..225],
+                                offset: 184,
                             },),],
                             original: &JUMBF[131..225],
+                            offset: 131,
                         },),],
                         original: &JUMBF[82..225],
+                        offset: 82,
                     },),
                     ChildBox::SuperBox(SuperBox {
                         desc: DescriptionBox {
-                            uuid: &[
+                            uuid: Uuid([
                                 99, 50, 99, 108, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,
-                            ],
+                            ]),
                             label: Some("c2pa.claim",),
                             requestable: true,
                             id: None,
                             hash: None,
                             private: None,
                             original: &JUMBF[233..269],
+                            offset: 233,
                         },
                         child_boxes: vec![ChildBox::DataBox(DataBox {
                             tbox: BoxType(*b"json"),
@@ -567,20 +624,23 @@ fn depth_limit_3() {
                                 32, 32, 32, 125,
                             ],
                             original: &JUMBF[269..496],
+                            offset: 269,
                         },),],
                         original: &JUMBF[225..496],
+                        offset: 225,
                     },),
                     ChildBox::SuperBox(SuperBox {
                         desc: DescriptionBox {
-                            uuid: &[
+                            uuid: Uuid([
                                 99, 50, 99, 115, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,
-                            ],
+                            ]),
                             label: Some("c2pa.signature",),
                             requestable: true,
                             id: None,
                             hash: None,
                             private: None,
                             original: &JUMBF[504..544],
+                            offset: 504,
                         },
                         child_boxes: vec![ChildBox::DataBox(DataBox {
                             tbox: BoxType(*b"uuid"),
@@ -592,13 +652,17 @@ fn depth_limit_3() {
                                 116, 97, 46, 46, 46,
                             ],
                             original: &JUMBF[544..615],
+                            offset: 544,
                         },),],
                         original: &JUMBF[496..615],
+                        offset: 496,
                     },),
                 ),
                 original: &JUMBF[38..615],
+                offset: 38,
             })),
             original: &JUMBF,
+            offset: 0,
         }
     );
 
@@ -606,13 +670,14 @@ fn depth_limit_3() {
         sbox.find_by_label("cb.adobe_1/c2pa.signature"),
         Some(&SuperBox {
             desc: DescriptionBox {
-                uuid: &[99, 50, 99, 115, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,],
+                uuid: Uuid([99, 50, 99, 115, 0, 17, 0, 16, 128, 0, 0, 170, 0, 56, 155, 113,]),
                 label: Some("c2pa.signature",),
                 requestable: true,
                 id: None,
                 hash: None,
                 private: None,
                 original: &JUMBF[504..544],
+                offset: 504,
             },
             child_boxes: vec![ChildBox::DataBox(DataBox {
                 tbox: BoxType(*b"uuid"),
@@ -623,8 +688,10 @@ fn depth_limit_3() {
                     117, 114, 101, 32, 100, 97, 116, 97, 46, 46, 46,
                 ],
                 original: &JUMBF[544..615],
+                offset: 544,
             },),],
             original: &JUMBF[496..615],
+            offset: 496,
         })
     );
 
@@ -644,8 +711,182 @@ fn depth_limit_3() {
                 101, 32, 100, 97, 116, 97, 46, 46, 46,
             ],
             original: &JUMBF[544..615],
+            offset: 544,
         })
     );
 
     assert_eq!(sbox.data_box(), None);
 }
+
+// A chain of `depth` nested superboxes, each with an empty description box.
+// Written by hand so that this test does not depend on the builder.
+pub(crate) fn pathologically_deep(depth: usize) -> Vec<u8> {
+    const LEVEL_LEN: usize = 8 + 25;
+
+    let mut jumbf = Vec::with_capacity(depth * LEVEL_LEN);
+    for level in 0..depth {
+        let size = ((depth - level) * LEVEL_LEN) as u32;
+        jumbf.extend_from_slice(&size.to_be_bytes());
+        jumbf.extend_from_slice(b"jumb");
+        jumbf.extend_from_slice(&hex!(
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+        ));
+    }
+
+    jumbf
+}
+
+// Verify that `sbox` is the outermost of `depth` nested superboxes as
+// generated by `pathologically_deep()`, then drop it. Dropping must not
+// overflow the stack either.
+fn assert_pathological_depth(sbox: SuperBox, depth: usize) {
+    let mut actual = 1;
+    let mut innermost = &sbox;
+    while let Some(ChildBox::SuperBox(child)) = innermost.child_boxes.first() {
+        actual += 1;
+        innermost = child;
+    }
+
+    assert_eq!(actual, depth);
+    assert!(innermost.child_boxes.is_empty());
+    assert_eq!(innermost.offset, (depth - 1) * 33);
+
+    drop(sbox);
+}
+
+#[test]
+fn pathological_depth() {
+    const DEPTH: usize = 100_000;
+
+    let jumbf = pathologically_deep(DEPTH);
+
+    let (rem, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    assert!(rem.is_empty());
+    assert_pathological_depth(sbox, DEPTH);
+
+    let (rem, sbox) = SuperBox::from_slice_with_depth_limit(&jumbf, usize::MAX).unwrap();
+    assert!(rem.is_empty());
+    assert_pathological_depth(sbox, DEPTH);
+
+    let (_, dbox) = DataBox::from_slice(&jumbf).unwrap();
+
+    let (rem, sbox) = SuperBox::from_data_box(&dbox).unwrap();
+    assert!(rem.is_empty());
+    assert_pathological_depth(sbox, DEPTH);
+
+    let (rem, sbox) = SuperBox::from_data_box_with_depth_limit(&dbox, usize::MAX).unwrap();
+    assert!(rem.is_empty());
+    assert_pathological_depth(sbox, DEPTH);
+
+    let options = ParseOptions {
+        max_depth: usize::MAX,
+        ..Default::default()
+    };
+
+    let (rem, sbox) = SuperBox::from_source_with_options(&jumbf, &options).unwrap();
+    assert!(rem.is_empty());
+    assert_pathological_depth(sbox, DEPTH);
+
+    let mut boxes_parsed = 0;
+    let mut observer = |progress: &ParseProgress| boxes_parsed = progress.boxes_parsed;
+    let (rem, sbox) = SuperBox::from_source_with_observer(&jumbf, &options, &mut observer).unwrap();
+    assert!(rem.is_empty());
+    assert_pathological_depth(sbox, DEPTH);
+    assert_eq!(boxes_parsed, 2 * DEPTH);
+
+    let err = SuperBox::from_source_with_report(&jumbf).unwrap_err();
+    assert_eq!(
+        err.root_cause(),
+        &Error::LimitExceeded {
+            limit: ParseLimit::Depth,
+            max: ParseOptions::DEFAULT_MAX_DEPTH
+        }
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn pathological_depth_from_reader() {
+    const DEPTH: usize = 100_000;
+
+    let jumbf = pathologically_deep(DEPTH);
+    let mut buf = vec![];
+
+    let sbox = SuperBox::from_reader(&mut jumbf.as_slice(), &mut buf).unwrap();
+    assert_pathological_depth(sbox, DEPTH);
+}
+
+#[test]
+fn pathological_depth_with_limit() {
+    let jumbf = pathologically_deep(100_000);
+    let (_, sbox) = SuperBox::from_slice_with_depth_limit(&jumbf, 2).unwrap();
+
+    let ChildBox::SuperBox(child) = &sbox.child_boxes[0] else {
+        panic!("expected superbox");
+    };
+    let ChildBox::SuperBox(grandchild) = &child.child_boxes[0] else {
+        panic!("expected superbox");
+    };
+    let ChildBox::DataBox(dbox) = &grandchild.child_boxes[0] else {
+        panic!("expected data box");
+    };
+
+    assert_eq!(dbox.tbox, BoxType(*b"jumb"));
+    assert_eq!(dbox.offset, 99);
+}
+
+#[test]
+fn error_at_pathological_depth() {
+    const DEPTH: usize = 10_000;
+
+    // Corrupt the type of the innermost description box.
+    let mut jumbf = pathologically_deep(DEPTH);
+    let desc_type = (DEPTH - 1) * 33 + 12;
+    jumbf[desc_type..desc_type + 4].copy_from_slice(b"jumc");
+
+    let options = ParseOptions {
+        max_depth: usize::MAX,
+        ..Default::default()
+    };
+    let mut observer = |_: &ParseProgress| {};
+
+    let errors = [
+        SuperBox::from_slice(&jumbf).unwrap_err(),
+        SuperBox::from_source_with_options(&jumbf, &options).unwrap_err(),
+        SuperBox::from_source_with_observer(&jumbf, &options, &mut observer).unwrap_err(),
+    ];
+
+    for err in errors {
+        let Error::InSuperBox { offset, path, .. } = &err else {
+            panic!("expected InSuperBox, got {err:?}");
+        };
+
+        assert_eq!(*offset, (DEPTH - 1) * 33);
+        assert_eq!(path.len(), DEPTH - 1);
+        assert!(path.iter().all(Option::is_none));
+        assert_eq!(
+            err.root_cause(),
+            &Error::InvalidDescriptionBoxType(BoxType(*b"jumc"))
+        );
+    }
+}
+
+#[test]
+fn queries_at_pathological_depth() {
+    const DEPTH: usize = 100_000;
+
+    let jumbf = pathologically_deep(DEPTH);
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+
+    assert_eq!(sbox.find_by_id(1), None);
+    assert!(sbox.is_canonical());
+
+    let (_, copy) = SuperBox::from_slice(&jumbf).unwrap();
+    assert!(sbox.eq_structure(&copy));
+    assert_pathological_depth(copy, DEPTH);
+
+    assert_pathological_depth(sbox, DEPTH);
+}
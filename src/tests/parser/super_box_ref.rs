@@ -0,0 +1,128 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{
+    parser::{SuperBox, SuperBoxRef},
+    Uuid,
+};
+
+const MANIFEST: &str = "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9";
+
+#[test]
+fn c2pa() {
+    let jumbf = include_bytes!("../fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let root: &dyn SuperBoxRef = &sbox;
+    assert_eq!(root.label(), Some("c2pa"));
+    assert_eq!(root.uuid(), sbox.desc.uuid);
+    assert!(root.is_requestable());
+    assert_eq!(root.id(), None);
+    assert_eq!(root.payload(), None);
+    assert_eq!(root.original(), jumbf);
+    assert_eq!(root.offset(), 0);
+    assert_eq!(root.child_count(), 1);
+    assert!(root.child(1).is_none());
+
+    let manifest = root.child(0).unwrap();
+    assert_eq!(manifest.label(), Some(MANIFEST));
+
+    let signature = root
+        .find_by_label(&format!("{MANIFEST}/c2pa.signature"))
+        .unwrap();
+    let expected = SuperBox::find_by_label(&sbox, &format!("{MANIFEST}/c2pa.signature")).unwrap();
+
+    assert_eq!(signature.offset(), expected.offset);
+    assert_eq!(signature.original(), expected.original);
+    assert_eq!(
+        signature.payload(),
+        expected.data_box().map(|dbox| dbox.data)
+    );
+
+    assert!(root.find_by_label("no.such.label").is_none());
+}
+
+#[test]
+fn mixed_children() {
+    let jumbf = hex!(
+        "00000056" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "0000000a" // box size
+            "61626364" // box type = 'abcd'
+            "6869" // payload ("hi")
+            // ----
+            "0000002b" // box size
+            "6a756d62" // box type = 'jumb'
+                "0000001b" // box size
+                "6a756d64" // box type = 'jumd'
+                "6a736f6e00110010800000aa00389b71" // UUID (JSON)
+                "02" // toggles
+                "6200" // label ("b")
+                // ----
+                "00000008" // box size
+                "6a736f6e" // box type = 'json'
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    let root: &dyn SuperBoxRef = &sbox;
+
+    assert_eq!(root.payload(), Some(&b"hi"[..]));
+    assert_eq!(root.child_count(), 1);
+
+    let child = root.child(0).unwrap();
+    assert_eq!(child.label(), Some("b"));
+    assert_eq!(child.uuid(), Uuid::JSON);
+    assert!(!child.is_requestable());
+    assert_eq!(child.payload(), Some(&[][..]));
+    assert_eq!(child.offset(), 43);
+    assert_eq!(child.child_count(), 0);
+}
+
+#[test]
+fn payload_after_super_box() {
+    let jumbf = hex!(
+        "0000004c" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "00000021" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000019" // box size
+                "6a756d64" // box type = 'jumd'
+                "00000000000000000000000000000000" // UUID
+                "00" // toggles
+            // ----
+            "0000000a" // box size
+            "61626364" // box type = 'abcd'
+            "6869" // payload ("hi")
+    );
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    let root: &dyn SuperBoxRef = &sbox;
+
+    // Only the first child box is considered, as with `SuperBox::data_box()`.
+    assert!(sbox.data_box().is_none());
+    assert_eq!(root.payload(), None);
+    assert_eq!(root.child_count(), 1);
+}
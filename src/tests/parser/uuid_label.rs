@@ -0,0 +1,77 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{parser::UuidLabel, Uuid};
+
+const UUID: Uuid = Uuid([
+    0x02, 0x1b, 0x55, 0x5e, 0x5e, 0x02, 0x40, 0x74, 0xb4, 0x44, 0x43, 0xd7, 0x91, 0x9d, 0x89, 0xb9,
+]);
+
+#[test]
+fn parse() {
+    let label = "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9";
+
+    assert_eq!(
+        UuidLabel::parse(label).unwrap(),
+        UuidLabel {
+            prefix: "contentauth",
+            urn: "urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9",
+            uuid: UUID,
+        }
+    );
+
+    assert_eq!(UuidLabel::parse(label).unwrap().to_string(), label);
+}
+
+#[test]
+fn parse_prefix_with_colons() {
+    let label =
+        UuidLabel::parse("adobe:c2pa:urn:uuid:021B555E-5E02-4074-B444-43D7919D89B9").unwrap();
+
+    assert_eq!(label.prefix, "adobe:c2pa");
+    assert_eq!(label.uuid, UUID);
+}
+
+#[test]
+fn parse_urn_only() {
+    let label = "urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9";
+
+    assert_eq!(
+        UuidLabel::parse(label).unwrap(),
+        UuidLabel {
+            prefix: "",
+            urn: label,
+            uuid: UUID,
+        }
+    );
+
+    assert_eq!(UuidLabel::parse(label).unwrap().to_string(), label);
+}
+
+#[test]
+fn parse_invalid() {
+    for label in [
+        "",
+        "c2pa.signature",
+        "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b",
+        "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89bz",
+        "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9x",
+        "contentauthurn:uuid:021b555e-5e02-4074-b444-43d7919d89b9",
+        "contentauth:urn:isbn:021b555e-5e02-4074-b444-43d7919d89b9",
+        "é:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b",
+    ] {
+        assert_eq!(UuidLabel::parse(label), None, "{label:?}");
+    }
+}
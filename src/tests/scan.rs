@@ -0,0 +1,149 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{
+    parser::{ChildBox, Error, SuperBox},
+    scan::{scan, BoxSummary},
+    BoxType,
+};
+
+#[test]
+fn matches_parsed_tree() {
+    let jumbf = include_bytes!("fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let mut expected = vec![];
+    summarize(&sbox, 0, &mut expected);
+
+    assert_eq!(scan(jumbf).unwrap(), expected);
+}
+
+fn summarize<'a>(sbox: &SuperBox<'a>, depth: usize, boxes: &mut Vec<BoxSummary<'a>>) {
+    boxes.push(BoxSummary {
+        tbox: BoxType(*b"jumb"),
+        offset: sbox.offset,
+        size: sbox.original.len(),
+        depth,
+        label: sbox.desc.label,
+    });
+
+    for child in &sbox.child_boxes {
+        match child {
+            ChildBox::SuperBox(child) => summarize(child, depth + 1, boxes),
+            ChildBox::DataBox(child) => boxes.push(BoxSummary {
+                tbox: child.tbox,
+                offset: child.offset,
+                size: child.original.len(),
+                depth: depth + 1,
+                label: None,
+            }),
+        }
+    }
+}
+
+#[test]
+fn multiple_top_level_boxes() {
+    let jumbf = hex!(
+        "00000008" // box size
+        "66726565" // box type = 'free'
+        // ----
+        "00000021" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+    );
+
+    assert_eq!(
+        scan(&jumbf).unwrap(),
+        [
+            BoxSummary {
+                tbox: BoxType(*b"free"),
+                offset: 0,
+                size: 8,
+                depth: 0,
+                label: None,
+            },
+            BoxSummary {
+                tbox: BoxType(*b"jumb"),
+                offset: 8,
+                size: 33,
+                depth: 0,
+                label: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn empty() {
+    assert_eq!(scan(&[]).unwrap(), []);
+}
+
+#[test]
+fn error_matches_parser() {
+    let jumbf = hex!(
+        "00000039" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ----
+            "00000018" // box size
+            "6a756d62" // box type = 'jumb'
+                "00000010" // box size
+                "6a756d64" // box type = 'jumd'
+                "0000000000000000" // truncated description box
+    );
+
+    let err = scan(&jumbf).unwrap_err();
+    assert_eq!(err, SuperBox::from_slice(&jumbf).unwrap_err());
+    assert!(matches!(err, Error::InSuperBox { offset: 33, .. }));
+}
+
+#[test]
+fn error_truncated_top_level_box() {
+    let jumbf = hex!(
+        "00000010" // box size
+        "66726565" // box type = 'free'
+    );
+
+    assert_eq!(scan(&jumbf).unwrap_err(), Error::Incomplete(8));
+}
+
+#[test]
+fn pathological_depth() {
+    use crate::tests::parser::super_box_depth_limit::pathologically_deep;
+
+    const DEPTH: usize = 100_000;
+
+    let jumbf = pathologically_deep(DEPTH);
+    let boxes = scan(&jumbf).unwrap();
+
+    assert_eq!(boxes.len(), DEPTH);
+    assert_eq!(
+        boxes.last(),
+        Some(&BoxSummary {
+            tbox: BoxType(*b"jumb"),
+            offset: (DEPTH - 1) * 33,
+            size: 33,
+            depth: DEPTH - 1,
+            label: None,
+        })
+    );
+}
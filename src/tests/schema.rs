@@ -0,0 +1,242 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{
+    c2pa::{MANIFEST_STORE_TYPE, MANIFEST_TYPE, UPDATE_MANIFEST_TYPE},
+    parser::SuperBox,
+    schema::{Cardinality, ChildSelector, Schema, Violation, ViolationKind},
+    BoxType, Uuid,
+};
+
+const MANIFEST: &str = "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9";
+
+fn parse() -> SuperBox<'static> {
+    let jumbf = include_bytes!("fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+    sbox
+}
+
+fn path(labels: &[&str]) -> Vec<Option<String>> {
+    labels
+        .iter()
+        .map(|label| Some((*label).to_owned()))
+        .collect()
+}
+
+#[test]
+fn empty_schema() {
+    assert!(Schema::new().validate(&parse()).is_empty());
+}
+
+#[test]
+fn c2pa() {
+    let sbox = parse();
+
+    let claim = Schema::new().payload(BoxType::CBOR);
+    let manifest = Schema::new()
+        .child("c2pa.assertions", Cardinality::ONE, Schema::new())
+        .child("c2pa.claim", Cardinality::OPTIONAL, claim.clone())
+        .child("c2pa.claim.v2", Cardinality::OPTIONAL, claim)
+        .child(
+            "c2pa.signature",
+            Cardinality::ONE,
+            Schema::new().payload(BoxType::CBOR),
+        );
+
+    let store = Schema::new()
+        .content_type(MANIFEST_STORE_TYPE)
+        .children_of_type(MANIFEST_TYPE, Cardinality::ANY, manifest.clone())
+        .children_of_type(UPDATE_MANIFEST_TYPE, Cardinality::ANY, manifest)
+        .deny_other_children();
+
+    assert_eq!(store.validate(&sbox), vec![]);
+}
+
+#[test]
+fn content_type() {
+    let sbox = parse();
+    let violations = Schema::new().content_type(Uuid::JSON).validate(&sbox);
+
+    assert_eq!(
+        violations,
+        vec![Violation {
+            path: path(&["c2pa"]),
+            offset: 0,
+            kind: ViolationKind::ContentType {
+                expected: Uuid::JSON,
+                actual: MANIFEST_STORE_TYPE,
+            },
+        }]
+    );
+
+    assert_eq!(
+        violations[0].to_string(),
+        "c2pa (offset 0): content type should be 6a736f6e-0011-0010-8000-00aa00389b71, was 63327061-0011-0010-8000-00aa00389b71"
+    );
+}
+
+#[test]
+fn payload() {
+    let sbox = parse();
+    let manifest = sbox.find_by_label(MANIFEST).unwrap();
+
+    let schema = Schema::new().children_of_type(
+        MANIFEST_TYPE,
+        Cardinality::ONE,
+        Schema::new()
+            .child(
+                "c2pa.assertions",
+                Cardinality::ONE,
+                Schema::new().payload(BoxType::JSON),
+            )
+            .child(
+                "c2pa.signature",
+                Cardinality::ONE,
+                Schema::new().payload(BoxType::JSON),
+            ),
+    );
+
+    let violations = schema.validate(&sbox);
+
+    assert_eq!(
+        violations,
+        vec![
+            Violation {
+                path: path(&["c2pa", MANIFEST, "c2pa.assertions"]),
+                offset: manifest.find_by_label("c2pa.assertions").unwrap().offset,
+                kind: ViolationKind::MissingPayload {
+                    expected: BoxType::JSON
+                },
+            },
+            Violation {
+                path: path(&["c2pa", MANIFEST, "c2pa.signature"]),
+                offset: manifest.find_by_label("c2pa.signature").unwrap().offset,
+                kind: ViolationKind::PayloadType {
+                    expected: BoxType::JSON,
+                    actual: BoxType::CBOR,
+                },
+            },
+        ]
+    );
+
+    assert_eq!(
+        violations[1].kind.to_string(),
+        "payload box type should be 'json', was 'cbor'"
+    );
+}
+
+#[test]
+fn cardinality() {
+    let sbox = parse();
+
+    let schema = Schema::new()
+        .child("c2pa.thing", Cardinality::ONE_OR_MORE, Schema::new())
+        .children_of_type(
+            MANIFEST_TYPE,
+            Cardinality {
+                min: 2,
+                max: Some(3),
+            },
+            Schema::new(),
+        )
+        .children_of_type(MANIFEST_TYPE, Cardinality::OPTIONAL, Schema::new());
+
+    let violations = schema.validate(&sbox);
+
+    assert_eq!(
+        violations,
+        vec![
+            Violation {
+                path: path(&["c2pa"]),
+                offset: 0,
+                kind: ViolationKind::Cardinality {
+                    selector: ChildSelector::Label("c2pa.thing".to_owned()),
+                    cardinality: Cardinality::ONE_OR_MORE,
+                    found: 0,
+                },
+            },
+            Violation {
+                path: path(&["c2pa"]),
+                offset: 0,
+                kind: ViolationKind::Cardinality {
+                    selector: ChildSelector::ContentType(MANIFEST_TYPE),
+                    cardinality: Cardinality {
+                        min: 2,
+                        max: Some(3),
+                    },
+                    found: 1,
+                },
+            },
+        ]
+    );
+
+    assert_eq!(
+        violations[0].to_string(),
+        "c2pa (offset 0): expected at least 1 superboxes labeled \"c2pa.thing\", found 0"
+    );
+    assert_eq!(
+        violations[1].kind.to_string(),
+        "expected 2 to 3 superboxes of type 63326d61-0011-0010-8000-00aa00389b71, found 1"
+    );
+}
+
+#[test]
+fn unexpected_child() {
+    let sbox = parse();
+    let manifest = sbox.find_by_label(MANIFEST).unwrap();
+
+    let schema = Schema::new().children_of_type(
+        MANIFEST_TYPE,
+        Cardinality::ONE,
+        Schema::new()
+            .child("c2pa.assertions", Cardinality::ONE, Schema::new())
+            .child("c2pa.claim", Cardinality::ONE, Schema::new())
+            .deny_other_children(),
+    );
+
+    let signature = manifest.find_by_label("c2pa.signature").unwrap();
+
+    let violations = schema.validate(&sbox);
+    assert_eq!(
+        violations,
+        vec![Violation {
+            path: path(&["c2pa", MANIFEST]),
+            offset: signature.offset,
+            kind: ViolationKind::UnexpectedChild {
+                label: Some("c2pa.signature".to_owned()),
+                uuid: signature.desc.uuid,
+            },
+        }]
+    );
+
+    assert_eq!(
+        violations[0].kind.to_string(),
+        "unexpected child superbox \"c2pa.signature\" of type 63326373-0011-0010-8000-00aa00389b71"
+    );
+}
+
+#[test]
+fn cardinality_contains() {
+    assert!(Cardinality::ANY.contains(0));
+    assert!(Cardinality::ANY.contains(usize::MAX));
+    assert!(!Cardinality::ONE.contains(0));
+    assert!(Cardinality::ONE.contains(1));
+    assert!(!Cardinality::ONE.contains(2));
+    assert!(Cardinality::OPTIONAL.contains(0));
+    assert!(!Cardinality::ONE_OR_MORE.contains(0));
+
+    assert_eq!(Cardinality::ONE.to_string(), "exactly 1");
+    assert_eq!(Cardinality::OPTIONAL.to_string(), "0 to 1");
+}
@@ -0,0 +1,139 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use hex_literal::hex;
+
+use crate::{
+    parser::{ChildBox, EmbeddedFile, SuperBox},
+    testing::{assert_jumbf_round_trip, fixtures},
+    BoxType,
+};
+
+#[test]
+fn c2pa_manifest_store() {
+    let (rem, sbox) = SuperBox::from_slice(fixtures::c2pa_manifest_store()).unwrap();
+    assert!(rem.is_empty());
+    assert_eq!(sbox.desc.label, Some("c2pa"));
+}
+
+#[test]
+fn simple() {
+    let expected_jumbf = hex!(
+        "00000032" // box size
+        "6a756d62" // box type = 'jumb'
+            "00000019" // box size
+            "6a756d64" // box type = 'jumd'
+            "00000000000000000000000000000000" // UUID
+            "00" // toggles
+            // ---
+            "00000011" // box size
+            "61626364" // box type = 'abcd'
+            "736f6d652064617461" // payload ("some data")
+    );
+
+    assert_eq!(fixtures::simple(), expected_jumbf);
+}
+
+#[test]
+fn empty_payload() {
+    let jumbf = fixtures::empty_payload();
+    assert_jumbf_round_trip(&jumbf);
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    let dbox = sbox.data_box().unwrap();
+    assert_eq!(dbox.original.len(), 8);
+    assert!(dbox.data.is_empty());
+}
+
+#[test]
+fn deeply_nested() {
+    let jumbf = fixtures::deeply_nested(10);
+    assert_jumbf_round_trip(&jumbf);
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    assert_eq!(sbox.desc.label, Some("level0"));
+
+    let path: Vec<String> = (1..10).map(|level| format!("level{level}")).collect();
+    let innermost = sbox.find_by_label(&path.join("/")).unwrap();
+    assert_eq!(innermost.data_box().unwrap().data, b"deepest");
+
+    assert_eq!(fixtures::deeply_nested(0), fixtures::simple());
+}
+
+#[test]
+fn large_size() {
+    let jumbf = fixtures::large_size();
+
+    let (rem, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    assert!(rem.is_empty());
+
+    assert!(jumbf.starts_with(&hex!("00000001 6a756d62")));
+    assert_eq!(sbox.desc.label, Some("large"));
+    assert_eq!(sbox.desc.original.len(), 16 + 16 + 1 + 6);
+
+    let dbox = sbox.data_box().unwrap();
+    assert_eq!(dbox.original.len(), 16 + 5);
+    assert_eq!(dbox.data, b"large");
+}
+
+#[test]
+fn zero_length() {
+    let jumbf = fixtures::zero_length();
+
+    let (rem, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    assert!(rem.is_empty());
+
+    assert!(jumbf.starts_with(&hex!("00000000 6a756d62")));
+    assert_eq!(sbox.desc.label, Some("zero"));
+
+    let data: Vec<&[u8]> = sbox
+        .child_boxes
+        .iter()
+        .map(|child| match child {
+            ChildBox::DataBox(dbox) => dbox.data,
+            ChildBox::SuperBox(_) => panic!("unexpected superbox"),
+        })
+        .collect();
+
+    assert_eq!(data, vec![&b"first"[..], &b"to end"[..]]);
+}
+
+#[test]
+fn embedded_file() {
+    let jumbf = fixtures::embedded_file("text/plain", Some("hello.txt"), b"hello");
+    assert_jumbf_round_trip(&jumbf);
+
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    let file = EmbeddedFile::from_super_box(&sbox).unwrap();
+
+    assert_eq!(file.desc.media_type, "text/plain");
+    assert_eq!(file.desc.file_name, Some("hello.txt"));
+    assert_eq!(file.data.tbox, BoxType(*b"bidb"));
+    assert_eq!(file.data.data, b"hello");
+
+    let jumbf = fixtures::embedded_file("image/jpeg", None, b"");
+    let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    let file = EmbeddedFile::from_super_box(&sbox).unwrap();
+    assert_eq!(file.desc.file_name, None);
+}
+
+#[test]
+fn corpus() {
+    let corpus = fixtures::corpus();
+    assert_eq!(corpus.len(), 7);
+
+    for (name, jumbf) in corpus {
+        let (rem, _) = SuperBox::from_slice(&jumbf).unwrap_or_else(|e| panic!("{name}: {e}"));
+        assert!(rem.is_empty(), "{name}");
+    }
+}
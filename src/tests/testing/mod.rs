@@ -0,0 +1,82 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+mod fixtures;
+
+use crate::{
+    testing::{
+        assert_jumbf_round_trip, assert_round_trip, ChildBoxSpec, DataBoxSpec, Generator,
+        SuperBoxSpec,
+    },
+    BoxType, Uuid,
+};
+
+#[test]
+fn reproducible() {
+    let a: Vec<_> = Generator::new(7).take(10).collect();
+    let b: Vec<_> = Generator::new(7).take(10).collect();
+    assert_eq!(a, b);
+
+    let c: Vec<_> = Generator::new(8).take(10).collect();
+    assert_ne!(a, c);
+}
+
+#[test]
+fn round_trip() {
+    for spec in Generator::new(0).take(200) {
+        assert_round_trip(&spec);
+    }
+}
+
+#[test]
+fn jumbf_round_trip() {
+    let mut generator = Generator::new(1);
+    for _ in 0..200 {
+        assert_jumbf_round_trip(&generator.jumbf());
+    }
+}
+
+#[test]
+#[should_panic(expected = "Parser left 1 bytes unread")]
+fn jumbf_round_trip_trailing_data() {
+    let mut jumbf = Generator::new(2).jumbf();
+    jumbf.push(0);
+    assert_jumbf_round_trip(&jumbf);
+}
+
+#[test]
+#[should_panic(expected = "Unable to parse JUMBF")]
+fn jumbf_round_trip_not_jumbf() {
+    assert_jumbf_round_trip(b"not jumbf");
+}
+
+#[test]
+#[should_panic(expected = "JUMBF round trip failed")]
+fn round_trip_failure() {
+    let spec = SuperBoxSpec {
+        uuid: Uuid([0; 16]),
+        label: Some("a/b".to_owned()),
+        requestable: true,
+        id: None,
+        hash: None,
+        private: None,
+        unknown_toggles: 0,
+        trailing_description_data: vec![],
+        child_boxes: vec![ChildBoxSpec::DataBox(DataBoxSpec {
+            tbox: BoxType(*b"json"),
+            data: vec![],
+        })],
+    };
+
+    assert_round_trip(&spec);
+}
@@ -0,0 +1,45 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use crate::Toggles;
+
+#[test]
+fn contains() {
+    let toggles = Toggles::REQUESTABLE | Toggles::HAS_LABEL;
+
+    assert!(toggles.contains(Toggles::REQUESTABLE));
+    assert!(toggles.contains(Toggles::REQUESTABLE | Toggles::HAS_LABEL));
+    assert!(!toggles.contains(Toggles::HAS_LABEL | Toggles::HAS_ID));
+    assert!(toggles.contains(Toggles::default()));
+}
+
+#[test]
+fn unknown() {
+    assert_eq!(Toggles(0x1f).unknown(), Toggles(0));
+    assert_eq!(Toggles(0xe3).unknown(), Toggles(0xe0));
+    assert_eq!(Toggles(0xe3) & Toggles::KNOWN, Toggles(0x03));
+    assert_eq!(!Toggles::KNOWN, Toggles(0xe0));
+}
+
+#[test]
+fn impl_debug() {
+    assert_eq!(format!("{:?}", Toggles(0)), "Toggles(0x00)");
+    assert_eq!(format!("{:?}", Toggles(0x01)), "Toggles(REQUESTABLE)");
+
+    assert_eq!(
+        format!("{:?}", Toggles(0x1e)),
+        "Toggles(HAS_LABEL | HAS_ID | HAS_HASH | HAS_PRIVATE_BOX)"
+    );
+
+    assert_eq!(format!("{:?}", Toggles(0x82)), "Toggles(HAS_LABEL | 0x80)");
+}
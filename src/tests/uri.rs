@@ -0,0 +1,329 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use pretty_assertions_sorted::assert_eq;
+
+use crate::{
+    parser::{Error, SuperBox},
+    uri::{self_uri, JumbfUri},
+};
+
+const MANIFEST: &str = "contentauth:urn:uuid:021b555e-5e02-4074-b444-43d7919d89b9";
+
+#[test]
+fn parse() {
+    assert_eq!(
+        JumbfUri::parse("self#jumbf=c2pa.assertions/c2pa.hash.data").unwrap(),
+        JumbfUri {
+            resource: "self",
+            absolute: false,
+            path: "c2pa.assertions/c2pa.hash.data",
+            query: None,
+            hashed_label: None,
+        }
+    );
+
+    assert_eq!(
+        JumbfUri::parse("self#jumbf=/c2pa/c2pa.claim?x=1&hl=abc").unwrap(),
+        JumbfUri {
+            resource: "self",
+            absolute: true,
+            path: "c2pa/c2pa.claim",
+            query: Some("x=1&hl=abc"),
+            hashed_label: Some("abc"),
+        }
+    );
+}
+
+#[test]
+fn parse_other_resource() {
+    let uri = JumbfUri::parse("https://example.com/image.jpg#jumbf=/c2pa").unwrap();
+
+    assert_eq!(uri.resource, "https://example.com/image.jpg");
+    assert!(!uri.refers_to_self());
+    assert_eq!(uri.path, "c2pa");
+
+    assert!(JumbfUri::parse("#jumbf=c2pa").unwrap().refers_to_self());
+}
+
+#[test]
+fn labels() {
+    let uri = JumbfUri::parse("self#jumbf=/c2pa/my%20label/c2pa.claim").unwrap();
+
+    let labels: Vec<&str> = uri.labels().collect();
+    assert_eq!(labels, ["c2pa", "my%20label", "c2pa.claim"]);
+}
+
+#[test]
+fn query_params() {
+    let uri = JumbfUri::parse("self#jumbf=c2pa?x=1&flag&&hl=abc&x=2").unwrap();
+
+    let params: Vec<(&str, &str)> = uri.query_params().collect();
+    assert_eq!(
+        params,
+        [("x", "1"), ("flag", ""), ("hl", "abc"), ("x", "2")]
+    );
+
+    assert_eq!(uri.query_param("x"), Some("1"));
+    assert_eq!(uri.query_param("flag"), Some(""));
+    assert_eq!(uri.query_param("y"), None);
+
+    let uri = JumbfUri::parse("self#jumbf=c2pa").unwrap();
+    assert_eq!(uri.query_params().count(), 0);
+}
+
+#[test]
+fn display() {
+    for uri in [
+        "self#jumbf=c2pa.assertions/c2pa.hash.data",
+        "self#jumbf=/c2pa/c2pa.claim?hl=abc",
+        "self#jumbf=/c2pa/c2pa.claim?x=1&hl=abc",
+        "https://example.com/#jumbf=c2pa",
+    ] {
+        assert_eq!(JumbfUri::parse(uri).unwrap().to_string(), uri);
+    }
+}
+
+#[test]
+fn error_not_jumbf_uri() {
+    for uri in [
+        "https://example.com/",
+        "self#c2pa/c2pa.claim",
+        "self#jumbf=",
+        "self#jumbf=/",
+        "self#jumbf=c2pa//c2pa.claim",
+    ] {
+        assert!(
+            matches!(JumbfUri::parse(uri), Err(Error::UnresolvedReference { .. })),
+            "{uri}"
+        );
+    }
+}
+
+#[test]
+fn resolve_c2pa() {
+    let jumbf = include_bytes!("fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let hash_data = sbox
+        .find_by_label(&format!("{MANIFEST}/c2pa.assertions/c2pa.hash.data"))
+        .unwrap();
+
+    // Relative to the outermost superbox.
+    assert_eq!(
+        sbox.resolve_uri(&format!(
+            "self#jumbf={MANIFEST}/c2pa.assertions/c2pa.hash.data"
+        ))
+        .unwrap(),
+        hash_data
+    );
+
+    // Absolute.
+    assert_eq!(
+        sbox.resolve_uri(&format!(
+            "self#jumbf=/c2pa/{MANIFEST}/c2pa.assertions/c2pa.hash.data"
+        ))
+        .unwrap(),
+        hash_data
+    );
+
+    // Relative, but beginning with the label of the outermost superbox.
+    assert_eq!(
+        sbox.resolve_uri(&format!(
+            "self#jumbf=c2pa/{MANIFEST}/c2pa.assertions/c2pa.hash.data?hl=xyz"
+        ))
+        .unwrap(),
+        hash_data
+    );
+
+    // Relative to the manifest.
+    let manifest = sbox.find_by_label(MANIFEST).unwrap();
+    assert_eq!(
+        manifest
+            .resolve_uri("self#jumbf=c2pa.assertions/c2pa.hash.data")
+            .unwrap(),
+        hash_data
+    );
+
+    assert_eq!(sbox.resolve_uri("self#jumbf=/c2pa").unwrap(), &sbox);
+}
+
+#[test]
+fn resolve_percent_encoded() {
+    let jumbf = include_bytes!("fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let hash_data = sbox
+        .find_by_label(&format!("{MANIFEST}/c2pa.assertions/c2pa.hash.data"))
+        .unwrap();
+
+    assert_eq!(
+        sbox.resolve_uri("self#jumbf=/c2pa/contentauth%3Aurn%3auuid:021b555e-5e02-4074-b444-43d7919d89b9/c2pa%2Eassertions/c2pa.hash.data")
+            .unwrap(),
+        hash_data
+    );
+
+    // An encoded `/` is part of a label, not a separator.
+    assert!(sbox
+        .resolve_uri(&format!(
+            "self#jumbf=/c2pa/{MANIFEST}%2Fc2pa.assertions/c2pa.hash.data"
+        ))
+        .is_err());
+}
+
+#[test]
+fn error_malformed_percent_encoding() {
+    let jumbf = include_bytes!("fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    assert_eq!(
+        sbox.resolve_uri("self#jumbf=/c2pa/c2pa%2").unwrap_err(),
+        Error::UnresolvedReference {
+            url: "self#jumbf=/c2pa/c2pa%2".to_owned(),
+            message: "path contains a malformed percent-encoded label".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn error_unresolved() {
+    let jumbf = include_bytes!("fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    assert_eq!(
+        sbox.resolve_uri("self#jumbf=/other/c2pa.claim")
+            .unwrap_err(),
+        Error::UnresolvedReference {
+            url: "self#jumbf=/other/c2pa.claim".to_owned(),
+            message: "no unique superbox with this label path".to_owned(),
+        }
+    );
+
+    assert!(sbox.resolve_uri("self#jumbf=c2pa.claim").is_err());
+}
+
+#[test]
+fn error_resolve_other_resource() {
+    let jumbf = include_bytes!("fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let uri = "https://example.com/#jumbf=/c2pa";
+    assert_eq!(
+        sbox.resolve_uri(uri).unwrap_err(),
+        Error::UnresolvedReference {
+            url: uri.to_owned(),
+            message: "URI refers to another resource".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn generate() {
+    assert_eq!(
+        self_uri(["c2pa", "my label", "c2pa.claim"]),
+        "self#jumbf=/c2pa/my%20label/c2pa.claim"
+    );
+}
+
+#[test]
+fn uri_of() {
+    let jumbf = include_bytes!("fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+    let path = format!("{MANIFEST}/c2pa.signature");
+    let signature = sbox.find_by_label(&path).unwrap();
+
+    let uri = sbox.uri_of(signature).unwrap();
+    assert_eq!(uri, format!("self#jumbf=/c2pa/{path}"));
+    assert_eq!(sbox.resolve_uri(&uri).unwrap(), signature);
+
+    assert_eq!(sbox.uri_of(&sbox).unwrap(), "self#jumbf=/c2pa");
+}
+
+#[test]
+fn uri_of_not_descendant() {
+    let jumbf = include_bytes!("fixtures/C.c2pa");
+    let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+    let (_, other) = SuperBox::from_slice(jumbf).unwrap();
+
+    let signature = sbox
+        .find_by_label(&format!("{MANIFEST}/c2pa.signature"))
+        .unwrap();
+    assert!(other.uri_of(signature).is_none());
+    assert!(signature.uri_of(&sbox).is_none());
+}
+
+#[cfg(feature = "hash")]
+mod hashed_label {
+    use pretty_assertions_sorted::assert_eq;
+
+    use crate::{
+        parser::{Error, SuperBox},
+        uri::{hashed_label, verify_hashed_label, JumbfUri},
+    };
+
+    #[test]
+    fn compute() {
+        assert_eq!(
+            hashed_label("a"),
+            "ypeBEsobvcr6wjGzmiPcTaeG7_gUfE5yuYB3ha_uSLs"
+        );
+    }
+
+    #[test]
+    fn verify() {
+        assert!(verify_hashed_label(
+            "ypeBEsobvcr6wjGzmiPcTaeG7_gUfE5yuYB3ha_uSLs",
+            "a"
+        ));
+
+        // Standard alphabet and padding are tolerated.
+        assert!(verify_hashed_label(
+            "ypeBEsobvcr6wjGzmiPcTaeG7/gUfE5yuYB3ha/uSLs=",
+            "a"
+        ));
+
+        assert!(!verify_hashed_label(
+            "ypeBEsobvcr6wjGzmiPcTaeG7_gUfE5yuYB3ha_uSLs",
+            "b"
+        ));
+        assert!(!verify_hashed_label("ypeBEsob", "a"));
+    }
+
+    #[test]
+    fn resolve_verified_c2pa() {
+        let jumbf = include_bytes!("fixtures/C.c2pa");
+        let (_, sbox) = SuperBox::from_slice(jumbf).unwrap();
+
+        let path = format!("{}/c2pa.signature", super::MANIFEST);
+
+        let uri = format!("self#jumbf={path}?hl={}", hashed_label("c2pa.signature"));
+        let uri = JumbfUri::parse(&uri).unwrap();
+        assert_eq!(
+            uri.resolve_verified(&sbox).unwrap(),
+            sbox.find_by_label(&path).unwrap()
+        );
+
+        // Without `hl`, nothing to verify.
+        let uri = format!("self#jumbf={path}");
+        let uri = JumbfUri::parse(&uri).unwrap();
+        assert!(uri.resolve_verified(&sbox).is_ok());
+
+        let uri = format!("self#jumbf={path}?hl={}", hashed_label("c2pa.claim"));
+        let uri = JumbfUri::parse(&uri).unwrap();
+        assert!(matches!(
+            uri.resolve_verified(&sbox),
+            Err(Error::UnresolvedReference { .. })
+        ));
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use std::str::FromStr;
+
+use hex_literal::hex;
+
+use crate::{ParseUuidError, Uuid};
+
+#[test]
+fn impl_display() {
+    assert_eq!(
+        Uuid::JSON.to_string(),
+        "6a736f6e-0011-0010-8000-00aa00389b71"
+    );
+    assert_eq!(
+        Uuid::CODESTREAM.to_string(),
+        "6579d6fb-dba2-446b-b2ac-1b82feeb89d1"
+    );
+    assert_eq!(
+        Uuid::EMBEDDED_FILE.to_string(),
+        "40cb0c32-bb8a-489d-a70b-2ad6f47f4369"
+    );
+}
+
+#[test]
+fn impl_debug() {
+    assert_eq!(
+        format!("{:?}", Uuid::CBOR),
+        "Uuid(63626f72-0011-0010-8000-00aa00389b71)"
+    );
+}
+
+#[test]
+fn from_str() {
+    assert_eq!(
+        Uuid::from_str("75756964-0011-0010-8000-00aa00389b71").unwrap(),
+        Uuid::UUID
+    );
+    assert_eq!(
+        Uuid::from_str("70627566-0011-0010-8000-00AA00389B71").unwrap(),
+        Uuid::PROTOBUF
+    );
+
+    assert_eq!(Uuid::JSON.to_string().parse::<Uuid>().unwrap(), Uuid::JSON);
+}
+
+#[test]
+fn error_from_str() {
+    for s in [
+        "",
+        "6a736f6e001100108000-00aa00389b71",
+        "6a736f6e-0011-0010-8000-00aa00389b7",
+        "6a736f6e-0011-0010-8000-00aa00389b712",
+        "6a736f6e00011-0010-8000-00aa00389b71",
+        "6a736f6e-0011-0010-8000-00aa00389b7g",
+        "+a736f6e-0011-0010-8000-00aa00389b71",
+    ] {
+        let err: ParseUuidError = Uuid::from_str(s).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "UUID must be 32 hex digits in hyphenated 8-4-4-4-12 form"
+        );
+    }
+}
+
+#[test]
+fn from_bytes() {
+    let bytes = hex!("6a736f6e001100108000 00aa00389b71");
+    assert_eq!(Uuid::from(bytes), Uuid::JSON);
+    assert_eq!(Uuid::from(&bytes), Uuid::JSON);
+    assert_eq!(Uuid::JSON, bytes);
+    assert_eq!(Uuid::JSON.as_bytes(), &bytes);
+}
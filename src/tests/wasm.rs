@@ -0,0 +1,76 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+// These tests run natively and therefore only exercise the parts of the
+// bindings which do not call into JavaScript.
+
+use crate::wasm::{JumbfBuilder, JumbfDocument};
+
+fn build_manifest() -> Vec<u8> {
+    let Ok(mut child) = JumbfBuilder::new("6a736f6e-0011-0010-8000-00aa00389b71") else {
+        panic!("invalid UUID");
+    };
+    child.set_label("c2pa.assertion");
+    assert!(child.add_data_box("json", b"{}".to_vec()).is_ok());
+
+    let Ok(mut sbox) = JumbfBuilder::new("00000000-0000-0000-0000-000000000000") else {
+        panic!("invalid UUID");
+    };
+    sbox.set_label("c2pa");
+    sbox.add_super_box(child);
+
+    let Ok(jumbf) = sbox.build() else {
+        panic!("unable to build JUMBF");
+    };
+    jumbf
+}
+
+#[test]
+fn round_trip() {
+    let jumbf = build_manifest();
+
+    let Ok(doc) = JumbfDocument::new(&jumbf) else {
+        panic!("unable to parse JUMBF");
+    };
+
+    assert_eq!(doc.label().as_deref(), Some("c2pa"));
+    assert_eq!(
+        doc.uuid().as_deref(),
+        Some("00000000-0000-0000-0000-000000000000")
+    );
+    assert_eq!(doc.bytes(), jumbf);
+    assert_eq!(doc.payload(), None);
+
+    let assertion = doc.find_by_label("c2pa.assertion").unwrap();
+    assert_eq!(assertion.label().as_deref(), Some("c2pa.assertion"));
+    assert_eq!(
+        assertion.uuid().as_deref(),
+        Some("6a736f6e-0011-0010-8000-00aa00389b71")
+    );
+    assert_eq!(assertion.payload(), Some(b"{}".to_vec()));
+
+    assert!(doc.find_by_label("c2pa.missing").is_none());
+}
+
+#[test]
+fn ignores_trailing_data() {
+    let mut jumbf = build_manifest();
+    let len = jumbf.len();
+    jumbf.extend_from_slice(b"trailing");
+
+    let Ok(doc) = JumbfDocument::new(&jumbf) else {
+        panic!("unable to parse JUMBF");
+    };
+
+    assert_eq!(doc.bytes().len(), len);
+}
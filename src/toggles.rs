@@ -14,21 +14,129 @@
 //! Toggles in a JUMBF description box describe which additional
 //! information is present in the description
 
-/// Toggle bit 0 (0x01) indicates that this superbox can be requested
-/// via URI requests.
-pub(crate) const REQUESTABLE: u8 = 0x01;
+use core::{
+    fmt::{Debug, Error, Formatter},
+    ops::{BitAnd, BitOr, BitOrAssign, Not},
+};
 
-/// Toggle bit 1 (0x02) indicates that the label has an optional textual label.
-pub(crate) const HAS_LABEL: u8 = 0x02;
+/// The toggles byte of a JUMBF description box.
+///
+/// Each bit describes a property of the superbox or indicates that an
+/// optional field is present in the description box. The bits defined by
+/// ISO/IEC 19566-5 are available as associated constants. Any other bits are
+/// reserved for future versions of the specification and are preserved as is.
+///
+/// ## Example
+///
+/// ```
+/// use jumbf::Toggles;
+///
+/// let toggles = Toggles::REQUESTABLE | Toggles::HAS_LABEL;
+/// assert_eq!(toggles.bits(), 0x03);
+/// assert!(toggles.contains(Toggles::HAS_LABEL));
+/// assert!(!toggles.contains(Toggles::HAS_ID));
+/// assert_eq!(format!("{toggles:?}"), "Toggles(REQUESTABLE | HAS_LABEL)");
+/// ```
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+pub struct Toggles(pub u8);
 
-/// Toggle bit 2 (0x04) indicates that the label has an optional
-/// application-specific 32-bit identifier.
-pub(crate) const HAS_ID: u8 = 0x04;
+impl Toggles {
+    /// Toggle bit 3 (0x08) indicates that a SHA-256 hash of the superbox's
+    /// data box is present.
+    pub const HAS_HASH: Toggles = Toggles(0x08);
+    /// Toggle bit 2 (0x04) indicates that the description box contains an
+    /// application-specific 32-bit identifier.
+    pub const HAS_ID: Toggles = Toggles(0x04);
+    /// Toggle bit 1 (0x02) indicates that the description box contains a
+    /// textual label.
+    pub const HAS_LABEL: Toggles = Toggles(0x02);
+    /// Toggle bit 4 (0x10) indicates that an application-specific "private"
+    /// box is contained within the description box.
+    pub const HAS_PRIVATE_BOX: Toggles = Toggles(0x10);
+    /// All bits defined by ISO/IEC 19566-5.
+    pub const KNOWN: Toggles = Toggles(0x1f);
+    /// Toggle bit 0 (0x01) indicates that this superbox can be requested
+    /// via URI requests.
+    pub const REQUESTABLE: Toggles = Toggles(0x01);
 
-/// Toggle bit 3 (0x08) indicates that a SHA-256 hash of the superbox's
-/// data box is present.
-pub(crate) const HAS_HASH: u8 = 0x08;
+    /// Returns the raw value of the toggles byte.
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
 
-/// Toggle bit 4 (0x10) indicates that an application-specific "private"
-/// box is contained within the description box.
-pub(crate) const HAS_PRIVATE_BOX: u8 = 0x10;
+    /// Returns `true` if all bits set in `other` are also set in `self`.
+    pub const fn contains(self, other: Toggles) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns only the bits which are not defined by ISO/IEC 19566-5.
+    pub const fn unknown(self) -> Toggles {
+        Toggles(self.0 & !Self::KNOWN.0)
+    }
+}
+
+impl BitAnd for Toggles {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl BitOr for Toggles {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Toggles {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl Not for Toggles {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(!self.0)
+    }
+}
+
+impl Debug for Toggles {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        const NAMES: [(Toggles, &str); 5] = [
+            (Toggles::REQUESTABLE, "REQUESTABLE"),
+            (Toggles::HAS_LABEL, "HAS_LABEL"),
+            (Toggles::HAS_ID, "HAS_ID"),
+            (Toggles::HAS_HASH, "HAS_HASH"),
+            (Toggles::HAS_PRIVATE_BOX, "HAS_PRIVATE_BOX"),
+        ];
+
+        f.write_str("Toggles(")?;
+
+        let mut first = true;
+        let mut separator = |f: &mut Formatter<'_>| {
+            let result = if first { Ok(()) } else { f.write_str(" | ") };
+            first = false;
+            result
+        };
+
+        for (toggle, name) in NAMES {
+            if self.contains(toggle) {
+                separator(f)?;
+                f.write_str(name)?;
+            }
+        }
+
+        let unknown = self.unknown();
+        if unknown.0 != 0 || self.0 == 0 {
+            separator(f)?;
+            write!(f, "{:#04x}", unknown.0)?;
+        }
+
+        f.write_str(")")
+    }
+}
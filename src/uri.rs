@@ -0,0 +1,424 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! JUMBF request URIs (ISO/IEC 19566-5, section A.3), such as
+//! `self#jumbf=c2pa/cb.adobe_1/c2pa.signature`.
+//!
+//! A request URI identifies a superbox by the path of requestable labels
+//! which leads to it. The path appears in the `jumbf=` fragment of a URI
+//! whose resource part names the asset containing the JUMBF data structure;
+//! `self` (as used by C2PA) refers to the same asset as the reference.
+//!
+//! [`JumbfUri`] parses a request URI and evaluates it against a parsed
+//! [`SuperBox`]. [`self_uri()`] generates a request URI from a path of
+//! labels, and [`SuperBox::uri_of()`] generates one for a specific superbox
+//! within a tree.
+//!
+//! This module is available when the `parser` feature is enabled.
+
+use alloc::{
+    borrow::{Cow, ToOwned},
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{Display, Formatter};
+
+use crate::{
+    label,
+    parser::{Error, SuperBox},
+};
+
+/// Resource name which refers to the asset containing the reference itself.
+const SELF_RESOURCE: &str = "self";
+
+/// Prefix of the URI fragment which contains a JUMBF path.
+const JUMBF_FRAGMENT: &str = "jumbf=";
+
+/// A parsed JUMBF request URI, such as
+/// `self#jumbf=c2pa/cb.adobe_1/c2pa.signature`.
+///
+/// URIs of this form are used (for example, in C2PA claims) to refer to a
+/// superbox by the path of requestable labels which leads to it.
+///
+/// ## Example
+///
+/// ```
+/// use jumbf::uri::JumbfUri;
+///
+/// let uri = JumbfUri::parse("self#jumbf=/c2pa/cb.adobe_1/c2pa.signature?hl=abc").unwrap();
+///
+/// assert_eq!(uri.resource, "self");
+/// assert!(uri.absolute);
+/// assert_eq!(uri.path, "c2pa/cb.adobe_1/c2pa.signature");
+/// assert_eq!(uri.hashed_label, Some("abc"));
+///
+/// let labels: Vec<&str> = uri.labels().collect();
+/// assert_eq!(labels, ["c2pa", "cb.adobe_1", "c2pa.signature"]);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct JumbfUri<'u> {
+    /// The part of the URI before `#`, which names the asset containing the
+    /// JUMBF data structure. This is `self` or empty if the URI refers to
+    /// the asset which contains it; see [`refers_to_self()`].
+    ///
+    /// [`refers_to_self()`]: Self::refers_to_self()
+    pub resource: &'u str,
+
+    /// True if the path began with `/`, meaning that its first label is that
+    /// of the outermost superbox.
+    pub absolute: bool,
+
+    /// Labels leading to the target superbox, separated by `/`. Does not
+    /// include any leading `/`. Labels may be percent-encoded.
+    pub path: &'u str,
+
+    /// The query string following `?`, if present, without the `?`.
+    pub query: Option<&'u str>,
+
+    /// Value of the `hl` (hashed label) query parameter, if present.
+    pub hashed_label: Option<&'u str>,
+}
+
+impl<'u> JumbfUri<'u> {
+    /// Parse a JUMBF request URI of the form
+    /// `<resource>#jumbf=<path>[?<query>]`.
+    ///
+    /// `<resource>` may be `self`, empty, or the URI of another asset.
+    /// `<path>` may begin with `/` to indicate an absolute path.
+    ///
+    /// Will return [`Error::UnresolvedReference`] if `uri` does not have this
+    /// form or the path is empty.
+    pub fn parse(uri: &'u str) -> Result<Self, Error> {
+        let Some((resource, fragment)) = uri.split_once('#') else {
+            return Err(unresolved(uri, "not a JUMBF URI"));
+        };
+
+        let Some(rest) = fragment.strip_prefix(JUMBF_FRAGMENT) else {
+            return Err(unresolved(uri, "not a JUMBF URI"));
+        };
+
+        let (path, query) = match rest.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (rest, None),
+        };
+
+        let (absolute, path) = match path.strip_prefix('/') {
+            Some(path) => (true, path),
+            None => (false, path),
+        };
+
+        if path.is_empty() || path.split('/').any(str::is_empty) {
+            return Err(unresolved(uri, "path is empty or contains an empty label"));
+        }
+
+        let mut uri = Self {
+            resource,
+            absolute,
+            path,
+            query,
+            hashed_label: None,
+        };
+
+        uri.hashed_label = uri.query_param("hl");
+        Ok(uri)
+    }
+
+    /// Returns `true` if this URI refers to the asset which contains it
+    /// (i.e. its resource is `self` or empty). Only such a URI can be
+    /// resolved within a parsed JUMBF data structure.
+    pub fn refers_to_self(&self) -> bool {
+        self.resource.is_empty() || self.resource == SELF_RESOURCE
+    }
+
+    /// Returns the labels in the path, in order from outermost to innermost.
+    ///
+    /// Labels are returned as they appear in the URI, which is to say they
+    /// may be percent-encoded (see [`label::percent_decode()`]).
+    pub fn labels(&self) -> impl Iterator<Item = &'u str> {
+        self.path.split('/')
+    }
+
+    /// Returns the name and value of each parameter in the query string.
+    ///
+    /// A parameter without `=` has an empty value.
+    pub fn query_params(&self) -> impl Iterator<Item = (&'u str, &'u str)> {
+        self.query
+            .into_iter()
+            .flat_map(|query| query.split('&'))
+            .filter(|param| !param.is_empty())
+            .map(|param| param.split_once('=').unwrap_or((param, "")))
+    }
+
+    /// Returns the value of the first query parameter named `name`, if any.
+    pub fn query_param(&self, name: &str) -> Option<&'u str> {
+        self.query_params()
+            .find_map(|(param, value)| (param == name).then_some(value))
+    }
+
+    /// Find the superbox identified by this URI within `sbox`, which should
+    /// be the outermost superbox of the JUMBF data structure.
+    ///
+    /// An absolute path (one that begins with `/`) must begin with the label
+    /// of `sbox` itself. Otherwise, the path is resolved relative to `sbox`
+    /// as described in [`SuperBox::find_by_label()`]. If that fails and the
+    /// first label of a relative path is the label of `sbox`, the path is
+    /// resolved as if it were absolute.
+    ///
+    /// Percent-encoded labels in the path are decoded before comparison (see
+    /// [`label::percent_decode()`]).
+    ///
+    /// Will return [`Error::UnresolvedReference`] if the URI refers to
+    /// another resource or if no unique matching superbox is found.
+    pub fn resolve<'s, 'a>(&self, sbox: &'s SuperBox<'a>) -> Result<&'s SuperBox<'a>, Error> {
+        if !self.refers_to_self() {
+            return Err(Error::UnresolvedReference {
+                url: self.to_string(),
+                message: "URI refers to another resource".to_owned(),
+            });
+        }
+
+        let path = decode_path(self.path).ok_or_else(|| Error::UnresolvedReference {
+            url: self.to_string(),
+            message: "path contains a malformed percent-encoded label".to_owned(),
+        })?;
+
+        let target = if self.absolute {
+            resolve_absolute(sbox, &path)
+        } else {
+            sbox.find_by_label(&path)
+                .or_else(|| resolve_absolute(sbox, &path))
+        };
+
+        target.ok_or_else(|| Error::UnresolvedReference {
+            url: self.to_string(),
+            message: "no unique superbox with this label path".to_owned(),
+        })
+    }
+
+    /// Find the superbox identified by this URI, as described in
+    /// [`resolve()`], and verify its label against the `hl` (hashed label)
+    /// query parameter, if present.
+    ///
+    /// Will return [`Error::UnresolvedReference`] if no unique matching
+    /// superbox is found or if the hashed label does not match.
+    ///
+    /// This function is available when the `hash` feature is enabled.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use hex_literal::hex;
+    /// use jumbf::{
+    ///     parser::SuperBox,
+    ///     uri::{hashed_label, JumbfUri},
+    /// };
+    ///
+    /// let jumbf = hex!(
+    ///     "0000002f" // box size
+    ///     "6a756d62" // box type = 'jumb'
+    ///         "00000027" // box size
+    ///         "6a756d64" // box type = 'jumd'
+    ///         "00000000000000000000000000000000" // UUID
+    ///         "03" // toggles
+    ///         "746573742e7375706572626f7800" // label
+    /// );
+    ///
+    /// let (_, sbox) = SuperBox::from_slice(&jumbf).unwrap();
+    ///
+    /// let uri = format!(
+    ///     "self#jumbf=/test.superbox?hl={}",
+    ///     hashed_label("test.superbox")
+    /// );
+    /// let uri = JumbfUri::parse(&uri).unwrap();
+    /// assert_eq!(uri.resolve_verified(&sbox).unwrap(), &sbox);
+    ///
+    /// let uri = JumbfUri::parse("self#jumbf=/test.superbox?hl=AAAA").unwrap();
+    /// assert!(uri.resolve_verified(&sbox).is_err());
+    /// ```
+    ///
+    /// [`resolve()`]: Self::resolve
+    #[cfg(feature = "hash")]
+    pub fn resolve_verified<'s, 'a>(
+        &self,
+        sbox: &'s SuperBox<'a>,
+    ) -> Result<&'s SuperBox<'a>, Error> {
+        let target = self.resolve(sbox)?;
+
+        if let Some(hl) = self.hashed_label {
+            if !verify_hashed_label(hl, target.desc.label.unwrap_or_default()) {
+                return Err(Error::UnresolvedReference {
+                    url: self.to_string(),
+                    message: "hashed label does not match target superbox".to_owned(),
+                });
+            }
+        }
+
+        Ok(target)
+    }
+}
+
+/// Compute the hashed form of `label` for use in the `hl` query parameter of
+/// a JUMBF request URI.
+///
+/// This is the SHA-256 hash of the UTF-8 encoded label, encoded as
+/// unpadded URL-safe base64.
+///
+/// This function is available when the `hash` feature is enabled.
+///
+/// ## Example
+///
+/// ```
+/// use jumbf::uri::hashed_label;
+///
+/// assert_eq!(
+///     hashed_label("c2pa.signature"),
+///     "kVuNzQULedLhS5O85rYMagpGEW3XIawS7Wrg6Tym6W8"
+/// );
+/// ```
+#[cfg(feature = "hash")]
+pub fn hashed_label(label: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    base64_url(&Sha256::digest(label.as_bytes()))
+}
+
+/// Returns `true` if `hl` is the hashed form of `label`.
+///
+/// Padding and the standard (rather than URL-safe) base64 alphabet are
+/// tolerated in `hl`.
+///
+/// This function is available when the `hash` feature is enabled.
+#[cfg(feature = "hash")]
+pub fn verify_hashed_label(hl: &str, label: &str) -> bool {
+    let expected = hashed_label(label);
+    let hl = hl.trim_end_matches('=');
+
+    hl.len() == expected.len()
+        && hl
+            .bytes()
+            .zip(expected.bytes())
+            .all(|(a, b)| a == b || (a == b'+' && b == b'-') || (a == b'/' && b == b'_'))
+}
+
+#[cfg(feature = "hash")]
+fn base64_url(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+    let mut result = String::with_capacity((data.len() * 4).div_ceil(3));
+
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+
+        for i in 0..=chunk.len() {
+            result.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3f] as char);
+        }
+    }
+
+    result
+}
+
+impl<'u> Display for JumbfUri<'u> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        write!(f, "{}#{JUMBF_FRAGMENT}", self.resource)?;
+        if self.absolute {
+            f.write_str("/")?;
+        }
+        f.write_str(self.path)?;
+        match (self.query, self.hashed_label) {
+            (Some(query), _) => write!(f, "?{query}")?,
+            (None, Some(hl)) => write!(f, "?hl={hl}")?,
+            (None, None) => (),
+        }
+        Ok(())
+    }
+}
+
+/// Generate an absolute JUMBF request URI which refers to the asset
+/// containing it, such as `self#jumbf=/c2pa/cb.adobe_1/c2pa.signature`.
+///
+/// `labels` lists the labels of the superboxes leading to the target, from
+/// outermost to innermost. Each label is percent-encoded as needed (see
+/// [`label::percent_encode()`]).
+///
+/// ## Example
+///
+/// ```
+/// use jumbf::uri::self_uri;
+///
+/// assert_eq!(
+///     self_uri(["c2pa", "cb.adobe_1", "c2pa.signature"]),
+///     "self#jumbf=/c2pa/cb.adobe_1/c2pa.signature"
+/// );
+/// ```
+pub fn self_uri<'l, I>(labels: I) -> String
+where
+    I: IntoIterator<Item = &'l str>,
+{
+    let mut uri = String::from(SELF_RESOURCE);
+    uri.push('#');
+    uri.push_str(JUMBF_FRAGMENT);
+
+    for l in labels {
+        uri.push('/');
+        uri.push_str(&label::percent_encode(l));
+    }
+
+    uri
+}
+
+fn resolve_absolute<'s, 'a>(sbox: &'s SuperBox<'a>, path: &str) -> Option<&'s SuperBox<'a>> {
+    let (root, rest) = match path.split_once('/') {
+        Some((root, rest)) => (root, Some(rest)),
+        None => (path, None),
+    };
+
+    if !sbox
+        .desc
+        .label
+        .is_some_and(|label| label::matches(label, root))
+    {
+        return None;
+    }
+
+    match rest {
+        Some(rest) => sbox.find_by_label(rest),
+        None => Some(sbox),
+    }
+}
+
+// Decode each percent-encoded label in `path`. Returns `None` if any label is
+// malformed or decodes to a string which is not a valid label.
+fn decode_path(path: &str) -> Option<Cow<'_, str>> {
+    if !path.contains('%') {
+        return Some(Cow::Borrowed(path));
+    }
+
+    let labels = path
+        .split('/')
+        .map(|segment| label::percent_decode(segment).filter(|l| label::is_valid(l)))
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(Cow::Owned(labels.join("/")))
+}
+
+fn unresolved(uri: &str, message: &str) -> Error {
+    Error::UnresolvedReference {
+        url: uri.to_owned(),
+        message: message.to_owned(),
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+use core::{
+    fmt::{Debug, Display, Error, Formatter},
+    str::FromStr,
+};
+
+/// A JUMBF superbox is identified by a 16-byte UUID which describes the type
+/// of content contained within the superbox.
+///
+/// This crate does not interpret the UUID. Any 16-byte value is allowed.
+/// ISO/IEC 19566-5 defines several standard content types, which are
+/// available here as associated constants (e.g. [`Uuid::JSON`]).
+///
+/// A `Uuid` is displayed and parsed in the canonical hyphenated form
+/// (e.g. `6a736f6e-0011-0010-8000-00aa00389b71`).
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Uuid(pub [u8; 16]);
+
+impl Uuid {
+    /// Content type for a superbox containing a CBOR content box
+    /// (`63626f72-0011-0010-8000-00aa00389b71`).
+    pub const CBOR: Uuid = Uuid(*b"cbor\x00\x11\x00\x10\x80\x00\x00\xaa\x00\x38\x9b\x71");
+    /// Content type for a superbox containing a contiguous codestream box
+    /// (`6579d6fb-dba2-446b-b2ac-1b82feeb89d1`).
+    pub const CODESTREAM: Uuid = Uuid([
+        0x65, 0x79, 0xd6, 0xfb, 0xdb, 0xa2, 0x44, 0x6b, 0xb2, 0xac, 0x1b, 0x82, 0xfe, 0xeb, 0x89,
+        0xd1,
+    ]);
+    /// Content type for a superbox containing an embedded file
+    /// (`40cb0c32-bb8a-489d-a70b-2ad6f47f4369`).
+    pub const EMBEDDED_FILE: Uuid = Uuid([
+        0x40, 0xcb, 0x0c, 0x32, 0xbb, 0x8a, 0x48, 0x9d, 0xa7, 0x0b, 0x2a, 0xd6, 0xf4, 0x7f, 0x43,
+        0x69,
+    ]);
+    /// Content type for a superbox containing a JSON content box
+    /// (`6a736f6e-0011-0010-8000-00aa00389b71`).
+    pub const JSON: Uuid = Uuid(*b"json\x00\x11\x00\x10\x80\x00\x00\xaa\x00\x38\x9b\x71");
+    /// Content type for a superbox containing a Protocol Buffers content box
+    /// (`70627566-0011-0010-8000-00aa00389b71`).
+    pub const PROTOBUF: Uuid = Uuid(*b"pbuf\x00\x11\x00\x10\x80\x00\x00\xaa\x00\x38\x9b\x71");
+    /// Content type for a superbox containing a UUID box
+    /// (`75756964-0011-0010-8000-00aa00389b71`).
+    pub const UUID: Uuid = Uuid(*b"uuid\x00\x11\x00\x10\x80\x00\x00\xaa\x00\x38\x9b\x71");
+    /// Content type for a superbox containing an XML content box
+    /// (`786d6c20-0011-0010-8000-00aa00389b71`).
+    pub const XML: Uuid = Uuid(*b"xml \x00\x11\x00\x10\x80\x00\x00\xaa\x00\x38\x9b\x71");
+
+    /// Returns the UUID as a byte array.
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+}
+
+impl Debug for Uuid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "Uuid({self})")
+    }
+}
+
+impl Display for Uuid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        for (i, b) in self.0.iter().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                f.write_str("-")?;
+            }
+            write!(f, "{b:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Uuid {
+    type Err = ParseUuidError;
+
+    /// Parse a UUID in the canonical hyphenated form
+    /// (e.g. `6a736f6e-0011-0010-8000-00aa00389b71`).
+    ///
+    /// Hex digits may be upper- or lowercase.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.as_bytes();
+        if s.len() != 36 {
+            return Err(ParseUuidError(()));
+        }
+
+        let mut uuid = [0u8; 16];
+        let mut digits = s.iter().enumerate().filter_map(|(i, c)| {
+            if matches!(i, 8 | 13 | 18 | 23) {
+                None
+            } else {
+                Some(c)
+            }
+        });
+
+        if [8, 13, 18, 23].iter().any(|i| s[*i] != b'-') {
+            return Err(ParseUuidError(()));
+        }
+
+        for b in uuid.iter_mut() {
+            let hi = digits.next().and_then(|c| hex_value(*c));
+            let lo = digits.next().and_then(|c| hex_value(*c));
+            match (hi, lo) {
+                (Some(hi), Some(lo)) => *b = (hi << 4) | lo,
+                _ => return Err(ParseUuidError(())),
+            }
+        }
+
+        Ok(Self(uuid))
+    }
+}
+
+impl From<[u8; 16]> for Uuid {
+    fn from(uuid: [u8; 16]) -> Self {
+        Self(uuid)
+    }
+}
+
+impl From<&[u8; 16]> for Uuid {
+    fn from(uuid: &[u8; 16]) -> Self {
+        Self(*uuid)
+    }
+}
+
+impl From<&Uuid> for Uuid {
+    fn from(uuid: &Uuid) -> Self {
+        *uuid
+    }
+}
+
+impl AsRef<[u8]> for Uuid {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl PartialEq<[u8; 16]> for Uuid {
+    fn eq(&self, other: &[u8; 16]) -> bool {
+        self.0 == *other
+    }
+}
+
+/// An error which can be returned when parsing a [`Uuid`] from a string.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseUuidError(());
+
+impl Display for ParseUuidError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.write_str("UUID must be 32 hex digits in hyphenated 8-4-4-4-12 form")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseUuidError {}
+
+pub(crate) fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
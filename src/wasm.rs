@@ -0,0 +1,225 @@
+// Copyright 2024 Adobe. All rights reserved.
+// This file is licensed to you under the Apache License,
+// Version 2.0 (http://www.apache.org/licenses/LICENSE-2.0)
+// or the MIT license (http://opensource.org/licenses/MIT),
+// at your option.
+
+// Unless required by applicable law or agreed to in writing,
+// this software is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR REPRESENTATIONS OF ANY KIND, either express or
+// implied. See the LICENSE-MIT and LICENSE-APACHE files for the
+// specific language governing permissions and limitations under
+// each license.
+
+//! JavaScript bindings for parsing and building JUMBF, generated via
+//! [`wasm-bindgen`].
+//!
+//! These bindings are intended for browser-based applications (such as
+//! provenance verifiers) which need to inspect or produce JUMBF data
+//! structures. They are available when the `wasm` feature is enabled.
+//!
+//! Since JavaScript can not hold references into a byte buffer in the way
+//! that the zero-copy [`parser`] does, [`JumbfDocument`] owns a copy of the
+//! JUMBF data and re-parses it as needed.
+//!
+//! [`wasm-bindgen`]: https://rustwasm.github.io/docs/wasm-bindgen/
+//! [`parser`]: crate::parser
+
+use std::{io::Cursor, str::FromStr};
+
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    box_type::SUPER_BOX_TYPE,
+    builder::{DataBoxBuilder, SuperBoxBuilder},
+    parser::{ChildBox, SuperBox},
+    BoxType, Uuid,
+};
+
+/// A parsed JUMBF superbox, as seen from JavaScript.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct JumbfDocument {
+    jumbf: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl JumbfDocument {
+    /// Parse `jumbf` as a JUMBF superbox.
+    ///
+    /// Throws an error if `jumbf` is not a valid superbox. Any data following
+    /// the superbox is ignored.
+    #[wasm_bindgen(constructor)]
+    pub fn new(jumbf: &[u8]) -> Result<JumbfDocument, JsError> {
+        let (_, sbox) = SuperBox::from_slice(jumbf).map_err(|e| JsError::new(&e.to_string()))?;
+
+        Ok(Self {
+            jumbf: sbox.original.to_vec(),
+        })
+    }
+
+    /// Returns the superbox's label, if any.
+    #[wasm_bindgen(getter)]
+    pub fn label(&self) -> Option<String> {
+        self.super_box()
+            .and_then(|sbox| sbox.desc.label.map(str::to_owned))
+    }
+
+    /// Returns the superbox's content type UUID in hyphenated form.
+    #[wasm_bindgen(getter)]
+    pub fn uuid(&self) -> Option<String> {
+        self.super_box().map(|sbox| sbox.desc.uuid.to_string())
+    }
+
+    /// Returns the serialized superbox.
+    #[wasm_bindgen(getter)]
+    pub fn bytes(&self) -> Vec<u8> {
+        self.jumbf.clone()
+    }
+
+    /// Find a descendant superbox by label, as described in
+    /// [`SuperBox::find_by_label()`].
+    ///
+    /// Returns `undefined` if no unique matching superbox is found.
+    #[wasm_bindgen(js_name = findByLabel)]
+    pub fn find_by_label(&self, label: &str) -> Option<JumbfDocument> {
+        self.super_box()
+            .and_then(|sbox| {
+                sbox.find_by_label(label)
+                    .map(|child| child.original.to_vec())
+            })
+            .map(|jumbf| Self { jumbf })
+    }
+
+    /// Returns the payload of the superbox's first child box, if that box is
+    /// not itself a superbox.
+    pub fn payload(&self) -> Option<Vec<u8>> {
+        self.super_box()
+            .and_then(|sbox| match sbox.child_boxes.first() {
+                Some(ChildBox::DataBox(dbox)) => Some(dbox.data.to_vec()),
+                _ => None,
+            })
+    }
+
+    /// Returns a plain JavaScript object which summarizes the superbox and
+    /// its descendants.
+    ///
+    /// Each superbox is described as
+    /// `{ type: "jumb", offset, size, uuid, label?, children: [...] }` and
+    /// each other box as `{ type, offset, size }`. Offsets are relative to
+    /// the start of this document.
+    pub fn summary(&self) -> Result<JsValue, JsError> {
+        let sbox = self
+            .super_box()
+            .ok_or_else(|| JsError::new("Unable to parse JUMBF"))?;
+
+        super_box_summary(&sbox)
+            .map(JsValue::from)
+            .map_err(|_| JsError::new("Unable to construct summary"))
+    }
+}
+
+impl JumbfDocument {
+    fn super_box(&self) -> Option<SuperBox<'_>> {
+        SuperBox::from_slice(&self.jumbf).ok().map(|(_, sbox)| sbox)
+    }
+}
+
+/// Builds a JUMBF superbox from JavaScript.
+///
+/// Unlike [`SuperBoxBuilder`], methods on this type modify the builder in
+/// place so that it can be used naturally from JavaScript.
+#[wasm_bindgen]
+pub struct JumbfBuilder {
+    sbox: SuperBoxBuilder<'static>,
+}
+
+#[wasm_bindgen]
+impl JumbfBuilder {
+    /// Create a new, empty superbox with the given content type UUID, which
+    /// must be given in hyphenated form.
+    #[wasm_bindgen(constructor)]
+    pub fn new(uuid: &str) -> Result<JumbfBuilder, JsError> {
+        let uuid = Uuid::from_str(uuid).map_err(|e| JsError::new(&e.to_string()))?;
+
+        Ok(Self {
+            sbox: SuperBoxBuilder::new(uuid),
+        })
+    }
+
+    /// Set a requestable label for the superbox.
+    #[wasm_bindgen(js_name = setLabel)]
+    pub fn set_label(&mut self, label: &str) {
+        self.update(|sbox| sbox.set_label(label));
+    }
+
+    /// Add a child box with the given four-character box type and payload.
+    #[wasm_bindgen(js_name = addDataBox)]
+    pub fn add_data_box(&mut self, tbox: &str, payload: Vec<u8>) -> Result<(), JsError> {
+        let tbox = BoxType::from_str(tbox).map_err(|e| JsError::new(&e.to_string()))?;
+
+        self.update(|sbox| sbox.add_child_box(DataBoxBuilder::from_owned(tbox, payload)));
+        Ok(())
+    }
+
+    /// Add a child superbox. The child builder is consumed.
+    #[wasm_bindgen(js_name = addSuperBox)]
+    pub fn add_super_box(&mut self, child: JumbfBuilder) {
+        self.update(|sbox| sbox.add_child_box(child.sbox));
+    }
+
+    /// Serialize the superbox as JUMBF.
+    pub fn build(&self) -> Result<Vec<u8>, JsError> {
+        let mut jumbf = Cursor::new(Vec::<u8>::new());
+        self.sbox
+            .write_jumbf(&mut jumbf)
+            .map_err(|e| JsError::new(&e.to_string()))?;
+
+        Ok(jumbf.into_inner())
+    }
+}
+
+impl JumbfBuilder {
+    fn update<F: FnOnce(SuperBoxBuilder<'static>) -> SuperBoxBuilder<'static>>(&mut self, f: F) {
+        let sbox = std::mem::replace(&mut self.sbox, SuperBoxBuilder::new([0; 16]));
+        self.sbox = f(sbox);
+    }
+}
+
+fn super_box_summary(sbox: &SuperBox) -> Result<Object, JsValue> {
+    let summary = box_summary(SUPER_BOX_TYPE, sbox.offset, sbox.original)?;
+    set(&summary, "uuid", sbox.desc.uuid.to_string())?;
+
+    if let Some(label) = sbox.desc.label {
+        set(&summary, "label", label)?;
+    }
+
+    let children = Array::new();
+    for child in &sbox.child_boxes {
+        let child = match child {
+            ChildBox::SuperBox(sbox) => super_box_summary(sbox)?,
+            ChildBox::DataBox(dbox) => box_summary(dbox.tbox, dbox.offset, dbox.original)?,
+        };
+        children.push(&child);
+    }
+    set(&summary, "children", children)?;
+
+    Ok(summary)
+}
+
+fn box_summary(tbox: BoxType, offset: usize, original: &[u8]) -> Result<Object, JsValue> {
+    let summary = Object::new();
+    set(&summary, "type", box_type_name(tbox))?;
+    set(&summary, "offset", offset as f64)?;
+    set(&summary, "size", original.len() as f64)?;
+    Ok(summary)
+}
+
+fn box_type_name(tbox: BoxType) -> String {
+    String::from_utf8_lossy(&tbox.0).into_owned()
+}
+
+fn set<V: Into<JsValue>>(object: &Object, key: &str, value: V) -> Result<(), JsValue> {
+    Reflect::set(object, &JsValue::from_str(key), &value.into()).map(|_| ())
+}